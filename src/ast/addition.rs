@@ -1,6 +1,8 @@
+use num_traits::One;
+
 use crate::{ast::Expr, explanation::FormattingObserver, prints::PrettyPrints, utils::transform_multiplication};
 
-use super::{Expression, SimplifyError, numeral};
+use super::{Expression, SimplifyError, SimplifyOptions};
 
 #[derive(Debug, PartialEq, Clone, PartialOrd, Eq, Ord, Hash)]
 pub struct Addition {
@@ -20,13 +22,17 @@ impl Addition {
 impl Expr for Addition {
     fn simplify(
         &mut self,
+        options: &SimplifyOptions,
         explanation: &mut Option<Box<FormattingObserver>>,
     ) -> Result<Expression, SimplifyError> {
         let simplified_terms: Vec<Expression> = self.terms
         .iter_mut()
-        .map(|term| term.simplify(explanation))
+        .map(|term| term.simplify_with(options, explanation))
         .collect::<Result<Vec<Expression>, _>>()?;
-    self.simplify_addition(simplified_terms, explanation)
+    if simplified_terms.iter().any(Expression::is_matrix) {
+        return Expression::add_matrices(simplified_terms, options, explanation);
+    }
+    self.simplify_addition(simplified_terms, options, explanation)
     }
 
     fn is_equal(&self, other: &Addition) -> bool {
@@ -147,6 +153,7 @@ impl Addition {
     pub fn simplify_addition(
         &self,
         terms: Vec<Expression>,
+        options: &SimplifyOptions,
         explanation: &mut Option<Box<FormattingObserver>>,
     ) -> Result<Expression, SimplifyError> {
         let before: Expression = Expression::addition(terms.clone());
@@ -172,8 +179,9 @@ impl Addition {
                         result.swap_remove(j);
                     }
                     // a + 0 => a
-                    (a, Expression::Number(numeral::Numeral::Integer(0)))
-                    | (Expression::Number(numeral::Numeral::Integer(0)), a) => {
+                    (a, Expression::Number(zero)) | (Expression::Number(zero), a)
+                        if zero.is_zero() =>
+                    {
                         if let Some(explanation) = explanation {
                             explanation.rule_applied("Adding zero stay the same", &before, a);
                         }
@@ -182,11 +190,11 @@ impl Addition {
                     }
                     // a + b => c
                     (Expression::Number(a), Expression::Number(b)) => {
-                        let after = Expression::Number(a.add(b));
+                        let mut after = Expression::Number(a.add(b));
                         if let Some(explanation) = explanation {
                             explanation.rule_applied("Add numbers", &before, &after);
                         }
-                        result[i] = after;
+                        result[i] = after.simplify_with(options, explanation)?;
                         result.swap_remove(j);
                     }
                     // a + a => 2a
@@ -198,24 +206,36 @@ impl Addition {
                         if let Some(explanation) = explanation {
                             explanation.rule_applied("Add same expression", &before, &after);
                         }
-                        result[i] = after.simplify(explanation)?;
+                        result[i] = after.simplify_with(options, explanation)?;
                         result.swap_remove(j);
                     }
                     // a - b => c
                     // -a + b => c
                     (Expression::Number(a), Expression::Negation(b))
                     | (Expression::Negation(b), Expression::Number(a)) => {
-                        if let Expression::Number(inner_b) = b.term {
-                            let after = a.sub(&inner_b);
+                        if let Expression::Number(inner_b) = &b.term {
+                            let mut after = a.sub(inner_b).into_expression();
                             if let Some(explanation) = explanation {
                                 explanation.rule_applied("Add numbers", &before, &after);
                             }
-                            result[i] = after;
+                            result[i] = after.simplify_with(options, explanation)?;
                             result.swap_remove(j);
                         } else {
                             j += 1;
                         }
                     }
+                    // c*sin(a)^2 + c*cos(a)^2 => c
+                    (a, b) if let Some(mut after) = Expression::pythagorean(a, b) => {
+                        if let Some(explanation) = explanation {
+                            explanation.rule_applied(
+                                "Pythagorean identity\nsin(a)^2 + cos(a)^2 => 1",
+                                &before,
+                                &after,
+                            );
+                        }
+                        result[i] = after.simplify_with(options, explanation)?;
+                        result.swap_remove(j);
+                    }
                     // aX + bX => (a + b)X
                     // aX - bX => (a - b)X
                     // -aX + bX => (- a + b)X
@@ -252,13 +272,13 @@ impl Addition {
                                     }
                                 }
                                 // aX + bX => (a + b)X
-                                _ => Expression::integer(lhs_coeff + rhs_coeff),
+                                (a, b) => Expression::integer(a + b),
                             });
                             let mut after = Expression::multiplication(rhs);
                             if let Some(explanation) = explanation {
                                 explanation.rule_applied("Add similar expression", &before, &after);
                             }
-                            result[i] = after.simplify(explanation)?;
+                            result[i] = after.simplify_with(options, explanation)?;
                             result.swap_remove(j);
                         } else {
                             j += 1
@@ -290,7 +310,7 @@ impl Addition {
                             }
                             // a - Xa
                             else if terms_neg {
-                                if coeff == 1 {
+                                if coeff.is_one() {
                                     Expression::integer(0)
                                 } else {
                                     Expression::negation(Expression::integer(coeff - 1))
@@ -298,7 +318,7 @@ impl Addition {
                             }
                             // -a + Xa
                             else if expr_neg {
-                                if coeff == 1 {
+                                if coeff.is_one() {
                                     Expression::integer(0)
                                 } else {
                                     Expression::integer(coeff - 1)
@@ -313,7 +333,7 @@ impl Addition {
                             if let Some(explanation) = explanation {
                                 explanation.rule_applied("Add similar expression", &before, &after);
                             }
-                            result[i] = after.simplify(explanation)?;
+                            result[i] = after.simplify_with(options, explanation)?;
                             result.swap_remove(j);
                         } else {
                             j += 1;
@@ -328,7 +348,7 @@ impl Addition {
                             }
                             // -Xa + a
                             else if terms_neg {
-                                if coeff == 1 {
+                                if coeff.is_one() {
                                     Expression::integer(0)
                                 } else {
                                     Expression::negation(Expression::integer(coeff - 1))
@@ -336,7 +356,7 @@ impl Addition {
                             }
                             // Xa - a
                             else if expr_neg {
-                                if coeff == 1 {
+                                if coeff.is_one() {
                                     Expression::integer(0)
                                 } else {
                                     Expression::integer(coeff - 1)
@@ -351,7 +371,7 @@ impl Addition {
                             if let Some(explanation) = explanation {
                                 explanation.rule_applied("Add similar expression", &before, &after);
                             }
-                            result[i] = after.simplify(explanation)?;
+                            result[i] = after.simplify_with(options, explanation)?;
                             result.swap_remove(j);
                         } else {
                             j += 1;
@@ -366,7 +386,7 @@ impl Addition {
                         if let Some(explanation) = explanation {
                             explanation.rule_applied("Add complex expression", &before, &after);
                         }
-                        result[i] = after.simplify(explanation)?;
+                        result[i] = after.simplify_with(options, explanation)?;
                         result.swap_remove(j);
                     }
                     // a + c + di => (a + c) + di
@@ -386,7 +406,7 @@ impl Addition {
                         //         &after,
                         //     );
                         // }
-                        result[i] = after.simplify(explanation)?;
+                        result[i] = after.simplify_with(options, explanation)?;
                         result.swap_remove(j);
                         if j >= result.len() && j > i + 1 {
                             j -= 1;
@@ -404,19 +424,22 @@ impl Addition {
                         if let Some(explanation) = explanation {
                             explanation.rule_applied("Add two fraction", &before, &after);
                         }
-                        result[i] = after.simplify(explanation)?;
+                        result[i] = after.simplify_with(options, explanation)?;
                         result.swap_remove(j);
                     }
                     // a + c/d => (ad + c)/(d)
                     (a, Expression::Division(div)) | (Expression::Division(div), a) => {
                         let mut after = Expression::division(
-                            Expression::addition(vec![a.clone(), div.num.clone()]),
+                            Expression::addition(vec![
+                                Expression::multiplication(vec![a.clone(), div.den.clone()]),
+                                div.num.clone(),
+                            ]),
                             div.den.clone(),
                         );
                         if let Some(explanation) = explanation {
                             explanation.rule_applied("Add with a fraction", &before, &after);
                         }
-                        result[i] = after.simplify(explanation)?;
+                        result[i] = after.simplify_with(options, explanation)?;
                         result.swap_remove(j);
                     }
                     _ => j += 1,
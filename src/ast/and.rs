@@ -0,0 +1,91 @@
+use std::collections::HashMap;
+
+use crate::{ast::Expr, explanation::FormattingObserver, prints::PrettyPrints};
+
+use super::{
+    Expression, SimplifyError, SimplifyOptions,
+    logic::{Connective, Infix},
+};
+
+#[derive(Debug, PartialEq, Clone, PartialOrd, Eq, Ord, Hash)]
+pub struct And {
+    pub terms: Vec<Expression>,
+    pub simplified: bool,
+}
+
+// Constructor
+impl And {
+    pub fn new(terms: Vec<Expression>, simplified: bool) -> Self {
+        Self { terms, simplified }
+    }
+}
+
+impl Expr for And {
+    fn simplify(
+        &mut self,
+        options: &SimplifyOptions,
+        explanation: &mut Option<Box<FormattingObserver>>,
+    ) -> Result<Expression, SimplifyError> {
+        let terms = self
+            .terms
+            .iter_mut()
+            .map(|term| term.simplify_with(options, explanation))
+            .collect::<Result<Vec<_>, _>>()?;
+        Connective::And.simplify_terms(terms, explanation)
+    }
+
+    fn is_equal(&self, other: &And) -> bool {
+        Expression::compare_expression_vectors(&self.terms, &other.terms)
+    }
+
+    fn contains_var(&self, variable: &str) -> bool {
+        self.terms.iter().any(|term| term.contains_var(variable))
+    }
+
+    fn is_single(&self) -> bool {
+        self.terms.len() <= 1
+    }
+}
+
+impl And {
+    fn infix(&self) -> Infix<'_> {
+        Infix {
+            terms: self.terms.iter().collect(),
+            symbol: Connective::And.symbol(),
+        }
+    }
+}
+
+impl std::fmt::Display for And {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        self.infix().display(f)
+    }
+}
+
+impl PrettyPrints for And {
+    fn calculate_tree(&self, indent: usize) -> String {
+        self.infix().calculate_tree("And", indent)
+    }
+
+    fn calculate_positions(
+        &self,
+        memoization: &mut HashMap<Expression, (usize, usize)>,
+        position: &mut Vec<(String, (usize, usize))>,
+        prev_pos: (usize, usize),
+    ) {
+        self.infix()
+            .calculate_positions(memoization, position, prev_pos)
+    }
+
+    fn get_below_height(&self, memoization: &mut HashMap<Expression, (usize, usize)>) -> usize {
+        self.infix().get_below_height(memoization)
+    }
+
+    fn get_height(&self, memoization: &mut HashMap<Expression, (usize, usize)>) -> usize {
+        self.infix().get_height(memoization)
+    }
+
+    fn get_length(&self, memoization: &mut HashMap<Expression, (usize, usize)>) -> usize {
+        self.infix().get_length(memoization)
+    }
+}
@@ -0,0 +1,366 @@
+use std::collections::HashMap;
+
+use num_integer::Integer;
+use num_traits::{Signed, Zero};
+
+use crate::solver::{Bound, Interval};
+
+use super::{Expression, constant::Constant, function::FunctionType, numeral::Numeral};
+
+/// What is assumed about the values of a variable, all the properties hold
+/// at once.
+///
+/// The constructors set the properties implied by the one they name, a
+/// positive variable is also real and nonzero.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct Assumption {
+    pub real: bool,
+    pub positive: bool,
+    pub integer: bool,
+    pub nonzero: bool,
+    /// The values are in the interval, its bounds are evaluated numerically
+    pub interval: Option<Interval>,
+}
+
+impl Assumption {
+    pub fn real() -> Self {
+        Self {
+            real: true,
+            ..Default::default()
+        }
+    }
+
+    pub fn positive() -> Self {
+        Self {
+            real: true,
+            positive: true,
+            nonzero: true,
+            ..Default::default()
+        }
+    }
+
+    pub fn integer() -> Self {
+        Self {
+            real: true,
+            integer: true,
+            ..Default::default()
+        }
+    }
+
+    pub fn nonzero() -> Self {
+        Self {
+            nonzero: true,
+            ..Default::default()
+        }
+    }
+
+    pub fn interval(interval: Interval) -> Self {
+        Self {
+            real: true,
+            interval: Some(interval),
+            ..Default::default()
+        }
+    }
+
+    /// Both assumptions hold, the interval of `other` replaces the one of `self`.
+    pub fn and(self, other: Assumption) -> Self {
+        Self {
+            real: self.real || other.real,
+            positive: self.positive || other.positive,
+            integer: self.integer || other.integer,
+            nonzero: self.nonzero || other.nonzero,
+            interval: other.interval.or(self.interval),
+        }
+    }
+
+    fn facts(&self) -> Facts {
+        let (mut nonnegative, mut nonpositive, mut nonzero) =
+            (self.positive, false, self.nonzero || self.positive);
+        if let Some(interval) = &self.interval {
+            let lower = bound_value(&interval.lower);
+            let upper = bound_value(&interval.upper);
+            nonnegative |= lower.is_some_and(|(lower, _)| lower >= 0.0);
+            nonpositive |= upper.is_some_and(|(upper, _)| upper <= 0.0);
+            nonzero |= lower.is_some_and(|(lower, open)| lower > 0.0 || (lower == 0.0 && open))
+                || upper.is_some_and(|(upper, open)| upper < 0.0 || (upper == 0.0 && open));
+        }
+        Facts {
+            real: self.real || self.integer || self.interval.is_some(),
+            nonnegative,
+            nonpositive,
+            nonzero,
+            integer: self.integer,
+        }
+    }
+}
+
+/// The assumptions on the variables, given to the simplification with
+/// `SimplifyOptions::assumptions`.
+///
+/// A variable without assumption can take any value, so the rules that fail
+/// for some values, like `a/a => 1` at zero, are only applied when the
+/// assumptions prove them.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct Assumptions {
+    variables: HashMap<String, Assumption>,
+}
+
+impl Assumptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an assumption on `variable`, it is combined with the previous ones.
+    ///
+    /// # Examples
+    /// ```
+    /// use sym_rustic::ast::assumptions::{Assumption, Assumptions};
+    ///
+    /// let mut assumptions = Assumptions::new();
+    /// assumptions.assume("x", Assumption::integer());
+    /// assumptions.assume("x", Assumption::nonzero());
+    /// assert_eq!(assumptions.get("x").map(|x| (x.integer, x.nonzero)), Some((true, true)));
+    /// ```
+    pub fn assume(&mut self, variable: &str, assumption: Assumption) {
+        let previous = self.variables.remove(variable).unwrap_or_default();
+        self.variables
+            .insert(variable.to_string(), previous.and(assumption));
+    }
+
+    pub fn get(&self, variable: &str) -> Option<&Assumption> {
+        self.variables.get(variable)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.variables.is_empty()
+    }
+}
+
+/// What can be proven about the values of an expression.
+#[derive(Debug, Clone, Copy, Default)]
+struct Facts {
+    real: bool,
+    nonnegative: bool,
+    nonpositive: bool,
+    nonzero: bool,
+    integer: bool,
+}
+
+impl Facts {
+    fn number(numeral: &Numeral) -> Self {
+        Facts {
+            real: true,
+            nonnegative: !numeral.is_negative(),
+            nonpositive: numeral.is_negative() || numeral.is_zero(),
+            nonzero: !numeral.is_zero(),
+            integer: matches!(numeral, Numeral::Integer(_)),
+        }
+    }
+
+    fn positive() -> Self {
+        Facts {
+            real: true,
+            nonnegative: true,
+            nonzero: true,
+            ..Default::default()
+        }
+    }
+
+    fn neg(self) -> Self {
+        Facts {
+            nonnegative: self.nonpositive,
+            nonpositive: self.nonnegative,
+            ..self
+        }
+    }
+
+    fn add(self, other: Facts) -> Self {
+        let nonnegative = self.nonnegative && other.nonnegative;
+        let nonpositive = self.nonpositive && other.nonpositive;
+        Facts {
+            real: self.real && other.real,
+            nonnegative,
+            nonpositive,
+            nonzero: (nonnegative || nonpositive) && (self.nonzero || other.nonzero),
+            integer: self.integer && other.integer,
+        }
+    }
+
+    fn mul(self, other: Facts) -> Self {
+        let same = (self.nonnegative && other.nonnegative) || (self.nonpositive && other.nonpositive);
+        let opposite =
+            (self.nonnegative && other.nonpositive) || (self.nonpositive && other.nonnegative);
+        Facts {
+            real: self.real && other.real,
+            nonnegative: same,
+            nonpositive: opposite,
+            nonzero: self.nonzero && other.nonzero,
+            integer: self.integer && other.integer,
+        }
+    }
+
+    fn pow(self, expo: &Expression, expo_facts: Facts) -> Self {
+        match expo {
+            Expression::Number(Numeral::Integer(n)) if n.is_zero() => {
+                Facts::number(&Numeral::Integer(1.into()))
+            }
+            // An even power of a real is nonnegative, an odd power keeps the sign
+            Expression::Number(Numeral::Integer(n)) => {
+                let even = n.is_even();
+                Facts {
+                    real: self.real,
+                    nonnegative: if even { self.real } else { self.nonnegative },
+                    nonpositive: !even && self.nonpositive,
+                    nonzero: self.nonzero,
+                    integer: self.integer && n.is_positive(),
+                }
+            }
+            _ if self.real && self.nonnegative && self.nonzero && expo_facts.real => {
+                Facts::positive()
+            }
+            _ => Facts::default(),
+        }
+    }
+}
+
+impl Expression {
+    /// Returns `true` if the expression is proven real under the assumptions.
+    pub fn is_real(&self, assumptions: &Assumptions) -> bool {
+        self.facts(assumptions).real
+    }
+
+    /// Returns `true` if the expression is proven real and `>= 0` under the assumptions.
+    ///
+    /// # Examples
+    /// ```
+    /// use sym_rustic::ast::{Expression, assumptions::{Assumption, Assumptions}};
+    ///
+    /// let x = Expression::variable("x");
+    /// let square = Expression::exponentiation(x.clone(), Expression::integer(2));
+    /// let mut assumptions = Assumptions::new();
+    /// assert!(!x.is_nonnegative(&assumptions));
+    /// assert!(!square.is_nonnegative(&assumptions));
+    /// assumptions.assume("x", Assumption::real());
+    /// assert!(square.is_nonnegative(&assumptions));
+    /// ```
+    pub fn is_nonnegative(&self, assumptions: &Assumptions) -> bool {
+        let facts = self.facts(assumptions);
+        facts.real && facts.nonnegative
+    }
+
+    /// Returns `true` if the expression is proven real and `> 0` under the assumptions.
+    pub fn is_positive(&self, assumptions: &Assumptions) -> bool {
+        let facts = self.facts(assumptions);
+        facts.real && facts.nonnegative && facts.nonzero
+    }
+
+    /// Returns `true` if the expression is proven real and `<= 0` under the assumptions.
+    pub fn is_nonpositive(&self, assumptions: &Assumptions) -> bool {
+        let facts = self.facts(assumptions);
+        facts.real && facts.nonpositive
+    }
+
+    /// Returns `true` if the expression is proven different from zero under the assumptions.
+    pub fn is_nonzero(&self, assumptions: &Assumptions) -> bool {
+        self.facts(assumptions).nonzero
+    }
+
+    /// Returns `true` if the expression is proven to be an integer under the assumptions.
+    pub fn is_integer_valued(&self, assumptions: &Assumptions) -> bool {
+        self.facts(assumptions).integer
+    }
+
+    fn facts(&self, assumptions: &Assumptions) -> Facts {
+        let facts = |expr: &Expression| expr.facts(assumptions);
+        match self {
+            Expression::Number(numeral) => Facts::number(numeral),
+            Expression::Variable(var) => assumptions
+                .get(&var.name)
+                .map(Assumption::facts)
+                .unwrap_or_default(),
+            Expression::Constant(Constant::Pi | Constant::E | Constant::Tau) => Facts::positive(),
+            Expression::Negation(neg) => facts(&neg.term).neg(),
+            Expression::Addition(add) => add
+                .terms
+                .iter()
+                .map(facts)
+                .reduce(Facts::add)
+                .unwrap_or_default(),
+            Expression::Subtraction(sub) => facts(&sub.left).add(facts(&sub.right).neg()),
+            Expression::Multiplication(mul) => mul
+                .terms
+                .iter()
+                .map(facts)
+                .reduce(Facts::mul)
+                .unwrap_or_default(),
+            Expression::Division(div) => {
+                let den = facts(&div.den);
+                let quotient = facts(&div.num).mul(den);
+                Facts {
+                    real: quotient.real && den.nonzero,
+                    integer: false,
+                    ..quotient
+                }
+            }
+            Expression::Exponentiation(exp) => {
+                facts(&exp.base).pow(&exp.expo, facts(&exp.expo))
+            }
+            // a + b i with a and b real is zero only if both are
+            Expression::Complex(comp) => {
+                let (real, imag) = (facts(&comp.real), facts(&comp.imag));
+                Facts {
+                    nonzero: real.real && imag.real && (real.nonzero || imag.nonzero),
+                    ..Default::default()
+                }
+            }
+            Expression::Function(fun) => {
+                let arg = fun.args.last().map(facts).unwrap_or_default();
+                match fun.name {
+                    FunctionType::Abs => Facts {
+                        real: true,
+                        nonnegative: true,
+                        nonpositive: false,
+                        nonzero: arg.nonzero,
+                        integer: arg.integer,
+                    },
+                    FunctionType::Sqrt | FunctionType::Root if arg.real && arg.nonnegative => {
+                        Facts {
+                            real: true,
+                            nonnegative: true,
+                            nonpositive: false,
+                            nonzero: arg.nonzero,
+                            integer: false,
+                        }
+                    }
+                    FunctionType::Exp | FunctionType::Cosh if arg.real => Facts::positive(),
+                    FunctionType::Sin
+                    | FunctionType::Cos
+                    | FunctionType::Tan
+                    | FunctionType::Atan
+                    | FunctionType::Sinh
+                    | FunctionType::Tanh
+                    | FunctionType::Asinh
+                        if arg.real =>
+                    {
+                        Facts {
+                            real: true,
+                            ..Default::default()
+                        }
+                    }
+                    _ => Facts::default(),
+                }
+            }
+            _ => Facts::default(),
+        }
+    }
+}
+
+/// The numerical value of a bound and whether it is open.
+fn bound_value(bound: &Bound) -> Option<(f64, bool)> {
+    let env = HashMap::new();
+    match bound {
+        Bound::Unbounded => None,
+        Bound::Open(value) => value.evaluate(&env).ok().map(|value| (value, true)),
+        Bound::Closed(value) => value.evaluate(&env).ok().map(|value| (value, false)),
+    }
+}
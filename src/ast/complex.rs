@@ -1,5 +1,5 @@
 use crate::{
-    ast::{numeral, Expr, SimplifyError},
+    ast::{Expr, SimplifyError, SimplifyOptions},
     explanation::FormattingObserver, prints::PrettyPrints,
 };
 
@@ -26,11 +26,12 @@ impl Complex {
 impl Expr for Complex {
     fn simplify(
         &mut self,
+        options: &SimplifyOptions,
         explanation: &mut Option<Box<FormattingObserver>>,
     ) -> Result<Expression, SimplifyError> {
-        let real = self.real.simplify(explanation)?;
-        let imag = self.imag.simplify(explanation)?;
-        if imag == Expression::Number(numeral::Numeral::Integer(0)) {
+        let real = self.real.simplify_with(options, explanation)?;
+        let imag = self.imag.simplify_with(options, explanation)?;
+        if imag == Expression::integer(0) {
             Ok(real)
         } else {
             Ok(Expression::complex(real, imag))
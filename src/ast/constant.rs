@@ -1,5 +1,5 @@
 use crate::{
-    ast::{Expr, SimplifyError},
+    ast::{Expr, SimplifyError, SimplifyOptions},
     explanation::FormattingObserver, prints::PrettyPrints,
 };
 
@@ -10,11 +10,17 @@ pub enum Constant {
     Pi,
     E,
     Tau,
+    /// Positive infinity, only used as the point or the value of a limit
+    Infinity,
+    /// Truth values of the logic expressions, they evaluate to `1` and `0`
+    True,
+    False,
 }
 
 impl Expr for Constant {
     fn simplify(
         &mut self,
+        _options: &SimplifyOptions,
         _explanation: &mut Option<Box<FormattingObserver>>,
     ) -> Result<Expression, SimplifyError> {
         Ok(Expression::Constant(*self))
@@ -39,6 +45,9 @@ impl std::fmt::Display for Constant {
             Constant::Pi => write!(f, "pi"),
             Constant::E => write!(f, "e"),
             Constant::Tau => write!(f, "tau"),
+            Constant::Infinity => write!(f, "inf"),
+            Constant::True => write!(f, "true"),
+            Constant::False => write!(f, "false"),
         }
     }
 }
@@ -49,6 +58,9 @@ impl Constant {
             Constant::Pi => std::f64::consts::PI,
             Constant::E => std::f64::consts::E,
             Constant::Tau => std::f64::consts::TAU,
+            Constant::Infinity => f64::INFINITY,
+            Constant::True => 1.0,
+            Constant::False => 0.0,
         }
     }
 }
@@ -82,6 +94,9 @@ impl PrettyPrints for Constant {
             Constant::Pi => 2,
             Constant::E => 1,
             Constant::Tau => 3,
+            Constant::Infinity => 3,
+            Constant::True => 4,
+            Constant::False => 5,
         }
     }
 }
\ No newline at end of file
@@ -2,7 +2,7 @@ use std::vec;
 
 use crate::{ast::Expr, explanation::FormattingObserver, prints::PrettyPrints};
 
-use super::{Expression, SimplifyError, constant::Constant, function::FunctionType};
+use super::{Expression, SimplifyError, SimplifyOptions, constant::Constant, function::FunctionType, series::Series};
 
 #[derive(Debug, PartialEq, Clone, PartialOrd, Eq, Ord, Hash)]
 pub struct Derivative {
@@ -27,10 +27,11 @@ impl Derivative {
 impl Expr for Derivative {
     fn simplify(
         &mut self,
+        options: &SimplifyOptions,
         explanation: &mut Option<Box<FormattingObserver>>,
     ) -> Result<Expression, SimplifyError> {
-        self.term.simplify(explanation)?
-            .differentiate_n(&self.variable, self.order, explanation)
+        self.term.simplify_with(options, explanation)?
+            .differentiate_n_with(&self.variable, self.order, options, explanation)
     }
 
     fn is_equal(&self, other: &Derivative) -> bool {
@@ -83,6 +84,18 @@ impl Expression {
         variable: &str,
         order: u32,
         explanation: &mut Option<Box<FormattingObserver>>,
+    ) -> Result<Expression, SimplifyError> {
+        self.differentiate_n_with(variable, order, &SimplifyOptions::default(), explanation)
+    }
+
+    /// Returns the derivative of order `order` like `Expression::differentiate_n`,
+    /// the result is simplified with `options`.
+    pub fn differentiate_n_with(
+        &self,
+        variable: &str,
+        order: u32,
+        options: &SimplifyOptions,
+        explanation: &mut Option<Box<FormattingObserver>>,
     ) -> Result<Expression, SimplifyError> {
         if order == 0 {
             return Ok(self.clone());
@@ -90,28 +103,38 @@ impl Expression {
 
         let mut expr = self.clone();
 
-        if let Some(explanation) = explanation {
-            if order != 1 {
-                explanation.open_explaination(format!("We take {} derivative", order));
-            }
+        if let Some(explanation) = explanation
+            && order != 1
+        {
+            explanation.open_explaination(format!("We take {} derivative", order));
         }
 
         for i in 0..order {
-            if let Some(explanation) = explanation {
-                if order != 1 {
-                    explanation.open_explaination(format!("{} derivative", i + 1));
-                }
+            if let Some(explanation) = explanation
+                && order != 1
+            {
+                explanation.open_explaination(format!("{} derivative", i + 1));
             }
-            dbg!(&expr);
-            expr = expr.differentiate(variable, explanation)?;
+            expr = expr.differentiate_with(variable, options, explanation)?;
         }
-        expr.simplify(explanation)
+        expr.simplify_with(options, explanation)
     }
 
     pub fn differentiate(
         &mut self,
         variable: &str,
         explanation: &mut Option<Box<FormattingObserver>>,
+    ) -> Result<Expression, SimplifyError> {
+        self.differentiate_with(variable, &SimplifyOptions::default(), explanation)
+    }
+
+    /// Returns the derivative like `Expression::differentiate`, every step is
+    /// simplified with `options`.
+    pub fn differentiate_with(
+        &mut self,
+        variable: &str,
+        options: &SimplifyOptions,
+        explanation: &mut Option<Box<FormattingObserver>>,
     ) -> Result<Expression, SimplifyError> {
         let before = Expression::derivative(self.clone(), variable, 1);
         let mut result = match self {
@@ -167,7 +190,7 @@ impl Expression {
                 }
 
                 Ok(Expression::negation(
-                    neg.term.differentiate(variable, explanation)?,
+                    neg.term.differentiate_with(variable, options, explanation)?,
                 ))
             }
             Expression::Addition(add) => {
@@ -187,7 +210,7 @@ impl Expression {
                 Ok(Expression::addition(
                     add.terms
                         .iter_mut()
-                        .map(|expr| expr.differentiate(variable, explanation))
+                        .map(|expr| expr.differentiate_with(variable, options, explanation))
                         .collect::<Result<Vec<Expression>, _>>()?,
                 ))
             }
@@ -204,51 +227,51 @@ impl Expression {
                     );
                 }
                 Ok(Expression::subtraction(
-                    sub.left.differentiate(variable, explanation)?,
-                    sub.right.differentiate(variable, explanation)?,
+                    sub.left.differentiate_with(variable, options, explanation)?,
+                    sub.right.differentiate_with(variable, options, explanation)?,
                 ))
             }
             Expression::Multiplication(mul) => {
                 let mut first = mul.terms[0].clone();
                 if mul.terms.len() > 1 {
-                    if let Some(explanation) = explanation {
-                        if let Some(rest_expr) = mul.terms.get(1..) {
-                            let after = Expression::addition(vec![
-                                Expression::multiplication(vec![
-                                    first.clone(),
-                                    Expression::derivative(
-                                        Expression::multiplication(rest_expr.to_vec()),
-                                        variable,
-                                        1,
-                                    ),
-                                ]),
-                                Expression::multiplication(vec![
-                                    Expression::derivative(first.clone(), variable, 1),
+                    if let Some(explanation) = explanation
+                        && let Some(rest_expr) = mul.terms.get(1..)
+                    {
+                        let after = Expression::addition(vec![
+                            Expression::multiplication(vec![
+                                first.clone(),
+                                Expression::derivative(
                                     Expression::multiplication(rest_expr.to_vec()),
-                                ]),
-                            ]);
-                            explanation.rule_applied(
-                                "Derivative of product is given by\n(f*g)' => f' * g + f * g'",
-                                &before,
-                                &after,
-                            );
-                        }
+                                    variable,
+                                    1,
+                                ),
+                            ]),
+                            Expression::multiplication(vec![
+                                Expression::derivative(first.clone(), variable, 1),
+                                Expression::multiplication(rest_expr.to_vec()),
+                            ]),
+                        ]);
+                        explanation.rule_applied(
+                            "Derivative of product is given by\n(f*g)' => f' * g + f * g'",
+                            &before,
+                            &after,
+                        );
                     }
                     if let Some(rest_expr) = mul.terms.get(1..) {
                         let mut rest = Expression::multiplication(rest_expr.to_vec());
 
-                        let d_first = first.differentiate(variable, explanation)?;
-                        let d_rest = rest.differentiate(variable, explanation)?;
+                        let d_first = first.differentiate_with(variable, options, explanation)?;
+                        let d_rest = rest.differentiate_with(variable, options, explanation)?;
 
                         Ok(Expression::addition(vec![
                             Expression::multiplication(vec![first, d_rest]),
                             Expression::multiplication(vec![d_first, rest]),
                         ]))
                     } else {
-                        first.differentiate(variable, explanation)
+                        first.differentiate_with(variable, options, explanation)
                     }
                 } else {
-                    first.differentiate(variable, explanation)
+                    first.differentiate_with(variable, options, explanation)
                 }
             }
             Expression::Division(div) => {
@@ -272,8 +295,8 @@ impl Expression {
                         &after,
                     );
                 }
-                let df = div.num.differentiate(variable, explanation)?;
-                let dg = div.den.differentiate(variable, explanation)?;
+                let df = div.num.differentiate_with(variable, options, explanation)?;
+                let dg = div.den.differentiate_with(variable, options, explanation)?;
 
                 Ok(Expression::division(
                     Expression::subtraction(
@@ -306,18 +329,22 @@ impl Expression {
                         }
                         Ok(after)
                     }
-                    // f^a => a * f^(a-1)
+                    // f^a => a * f^(a-1) * f'
                     (true, false) => {
-                        let after = Expression::multiplication(vec![
+                        let mut terms = vec![
                             exp.expo.clone(),
                             Expression::exponentiation(
                                 exp.base.clone(),
                                 Expression::subtraction(exp.expo.clone(), Expression::integer(1)),
                             ),
-                        ]);
+                        ];
+                        if !matches!(&exp.base, Expression::Variable(var) if var.name == variable) {
+                            terms.push(Expression::derivative(exp.base.clone(), variable, 1));
+                        }
+                        let after = Expression::multiplication(terms);
                         if let Some(explanation) = explanation {
                             explanation.rule_applied(
-                                "Derivative of exponentiation is given by\n(f^a)' => a * f^(a-1)'",
+                                "Derivative of exponentiation is given by\n(f^a)' => a * f^(a-1) * f'",
                                 &before,
                                 &after,
                             );
@@ -373,9 +400,10 @@ impl Expression {
                 }
             }
             Expression::Function(fun) => {
-                Self::differentiate_function(&fun.name, &fun.args, variable, explanation)
+                Self::differentiate_function(&fun.name, &fun.args, variable, options, explanation)
             }
             Expression::Equality(_equ) => Err(SimplifyError::Unsupported),
+            Expression::Inequality(_) => Err(SimplifyError::Unsupported),
             Expression::Derivative(der) => {
                 if let Some(explanation) = explanation {
                     let after = Expression::derivative(
@@ -389,22 +417,148 @@ impl Expression {
                         &after,
                     );
                 }
-                let expr_diff = der.term.differentiate(variable, explanation)?;
+                let expr_diff = der.term.differentiate_with(variable, options, explanation)?;
                 Ok(Expression::derivative(expr_diff, variable, der.order))
             }
+            Expression::Integral(int) => match &int.bounds {
+                None if int.variable == variable => {
+                    let after = int.term.clone();
+                    if let Some(explanation) = explanation {
+                        explanation.rule_applied(
+                            "Derivative of an integral is given by\n(∫f dx)' => f",
+                            &before,
+                            &after,
+                        );
+                    }
+                    Ok(after)
+                }
+                Some((lower, upper))
+                    if int.variable == variable || !int.term.contains_var(variable) =>
+                {
+                    let at = |bound: &Expression| {
+                        int.term.substitute(&Expression::variable(&int.variable), bound)
+                    };
+                    let after = Expression::subtraction(
+                        Expression::multiplication(vec![
+                            at(upper),
+                            Expression::derivative(upper.clone(), variable, 1),
+                        ]),
+                        Expression::multiplication(vec![
+                            at(lower),
+                            Expression::derivative(lower.clone(), variable, 1),
+                        ]),
+                    );
+                    if let Some(explanation) = explanation {
+                        explanation.rule_applied(
+                            "Leibniz integral rule\n(∫[a, b] f(t) dt)' => f(b)*b' - f(a)*a'",
+                            &before,
+                            &after,
+                        );
+                    }
+                    Ok(after)
+                }
+                _ => Err(SimplifyError::Unsupported),
+            },
+            Expression::Limit(_) => Err(SimplifyError::Unsupported),
+            Expression::Series(series) if series.variable == variable && series.order > 0 => {
+                let after = Expression::Series(Box::new(Series::new(
+                    series.terms.differentiate_with(variable, options, explanation)?,
+                    series.variable.clone(),
+                    series.point.clone(),
+                    series.order - 1,
+                    false,
+                )));
+                if let Some(explanation) = explanation {
+                    explanation.rule_applied(
+                        "Derivative of a series is taken term by term\n(f + O(x^n))' => f' + O(x^(n-1))",
+                        &before,
+                        &after,
+                    );
+                }
+                Ok(after)
+            }
+            Expression::Series(_) => Err(SimplifyError::Unsupported),
+            Expression::Summation(sum)
+                if sum.variable != variable
+                    && !sum.lower.contains_var(variable)
+                    && !sum.upper.contains_var(variable) =>
+            {
+                if let Some(explanation) = explanation {
+                    let after = Expression::summation(
+                        Expression::derivative(sum.term.clone(), variable, 1),
+                        &sum.variable,
+                        sum.lower.clone(),
+                        sum.upper.clone(),
+                    );
+                    explanation.rule_applied(
+                        "Derivative of a sum is given by\n(Σ(k=a..b) f)' => Σ(k=a..b) f'",
+                        &before,
+                        &after,
+                    );
+                }
+                Ok(Expression::summation(
+                    sum.term.differentiate_with(variable, options, explanation)?,
+                    &sum.variable,
+                    sum.lower.clone(),
+                    sum.upper.clone(),
+                ))
+            }
+            Expression::Summation(_) | Expression::Product(_) => Err(SimplifyError::Unsupported),
+            Expression::And(_) | Expression::Or(_) | Expression::Not(_) | Expression::Xor(_) => {
+                Err(SimplifyError::Unsupported)
+            }
+            Expression::Matrix(matrix) => {
+                let after = Expression::matrix(
+                    matrix
+                        .rows
+                        .iter()
+                        .map(|row| {
+                            row.iter()
+                                .map(|entry| Expression::derivative(entry.clone(), variable, 1))
+                                .collect()
+                        })
+                        .collect(),
+                );
+                if let Some(explanation) = explanation {
+                    explanation.rule_applied(
+                        "Derivative of a matrix is taken entry by entry\n(A')_ij => (A_ij)'",
+                        &before,
+                        &after,
+                    );
+                }
+                Ok(after)
+            }
+            Expression::Vector(vector) => {
+                let after = Expression::vector(
+                    vector
+                        .entries
+                        .iter()
+                        .map(|entry| Expression::derivative(entry.clone(), variable, 1))
+                        .collect(),
+                );
+                if let Some(explanation) = explanation {
+                    explanation.rule_applied(
+                        "Derivative of a vector is taken entry by entry\n(v')_i => (v_i)'",
+                        &before,
+                        &after,
+                    );
+                }
+                Ok(after)
+            }
             Expression::Complex(_com) => {
                 // TODO
                 Err(SimplifyError::Unsupported)
             }
         }?;
 
-        result.simplify(explanation)
+        result.simplify_with(options, explanation)
     }
 
     fn differentiate_function(
         func: &FunctionType,
         args: &[Expression],
         variable: &str,
+        options: &SimplifyOptions,
         explanation: &mut Option<Box<FormattingObserver>>,
     ) -> Result<Expression, SimplifyError> {
         let before = Expression::derivative(
@@ -1011,7 +1165,7 @@ impl Expression {
             }
         };
 
-        result.simplify(explanation)
+        result.simplify_with(options, explanation)
     }
 
     fn quotient_rule(expr: &Expression, variable: &str) -> Option<Expression> {
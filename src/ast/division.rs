@@ -1,5 +1,5 @@
 use crate::{
-    ast::{complex::Complex, numeral, Expr, Expression, SimplifyError},
+    ast::{complex::Complex, Expr, Expression, SimplifyError, SimplifyOptions},
     explanation::FormattingObserver, prints::PrettyPrints,
 };
 
@@ -24,11 +24,21 @@ impl Division {
 impl Expr for Division {
     fn simplify(
         &mut self,
+        options: &SimplifyOptions,
         explanation: &mut Option<Box<FormattingObserver>>,
     ) -> Result<Expression, SimplifyError> {
-        let num = self.num.simplify(explanation)?;
-        let den = self.den.simplify(explanation)?;
-        self.simplify_division(num, den, explanation)
+        let num = self.num.simplify_with(options, explanation)?;
+        let den = self.den.simplify_with(options, explanation)?;
+        // A/B => A*B^-1 and A/c => (1/c)*A
+        if den.is_matrix() {
+            let inverse = Expression::power_matrix(den, Expression::integer(-1), options, explanation)?;
+            return Expression::multiply_matrices(vec![num, inverse], options, explanation);
+        }
+        if num.is_matrix() {
+            let inverse = Expression::division(Expression::integer(1), den).simplify_with(options, explanation)?;
+            return Expression::multiply_matrices(vec![inverse, num], options, explanation);
+        }
+        self.simplify_division(num, den, options, explanation)
     }
 
     fn is_equal(&self, other: &Division) -> bool {
@@ -68,16 +78,17 @@ impl Division {
         &mut self,
         lhs: Expression,
         rhs: Expression,
+        options: &SimplifyOptions,
         explanation: &mut Option<Box<FormattingObserver>>,
     ) -> Result<Expression, SimplifyError> {
         let before = Expression::division(lhs.clone(), rhs.clone());
         match (lhs, rhs) {
             // a/0 => DivisionByZero
-            (_, Expression::Number(numeral::Numeral::Integer(0))) => {
+            (_, Expression::Number(den)) if den.is_zero() => {
                 Err(SimplifyError::DivisionByZero)
             }
             // a/1 => a
-            (lhs, Expression::Number(numeral::Numeral::Integer(1))) => {
+            (lhs, Expression::Number(den)) if den.is_integer(1) => {
                 let after = lhs;
                 if let Some(explanation) = explanation {
                     explanation.rule_applied("Division by one stay the same", &before, &after);
@@ -86,7 +97,7 @@ impl Division {
                 Ok(after)
             }
             // 0/a => 0
-            (Expression::Number(numeral::Numeral::Integer(0)), _) => {
+            (Expression::Number(num), _) if num.is_integer(0) => {
                 if let Some(explanation) = explanation {
                     explanation.rule_applied(
                         "Zero divided by something stay zero",
@@ -96,8 +107,8 @@ impl Division {
                 }
                 Ok(Expression::integer(0))
             }
-            // a/a => 1
-            (lhs, rhs) if lhs.is_equal(&rhs) => {
+            // a/a => 1 when a is proven nonzero
+            (lhs, rhs) if lhs.is_equal(&rhs) && lhs.is_nonzero(&options.assumptions) => {
                 if let Some(explanation) = explanation {
                     explanation.rule_applied(
                         &format!("Simplify by the common factor {}", lhs),
@@ -109,7 +120,7 @@ impl Division {
             }
             // a/b where a & b are numeral
             (Expression::Number(lhs), Expression::Number(rhs)) => {
-                Expression::Number(lhs.div(&rhs)).simplify(explanation)
+                Expression::Number(lhs.div(&rhs)).simplify_with(options, explanation)
             }
             // a/(b/c) => (a*c)/b
             (lhs, Expression::Division(rhs)) => {
@@ -124,7 +135,7 @@ impl Division {
                         &after,
                     );
                 }
-                after.simplify(explanation)
+                after.simplify_with(options, explanation)
             }
             // (a/b)/c => a/(b*c)
             (Expression::Division(lhs), rhs) => {
@@ -137,7 +148,7 @@ impl Division {
                         &after,
                     );
                 }
-                after.simplify(explanation)
+                after.simplify_with(options, explanation)
             }
             // (-a)/b => -(a/b)
             (Expression::Negation(inner), rhs) => {
@@ -149,7 +160,7 @@ impl Division {
                         &after,
                     );
                 }
-                after.simplify(explanation)
+                after.simplify_with(options, explanation)
             }
             // a/(-b) => -(a/b)
             (lhs, Expression::Negation(inner)) => {
@@ -161,7 +172,7 @@ impl Division {
                         &after,
                     );
                 }
-                after.simplify(explanation)
+                after.simplify_with(options, explanation)
             }
             // // a^x / a => a^(x-1)
             // (Expression::Exponentiation(base, exp), rhs) if base.is_equal(&rhs) => {
@@ -175,7 +186,7 @@ impl Division {
             //             ))),
             //         ])),
             //     )
-            //     .simplify(explanation)
+            //     .simplify_with(options, explanation)
             // }
             // // a^x / a^y => a^(x-y)
             // (
@@ -190,7 +201,7 @@ impl Division {
             //             Expression::Negation(Box::new(*rhs_exp)),
             //         ])),
             //     )
-            //     .simplify(explanation)
+            //     .simplify_with(options, explanation)
             // }
             // // a/sqrt(b) => a*sqrt(b)/b
             // (a, Expression::Function(function::Function::Sqrt, args)) => {
@@ -202,7 +213,7 @@ impl Division {
             //         ])),
             //         Box::new(args[0].clone()),
             //     )
-            //     .simplify(explanation)
+            //     .simplify_with(options, explanation)
             // }
             // c/complex(a, b) => (c*complex(a, b))/(complex(a, b)*complcomplex(a, b))
             (lhs, Expression::Complex(comp)) => {
@@ -217,7 +228,28 @@ impl Division {
                 if let Some(explanation) = explanation {
                     explanation.rule_applied("We take the complex out of the denominator by multiplying by the conjugate\nc/(a + b i) => (c*(a - b i))/((a + b i)(a - b i))", &before, &after);
                 }
-                after.simplify(explanation)
+                after.simplify_with(options, explanation)
+            }
+            // a/sqrt(b) => a*sqrt(b)/b and a/(p + q*sqrt(c)) => a*(p - q*sqrt(c))/(p^2 - q^2*c)
+            (lhs, rhs) if let Some((rule, mut after)) = Expression::rationalize(&lhs, &rhs) => {
+                if let Some(explanation) = explanation {
+                    explanation.rule_applied(rule, &before, &after);
+                }
+                after.simplify_with(options, explanation)
+            }
+            // (a*c)/(b*c) => a/b with a, b and c polynomials, c proven nonzero
+            (lhs, rhs) if let Some((num, den, factor)) = Expression::cancel_fraction(&lhs, &rhs)
+                && factor.is_nonzero(&options.assumptions) =>
+            {
+                let mut after = Expression::division(num, den);
+                if let Some(explanation) = explanation {
+                    explanation.rule_applied(
+                        &format!("Simplify by the common factor {}", factor),
+                        &before,
+                        &after,
+                    );
+                }
+                after.simplify_with(options, explanation)
             }
             // Default case
             (lhs, rhs) => {
@@ -230,7 +262,7 @@ impl Division {
                 //         )))),
                 //     ),
                 // ])
-                // .simplify(explanation)
+                // .simplify_with(options, explanation)
                 Ok(Expression::division(lhs, rhs))
             }
         }
@@ -292,7 +324,6 @@ impl PrettyPrints for Division {
                 if top {
                     pos.1 += span;
                     self.num.calculate_positions(memoization, position, pos);
-                    pos.1 -= span;
                 } else {
                     self.num.calculate_positions(memoization, position, pos);
                 }
@@ -1,5 +1,5 @@
 use crate::{
-    ast::{Expr, SimplifyError},
+    ast::{Expr, SimplifyError, SimplifyOptions},
     explanation::FormattingObserver, prints::PrettyPrints,
 };
 
@@ -26,10 +26,11 @@ impl Equality {
 impl Expr for Equality {
     fn simplify(
         &mut self,
+        options: &SimplifyOptions,
         explanation: &mut Option<Box<FormattingObserver>>,
     ) -> Result<Expression, SimplifyError> {
-        let left = self.left.simplify(explanation)?;
-        let right = self.right.simplify(explanation)?;
+        let left = self.left.simplify_with(options, explanation)?;
+        let right = self.right.simplify_with(options, explanation)?;
         Ok(Expression::equality(left, right))
         
     }
@@ -0,0 +1,556 @@
+use std::collections::HashMap;
+
+use num_complex::Complex64;
+use num_traits::Zero;
+
+use super::{Expression, function::FunctionType, numeral::Numeral};
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum EvalError {
+    /// The variable has no value in the environment
+    UnboundVariable(String),
+    DivisionByZero,
+    ZeroExponentiationZero,
+    /// The function is not defined at this value, like `ln(-1)` for a real
+    /// evaluation. Exponentiations are reported as `FunctionType::Pow`.
+    Domain(FunctionType, f64),
+    /// The value has an imaginary part, use `Expression::evaluate_complex`
+    ComplexValue,
+    /// The numerical quadrature of a definite integral did not reach the tolerance
+    NoConvergence,
+    /// Equations, derivatives, limits, indefinite integrals, infinite sums and matrices
+    /// have no numerical value
+    Unsupported,
+}
+
+/// Maximum number of terms added or multiplied when evaluating a sum or a product.
+const MAX_EVALUATED_TERMS: f64 = 1e6;
+/// Relative tolerance of the numerical quadrature.
+const QUADRATURE_TOLERANCE: f64 = 1e-10;
+/// Maximum number of intervals the quadrature is split into.
+const QUADRATURE_INTERVALS: usize = 2000;
+
+/// Positive nodes of the 15 points Kronrod rule, the odd ones are the 7 points Gauss nodes.
+const KRONROD_NODES: [f64; 8] = [
+    0.991_455_371_120_812_6,
+    0.949_107_912_342_758_5,
+    0.864_864_423_359_769_1,
+    0.741_531_185_599_394_4,
+    0.586_087_235_467_691_1,
+    0.405_845_151_377_397_2,
+    0.207_784_955_007_898_5,
+    0.0,
+];
+const KRONROD_WEIGHTS: [f64; 8] = [
+    0.022_935_322_010_529_22,
+    0.063_092_092_629_978_55,
+    0.104_790_010_322_250_18,
+    0.140_653_259_715_525_92,
+    0.169_004_726_639_267_9,
+    0.190_350_578_064_785_4,
+    0.204_432_940_075_298_9,
+    0.209_482_141_084_727_83,
+];
+const GAUSS_WEIGHTS: [f64; 4] = [
+    0.129_484_966_168_869_7,
+    0.279_705_391_489_276_7,
+    0.381_830_050_505_118_9,
+    0.417_959_183_673_469_4,
+];
+
+// Numerical evaluation
+impl Expression {
+    /// Evaluate the expression to a real number, the variables take their
+    /// value from `env`.
+    ///
+    /// Odd roots of negative numbers are real, `root(3, -8)` is `-2`.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::collections::HashMap;
+    /// use sym_rustic::ast::Expression;
+    ///
+    /// let expr = Expression::addition(vec![
+    ///     Expression::exponentiation(
+    ///         Expression::sin(Expression::variable("x")),
+    ///         Expression::integer(2),
+    ///     ),
+    ///     Expression::multiplication(vec![Expression::pi(), Expression::variable("y")]),
+    /// ]);
+    /// let env = HashMap::from([("x".to_string(), 0.0), ("y".to_string(), 2.0)]);
+    /// assert_eq!(expr.evaluate(&env), Ok(2.0 * std::f64::consts::PI));
+    /// ```
+    pub fn evaluate(&self, env: &HashMap<String, f64>) -> Result<f64, EvalError> {
+        match self {
+            Expression::Number(numeral) => evaluate_numeral(numeral),
+            Expression::Variable(variable) => env
+                .get(&variable.name)
+                .copied()
+                .ok_or_else(|| EvalError::UnboundVariable(variable.name.clone())),
+            Expression::Constant(constant) => Ok(constant.evaluate()),
+            Expression::Negation(neg) => Ok(-neg.term.evaluate(env)?),
+            Expression::Addition(add) => add.terms.iter().map(|term| term.evaluate(env)).sum(),
+            Expression::Multiplication(mul) => {
+                mul.terms.iter().map(|term| term.evaluate(env)).product()
+            }
+            Expression::Subtraction(sub) => Ok(sub.left.evaluate(env)? - sub.right.evaluate(env)?),
+            Expression::Division(div) => divide(div.num.evaluate(env)?, div.den.evaluate(env)?),
+            Expression::Exponentiation(exp) => {
+                power(exp.base.evaluate(env)?, exp.expo.evaluate(env)?)
+            }
+            Expression::Complex(comp) => {
+                if comp.imag.evaluate(env)? == 0.0 {
+                    comp.real.evaluate(env)
+                } else {
+                    Err(EvalError::ComplexValue)
+                }
+            }
+            Expression::Function(function) => {
+                let args = function
+                    .args
+                    .iter()
+                    .map(|arg| arg.evaluate(env))
+                    .collect::<Result<Vec<_>, _>>()?;
+                apply_real(&function.name, &args)
+            }
+            Expression::Integral(int) => match &int.bounds {
+                Some((lower, upper)) => int
+                    .term
+                    .quadrature(&int.variable, lower.evaluate(env)?, upper.evaluate(env)?, env)
+                    .map(|(value, _)| value),
+                None => Err(EvalError::Unsupported),
+            },
+            // The remainder is dropped
+            Expression::Series(series) => series.terms.evaluate(env),
+            Expression::Summation(sum) => {
+                let mut env = env.clone();
+                integer_range(sum.lower.evaluate(&env)?, sum.upper.evaluate(&env)?)?
+                    .map(|k| {
+                        env.insert(sum.variable.clone(), k as f64);
+                        sum.term.evaluate(&env)
+                    })
+                    .sum()
+            }
+            Expression::Product(prod) => {
+                let mut env = env.clone();
+                integer_range(prod.lower.evaluate(&env)?, prod.upper.evaluate(&env)?)?
+                    .map(|k| {
+                        env.insert(prod.variable.clone(), k as f64);
+                        prod.term.evaluate(&env)
+                    })
+                    .product()
+            }
+            // The logic connectives give 1 for true and 0 for false, a non zero operand is true
+            Expression::And(and) => and
+                .terms
+                .iter()
+                .try_fold(true, |value, term| Ok(value & (term.evaluate(env)? != 0.0)))
+                .map(truth),
+            Expression::Or(or) => or
+                .terms
+                .iter()
+                .try_fold(false, |value, term| Ok(value | (term.evaluate(env)? != 0.0)))
+                .map(truth),
+            Expression::Not(not) => Ok(truth(not.term.evaluate(env)? == 0.0)),
+            Expression::Xor(xor) => {
+                Ok(truth((xor.left.evaluate(env)? != 0.0) != (xor.right.evaluate(env)? != 0.0)))
+            }
+            Expression::Inequality(ineq) => {
+                let difference = ineq.left.evaluate(env)? - ineq.right.evaluate(env)?;
+                let ordering = difference.partial_cmp(&0.0).ok_or(EvalError::Unsupported)?;
+                Ok(truth(ineq.relation.holds(ordering)))
+            }
+            Expression::Equality(_)
+            | Expression::Derivative(_)
+            | Expression::Limit(_)
+            | Expression::Matrix(_)
+            | Expression::Vector(_) => Err(EvalError::Unsupported),
+        }
+    }
+
+    /// Approximate `∫[lower, upper] self dvariable` with adaptive Gauss–Kronrod
+    /// quadrature, returns the value and its estimated absolute error.
+    ///
+    /// The integrand is never evaluated at the bounds, so an integrable
+    /// singularity there is accepted.
+    pub(crate) fn quadrature(
+        &self,
+        variable: &str,
+        lower: f64,
+        upper: f64,
+        env: &HashMap<String, f64>,
+    ) -> Result<(f64, f64), EvalError> {
+        let mut env = env.clone();
+        let mut f = |x: f64| {
+            env.insert(variable.to_string(), x);
+            self.evaluate(&env)
+        };
+
+        // The interval with the largest error is split until the sum is precise enough
+        let mut intervals = vec![(lower, upper, gauss_kronrod(&mut f, lower, upper)?)];
+        loop {
+            let total: f64 = intervals.iter().map(|(_, _, (value, _))| value).sum();
+            let total_error: f64 = intervals.iter().map(|(_, _, (_, error))| error).sum();
+            if total_error <= QUADRATURE_TOLERANCE * total.abs().max(1.0) {
+                return if total.is_finite() {
+                    Ok((total, total_error))
+                } else {
+                    Err(EvalError::NoConvergence)
+                };
+            }
+            if intervals.len() >= QUADRATURE_INTERVALS || !total_error.is_finite() {
+                return Err(EvalError::NoConvergence);
+            }
+
+            let worst = (0..intervals.len())
+                .max_by(|lhs, rhs| intervals[*lhs].2.1.total_cmp(&intervals[*rhs].2.1))
+                .unwrap_or_default();
+            let (a, b, _) = intervals.swap_remove(worst);
+            let middle = (a + b) / 2.0;
+            intervals.push((a, middle, gauss_kronrod(&mut f, a, middle)?));
+            intervals.push((middle, b, gauss_kronrod(&mut f, middle, b)?));
+        }
+    }
+
+    /// Evaluate the expression to a complex number, the variables take their
+    /// value from `env`.
+    ///
+    /// Every function uses its principal branch, `sqrt(-4)` is `2i` and
+    /// `root(3, -8)` is `1 + sqrt(3)i`.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::collections::HashMap;
+    /// use num_complex::Complex64;
+    /// use sym_rustic::ast::Expression;
+    ///
+    /// let expr = Expression::sqrt(Expression::variable("z"));
+    /// let env = HashMap::from([("z".to_string(), Complex64::new(-4.0, 0.0))]);
+    /// assert_eq!(expr.evaluate_complex(&env), Ok(Complex64::new(0.0, 2.0)));
+    /// ```
+    pub fn evaluate_complex(
+        &self,
+        env: &HashMap<String, Complex64>,
+    ) -> Result<Complex64, EvalError> {
+        match self {
+            Expression::Number(numeral) => Ok(Complex64::from(evaluate_numeral(numeral)?)),
+            Expression::Variable(variable) => env
+                .get(&variable.name)
+                .copied()
+                .ok_or_else(|| EvalError::UnboundVariable(variable.name.clone())),
+            Expression::Constant(constant) => Ok(Complex64::from(constant.evaluate())),
+            Expression::Negation(neg) => Ok(-neg.term.evaluate_complex(env)?),
+            Expression::Addition(add) => add
+                .terms
+                .iter()
+                .map(|term| term.evaluate_complex(env))
+                .sum(),
+            Expression::Multiplication(mul) => mul
+                .terms
+                .iter()
+                .map(|term| term.evaluate_complex(env))
+                .product(),
+            Expression::Subtraction(sub) => {
+                Ok(sub.left.evaluate_complex(env)? - sub.right.evaluate_complex(env)?)
+            }
+            Expression::Division(div) => divide_complex(
+                div.num.evaluate_complex(env)?,
+                div.den.evaluate_complex(env)?,
+            ),
+            Expression::Exponentiation(exp) => power_complex(
+                exp.base.evaluate_complex(env)?,
+                exp.expo.evaluate_complex(env)?,
+            ),
+            Expression::Complex(comp) => Ok(comp.real.evaluate_complex(env)?
+                + Complex64::i() * comp.imag.evaluate_complex(env)?),
+            Expression::Function(function) => {
+                let args = function
+                    .args
+                    .iter()
+                    .map(|arg| arg.evaluate_complex(env))
+                    .collect::<Result<Vec<_>, _>>()?;
+                apply_complex(&function.name, &args)
+            }
+            Expression::Series(series) => series.terms.evaluate_complex(env),
+            Expression::Summation(sum) => {
+                let mut env = env.clone();
+                integer_range(
+                    real_bound(sum.lower.evaluate_complex(&env)?)?,
+                    real_bound(sum.upper.evaluate_complex(&env)?)?,
+                )?
+                .map(|k| {
+                    env.insert(sum.variable.clone(), Complex64::from(k as f64));
+                    sum.term.evaluate_complex(&env)
+                })
+                .sum()
+            }
+            Expression::Product(prod) => {
+                let mut env = env.clone();
+                integer_range(
+                    real_bound(prod.lower.evaluate_complex(&env)?)?,
+                    real_bound(prod.upper.evaluate_complex(&env)?)?,
+                )?
+                .map(|k| {
+                    env.insert(prod.variable.clone(), Complex64::from(k as f64));
+                    prod.term.evaluate_complex(&env)
+                })
+                .product()
+            }
+            Expression::And(and) => and
+                .terms
+                .iter()
+                .try_fold(true, |value, term| Ok(value & !term.evaluate_complex(env)?.is_zero()))
+                .map(|value| Complex64::from(truth(value))),
+            Expression::Or(or) => or
+                .terms
+                .iter()
+                .try_fold(false, |value, term| Ok(value | !term.evaluate_complex(env)?.is_zero()))
+                .map(|value| Complex64::from(truth(value))),
+            Expression::Not(not) => Ok(Complex64::from(truth(not.term.evaluate_complex(env)?.is_zero()))),
+            Expression::Xor(xor) => Ok(Complex64::from(truth(
+                xor.left.evaluate_complex(env)?.is_zero() != xor.right.evaluate_complex(env)?.is_zero(),
+            ))),
+            // Only real values can be compared
+            Expression::Inequality(ineq) => {
+                let difference =
+                    ineq.left.evaluate_complex(env)? - ineq.right.evaluate_complex(env)?;
+                if difference.im != 0.0 {
+                    return Err(EvalError::ComplexValue);
+                }
+                let ordering = difference.re.partial_cmp(&0.0).ok_or(EvalError::Unsupported)?;
+                Ok(Complex64::from(truth(ineq.relation.holds(ordering))))
+            }
+            Expression::Equality(_)
+            | Expression::Derivative(_)
+            | Expression::Integral(_)
+            | Expression::Limit(_)
+            | Expression::Matrix(_)
+            | Expression::Vector(_) => Err(EvalError::Unsupported),
+        }
+    }
+}
+
+/// The numerical value of a truth value.
+fn truth(value: bool) -> f64 {
+    if value { 1.0 } else { 0.0 }
+}
+
+/// 15 points Kronrod rule on `[a, b]`, the difference with the embedded
+/// 7 points Gauss rule is the error estimate.
+fn gauss_kronrod(
+    f: &mut impl FnMut(f64) -> Result<f64, EvalError>,
+    a: f64,
+    b: f64,
+) -> Result<(f64, f64), EvalError> {
+    let center = (a + b) / 2.0;
+    let half = (b - a) / 2.0;
+
+    let value = f(center)?;
+    let mut kronrod = KRONROD_WEIGHTS[7] * value;
+    let mut gauss = GAUSS_WEIGHTS[3] * value;
+    for i in 0..7 {
+        let offset = half * KRONROD_NODES[i];
+        let sum = f(center - offset)? + f(center + offset)?;
+        kronrod += KRONROD_WEIGHTS[i] * sum;
+        if i % 2 == 1 {
+            gauss += GAUSS_WEIGHTS[i / 2] * sum;
+        }
+    }
+    Ok((kronrod * half, ((kronrod - gauss) * half).abs()))
+}
+
+/// The integers from `lower` to `upper`, the index of a sum or a product.
+fn integer_range(lower: f64, upper: f64) -> Result<std::ops::RangeInclusive<i64>, EvalError> {
+    if lower.fract() != 0.0 || upper.fract() != 0.0 || upper - lower >= MAX_EVALUATED_TERMS {
+        return Err(EvalError::Unsupported);
+    }
+    Ok(lower as i64..=upper as i64)
+}
+
+fn real_bound(bound: Complex64) -> Result<f64, EvalError> {
+    if bound.im == 0.0 {
+        Ok(bound.re)
+    } else {
+        Err(EvalError::Unsupported)
+    }
+}
+
+fn evaluate_numeral(numeral: &Numeral) -> Result<f64, EvalError> {
+    match numeral {
+        Numeral::Rational(_, d) if d.is_zero() => Err(EvalError::DivisionByZero),
+        numeral => Ok(numeral.to_f64()),
+    }
+}
+
+fn divide(num: f64, den: f64) -> Result<f64, EvalError> {
+    if den == 0.0 {
+        Err(EvalError::DivisionByZero)
+    } else {
+        Ok(num / den)
+    }
+}
+
+fn power(base: f64, expo: f64) -> Result<f64, EvalError> {
+    if base == 0.0 && expo == 0.0 {
+        Err(EvalError::ZeroExponentiationZero)
+    } else if base == 0.0 && expo < 0.0 {
+        Err(EvalError::DivisionByZero)
+    } else if base < 0.0 && expo.fract() != 0.0 {
+        Err(EvalError::Domain(FunctionType::Pow, base))
+    } else {
+        Ok(base.powf(expo))
+    }
+}
+
+/// Check that `value` is in the domain of `name` before applying it.
+fn checked(
+    name: &FunctionType,
+    value: f64,
+    valid: bool,
+    f: fn(f64) -> f64,
+) -> Result<f64, EvalError> {
+    if valid {
+        Ok(f(value))
+    } else {
+        Err(EvalError::Domain(name.clone(), value))
+    }
+}
+
+fn apply_real(name: &FunctionType, args: &[f64]) -> Result<f64, EvalError> {
+    match (name, args) {
+        (FunctionType::Sin, [x]) => Ok(x.sin()),
+        (FunctionType::Cos, [x]) => Ok(x.cos()),
+        (FunctionType::Tan, [x]) => Ok(x.tan()),
+        (FunctionType::Asin, [x]) => checked(name, *x, x.abs() <= 1.0, f64::asin),
+        (FunctionType::Acos, [x]) => checked(name, *x, x.abs() <= 1.0, f64::acos),
+        (FunctionType::Atan, [x]) => Ok(x.atan()),
+        (FunctionType::Sinh, [x]) => Ok(x.sinh()),
+        (FunctionType::Cosh, [x]) => Ok(x.cosh()),
+        (FunctionType::Tanh, [x]) => Ok(x.tanh()),
+        (FunctionType::Asinh, [x]) => Ok(x.asinh()),
+        (FunctionType::Acosh, [x]) => checked(name, *x, *x >= 1.0, f64::acosh),
+        (FunctionType::Atanh, [x]) => checked(name, *x, x.abs() < 1.0, f64::atanh),
+        (FunctionType::Sqrt, [x]) => checked(name, *x, *x >= 0.0, f64::sqrt),
+        (FunctionType::Exp, [x]) => Ok(x.exp()),
+        (FunctionType::Ln, [x]) => checked(name, *x, *x > 0.0, f64::ln),
+        (FunctionType::Log2, [x]) => checked(name, *x, *x > 0.0, f64::log2),
+        (FunctionType::Log10, [x]) => checked(name, *x, *x > 0.0, f64::log10),
+        (FunctionType::Abs, [x]) => Ok(x.abs()),
+        (FunctionType::Ceil, [x]) => Ok(x.ceil()),
+        (FunctionType::Floor, [x]) => Ok(x.floor()),
+        // log(b, x)
+        (FunctionType::Log, [base, x]) => {
+            if *base <= 0.0 || *base == 1.0 {
+                Err(EvalError::Domain(FunctionType::Log, *base))
+            } else if *x <= 0.0 {
+                Err(EvalError::Domain(FunctionType::Log, *x))
+            } else {
+                Ok(x.log(*base))
+            }
+        }
+        // pow(o, x) = x^o
+        (FunctionType::Pow, [order, x]) => power(*x, *order),
+        // root(o, x)
+        (FunctionType::Root, [order, x]) => {
+            let inverse = divide(1.0, *order)?;
+            // Odd roots of negative numbers are real
+            if *x < 0.0 && order.rem_euclid(2.0) == 1.0 {
+                Ok(-(-x).powf(inverse))
+            } else if *x < 0.0 {
+                Err(EvalError::Domain(FunctionType::Root, *x))
+            } else {
+                power(*x, inverse)
+            }
+        }
+        _ => Err(EvalError::Unsupported),
+    }
+}
+
+fn divide_complex(num: Complex64, den: Complex64) -> Result<Complex64, EvalError> {
+    if den == Complex64::ZERO {
+        Err(EvalError::DivisionByZero)
+    } else {
+        Ok(num / den)
+    }
+}
+
+fn power_complex(base: Complex64, expo: Complex64) -> Result<Complex64, EvalError> {
+    if base == Complex64::ZERO {
+        if expo == Complex64::ZERO {
+            Err(EvalError::ZeroExponentiationZero)
+        } else if expo.re <= 0.0 {
+            Err(EvalError::DivisionByZero)
+        } else {
+            Ok(Complex64::ZERO)
+        }
+    } else if expo.im == 0.0 && expo.re.fract() == 0.0 && expo.re.abs() <= i32::MAX as f64 {
+        // Exact for integer exponents
+        Ok(base.powi(expo.re as i32))
+    } else {
+        Ok(base.powc(expo))
+    }
+}
+
+/// Logarithm with a pole at zero.
+fn ln_complex(name: &FunctionType, z: Complex64) -> Result<Complex64, EvalError> {
+    if z == Complex64::ZERO {
+        Err(EvalError::Domain(name.clone(), 0.0))
+    } else {
+        Ok(z.ln())
+    }
+}
+
+/// Only defined for real arguments.
+fn real_only(name: &FunctionType, z: Complex64, f: fn(f64) -> f64) -> Result<Complex64, EvalError> {
+    if z.im == 0.0 {
+        Ok(Complex64::from(f(z.re)))
+    } else {
+        Err(EvalError::Domain(name.clone(), z.re))
+    }
+}
+
+fn apply_complex(name: &FunctionType, args: &[Complex64]) -> Result<Complex64, EvalError> {
+    match (name, args) {
+        (FunctionType::Sin, [z]) => Ok(z.sin()),
+        (FunctionType::Cos, [z]) => Ok(z.cos()),
+        (FunctionType::Tan, [z]) => Ok(z.tan()),
+        (FunctionType::Asin, [z]) => Ok(z.asin()),
+        (FunctionType::Acos, [z]) => Ok(z.acos()),
+        (FunctionType::Atan, [z]) => Ok(z.atan()),
+        (FunctionType::Sinh, [z]) => Ok(z.sinh()),
+        (FunctionType::Cosh, [z]) => Ok(z.cosh()),
+        (FunctionType::Tanh, [z]) => Ok(z.tanh()),
+        (FunctionType::Asinh, [z]) => Ok(z.asinh()),
+        (FunctionType::Acosh, [z]) => Ok(z.acosh()),
+        (FunctionType::Atanh, [z]) => {
+            if z.im == 0.0 && z.re.abs() == 1.0 {
+                Err(EvalError::Domain(FunctionType::Atanh, z.re))
+            } else {
+                Ok(z.atanh())
+            }
+        }
+        (FunctionType::Sqrt, [z]) => Ok(z.sqrt()),
+        (FunctionType::Exp, [z]) => Ok(z.exp()),
+        (FunctionType::Ln, [z]) => ln_complex(name, *z),
+        (FunctionType::Log2, [z]) => Ok(ln_complex(name, *z)? / std::f64::consts::LN_2),
+        (FunctionType::Log10, [z]) => Ok(ln_complex(name, *z)? / std::f64::consts::LN_10),
+        (FunctionType::Abs, [z]) => Ok(Complex64::from(z.norm())),
+        (FunctionType::Ceil, [z]) => real_only(name, *z, f64::ceil),
+        (FunctionType::Floor, [z]) => real_only(name, *z, f64::floor),
+        // log(b, z)
+        (FunctionType::Log, [base, z]) => {
+            let ln_base = ln_complex(name, *base)?;
+            if ln_base == Complex64::ZERO {
+                return Err(EvalError::Domain(FunctionType::Log, base.re));
+            }
+            Ok(ln_complex(name, *z)? / ln_base)
+        }
+        // pow(o, z) = z^o
+        (FunctionType::Pow, [order, z]) => power_complex(*z, *order),
+        // root(o, z)
+        (FunctionType::Root, [order, z]) => {
+            power_complex(*z, divide_complex(Complex64::ONE, *order)?)
+        }
+        _ => Err(EvalError::Unsupported),
+    }
+}
@@ -0,0 +1,219 @@
+use num_bigint::BigInt;
+use num_traits::{One, ToPrimitive};
+
+use crate::{explanation::FormattingObserver, utils};
+
+use super::{Expression, SimplifyError, numeral::Numeral};
+
+/// Maximum number of terms produced by a single expansion.
+const MAX_EXPANDED_TERMS: usize = 10_000;
+
+/// The rewritings done while walking the expression.
+#[derive(Debug, Clone, Copy)]
+struct Expansion {
+    powers: bool,
+    products: bool,
+}
+
+impl Expression {
+    /// Expand the natural powers of sums and distribute the products over the
+    /// sums, then simplify the result.
+    ///
+    /// # Examples
+    /// ```
+    /// use sym_rustic::ast::Expression;
+    ///
+    /// let x = Expression::variable("x");
+    /// let expr = Expression::exponentiation(
+    ///     Expression::addition(vec![x.clone(), Expression::integer(1)]),
+    ///     Expression::integer(2),
+    /// );
+    /// assert_eq!(expr.expand(&mut None).unwrap().to_string(), "1 + (2 * x) + (x^2)");
+    /// ```
+    pub fn expand(
+        &self,
+        explanation: &mut Option<Box<FormattingObserver>>,
+    ) -> Result<Expression, SimplifyError> {
+        let expansion = Expansion {
+            powers: true,
+            products: true,
+        };
+        self.expand_with(expansion, explanation)?.simplify(explanation)
+    }
+
+    /// Expand only the natural powers of sums, `(a + b)^2` becomes
+    /// `a^2 + 2ab + b^2`, the rest of the expression is kept as is.
+    pub fn expand_power(
+        &self,
+        explanation: &mut Option<Box<FormattingObserver>>,
+    ) -> Result<Expression, SimplifyError> {
+        let expansion = Expansion {
+            powers: true,
+            products: false,
+        };
+        self.expand_with(expansion, explanation)
+    }
+
+    /// Only distribute the products over the sums, `a(b + c)` becomes
+    /// `ab + ac`, the rest of the expression is kept as is.
+    pub fn expand_mul(
+        &self,
+        explanation: &mut Option<Box<FormattingObserver>>,
+    ) -> Result<Expression, SimplifyError> {
+        let expansion = Expansion {
+            powers: false,
+            products: true,
+        };
+        self.expand_with(expansion, explanation)
+    }
+
+    /// Expand the subexpressions first so the new sums are expanded again.
+    fn expand_with(
+        &self,
+        expansion: Expansion,
+        explanation: &mut Option<Box<FormattingObserver>>,
+    ) -> Result<Expression, SimplifyError> {
+        let expanded = self.map_children(|expr| expr.expand_with(expansion, explanation))?;
+        match expanded {
+            Expression::Multiplication(mul) if expansion.products => {
+                Self::distribute(mul.terms, explanation)
+            }
+            Expression::Exponentiation(exp) if expansion.powers => {
+                match (sum_terms(&exp.base), natural_value(&exp.expo)) {
+                    (Some(terms), Some(n)) => {
+                        Self::expand_multinomial(&exp.base, &terms, n, expansion, explanation)
+                    }
+                    _ => Ok(Expression::Exponentiation(exp)),
+                }
+            }
+            expr => Ok(expr),
+        }
+    }
+
+    /// `(a + b)^n` with the multinomial theorem.
+    fn expand_multinomial(
+        base: &Expression,
+        terms: &[Expression],
+        n: u64,
+        expansion: Expansion,
+        explanation: &mut Option<Box<FormattingObserver>>,
+    ) -> Result<Expression, SimplifyError> {
+        if multinomial_terms(terms.len(), n) > MAX_EXPANDED_TERMS {
+            return Err(SimplifyError::Unsupported);
+        }
+        let before = Expression::exponentiation(base.clone(), Expression::integer(n));
+        // (-a)^k is kept by simplify while (-1 * a)^k is computed
+        let terms: Vec<Expression> = terms
+            .iter()
+            .map(|term| match term {
+                Expression::Negation(neg) => {
+                    Expression::multiplication(vec![Expression::integer(-1), neg.term.clone()])
+                }
+                term => term.clone(),
+            })
+            .collect();
+        let mut after = utils::multinomial_expansion(&terms, n);
+        if let Some(explanation) = explanation {
+            explanation.rule_applied("Use the multinomial theorem", &before, &after);
+        }
+        after = after.simplify(explanation)?;
+        // The terms of the base can be products of sums
+        if expansion.products {
+            after = after.expand_with(expansion, explanation)?;
+        }
+        Ok(after)
+    }
+
+    /// `a(b + c)(d + e)` => `abd + abe + acd + ace`
+    fn distribute(
+        terms: Vec<Expression>,
+        explanation: &mut Option<Box<FormattingObserver>>,
+    ) -> Result<Expression, SimplifyError> {
+        let count = terms
+            .iter()
+            .map(|term| sum_terms(term).map_or(1, |terms| terms.len()))
+            .try_fold(1usize, |acc, len| acc.checked_mul(len))
+            .unwrap_or(usize::MAX);
+        if count == 1 {
+            return Ok(Expression::multiplication(terms));
+        }
+        if count > MAX_EXPANDED_TERMS {
+            return Err(SimplifyError::Unsupported);
+        }
+
+        let mut products: Vec<Vec<Expression>> = vec![vec![]];
+        for term in &terms {
+            products = match sum_terms(term) {
+                Some(sum) => products
+                    .iter()
+                    .flat_map(|product| {
+                        sum.iter().map(move |summand| {
+                            let mut product = product.clone();
+                            product.push(summand.clone());
+                            product
+                        })
+                    })
+                    .collect(),
+                None => products
+                    .into_iter()
+                    .map(|mut product| {
+                        product.push(term.clone());
+                        product
+                    })
+                    .collect(),
+            };
+        }
+        let before = Expression::multiplication(terms);
+        let mut after = Expression::addition(
+            products
+                .into_iter()
+                .map(Expression::multiplication)
+                .collect(),
+        );
+        if let Some(explanation) = explanation {
+            explanation.rule_applied("Multiply by distributing each term", &before, &after);
+        }
+        after.simplify(explanation)
+    }
+}
+
+/// The terms of a sum, `a - b` has the terms `a` and `-b` and the nested sums
+/// are flattened, `a - (b + c)` has the terms `a`, `-b` and `-c`.
+fn sum_terms(expr: &Expression) -> Option<Vec<Expression>> {
+    let flatten = |terms: &[Expression]| {
+        terms
+            .iter()
+            .flat_map(|term| match term {
+                Expression::Negation(neg) => match sum_terms(&neg.term) {
+                    Some(terms) => terms.into_iter().map(Expression::negation).collect(),
+                    None => vec![term.clone()],
+                },
+                term => sum_terms(term).unwrap_or_else(|| vec![term.clone()]),
+            })
+            .collect()
+    };
+    match expr {
+        Expression::Addition(add) => Some(flatten(&add.terms)),
+        Expression::Subtraction(sub) => Some(flatten(&[
+            sub.left.clone(),
+            Expression::negation(sub.right.clone()),
+        ])),
+        _ => None,
+    }
+}
+
+fn natural_value(expr: &Expression) -> Option<u64> {
+    match expr {
+        Expression::Number(Numeral::Integer(n)) => n.to_u64(),
+        _ => None,
+    }
+}
+
+/// The number of terms of `(a_1 + ... + a_m)^n`, that is `(n + m - 1)! / (n! (m - 1)!)`.
+fn multinomial_terms(m: usize, n: u64) -> usize {
+    let mut count = BigInt::one();
+    for i in 1..m as u64 {
+        count = count * (n + i) / i;
+    }
+    count.to_usize().unwrap_or(usize::MAX)
+}
@@ -1,6 +1,12 @@
-use crate::{ast::{function::{Function, FunctionType}, Expr}, explanation::FormattingObserver, prints::PrettyPrints, utils};
+use num_integer::Integer;
+use num_traits::{Signed, ToPrimitive};
 
-use super::{Expression, SimplifyError, numeral};
+use crate::{ast::{function::{Function, FunctionType}, Expr}, explanation::FormattingObserver, prints::PrettyPrints};
+
+use super::{Expression, SimplifyError, SimplifyOptions, numeral};
+
+/// Largest exponent of a number computed when simplifying.
+pub(crate) const MAX_NUMERIC_EXPONENT: u32 = 1024;
 
 
 #[derive(Debug, PartialEq, Clone, PartialOrd, Eq, Ord, Hash)]
@@ -24,12 +30,16 @@ impl Exponentiation {
 impl Expr for Exponentiation {
     fn simplify(
         &mut self,
+        options: &SimplifyOptions,
         explanation: &mut Option<Box<FormattingObserver>>,
     ) -> Result<Expression, SimplifyError> {
-        let base = self.base.simplify(explanation)?;
-        let expo = self.expo.simplify(explanation)?;
+        let base = self.base.simplify_with(options, explanation)?;
+        let expo = self.expo.simplify_with(options, explanation)?;
 
-        self.simplify_exponentiation(base, expo, explanation)
+        if base.is_matrix() || expo.is_matrix() {
+            return Expression::power_matrix(base, expo, options, explanation);
+        }
+        self.simplify_exponentiation(base, expo, options, explanation)
     }
 
     fn is_equal(&self, other: &Exponentiation) -> bool {
@@ -69,36 +79,71 @@ impl Exponentiation {
         &mut self,
         lhs: Expression,
         rhs: Expression,
+        options: &SimplifyOptions,
         explanation: &mut Option<Box<FormattingObserver>>,
     ) -> Result<Expression, SimplifyError> {
         let before = Expression::exponentiation(lhs.clone(), rhs.clone());
 
-        let result = match (lhs, rhs) {
+        // -a^n where a is a number is computed like a^n
+        let (lhs, rhs) = match (Self::signed_number(&lhs), Self::signed_number(&rhs)) {
+            (Some(base), Some(expo)) if matches!(expo, numeral::Numeral::Integer(_)) => {
+                (Expression::Number(base), Expression::Number(expo))
+            }
+            _ => (lhs, rhs),
+        };
+
+        match (lhs, rhs) {
             // 0^0 => ZeroExponentiationZero
-            (
-                Expression::Number(numeral::Numeral::Integer(0)),
-                Expression::Number(numeral::Numeral::Integer(0)),
-            ) => Err(SimplifyError::ZeroExponentiationZero),
+            (Expression::Number(base), Expression::Number(expo))
+                if base.is_integer(0) && expo.is_integer(0) =>
+            {
+                Err(SimplifyError::ZeroExponentiationZero)
+            }
             // a^0 => 1
-            (_, Expression::Number(numeral::Numeral::Integer(0))) => {
+            (_, Expression::Number(expo)) if expo.is_integer(0) => {
                 if let Some(explanation) = explanation {
                     explanation.rule_applied("Something to the 0th power is one", &before, &Expression::integer(1));
                 }
                 Ok(Expression::integer(1))
             }
             // 1^x
-            (Expression::Number(numeral::Numeral::Integer(1)), Expression::Number(_))  => {
+            (Expression::Number(base), Expression::Number(_)) if base.is_integer(1) => {
                 if let Some(explanation) = explanation {
                     explanation.rule_applied("One to any power is one", &before, &Expression::integer(1));
                 }
                 Ok(Expression::integer(1))
             }
+            // 0^a => 0 and 0^(-a) => DivisionByZero
+            (Expression::Number(base), Expression::Number(expo)) if base.is_zero() => {
+                if expo.is_negative() {
+                    return Err(SimplifyError::DivisionByZero);
+                }
+                if let Some(explanation) = explanation {
+                    explanation.rule_applied("Zero to a positive power is zero", &before, &Expression::integer(0));
+                }
+                Ok(Expression::integer(0))
+            }
+            // a^n => a*a*...*a when a is a number
+            (Expression::Number(base), Expression::Number(numeral::Numeral::Integer(n)))
+                if n.magnitude().to_u32().is_some_and(|n| n <= MAX_NUMERIC_EXPONENT) =>
+            {
+                let power = base.pow(n.magnitude().to_u32().unwrap_or_default());
+                let mut after = if n.is_negative() {
+                    Expression::Number(numeral::Numeral::Integer(1.into()).div(&power))
+                } else {
+                    Expression::Number(power)
+                };
+                if let Some(explanation) = explanation {
+                    explanation.rule_applied("Compute the power", &before, &after);
+                }
+                after.simplify_with(options, explanation)
+            }
             // (Expression::Number(numeral::Numeral::Integer(1)), Expression::Negation(x)) if *x == Expression::Number(_) => {
             //     rule = "using 1^(-x) => 1";
             //     Ok(Expression::integer(1))
             // }
             // a^1 => a
-            (lhs, Expression::Number(numeral::Numeral::Integer(1))) => {
+            (lhs, Expression::Number(expo)) if expo.is_integer(1) => {
                 if let Some(explanation) = explanation {
                     explanation.rule_applied("Anything to the 1st power stay the same", &before, &lhs);
                 }
@@ -107,8 +152,8 @@ impl Exponentiation {
             // sqrt(a)^2 => a
             (
                 Expression::Function(Function { name: FunctionType::Sqrt, args, simplified: _ }),
-                Expression::Number(numeral::Numeral::Integer(2)),
-            ) => {
+                Expression::Number(expo),
+            ) if expo.is_integer(2) => {
                 if let Some(explanation) = explanation {
                     explanation.rule_applied("Square root to the 2th power cancel", &before, &args[0]);
                 }
@@ -118,12 +163,45 @@ impl Exponentiation {
             (
                 Expression::Function(Function { name: FunctionType::Root, args, simplified: _ }),
                 Expression::Number(numeral::Numeral::Integer(x)),
-            ) if args[0] == Expression::Number(numeral::Numeral::Integer(x)) => {
+            ) if args[0] == Expression::integer(x.clone()) => {
                 if let Some(explanation) = explanation {
                     explanation.rule_applied("nth root to nth power cancel", &before, &Expression::integer(x));
                 }
                 Ok(args[1].clone())
             }
+            // b^(c*log_b(a)) => a^c
+            (lhs, rhs) if let Some(mut after) = Function::exp_of_log(&lhs, &rhs) => {
+                if let Some(explanation) = explanation {
+                    explanation.rule_applied("Exponential and logarithm are inverse\nb^(c*log_b(a)) => a^c", &before, &after);
+                }
+                after.simplify_with(options, explanation)
+            }
+            // (a^b)^c => abs(a)^(b*c) when b is even and c is not an integer
+            (Expression::Exponentiation(exp), rhs)
+                if matches!(&exp.expo, Expression::Number(numeral::Numeral::Integer(b)) if b.is_even())
+                    && !matches!(rhs, Expression::Number(numeral::Numeral::Integer(_)))
+                    && !exp.base.is_nonnegative(&options.assumptions) =>
+            {
+                let mut after = Expression::exponentiation(
+                    Expression::function(FunctionType::Abs, vec![exp.base]),
+                    Expression::multiplication(vec![exp.expo, rhs]),
+                );
+                if let Some(explanation) = explanation {
+                    explanation.rule_applied("The even power hides the sign of the base", &before, &after);
+                }
+                after.simplify_with(options, explanation)
+            }
+            // abs(a)^n => a^n when a is real and n is even
+            (
+                Expression::Function(Function { name: FunctionType::Abs, args, simplified: _ }),
+                Expression::Number(numeral::Numeral::Integer(n)),
+            ) if n.is_even() && args[0].is_real(&options.assumptions) => {
+                let mut after = Expression::exponentiation(args[0].clone(), Expression::integer(n));
+                if let Some(explanation) = explanation {
+                    explanation.rule_applied("An even power of a real number hides its sign", &before, &after);
+                }
+                after.simplify_with(options, explanation)
+            }
             // (a^b)^c => a^(b*c)
             (Expression::Exponentiation(exp), rhs) => {
                     if let Some(explanation) = explanation {
@@ -136,7 +214,7 @@ impl Exponentiation {
                         exp.base,
                         Expression::multiplication(vec![exp.expo, rhs]),
                     )
-                    .simplify(explanation)
+                    .simplify_with(options, explanation)
             }
             // (a*b)^c => a^c*b^c
             (Expression::Multiplication(mul), rhs) => {
@@ -149,21 +227,50 @@ impl Exponentiation {
                 if let Some(explanation) = explanation {
                     explanation.rule_applied("Distribute the exponent", &before, &after);
                 };
-                after.simplify(explanation)
+                after.simplify_with(options, explanation)
+            }
+            // (a + b)^n => a^n + ... + b^n when the powers of sums are expanded
+            (lhs @ Expression::Addition(_), Expression::Number(numeral::Numeral::Integer(n)))
+                if options.expand && !n.is_negative() =>
+            {
+                match before.expand_power(explanation) {
+                    Ok(mut after) => after.simplify_with(options, explanation),
+                    // Too many terms, keep the power
+                    Err(SimplifyError::Unsupported) => Ok(Expression::Exponentiation(Box::new(
+                        Exponentiation::new(lhs, Expression::integer(n), true),
+                    ))),
+                    Err(err) => Err(err),
+                }
             }
-            // (a + b)^n where n is a integer
-            (Expression::Addition(add), Expression::Number(numeral::Numeral::Integer(n))) => {
-                let mut after = utils::multinomial_expansion(&add.terms, n);
+            // (-a)^n => a^n when n is even and -(a^n) when n is odd
+            (Expression::Negation(neg), Expression::Number(numeral::Numeral::Integer(n))) => {
+                let power = Expression::exponentiation(neg.term.clone(), Expression::integer(n.clone()));
+                let (mut after, rule) = if n % 2 == 0.into() {
+                    (power, "An even power cancels the sign")
+                } else {
+                    (Expression::negation(power), "An odd power keeps the sign")
+                };
                 if let Some(explanation) = explanation {
-                    explanation.rule_applied("Use the multinomial theoerm", &before, &after);
+                    explanation.rule_applied(rule, &before, &after);
                 };
-                after.simplify(explanation)
+                after.simplify_with(options, explanation)
             }
             // a^b => a^b
             (lhs, rhs) => Ok(Expression::Exponentiation(Box::new(Exponentiation::new(lhs, rhs, true)))),
-        };
+        }
+    }
+}
 
-        result
+impl Exponentiation {
+    fn signed_number(expr: &Expression) -> Option<numeral::Numeral> {
+        match expr {
+            Expression::Number(n) => Some(n.clone()),
+            Expression::Negation(neg) => match &neg.term {
+                Expression::Number(n) => Some(n.neg()),
+                _ => None,
+            },
+            _ => None,
+        }
     }
 }
 
@@ -1,5 +1,12 @@
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::{Signed, Zero};
+
 use crate::{
-    ast::{numeral::Numeral, Expr, Expression, SimplifyError},
+    ast::{
+        Expr, Expression, SimplifyError, SimplifyOptions, assumptions::Assumptions,
+        constant::Constant, exponentiation::Exponentiation, numeral::Numeral,
+    },
     explanation::FormattingObserver, prints::PrettyPrints,
 };
 
@@ -24,14 +31,10 @@ impl Function {
 impl Expr for Function {
     fn simplify(
         &mut self,
+        options: &SimplifyOptions,
         explanation: &mut Option<Box<FormattingObserver>>,
     ) -> Result<Expression, SimplifyError> {
-        let args: Vec<Expression> = self
-            .args
-            .iter_mut()
-            .map(|arg| arg.simplify(explanation))
-            .collect::<Result<Vec<Expression>, _>>()?;
-        Ok(Expression::Function(Function::new(self.name.clone(), args, true)))
+        self.simplify_function(options, explanation)
     }
 
     fn is_equal(&self, other: &Function) -> bool {
@@ -51,6 +54,159 @@ impl Expr for Function {
     }
 }
 
+impl Function {
+    /// Exact value of the elementary functions at `0`, `1`, `e` and `pi`,
+    /// like `cos(0) => 1` or `ln(e) => 1`.
+    fn special_value(expr: &Expression) -> Option<(&'static str, Expression)> {
+        let Expression::Function(function) = expr else {
+            return None;
+        };
+        let [arg] = function.args.as_slice() else {
+            return None;
+        };
+        let is = |value: i32| matches!(arg, Expression::Number(n) if n.is_integer(value));
+
+        let value = match function.name {
+            FunctionType::Sin
+            | FunctionType::Tan
+            | FunctionType::Asin
+            | FunctionType::Atan
+            | FunctionType::Sinh
+            | FunctionType::Tanh
+            | FunctionType::Asinh
+            | FunctionType::Atanh
+                if is(0) =>
+            {
+                Some(Expression::integer(0))
+            }
+            FunctionType::Cos | FunctionType::Cosh | FunctionType::Exp if is(0) => {
+                Some(Expression::integer(1))
+            }
+            FunctionType::Sin | FunctionType::Tan
+                if matches!(arg, Expression::Constant(Constant::Pi)) =>
+            {
+                Some(Expression::integer(0))
+            }
+            FunctionType::Cos if matches!(arg, Expression::Constant(Constant::Pi)) => {
+                Some(Expression::negation(Expression::integer(1)))
+            }
+            FunctionType::Ln | FunctionType::Log2 | FunctionType::Log10 | FunctionType::Acosh
+                if is(1) =>
+            {
+                Some(Expression::integer(0))
+            }
+            FunctionType::Acos if is(1) => Some(Expression::integer(0)),
+            FunctionType::Sqrt if is(0) || is(1) => Some(arg.clone()),
+            FunctionType::Ln if matches!(arg, Expression::Constant(Constant::E)) => {
+                Some(Expression::integer(1))
+            }
+            FunctionType::Abs if matches!(arg, Expression::Constant(_)) => Some(arg.clone()),
+            _ => None,
+        };
+        value.map(|value| ("Value of the function is known", value))
+    }
+
+    /// `abs`, `ceil` and `floor` of an exact number, like `floor(7/2) => 3`.
+    fn rounding_value(expr: &Expression) -> Option<(&'static str, Expression)> {
+        let Expression::Function(function) = expr else {
+            return None;
+        };
+        let [arg] = function.args.as_slice() else {
+            return None;
+        };
+        let n = match arg {
+            Expression::Number(n) => n.clone(),
+            Expression::Negation(neg) => match &neg.term {
+                Expression::Number(n) => n.neg(),
+                _ => return None,
+            },
+            _ => return None,
+        };
+        let (num, den) = match &n {
+            Numeral::Integer(n) => (n.clone(), BigInt::from(1)),
+            Numeral::Rational(num, den) if !den.is_zero() => (num.clone(), den.clone()),
+            _ => return None,
+        };
+        let (num, den) = if den.is_negative() { (-num, -den) } else { (num, den) };
+        match function.name {
+            FunctionType::Abs => Some((
+                "Absolute value of a number",
+                Expression::Number(n.abs()),
+            )),
+            FunctionType::Ceil => Some((
+                "Ceiling of a number\nceil(a) => the smallest integer >= a",
+                Expression::integer(-((-num).div_floor(&den))),
+            )),
+            FunctionType::Floor => Some((
+                "Floor of a number\nfloor(a) => the largest integer <= a",
+                Expression::integer(num.div_floor(&den)),
+            )),
+            _ => None,
+        }
+    }
+}
+
+impl Function {
+    /// Rules that depend on the sign of the argument, like `abs(x) => x` when
+    /// `x >= 0` or `sqrt(x^2) => abs(x)`.
+    fn assumed_value(
+        expr: &Expression,
+        assumptions: &Assumptions,
+    ) -> Option<(&'static str, Expression)> {
+        let Expression::Function(function) = expr else {
+            return None;
+        };
+        match (&function.name, function.args.as_slice()) {
+            (FunctionType::Abs, [arg]) if arg.is_nonnegative(assumptions) => Some((
+                "The absolute value of a nonnegative number is itself",
+                arg.clone(),
+            )),
+            (FunctionType::Abs, [arg]) if arg.is_nonpositive(assumptions) => Some((
+                "The absolute value of a nonpositive number is its opposite",
+                Expression::negation(arg.clone()),
+            )),
+            (FunctionType::Sqrt, [Expression::Exponentiation(exp)]) => {
+                Self::root_of_power(exp, &BigInt::from(2), assumptions)
+            }
+            (
+                FunctionType::Root,
+                [Expression::Number(Numeral::Integer(index)), Expression::Exponentiation(exp)],
+            ) => Self::root_of_power(exp, index, assumptions),
+            _ => None,
+        }
+    }
+
+    /// `root(k, a^n) => a^(n/k)` when `k` divides `n`, an even root loses the
+    /// sign of `a` so it gives `abs(a)^(n/k)` unless `a >= 0`.
+    fn root_of_power(
+        exp: &Exponentiation,
+        index: &BigInt,
+        assumptions: &Assumptions,
+    ) -> Option<(&'static str, Expression)> {
+        let Expression::Number(Numeral::Integer(n)) = &exp.expo else {
+            return None;
+        };
+        if !index.is_positive() || !n.is_multiple_of(index) {
+            return None;
+        }
+        let expo = Expression::integer(n / index);
+        if index.is_odd() || exp.base.is_nonnegative(assumptions) {
+            Some((
+                "The root cancels the power",
+                Expression::exponentiation(exp.base.clone(), expo),
+            ))
+        } else {
+            Some((
+                "An even root of an even power is the absolute value",
+                Expression::exponentiation(
+                    Expression::function(FunctionType::Abs, vec![exp.base.clone()]),
+                    expo,
+                ),
+            ))
+        }
+    }
+}
+
 impl std::fmt::Display for Function {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         let args: Vec<String> = self.args.iter().map(|arg| arg.to_string()).collect();
@@ -59,51 +215,71 @@ impl std::fmt::Display for Function {
 }
 
 impl Function {
+    /// Simplify the arguments, then the function itself with the first rule
+    /// that applies, the result is simplified again.
     pub fn simplify_function(
         &mut self,
-        func: Function,
-        args: Vec<Expression>,
-        explanation: &mut Option<Vec<String>>,
+        options: &SimplifyOptions,
+        explanation: &mut Option<Box<FormattingObserver>>,
     ) -> Result<Expression, SimplifyError> {
-        let mut rule = "";
+        let args: Vec<Expression> = self
+            .args
+            .iter_mut()
+            .map(|arg| arg.simplify_with(options, explanation))
+            .collect::<Result<Vec<Expression>, _>>()?;
+        let before = Expression::Function(Function::new(self.name.clone(), args, true));
 
-        let result = match func.name {
-            FunctionType::Pow => {
-                match (&args[0], &args[1]) {
-                    // 0^0 => ZeroExponentiationZero
-                    (
-                        Expression::Number(Numeral::Integer(0)),
-                        Expression::Number(Numeral::Integer(0)),
-                    ) => Err(SimplifyError::ZeroExponentiationZero),
-                    // a^0 => 1
-                    (Expression::Number(Numeral::Integer(0)), _) => {
-                        rule = "using a^0 => 1";
-                        Ok(Expression::integer(1))
-                    }
-                    // 1^x
-                    (Expression::Number(_), Expression::Number(Numeral::Integer(1))) => {
-                        rule = "using 1^x => 1";
-                        Ok(Expression::integer(1))
-                    }
-                    // a^1 => a
-                    (Expression::Number(Numeral::Integer(1)), lhs) => {
-                        rule = "using a^1 => a";
-                        Ok(lhs.clone())
-                    }
-                    _ => Ok(Expression::pow(args[1].clone(), args[0].clone())),
+        let value = Self::power_value(&before)?
+            .or_else(|| Self::special_value(&before))
+            .or_else(|| Self::rounding_value(&before))
+            .or_else(|| Self::trig_value(&before))
+            .or_else(|| Self::parity(&before))
+            .or_else(|| Self::log_value(&before, &options.assumptions))
+            .or_else(|| Self::radical_value(&before))
+            .or_else(|| Self::assumed_value(&before, &options.assumptions));
+        match value {
+            Some((rule, mut after)) => {
+                if let Some(explanation) = explanation {
+                    explanation.rule_applied(rule, &before, &after);
                 }
+                after.simplify_with(options, explanation)
             }
-            a => Ok(Expression::Function(Function::new(a, args, true)))
-            ,
-        };
+            None => Ok(before),
+        }
+    }
 
-        if !rule.is_empty() {
-            if let Some(explanation) = explanation {
-                explanation.push(format!("Simplifiyng Exponentiation {}", rule,));
+    /// `pow(n, a)` is `a^n`, like `pow(0, a) => 1` or `pow(1, a) => a`.
+    fn power_value(expr: &Expression) -> Result<Option<(&'static str, Expression)>, SimplifyError> {
+        let Expression::Function(function) = expr else {
+            return Ok(None);
+        };
+        let (FunctionType::Pow, [expo, base]) = (&function.name, function.args.as_slice()) else {
+            return Ok(None);
+        };
+        match (expo, base) {
+            // 0^0 => ZeroExponentiationZero
+            (Expression::Number(expo), Expression::Number(base))
+                if expo.is_integer(0) && base.is_integer(0) =>
+            {
+                Err(SimplifyError::ZeroExponentiationZero)
             }
+            // a^0 => 1
+            (Expression::Number(expo), _) if expo.is_integer(0) => Ok(Some((
+                "Anything to the 0th power is 1\npow(0, a) => 1",
+                Expression::integer(1),
+            ))),
+            // 1^a => 1
+            (_, Expression::Number(base)) if base.is_integer(1) => Ok(Some((
+                "1 to any power is 1\npow(a, 1) => 1",
+                Expression::integer(1),
+            ))),
+            // a^1 => a
+            (Expression::Number(expo), base) if expo.is_integer(1) => Ok(Some((
+                "Anything to the 1st power stay the same\npow(1, a) => a",
+                base.clone(),
+            ))),
+            _ => Ok(None),
         }
-
-        result
     }
 }
 
@@ -0,0 +1,181 @@
+use std::{cmp::Ordering, collections::HashMap};
+
+use crate::{
+    ast::{Expr, SimplifyError, SimplifyOptions},
+    explanation::FormattingObserver,
+    prints::PrettyPrints,
+};
+
+use super::Expression;
+
+/// Differences closer to zero than this are considered to be zero when two
+/// constant sides are compared.
+const COMPARISON_TOLERANCE: f64 = 1e-12;
+
+/// The relation between the two sides of an `Inequality`.
+#[derive(Debug, PartialEq, Copy, Clone, PartialOrd, Eq, Ord, Hash)]
+pub enum Relation {
+    Less,
+    LessEqual,
+    Greater,
+    GreaterEqual,
+    NotEqual,
+}
+
+impl Relation {
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Relation::Less => "<",
+            Relation::LessEqual => "<=",
+            Relation::Greater => ">",
+            Relation::GreaterEqual => ">=",
+            Relation::NotEqual => "!=",
+        }
+    }
+
+    /// Returns `true` if `left - right` having the sign `ordering` satisfies the relation.
+    pub fn holds(&self, ordering: Ordering) -> bool {
+        match self {
+            Relation::Less => ordering == Ordering::Less,
+            Relation::LessEqual => ordering != Ordering::Greater,
+            Relation::Greater => ordering == Ordering::Greater,
+            Relation::GreaterEqual => ordering != Ordering::Less,
+            Relation::NotEqual => ordering != Ordering::Equal,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, PartialOrd, Eq, Ord, Hash)]
+pub struct Inequality {
+    pub left: Expression,
+    pub relation: Relation,
+    pub right: Expression,
+    pub simplified: bool,
+}
+
+// Constructor
+impl Inequality {
+    pub fn new(left: Expression, relation: Relation, right: Expression, simplified: bool) -> Self {
+        Self {
+            left,
+            relation,
+            right,
+            simplified,
+        }
+    }
+}
+
+impl Expr for Inequality {
+    fn simplify(
+        &mut self,
+        options: &SimplifyOptions,
+        explanation: &mut Option<Box<FormattingObserver>>,
+    ) -> Result<Expression, SimplifyError> {
+        let left = self.left.simplify_with(options, explanation)?;
+        let right = self.right.simplify_with(options, explanation)?;
+        let before = Expression::inequality(left.clone(), self.relation, right.clone());
+
+        // 1 < 2 => true
+        let difference = Expression::subtraction(left.clone(), right.clone())
+            .evaluate(&HashMap::new())
+            .ok()
+            .filter(|difference| difference.is_finite());
+        let Some(difference) = difference else {
+            return Ok(Expression::Inequality(Box::new(Inequality::new(
+                left,
+                self.relation,
+                right,
+                true,
+            ))));
+        };
+        let ordering = if difference.abs() < COMPARISON_TOLERANCE {
+            Ordering::Equal
+        } else if difference > 0.0 {
+            Ordering::Greater
+        } else {
+            Ordering::Less
+        };
+        let after = Expression::boolean(self.relation.holds(ordering));
+        if let Some(explanation) = explanation {
+            explanation.rule_applied("Compare the two sides", &before, &after);
+        }
+        Ok(after)
+    }
+
+    fn is_equal(&self, other: &Inequality) -> bool {
+        self.relation == other.relation
+            && self.left.is_equal(&other.left)
+            && self.right.is_equal(&other.right)
+    }
+
+    fn contains_var(&self, variable: &str) -> bool {
+        self.left.contains_var(variable) || self.right.contains_var(variable)
+    }
+
+    fn is_single(&self) -> bool {
+        false
+    }
+}
+
+impl std::fmt::Display for Inequality {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{} {} {}", self.left, self.relation.symbol(), self.right)
+    }
+}
+
+impl PrettyPrints for Inequality {
+    fn calculate_tree(&self, indent: usize) -> String {
+        let next_indent = indent + 2;
+        let next_indent_str = " ".repeat(next_indent);
+
+        format!(
+            "Inequality:\n{}{}\n{}{} {}",
+            next_indent_str,
+            self.left.calculate_tree(next_indent),
+            next_indent_str,
+            self.relation.symbol(),
+            self.right.calculate_tree(next_indent)
+        )
+    }
+
+    fn calculate_positions(
+        &self,
+        memoization: &mut HashMap<Expression, (usize, usize)>,
+        position: &mut Vec<(String, (usize, usize))>,
+        prev_pos: (usize, usize),
+    ) {
+        let below_height = self.get_below_height(memoization);
+        let baseline = prev_pos.0 + below_height;
+
+        let mut pos = (baseline - self.left.get_below_height(memoization), prev_pos.1);
+        self.left.calculate_positions(memoization, position, pos);
+        pos.1 += self.left.get_length(memoization);
+        for c in format!(" {} ", self.relation.symbol()).chars() {
+            position.push((c.to_string(), (baseline, pos.1)));
+            pos.1 += 1;
+        }
+        pos.0 = baseline - self.right.get_below_height(memoization);
+        self.right.calculate_positions(memoization, position, pos);
+    }
+
+    fn get_below_height(&self, memoization: &mut HashMap<Expression, (usize, usize)>) -> usize {
+        self.left
+            .get_below_height(memoization)
+            .max(self.right.get_below_height(memoization))
+    }
+
+    fn get_height(&self, memoization: &mut HashMap<Expression, (usize, usize)>) -> usize {
+        let left_above =
+            self.left.get_height(memoization) - self.left.get_below_height(memoization);
+        let right_above =
+            self.right.get_height(memoization) - self.right.get_below_height(memoization);
+        self.get_below_height(memoization) + left_above.max(right_above)
+    }
+
+    fn get_length(&self, memoization: &mut HashMap<Expression, (usize, usize)>) -> usize {
+        self.left.get_length(memoization)
+            + self.relation.symbol().len()
+            + 2
+            + self.right.get_length(memoization)
+    }
+}
@@ -0,0 +1,1624 @@
+use std::collections::HashMap;
+
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::{One, Signed};
+
+use crate::{
+    ast::Expr,
+    explanation::FormattingObserver,
+    prints::PrettyPrints,
+    solver::{Fraction, Solution, Solver, partial_fractions, polynomial_coefficients, square_root},
+};
+
+use super::{
+    Expression, SimplifyError, SimplifyOptions,
+    constant::Constant,
+    function::FunctionType,
+    limit::{Direction, defined},
+    numeral::Numeral,
+};
+
+/// Maximum number of nested substitutions, integrations by parts and distributions.
+const MAX_DEPTH: usize = 5;
+/// Number of intervals on which a non polynomial expression is checked for a root.
+const ROOT_SAMPLES: usize = 1000;
+
+#[derive(Debug, PartialEq, Clone, PartialOrd, Eq, Ord, Hash)]
+pub struct Integral {
+    pub term: Expression,
+    pub variable: String,
+    /// `(lower, upper)` for a definite integral
+    pub bounds: Option<(Expression, Expression)>,
+    pub simplified: bool,
+}
+
+/// Value of a definite integral.
+#[derive(Debug, PartialEq, Clone)]
+pub enum IntegralValue {
+    /// Found with the fundamental theorem of calculus
+    Exact(Expression),
+    /// Found with numerical quadrature, `error` is the estimated absolute error
+    Approximate { value: f64, error: f64 },
+}
+
+// Constructor
+impl Integral {
+    pub fn new(
+        term: Expression,
+        variable: String,
+        bounds: Option<(Expression, Expression)>,
+        simplified: bool,
+    ) -> Self {
+        Self {
+            term,
+            variable,
+            bounds,
+            simplified,
+        }
+    }
+}
+
+impl Expr for Integral {
+    fn simplify(
+        &mut self,
+        options: &SimplifyOptions,
+        explanation: &mut Option<Box<FormattingObserver>>,
+    ) -> Result<Expression, SimplifyError> {
+        let term = self.term.simplify_with(options, explanation)?;
+        let Some((lower, upper)) = &mut self.bounds else {
+            return match term.integrate_with(&self.variable, options, explanation) {
+                // No antiderivative was found, the integral is kept
+                Err(SimplifyError::Unsupported) => Ok(Expression::Integral(Box::new(
+                    Integral::new(term, self.variable.clone(), None, true),
+                ))),
+                result => result,
+            };
+        };
+
+        let lower = lower.simplify_with(options, explanation)?;
+        let upper = upper.simplify_with(options, explanation)?;
+        match term.integrate_definite_with(&self.variable, &lower, &upper, options, explanation) {
+            Ok(IntegralValue::Exact(value)) => Ok(value),
+            Ok(IntegralValue::Approximate { value, .. }) => Ok(Expression::float(value)),
+            Err(SimplifyError::Unsupported) => Ok(Expression::Integral(Box::new(Integral::new(
+                term,
+                self.variable.clone(),
+                Some((lower, upper)),
+                true,
+            )))),
+            Err(err) => Err(err),
+        }
+    }
+
+    fn is_equal(&self, other: &Integral) -> bool {
+        self.variable == other.variable
+            && self.term.is_equal(&other.term)
+            && match (&self.bounds, &other.bounds) {
+                (Some((lhs_lower, lhs_upper)), Some((rhs_lower, rhs_upper))) => {
+                    lhs_lower.is_equal(rhs_lower) && lhs_upper.is_equal(rhs_upper)
+                }
+                (None, None) => true,
+                _ => false,
+            }
+    }
+
+    fn contains_var(&self, variable: &str) -> bool {
+        match &self.bounds {
+            // The integration variable is bound
+            Some((lower, upper)) => {
+                lower.contains_var(variable)
+                    || upper.contains_var(variable)
+                    || (self.variable != variable && self.term.contains_var(variable))
+            }
+            None => self.term.contains_var(variable),
+        }
+    }
+
+    fn is_single(&self) -> bool {
+        false
+    }
+}
+
+impl std::fmt::Display for Integral {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "∫")?;
+        if let Some((lower, upper)) = &self.bounds {
+            write!(f, "[{}, {}] ", lower, upper)?;
+        }
+        if self.term.is_single() {
+            write!(f, "{} d{}", self.term, self.variable)
+        } else {
+            write!(f, "({}) d{}", self.term, self.variable)
+        }
+    }
+}
+
+impl Expression {
+    /// Returns the antiderivative of the expression with respect to `variable`,
+    /// the constant of integration is left out.
+    ///
+    /// Polynomials, rational functions, the elementary functions of a linear
+    /// argument and the integrals found by substitution or by parts are supported,
+    /// otherwise `SimplifyError::Unsupported` is returned.
+    ///
+    /// # Examples
+    /// ```
+    /// use sym_rustic::ast::Expression;
+    ///
+    /// // ∫cos(x) dx = sin(x)
+    /// let result = Expression::cos(Expression::variable("x")).integrate("x", &mut None);
+    /// assert!(result.unwrap().is_equal(&Expression::sin(Expression::variable("x"))));
+    /// ```
+    pub fn integrate(
+        &self,
+        variable: &str,
+        explanation: &mut Option<Box<FormattingObserver>>,
+    ) -> Result<Expression, SimplifyError> {
+        self.integrate_with(variable, &SimplifyOptions::default(), explanation)
+    }
+
+    /// Returns the antiderivative like `Expression::integrate`, the integrand
+    /// and the result are simplified with `options`.
+    pub fn integrate_with(
+        &self,
+        variable: &str,
+        options: &SimplifyOptions,
+        explanation: &mut Option<Box<FormattingObserver>>,
+    ) -> Result<Expression, SimplifyError> {
+        // Steps are only kept when an antiderivative is found
+        let mut trial = explanation.clone();
+        let integrand = self.clone().simplify_with(options, &mut trial)?;
+        let result = integrand
+            .antiderivative(variable, MAX_DEPTH, &mut trial)?
+            .simplify_with(options, &mut trial)?;
+        *explanation = trial;
+        Ok(result)
+    }
+
+    /// Returns `∫[lower, upper] self dvariable`.
+    ///
+    /// The integral is exact when an antiderivative is found, otherwise it is
+    /// approximated by adaptive Gauss–Kronrod quadrature which needs the bounds
+    /// and the integrand to be numerical. `SimplifyError::Unsupported` is returned
+    /// when neither is possible.
+    ///
+    /// When the integrand is not defined somewhere in `[lower, upper]` the
+    /// integral is split there and the antiderivative is replaced by its one-sided
+    /// limits, `SimplifyError::DivergentIntegral` is returned if one is infinite.
+    ///
+    /// # Examples
+    /// ```
+    /// use sym_rustic::ast::{Expression, integral::IntegralValue};
+    ///
+    /// // ∫[0, 1] x dx = 1/2
+    /// let result = Expression::variable("x").integrate_definite(
+    ///     "x",
+    ///     &Expression::integer(0),
+    ///     &Expression::integer(1),
+    ///     &mut None,
+    /// );
+    /// assert_eq!(result, Ok(IntegralValue::Exact(Expression::rational(1, 2))));
+    /// ```
+    pub fn integrate_definite(
+        &self,
+        variable: &str,
+        lower: &Expression,
+        upper: &Expression,
+        explanation: &mut Option<Box<FormattingObserver>>,
+    ) -> Result<IntegralValue, SimplifyError> {
+        self.integrate_definite_with(variable, lower, upper, &SimplifyOptions::default(), explanation)
+    }
+
+    /// Returns `∫[lower, upper] self dvariable` like `Expression::integrate_definite`,
+    /// the antiderivative and the exact value are simplified with `options`.
+    pub fn integrate_definite_with(
+        &self,
+        variable: &str,
+        lower: &Expression,
+        upper: &Expression,
+        options: &SimplifyOptions,
+        explanation: &mut Option<Box<FormattingObserver>>,
+    ) -> Result<IntegralValue, SimplifyError> {
+        let before =
+            Expression::definite_integral(self.clone(), variable, lower.clone(), upper.clone());
+        let singularities = self.singularities(variable, lower, upper)?;
+        match self.integrate_with(variable, options, explanation) {
+            Ok(antiderivative) => {
+                // F(b) - F(a), or the sum of lim(x->b-) F - lim(x->a+) F on each piece
+                let at = |bound: &Expression, direction: Direction| {
+                    let value = antiderivative.substitute(&Expression::variable(variable), bound);
+                    if defined(&value, options).is_some() {
+                        return Ok(value);
+                    }
+                    match antiderivative.find_limit_with(variable, bound, direction, options, &mut None) {
+                        Ok(value) if defined(&value, options).is_some() => Ok(value),
+                        Ok(_) => Err(SimplifyError::DivergentIntegral),
+                        Err(_) => Err(SimplifyError::Unsupported),
+                    }
+                };
+                let mut points = vec![lower];
+                points.extend(&singularities);
+                points.push(upper);
+                let mut pieces = Vec::new();
+                for piece in points.windows(2) {
+                    pieces.push(Expression::subtraction(
+                        at(piece[1], Direction::Left)?,
+                        at(piece[0], Direction::Right)?,
+                    ));
+                }
+                let after = match pieces.len() {
+                    1 => pieces.remove(0),
+                    _ => Expression::addition(pieces),
+                };
+                if let Some(explanation) = explanation {
+                    explanation.rule_applied(
+                        "Fundamental theorem of calculus\n∫[a, b] f dx => F(b) - F(a)",
+                        &before,
+                        &after,
+                    );
+                }
+                Ok(IntegralValue::Exact(after.clone().simplify_with(options, explanation)?))
+            }
+            // The quadrature doesn't converge at a singularity
+            Err(SimplifyError::Unsupported) if !singularities.is_empty() => {
+                Err(SimplifyError::Unsupported)
+            }
+            Err(SimplifyError::Unsupported) => {
+                let env = HashMap::new();
+                let (value, error) = lower
+                    .evaluate(&env)
+                    .and_then(|lower| Ok((lower, upper.evaluate(&env)?)))
+                    .and_then(|(lower, upper)| self.quadrature(variable, lower, upper, &env))
+                    .map_err(|_| SimplifyError::Unsupported)?;
+                if let Some(explanation) = explanation {
+                    explanation.rule_applied(
+                        "No antiderivative was found, the integral is approximated\nby adaptive Gauss–Kronrod quadrature",
+                        &before,
+                        &Expression::float(value),
+                    );
+                }
+                Ok(IntegralValue::Approximate { value, error })
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    /// The points of `(lower, upper)` where the integrand is not defined, in
+    /// increasing order, found from the zeros of the denominators and of the
+    /// arguments of the logarithms and from the poles of `tan`.
+    ///
+    /// `SimplifyError::Unsupported` is returned if they can't be found exactly.
+    fn singularities(
+        &self,
+        variable: &str,
+        lower: &Expression,
+        upper: &Expression,
+    ) -> Result<Vec<Expression>, SimplifyError> {
+        let mut vanishing = Vec::new();
+        self.vanishing_parts(variable, &mut vanishing);
+        if vanishing.is_empty() {
+            return Ok(vec![]);
+        }
+        let env = HashMap::new();
+        let (a, b) = match (lower.evaluate(&env), upper.evaluate(&env)) {
+            (Ok(a), Ok(b)) if a <= b => (a, b),
+            _ => return Err(SimplifyError::Unsupported),
+        };
+
+        let mut points: Vec<(f64, Expression)> = Vec::new();
+        for expr in vanishing {
+            for root in roots(&expr, variable, a, b)? {
+                let value = root
+                    .evaluate(&env)
+                    .map_err(|_| SimplifyError::Unsupported)?;
+                if a < value && value < b && !points.iter().any(|(other, _)| *other == value) {
+                    points.push((value, root));
+                }
+            }
+        }
+        points.sort_by(|(lhs, _), (rhs, _)| lhs.total_cmp(rhs));
+        Ok(points.into_iter().map(|(_, root)| root).collect())
+    }
+
+    /// Push the expressions whose zeros make `self` undefined.
+    fn vanishing_parts(&self, variable: &str, parts: &mut Vec<Expression>) {
+        if !self.contains_var(variable) {
+            return;
+        }
+        match self {
+            Expression::Division(div) if div.den.contains_var(variable) => {
+                parts.push(div.den.clone())
+            }
+            Expression::Exponentiation(exp)
+                if exp.base.contains_var(variable)
+                    && exp
+                        .expo
+                        .evaluate(&HashMap::new())
+                        .is_ok_and(|expo| expo < 0.0) =>
+            {
+                parts.push(exp.base.clone())
+            }
+            Expression::Function(fun) => match (&fun.name, fun.args.as_slice()) {
+                (FunctionType::Ln | FunctionType::Log2 | FunctionType::Log10, [arg])
+                | (FunctionType::Log, [_, arg]) => parts.push(arg.clone()),
+                (FunctionType::Tan, [arg]) => parts.push(Expression::cos(arg.clone())),
+                _ => {}
+            },
+            _ => {}
+        }
+        let _ = self.map_children(|child| {
+            child.vanishing_parts(variable, parts);
+            Ok(child.clone())
+        });
+    }
+
+    fn antiderivative(
+        &self,
+        variable: &str,
+        depth: usize,
+        explanation: &mut Option<Box<FormattingObserver>>,
+    ) -> Result<Expression, SimplifyError> {
+        let before = Expression::integral(self.clone(), variable);
+        if !self.contains_var(variable) {
+            let after =
+                Expression::multiplication(vec![self.clone(), Expression::variable(variable)]);
+            if let Some(explanation) = explanation {
+                explanation.rule_applied(
+                    "Integral of a constant is given by\n∫a dx => a*x",
+                    &before,
+                    &after,
+                );
+            }
+            return Ok(after);
+        }
+
+        match self {
+            Expression::Negation(neg) => {
+                if let Some(explanation) = explanation {
+                    let after =
+                        Expression::negation(Expression::integral(neg.term.clone(), variable));
+                    explanation.rule_applied(
+                        "The negative constant is highlighted\n∫-f dx => -∫f dx",
+                        &before,
+                        &after,
+                    );
+                }
+                Ok(Expression::negation(
+                    neg.term.antiderivative(variable, depth, explanation)?,
+                ))
+            }
+            Expression::Addition(add) => {
+                if let Some(explanation) = explanation {
+                    let after = Expression::addition(
+                        add.terms
+                            .iter()
+                            .map(|expr| Expression::integral(expr.clone(), variable))
+                            .collect(),
+                    );
+                    explanation.rule_applied(
+                        "Integral of sum is given by\n∫(f + g) dx => ∫f dx + ∫g dx",
+                        &before,
+                        &after,
+                    );
+                }
+                Ok(Expression::addition(
+                    add.terms
+                        .iter()
+                        .map(|expr| expr.antiderivative(variable, depth, explanation))
+                        .collect::<Result<Vec<Expression>, _>>()?,
+                ))
+            }
+            Expression::Subtraction(sub) => {
+                if let Some(explanation) = explanation {
+                    let after = Expression::subtraction(
+                        Expression::integral(sub.left.clone(), variable),
+                        Expression::integral(sub.right.clone(), variable),
+                    );
+                    explanation.rule_applied(
+                        "Integral of sum is given by\n∫(f - g) dx => ∫f dx - ∫g dx",
+                        &before,
+                        &after,
+                    );
+                }
+                Ok(Expression::subtraction(
+                    sub.left.antiderivative(variable, depth, explanation)?,
+                    sub.right.antiderivative(variable, depth, explanation)?,
+                ))
+            }
+            Expression::Derivative(der) if der.variable == variable => {
+                let after = if der.order == 1 {
+                    der.term.clone()
+                } else {
+                    Expression::derivative(der.term.clone(), variable, der.order - 1)
+                };
+                if let Some(explanation) = explanation {
+                    explanation.rule_applied(
+                        "Integral of a derivative is given by\n∫f' dx => f",
+                        &before,
+                        &after,
+                    );
+                }
+                Ok(after)
+            }
+            Expression::Variable(_)
+            | Expression::Multiplication(_)
+            | Expression::Division(_)
+            | Expression::Exponentiation(_)
+            | Expression::Function(_) => self.integrate_product(variable, depth, explanation),
+            _ => Err(SimplifyError::Unsupported),
+        }
+    }
+
+    /// Integrate a product of powers, the rules are tried from the cheapest to the most expensive.
+    fn integrate_product(
+        &self,
+        variable: &str,
+        depth: usize,
+        explanation: &mut Option<Box<FormattingObserver>>,
+    ) -> Result<Expression, SimplifyError> {
+        let before = Expression::integral(self.clone(), variable);
+        let product = Product::new(self, variable)?;
+        let (constant, dependent) = product.split(variable);
+
+        if !constant.is_one() {
+            let integrand = dependent.to_expression();
+            if let Some(explanation) = explanation {
+                let after = Expression::multiplication(vec![
+                    constant.to_expression(),
+                    Expression::integral(integrand.clone(), variable),
+                ]);
+                explanation.rule_applied(
+                    "The constant factor is taken out\n∫a*f dx => a*∫f dx",
+                    &before,
+                    &after,
+                );
+            }
+            let mut constant = constant;
+            let mut result = integrand.antiderivative(variable, depth, explanation)?;
+            // a*(F/n) => (a/n)*F
+            if let Expression::Division(div) = &result
+                && let Some(den) = numeral(&div.den)
+                && !den.is_zero()
+            {
+                constant.coefficient = reduce(constant.coefficient.div(&den));
+                result = div.num.clone();
+            }
+            return Ok(Expression::multiplication(vec![constant.to_expression(), result]));
+        }
+
+        if let [(base, expo)] = dependent.factors.as_slice()
+            && let Some(after) = Self::integrate_factor(base, expo, variable, &before, explanation)?
+        {
+            return Ok(after);
+        }
+
+        if depth == 0 {
+            return Err(SimplifyError::Unsupported);
+        }
+        let methods: [Method; 5] = [
+            Self::integrate_by_substitution,
+            Self::integrate_polynomial,
+            Self::integrate_rational,
+            Self::integrate_by_distribution,
+            Self::integrate_by_parts,
+        ];
+        for method in methods {
+            let mut trial = explanation.clone();
+            if let Ok(after) = method(self, &dependent, variable, depth, &mut trial) {
+                *explanation = trial;
+                return Ok(after);
+            }
+        }
+        Err(SimplifyError::Unsupported)
+    }
+
+    /// Integrals of a single power `base^expo` found in a table, `None` if there is none.
+    fn integrate_factor(
+        base: &Expression,
+        expo: &Expression,
+        variable: &str,
+        before: &Expression,
+        explanation: &mut Option<Box<FormattingObserver>>,
+    ) -> Result<Option<Expression>, SimplifyError> {
+        // x^n => x^(n+1)/(n+1)
+        if let Some(slope) = linear_slope(base, variable) {
+            let next = Expression::addition(vec![expo.clone(), Expression::integer(1)])
+                .simplify(&mut None)?;
+            let (rule, after) = if is_zero(&next) {
+                (
+                    if base.is_equal(&Expression::variable(variable)) {
+                        "using ∫1/x dx => ln(|x|)"
+                    } else {
+                        "using ∫1/(a*x + b) dx => ln(|a*x + b|)/a"
+                    },
+                    divide_by(Expression::ln(Expression::abs(base.clone())), slope),
+                )
+            } else {
+                (
+                    if base.is_equal(&Expression::variable(variable)) {
+                        "Power rule for integrals\n∫x^n dx => x^(n+1)/(n+1)"
+                    } else {
+                        "Power rule for integrals\n∫(a*x + b)^n dx => (a*x + b)^(n+1)/(a*(n+1))"
+                    },
+                    divide_by(
+                        Expression::exponentiation(base.clone(), next.clone()),
+                        multiply(&next, &slope)?,
+                    ),
+                )
+            };
+            if let Some(explanation) = explanation {
+                explanation.rule_applied(rule, before, &after);
+            }
+            return Ok(Some(after));
+        }
+
+        if !is_one(expo) {
+            return Ok(None);
+        }
+        let (rule, after) = match base {
+            // a^f => a^f/(f'*ln(a))
+            Expression::Exponentiation(exp) if !exp.base.contains_var(variable) => {
+                let Some(slope) = linear_slope(&exp.expo, variable) else {
+                    return Ok(None);
+                };
+                if let Expression::Constant(Constant::E) = exp.base {
+                    ("using ∫e^(a*x + b) dx => e^(a*x + b)/a", divide_by(base.clone(), slope))
+                } else {
+                    (
+                        "using ∫c^(a*x + b) dx => c^(a*x + b)/(a*ln(c))",
+                        divide_by(
+                            base.clone(),
+                            Expression::multiplication(vec![slope, Expression::ln(exp.base.clone())]),
+                        ),
+                    )
+                }
+            }
+            // f(a*x + b) => F(a*x + b)/a
+            Expression::Function(fun) => {
+                let Some(slope) = fun.args.last().and_then(|arg| linear_slope(arg, variable))
+                else {
+                    return Ok(None);
+                };
+                let Some((rule, antiderivative)) =
+                    Self::function_antiderivative(&fun.name, &fun.args, variable)
+                else {
+                    return Ok(None);
+                };
+                (rule, divide_by(antiderivative, slope))
+            }
+            _ => return Ok(None),
+        };
+        if let Some(explanation) = explanation {
+            explanation.rule_applied(rule, before, &after);
+        }
+        Ok(Some(after))
+    }
+
+    /// Antiderivative of the function with respect to its argument.
+    fn function_antiderivative(
+        func: &FunctionType,
+        args: &[Expression],
+        variable: &str,
+    ) -> Option<(&'static str, Expression)> {
+        let arg = args.last()?.clone();
+        let square = Expression::exponentiation(arg.clone(), Expression::integer(2));
+        let times_arg = |expr: Expression| Expression::multiplication(vec![arg.clone(), expr]);
+        let half = |expr: Expression| Expression::division(expr, Expression::integer(2));
+        // x*ln(x) - x
+        let x_ln_x = Expression::subtraction(times_arg(Expression::ln(arg.clone())), arg.clone());
+
+        Some(match func {
+            FunctionType::Sin => (
+                "using ∫sin(x) dx => -cos(x)",
+                Expression::negation(Expression::cos(arg)),
+            ),
+            FunctionType::Cos => ("using ∫cos(x) dx => sin(x)", Expression::sin(arg)),
+            FunctionType::Tan => (
+                "using ∫tan(x) dx => -ln(|cos(x)|)",
+                Expression::negation(Expression::ln(Expression::abs(Expression::cos(arg)))),
+            ),
+            FunctionType::Asin => (
+                "using ∫asin(x) dx => x*asin(x) + sqrt(1 - x^2)",
+                Expression::addition(vec![
+                    times_arg(Expression::asin(arg.clone())),
+                    Expression::sqrt(Expression::subtraction(Expression::integer(1), square)),
+                ]),
+            ),
+            FunctionType::Acos => (
+                "using ∫acos(x) dx => x*acos(x) - sqrt(1 - x^2)",
+                Expression::subtraction(
+                    times_arg(Expression::acos(arg.clone())),
+                    Expression::sqrt(Expression::subtraction(Expression::integer(1), square)),
+                ),
+            ),
+            FunctionType::Atan => (
+                "using ∫atan(x) dx => x*atan(x) - ln(1 + x^2)/2",
+                Expression::subtraction(
+                    times_arg(Expression::atan(arg.clone())),
+                    half(Expression::ln(Expression::addition(vec![
+                        Expression::integer(1),
+                        square,
+                    ]))),
+                ),
+            ),
+            FunctionType::Sinh => ("using ∫sinh(x) dx => cosh(x)", Expression::cosh(arg)),
+            FunctionType::Cosh => ("using ∫cosh(x) dx => sinh(x)", Expression::sinh(arg)),
+            FunctionType::Tanh => (
+                "using ∫tanh(x) dx => ln(cosh(x))",
+                Expression::ln(Expression::cosh(arg)),
+            ),
+            FunctionType::Asinh => (
+                "using ∫asinh(x) dx => x*asinh(x) - sqrt(x^2 + 1)",
+                Expression::subtraction(
+                    times_arg(Expression::asinh(arg.clone())),
+                    Expression::sqrt(Expression::addition(vec![square, Expression::integer(1)])),
+                ),
+            ),
+            FunctionType::Acosh => (
+                "using ∫acosh(x) dx => x*acosh(x) - sqrt(x^2 - 1)",
+                Expression::subtraction(
+                    times_arg(Expression::acosh(arg.clone())),
+                    Expression::sqrt(Expression::subtraction(square, Expression::integer(1))),
+                ),
+            ),
+            FunctionType::Atanh => (
+                "using ∫atanh(x) dx => x*atanh(x) + ln(1 - x^2)/2",
+                Expression::addition(vec![
+                    times_arg(Expression::atanh(arg.clone())),
+                    half(Expression::ln(Expression::subtraction(
+                        Expression::integer(1),
+                        square,
+                    ))),
+                ]),
+            ),
+            FunctionType::Ln => ("using ∫ln(x) dx => x*ln(x) - x", x_ln_x),
+            FunctionType::Log2 => (
+                "using ∫log2(x) dx => (x*ln(x) - x)/ln(2)",
+                Expression::division(x_ln_x, Expression::ln(Expression::integer(2))),
+            ),
+            FunctionType::Log10 => (
+                "using ∫log10(x) dx => (x*ln(x) - x)/ln(10)",
+                Expression::division(x_ln_x, Expression::ln(Expression::integer(10))),
+            ),
+            FunctionType::Log if !args[0].contains_var(variable) => (
+                "using ∫log(b, x) dx => (x*ln(x) - x)/ln(b)",
+                Expression::division(x_ln_x, Expression::ln(args[0].clone())),
+            ),
+            FunctionType::Abs => (
+                "using ∫abs(x) dx => x*abs(x)/2",
+                half(times_arg(Expression::abs(arg.clone()))),
+            ),
+            // sqrt, exp, pow and root are integrated as powers
+            _ => return None,
+        })
+    }
+
+    /// ∫f(g(x))*g'(x) dx => ∫f(u) du with u = g(x)
+    fn integrate_by_substitution(
+        &self,
+        product: &Product,
+        variable: &str,
+        depth: usize,
+        explanation: &mut Option<Box<FormattingObserver>>,
+    ) -> Result<Expression, SimplifyError> {
+        let before = Expression::integral(self.clone(), variable);
+        let name = fresh_variable(self, variable);
+        let u = Expression::variable(&name);
+
+        for candidate in product.substitution_candidates(variable) {
+            let Ok(derivative) = candidate.clone().differentiate(variable, &mut None) else {
+                continue;
+            };
+            let integrand = product
+                .divide(&Product::new(&derivative, variable)?)?
+                .to_expression()
+                .substitute(&candidate, &u);
+            if integrand.contains_var(variable) {
+                continue;
+            }
+
+            let mut trial = explanation.clone();
+            if let Some(explanation) = &mut trial {
+                explanation.rule_applied(
+                    &format!(
+                        "Substitution of {} = {}\nd{} = {} d{}",
+                        name, candidate, name, derivative, variable
+                    ),
+                    &before,
+                    &Expression::integral(integrand.clone(), &name),
+                );
+            }
+            let Ok(result) = integrand.antiderivative(&name, depth - 1, &mut trial) else {
+                continue;
+            };
+            let after = result.substitute(&u, &candidate);
+            if let Some(explanation) = &mut trial {
+                explanation.rule_applied(
+                    &format!("Substitute back {} = {}", name, candidate),
+                    &result,
+                    &after,
+                );
+            }
+            *explanation = trial;
+            return Ok(after);
+        }
+        Err(SimplifyError::Unsupported)
+    }
+
+    /// ∫(cn*x^n + ... + c0) dx => cn*x^(n+1)/(n+1) + ... + c0*x
+    fn integrate_polynomial(
+        &self,
+        _product: &Product,
+        variable: &str,
+        _depth: usize,
+        explanation: &mut Option<Box<FormattingObserver>>,
+    ) -> Result<Expression, SimplifyError> {
+        let coefficients =
+            polynomial_coefficients(self, variable).map_err(|_| SimplifyError::Unsupported)?;
+        let after = integrate_coefficients(&coefficients, variable);
+        if let Some(explanation) = explanation {
+            explanation.rule_applied(
+                "Integrate the polynomial term by term\n∫a*x^n dx => a*x^(n+1)/(n+1)",
+                &Expression::integral(self.clone(), variable),
+                &after,
+            );
+        }
+        Ok(after)
+    }
+
+    /// Integrate a rational function through its partial fraction decomposition.
+    fn integrate_rational(
+        &self,
+        product: &Product,
+        variable: &str,
+        _depth: usize,
+        explanation: &mut Option<Box<FormattingObserver>>,
+    ) -> Result<Expression, SimplifyError> {
+        let (num, den) = product.fraction().ok_or(SimplifyError::Unsupported)?;
+        let decomposition =
+            partial_fractions(&num, &den, variable).ok_or(SimplifyError::Unsupported)?;
+        let x = Expression::variable(variable);
+
+        let mut parts = Vec::new();
+        if decomposition.polynomial.iter().any(|coeff| !is_zero(coeff)) {
+            let polynomial = polynomial_expression(&decomposition.polynomial, variable);
+            parts.push((polynomial, None));
+        }
+        for fraction in &decomposition.fractions {
+            let term = match fraction {
+                Fraction::Linear { coeff, root, power } => Expression::division(
+                    coeff.clone(),
+                    power_of(Expression::subtraction(x.clone(), root.clone()), *power),
+                ),
+                Fraction::Quadratic { a, b, p, q } => Expression::division(
+                    Expression::addition(vec![
+                        Expression::multiplication(vec![a.clone(), x.clone()]),
+                        b.clone(),
+                    ]),
+                    quadratic(p, q, variable),
+                ),
+            };
+            parts.push((term, Some(fraction)));
+        }
+        if let Some(explanation) = explanation {
+            let after = Expression::addition(
+                parts
+                    .iter()
+                    .map(|(term, _)| Expression::integral(term.clone(), variable))
+                    .collect(),
+            );
+            explanation.rule_applied(
+                "Decompose the rational function into partial fractions",
+                &Expression::integral(self.clone(), variable),
+                &after,
+            );
+        }
+
+        let mut terms = Vec::new();
+        for (term, fraction) in parts {
+            let (rule, after) = match fraction {
+                None => (
+                    "Integrate the polynomial term by term\n∫a*x^n dx => a*x^(n+1)/(n+1)",
+                    integrate_coefficients(&decomposition.polynomial, variable),
+                ),
+                Some(Fraction::Linear { coeff, root, power: 1 }) => (
+                    "using ∫a/(x - r) dx => a*ln(|x - r|)",
+                    Expression::multiplication(vec![
+                        coeff.clone(),
+                        Expression::ln(Expression::abs(Expression::subtraction(
+                            x.clone(),
+                            root.clone(),
+                        ))),
+                    ]),
+                ),
+                Some(Fraction::Linear { coeff, root, power }) => (
+                    "using ∫a/(x - r)^n dx => -a/((n - 1)*(x - r)^(n - 1))",
+                    Expression::negation(Expression::division(
+                        coeff.clone(),
+                        Expression::multiplication(vec![
+                            Expression::integer(power - 1),
+                            power_of(Expression::subtraction(x.clone(), root.clone()), power - 1),
+                        ]),
+                    )),
+                ),
+                Some(Fraction::Quadratic { a, b, p, q }) => (
+                    "using ∫(a*x + b)/(x^2 + p*x + q) dx => a/2*ln(x^2 + p*x + q) + (b - a*p/2)*∫1/(x^2 + p*x + q) dx",
+                    quadratic_antiderivative(a, b, p, q, variable)?,
+                ),
+            };
+            if let Some(explanation) = explanation {
+                explanation.rule_applied(rule, &Expression::integral(term, variable), &after);
+            }
+            terms.push(after);
+        }
+
+        Ok(match terms.len() {
+            1 => terms.remove(0),
+            _ => Expression::addition(terms),
+        })
+    }
+
+    /// ∫(f + g)*h dx => ∫f*h dx + ∫g*h dx
+    fn integrate_by_distribution(
+        &self,
+        product: &Product,
+        variable: &str,
+        depth: usize,
+        explanation: &mut Option<Box<FormattingObserver>>,
+    ) -> Result<Expression, SimplifyError> {
+        let index = product
+            .factors
+            .iter()
+            .position(|(base, expo)| {
+                is_one(expo)
+                    && matches!(base, Expression::Addition(_) | Expression::Subtraction(_))
+            })
+            .ok_or(SimplifyError::Unsupported)?;
+        let (sum, rest) = product.take(index);
+        let terms = match sum.to_expression() {
+            Expression::Addition(add) => add.terms,
+            Expression::Subtraction(sub) => vec![sub.left, Expression::negation(sub.right)],
+            _ => return Err(SimplifyError::Unsupported),
+        };
+        let rest = rest.to_expression();
+
+        let integrand = Expression::addition(
+            terms
+                .into_iter()
+                .map(|term| Expression::multiplication(vec![term, rest.clone()]))
+                .collect(),
+        );
+        if let Some(explanation) = explanation {
+            explanation.rule_applied(
+                "The product is distributed over the sum\n(f + g)*h => f*h + g*h",
+                &Expression::integral(self.clone(), variable),
+                &Expression::integral(integrand.clone(), variable),
+            );
+        }
+        integrand.antiderivative(variable, depth - 1, explanation)
+    }
+
+    /// ∫u*dv => u*v - ∫v*du, `u` is chosen with the LIATE order.
+    fn integrate_by_parts(
+        &self,
+        product: &Product,
+        variable: &str,
+        depth: usize,
+        explanation: &mut Option<Box<FormattingObserver>>,
+    ) -> Result<Expression, SimplifyError> {
+        let (index, rank) = product
+            .factors
+            .iter()
+            .enumerate()
+            .filter_map(|(i, (base, expo))| liate_rank(base, expo, variable).map(|rank| (i, rank)))
+            .max_by_key(|(_, rank)| *rank)
+            .ok_or(SimplifyError::Unsupported)?;
+        let (u, dv) = product.take(index);
+        // Only logarithms and inverse functions are worth integrating against dx
+        if dv.is_one() && rank < Liate::Inverse {
+            return Err(SimplifyError::Unsupported);
+        }
+        let (u, dv) = (u.to_expression(), dv.to_expression());
+
+        let v = dv
+            .antiderivative(variable, depth - 1, explanation)?
+            .simplify(&mut None)?;
+        let du = u.clone().differentiate(variable, &mut None)?;
+        let integrand = Product::new(&v, variable)?
+            .multiply(&Product::new(&du, variable)?)?
+            .to_expression();
+        let uv = Expression::multiplication(vec![u, v]);
+
+        if let Some(explanation) = explanation {
+            let after = Expression::subtraction(
+                uv.clone(),
+                Expression::integral(integrand.clone(), variable),
+            );
+            explanation.rule_applied(
+                "Integration by parts is given by\n∫u*dv => u*v - ∫v*du",
+                &Expression::integral(self.clone(), variable),
+                &after,
+            );
+        }
+        Ok(Expression::subtraction(
+            uv,
+            integrand.antiderivative(variable, depth - 1, explanation)?,
+        ))
+    }
+}
+
+type Method = fn(
+    &Expression,
+    &Product,
+    &str,
+    usize,
+    &mut Option<Box<FormattingObserver>>,
+) -> Result<Expression, SimplifyError>;
+
+/// Order in which the factor differentiated by an integration by parts is chosen.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+enum Liate {
+    Exponential,
+    Trigonometric,
+    Algebraic,
+    Inverse,
+    Logarithmic,
+}
+
+fn liate_rank(base: &Expression, expo: &Expression, variable: &str) -> Option<Liate> {
+    // Only positive integer powers get simpler when differentiated
+    if !integer(expo).is_some_and(|n| n.is_positive()) {
+        return None;
+    }
+    match base {
+        Expression::Function(fun) => match fun.name {
+            FunctionType::Ln | FunctionType::Log | FunctionType::Log2 | FunctionType::Log10 => {
+                Some(Liate::Logarithmic)
+            }
+            FunctionType::Asin
+            | FunctionType::Acos
+            | FunctionType::Atan
+            | FunctionType::Asinh
+            | FunctionType::Acosh
+            | FunctionType::Atanh => Some(Liate::Inverse),
+            FunctionType::Sin
+            | FunctionType::Cos
+            | FunctionType::Tan
+            | FunctionType::Sinh
+            | FunctionType::Cosh
+            | FunctionType::Tanh => Some(Liate::Trigonometric),
+            _ => None,
+        },
+        Expression::Exponentiation(exp) if exp.expo.contains_var(variable) => {
+            Some(Liate::Exponential)
+        }
+        base => polynomial_coefficients(base, variable)
+            .is_ok()
+            .then_some(Liate::Algebraic),
+    }
+}
+
+/// `coefficient * base_1^expo_1 * ... * base_n^expo_n` with distinct bases.
+///
+/// Used while integrating to cancel the factors, `x^2/x` or `2*x/(2*x)` are not
+/// reduced by the simplification.
+#[derive(Debug, Clone)]
+struct Product {
+    coefficient: Numeral,
+    factors: Vec<(Expression, Expression)>,
+}
+
+impl Product {
+    fn new(expr: &Expression, variable: &str) -> Result<Self, SimplifyError> {
+        let mut product = Product {
+            coefficient: Numeral::Integer(BigInt::one()),
+            factors: Vec::new(),
+        };
+        product.collect(expr, &Expression::integer(1), variable)?;
+        Ok(product)
+    }
+
+    fn collect(
+        &mut self,
+        expr: &Expression,
+        expo: &Expression,
+        variable: &str,
+    ) -> Result<(), SimplifyError> {
+        let integer_expo = integer(expo);
+        match expr {
+            Expression::Multiplication(mul) => {
+                for term in &mul.terms {
+                    self.collect(term, expo, variable)?;
+                }
+            }
+            Expression::Division(div) => {
+                self.collect(&div.num, expo, variable)?;
+                self.collect(&div.den, &negate(expo)?, variable)?;
+            }
+            // (-f)^n => (-1)^n * f^n
+            Expression::Negation(neg) if integer_expo.is_some() => {
+                if integer_expo.is_some_and(|n| n.is_odd()) {
+                    self.coefficient = self.coefficient.neg();
+                }
+                self.collect(&neg.term, expo, variable)?;
+            }
+            Expression::Number(n) if integer_expo.as_ref().is_some_and(|n| n.is_one()) => {
+                self.coefficient = reduce(self.coefficient.mul(n));
+            }
+            Expression::Number(n)
+                if !n.is_zero() && integer_expo.as_ref().is_some_and(|n| *n == -BigInt::one()) =>
+            {
+                self.coefficient = reduce(self.coefficient.div(n));
+            }
+            Expression::Exponentiation(exp) if exp.expo.contains_var(variable) => {
+                let expr = if is_one(expo) {
+                    expr.clone()
+                } else {
+                    Expression::exponentiation(exp.base.clone(), multiply(&exp.expo, expo)?)
+                };
+                self.push(expr, Expression::integer(1))?;
+            }
+            // (f^n)^m => f^(n*m) when n is an integer, otherwise f is kept whole
+            Expression::Exponentiation(exp) => {
+                if integer(&exp.expo).is_some() {
+                    self.collect(&exp.base, &multiply(&exp.expo, expo)?, variable)?;
+                } else {
+                    self.push(exp.base.clone(), multiply(&exp.expo, expo)?)?;
+                }
+            }
+            Expression::Function(fun) => {
+                let power = match fun.name {
+                    FunctionType::Sqrt => Some(Expression::exponentiation(
+                        fun.args[0].clone(),
+                        Expression::rational(1, 2),
+                    )),
+                    FunctionType::Exp => Some(Expression::exponentiation(
+                        Expression::e(),
+                        fun.args[0].clone(),
+                    )),
+                    // pow(o, f) => f^o
+                    FunctionType::Pow => Some(Expression::exponentiation(
+                        fun.args[1].clone(),
+                        fun.args[0].clone(),
+                    )),
+                    // root(o, f) => f^(1/o)
+                    FunctionType::Root => Some(Expression::exponentiation(
+                        fun.args[1].clone(),
+                        Expression::division(Expression::integer(1), fun.args[0].clone())
+                            .simplify(&mut None)?,
+                    )),
+                    _ => None,
+                };
+                match power {
+                    Some(power) => self.collect(&power, expo, variable)?,
+                    None => self.push(expr.clone(), expo.clone())?,
+                }
+            }
+            _ => self.push(expr.clone(), expo.clone())?,
+        }
+        Ok(())
+    }
+
+    /// Multiply by `base^expo`, merging it with a factor of the same base.
+    fn push(&mut self, base: Expression, expo: Expression) -> Result<(), SimplifyError> {
+        match self.factors.iter().position(|(b, _)| b.is_equal(&base)) {
+            Some(i) => {
+                let sum = Expression::addition(vec![self.factors[i].1.clone(), expo])
+                    .simplify(&mut None)?;
+                if is_zero(&sum) {
+                    self.factors.remove(i);
+                } else {
+                    self.factors[i].1 = sum;
+                }
+            }
+            None => self.factors.push((base, expo)),
+        }
+        Ok(())
+    }
+
+    fn is_one(&self) -> bool {
+        self.coefficient.is_one() && self.factors.is_empty()
+    }
+
+    /// Returns the factors free of `variable` and the ones depending on it.
+    fn split(&self, variable: &str) -> (Product, Product) {
+        let (dependent, constant) = self
+            .factors
+            .iter()
+            .cloned()
+            .partition(|(base, _)| base.contains_var(variable));
+        (
+            Product {
+                coefficient: self.coefficient.clone(),
+                factors: constant,
+            },
+            Product {
+                coefficient: Numeral::Integer(BigInt::one()),
+                factors: dependent,
+            },
+        )
+    }
+
+    /// Returns the factor at `index` and the rest of the product.
+    fn take(&self, index: usize) -> (Product, Product) {
+        let mut rest = self.clone();
+        let factor = rest.factors.remove(index);
+        (
+            Product {
+                coefficient: Numeral::Integer(BigInt::one()),
+                factors: vec![factor],
+            },
+            rest,
+        )
+    }
+
+    fn multiply(&self, other: &Product) -> Result<Product, SimplifyError> {
+        let mut product = self.clone();
+        product.coefficient = reduce(product.coefficient.mul(&other.coefficient));
+        for (base, expo) in &other.factors {
+            product.push(base.clone(), expo.clone())?;
+        }
+        Ok(product)
+    }
+
+    fn divide(&self, other: &Product) -> Result<Product, SimplifyError> {
+        if other.coefficient.is_zero() {
+            return Err(SimplifyError::DivisionByZero);
+        }
+        let inverse = Product {
+            coefficient: reduce(Numeral::Integer(BigInt::one()).div(&other.coefficient)),
+            factors: other
+                .factors
+                .iter()
+                .map(|(base, expo)| Ok((base.clone(), negate(expo)?)))
+                .collect::<Result<_, SimplifyError>>()?,
+        };
+        self.multiply(&inverse)
+    }
+
+    /// Inner expressions `g` tried for a substitution `u = g(x)`.
+    fn substitution_candidates(&self, variable: &str) -> Vec<Expression> {
+        let mut candidates: Vec<Expression> = Vec::new();
+        let mut push = |expr: &Expression| {
+            if expr.contains_var(variable)
+                && linear_slope(expr, variable).is_none()
+                && !candidates.iter().any(|candidate| candidate.is_equal(expr))
+            {
+                candidates.push(expr.clone());
+            }
+        };
+        for (base, _) in &self.factors {
+            push(base);
+            match base {
+                Expression::Function(fun) => fun.args.iter().for_each(&mut push),
+                Expression::Exponentiation(exp) => {
+                    push(&exp.expo);
+                    push(&exp.base);
+                }
+                _ => {}
+            }
+        }
+        candidates
+    }
+
+    /// The terms of the numerator and of the denominator.
+    fn terms(&self) -> (Vec<Expression>, Vec<Expression>) {
+        let mut num = Vec::new();
+        let mut den = Vec::new();
+        match self.coefficient.abs() {
+            Numeral::Rational(n, d) => {
+                if !n.is_one() {
+                    num.push(Expression::integer(n));
+                }
+                den.push(Expression::integer(d));
+            }
+            coefficient if !coefficient.is_one() => num.push(Expression::Number(coefficient)),
+            _ => {}
+        }
+        for (base, expo) in &self.factors {
+            match numeral(expo) {
+                Some(n) if n.is_negative() => {
+                    den.push(power(base.clone(), Expression::Number(n.abs())))
+                }
+                _ => num.push(power(base.clone(), expo.clone())),
+            }
+        }
+        (num, den)
+    }
+
+    /// Returns `(numerator, denominator)` if the product is a fraction.
+    fn fraction(&self) -> Option<(Expression, Expression)> {
+        let (num, den) = self.terms();
+        (!den.is_empty()).then(|| (join(num), join(den)))
+    }
+
+    fn to_expression(&self) -> Expression {
+        let (num, den) = self.terms();
+        let expr = if den.is_empty() {
+            join(num)
+        } else {
+            Expression::division(join(num), join(den))
+        };
+        if self.coefficient.is_negative() {
+            Expression::negation(expr)
+        } else {
+            expr
+        }
+    }
+}
+
+/// The real roots of `expr`, exact for a polynomial.
+///
+/// Other expressions are sampled inside `(a, b)` and `SimplifyError::Unsupported`
+/// is returned when they vanish or change sign, their roots are not known exactly.
+fn roots(expr: &Expression, variable: &str, a: f64, b: f64) -> Result<Vec<Expression>, SimplifyError> {
+    if polynomial_coefficients(expr, variable).is_ok() {
+        let equation = Expression::equality(expr.clone(), Expression::integer(0));
+        match Solver::solve_for(equation, Expression::variable(variable), &mut None) {
+            Ok(Solution::Single(root)) => return Ok(real_roots(vec![root])),
+            Ok(Solution::Multiple(roots)) => return Ok(real_roots(roots)),
+            Ok(Solution::NoSolution) => return Ok(vec![]),
+            Ok(Solution::Infinite) => return Err(SimplifyError::Unsupported),
+            // Sampled as the other expressions
+            Err(_) => {}
+        }
+    }
+    // A root at a bound is not a singularity inside the interval
+    let mut previous: Option<f64> = None;
+    for i in 1..ROOT_SAMPLES {
+        let x = a + (b - a) * i as f64 / ROOT_SAMPLES as f64;
+        let env = HashMap::from([(variable.to_string(), x)]);
+        match expr.evaluate(&env) {
+            Ok(value) if value != 0.0 && value.is_finite() => {
+                if previous.is_some_and(|previous| previous.signum() != value.signum()) {
+                    return Err(SimplifyError::Unsupported);
+                }
+                previous = Some(value);
+            }
+            _ => return Err(SimplifyError::Unsupported),
+        }
+    }
+    Ok(vec![])
+}
+
+/// The roots without the complex ones.
+fn real_roots(roots: Vec<Expression>) -> Vec<Expression> {
+    roots
+        .into_iter()
+        .filter(|root| !matches!(root, Expression::Complex(_)))
+        .collect()
+}
+
+/// Cancel the common factors of a product or a quotient, `x^2/x => x`.
+pub(super) fn cancel_factors(expr: &Expression, variable: &str) -> Result<Expression, SimplifyError> {
+    Ok(Product::new(expr, variable)?.to_expression())
+}
+
+/// Multiply the terms, an empty product is one.
+fn join(mut terms: Vec<Expression>) -> Expression {
+    match terms.len() {
+        0 => Expression::integer(1),
+        1 => terms.remove(0),
+        _ => Expression::multiplication(terms),
+    }
+}
+
+fn power(base: Expression, expo: Expression) -> Expression {
+    if is_one(&expo) {
+        base
+    } else {
+        Expression::exponentiation(base, expo)
+    }
+}
+
+fn power_of(base: Expression, expo: u32) -> Expression {
+    power(base, Expression::integer(expo))
+}
+
+/// `expr/divisor`, a fraction divisor is written as a product by its inverse.
+fn divide_by(expr: Expression, divisor: Expression) -> Expression {
+    match numeral(&divisor) {
+        Some(n) if n.is_one() => expr,
+        Some(Numeral::Rational(n, d)) => Expression::multiplication(vec![
+            reduce(Numeral::Rational(d, n)).into_expression(),
+            expr,
+        ]),
+        _ => Expression::division(expr, divisor),
+    }
+}
+
+fn multiply(lhs: &Expression, rhs: &Expression) -> Result<Expression, SimplifyError> {
+    Expression::multiplication(vec![lhs.clone(), rhs.clone()]).simplify(&mut None)
+}
+
+fn negate(expr: &Expression) -> Result<Expression, SimplifyError> {
+    Expression::negation(expr.clone()).simplify(&mut None)
+}
+
+fn numeral(expr: &Expression) -> Option<Numeral> {
+    match expr {
+        Expression::Number(n) => Some(n.clone()),
+        Expression::Negation(neg) => numeral(&neg.term).map(|n| n.neg()),
+        _ => None,
+    }
+}
+
+fn integer(expr: &Expression) -> Option<BigInt> {
+    match numeral(expr)? {
+        Numeral::Integer(n) => Some(n),
+        _ => None,
+    }
+}
+
+fn is_zero(expr: &Expression) -> bool {
+    numeral(expr).is_some_and(|n| n.is_zero())
+}
+
+fn is_one(expr: &Expression) -> bool {
+    numeral(expr).is_some_and(|n| n.is_one())
+}
+
+fn reduce(value: Numeral) -> Numeral {
+    match value {
+        Numeral::Rational(n, d) => {
+            let gcd = n.gcd(&d);
+            let (mut n, mut d) = (n / &gcd, d / &gcd);
+            if d.is_negative() {
+                (n, d) = (-n, -d);
+            }
+            if d.is_one() {
+                Numeral::Integer(n)
+            } else {
+                Numeral::Rational(n, d)
+            }
+        }
+        value => value,
+    }
+}
+
+/// Returns `a` if `expr = a*variable + b`.
+fn linear_slope(expr: &Expression, variable: &str) -> Option<Expression> {
+    match polynomial_coefficients(expr, variable).ok()?.as_slice() {
+        [_, slope] => Some(slope.clone()),
+        _ => None,
+    }
+}
+
+/// Name of the variable introduced by a substitution, absent from `expr`.
+fn fresh_variable(expr: &Expression, variable: &str) -> String {
+    (0..)
+        .map(|i| match i {
+            0 => "u".to_string(),
+            i => format!("u_{}", i),
+        })
+        .find(|name| name != variable && !expr.contains_var(name))
+        .unwrap_or_default()
+}
+
+/// Build `cn*x^n + ... + c0` from the coefficients `[c0, ..., cn]`.
+fn polynomial_expression(coefficients: &[Expression], variable: &str) -> Expression {
+    let terms = coefficients
+        .iter()
+        .enumerate()
+        .filter(|(_, coeff)| !is_zero(coeff))
+        .map(|(degree, coeff)| {
+            Expression::multiplication(vec![
+                coeff.clone(),
+                power_of(Expression::variable(variable), degree as u32),
+            ])
+        })
+        .collect();
+    Expression::addition(terms)
+}
+
+/// Integrate the polynomial `[c0, ..., cn]` term by term.
+fn integrate_coefficients(coefficients: &[Expression], variable: &str) -> Expression {
+    let terms = coefficients
+        .iter()
+        .enumerate()
+        .filter(|(_, coeff)| !is_zero(coeff))
+        .map(|(degree, coeff)| {
+            let next = degree as u32 + 1;
+            Expression::division(
+                Expression::multiplication(vec![
+                    coeff.clone(),
+                    power_of(Expression::variable(variable), next),
+                ]),
+                Expression::integer(next),
+            )
+        })
+        .collect();
+    Expression::addition(terms)
+}
+
+/// `x^2 + p*x + q`
+fn quadratic(p: &Expression, q: &Expression, variable: &str) -> Expression {
+    let x = Expression::variable(variable);
+    Expression::addition(vec![
+        Expression::exponentiation(x.clone(), Expression::integer(2)),
+        Expression::multiplication(vec![p.clone(), x]),
+        q.clone(),
+    ])
+}
+
+/// ∫(a*x + b)/(x^2 + p*x + q) dx where the quadratic has no rational root.
+///
+/// With `x^2 + p*x + q = (x + p/2)^2 + d` the remaining integral is an `atan`
+/// when `d > 0` and a logarithm when `d < 0`.
+fn quadratic_antiderivative(
+    a: &Expression,
+    b: &Expression,
+    p: &Expression,
+    q: &Expression,
+    variable: &str,
+) -> Result<Expression, SimplifyError> {
+    let [a_value, b_value, p_value, q_value] =
+        [a, b, p, q].map(|coeff| numeral(coeff).ok_or(SimplifyError::Unsupported));
+    let half_p = reduce(p_value?.div(&Numeral::Integer(BigInt::from(2))));
+    let d = reduce(q_value?.sub(&half_p.mul(&half_p)));
+    let c = reduce(b_value?.sub(&a_value?.mul(&half_p)));
+    let shifted = Expression::addition(vec![
+        Expression::variable(variable),
+        half_p.into_expression(),
+    ]);
+    let root = square_root(d.abs().into_expression()).map_err(|_| SimplifyError::Unsupported)?;
+
+    let mut terms = Vec::new();
+    if !is_zero(a) {
+        terms.push(Expression::multiplication(vec![
+            Expression::division(a.clone(), Expression::integer(2)),
+            Expression::ln(Expression::abs(quadratic(p, q, variable))),
+        ]));
+    }
+    if !c.is_zero() {
+        if d.is_negative() {
+            // c/(2*sqrt(-d)) * ln(|(x + p/2 - sqrt(-d))/(x + p/2 + sqrt(-d))|)
+            terms.push(Expression::multiplication(vec![
+                Expression::division(
+                    c.into_expression(),
+                    Expression::multiplication(vec![Expression::integer(2), root.clone()]),
+                ),
+                Expression::ln(Expression::abs(Expression::division(
+                    Expression::subtraction(shifted.clone(), root.clone()),
+                    Expression::addition(vec![shifted, root]),
+                ))),
+            ]));
+        } else {
+            // c/sqrt(d) * atan((x + p/2)/sqrt(d))
+            terms.push(Expression::multiplication(vec![
+                Expression::division(c.into_expression(), root.clone()),
+                Expression::atan(Expression::division(shifted, root)),
+            ]));
+        }
+    }
+    Ok(join_sum(terms))
+}
+
+fn join_sum(mut terms: Vec<Expression>) -> Expression {
+    match terms.len() {
+        0 => Expression::integer(0),
+        1 => terms.remove(0),
+        _ => Expression::addition(terms),
+    }
+}
+
+impl Integral {
+    /// Columns taken by the bounds, the upper bound is shifted by one column
+    /// like the top of the integral sign.
+    fn bounds_length(&self, memoization: &mut HashMap<Expression, (usize, usize)>) -> usize {
+        match &self.bounds {
+            Some((lower, upper)) => lower
+                .get_length(memoization)
+                .max(upper.get_length(memoization) + 1),
+            None => 0,
+        }
+    }
+
+    /// A sum is put in parenthesis so the `dx` is not read as one of its terms
+    fn needs_parenthesis(&self) -> bool {
+        matches!(
+            self.term,
+            Expression::Addition(_) | Expression::Subtraction(_) | Expression::Equality(_)
+        )
+    }
+}
+
+impl PrettyPrints for Integral {
+    fn calculate_tree(&self, indent: usize) -> String {
+        let next_indent = indent + 2;
+        let next_indent_str = " ".repeat(next_indent);
+
+        let mut tree = format!(
+            "Integral:\n{}{}\n{}d {}",
+            next_indent_str,
+            self.term.calculate_tree(next_indent),
+            next_indent_str,
+            self.variable,
+        );
+        if let Some((lower, upper)) = &self.bounds {
+            tree += &format!(
+                "\n{}from {}\n{}to {}",
+                next_indent_str,
+                lower.calculate_tree(next_indent),
+                next_indent_str,
+                upper.calculate_tree(next_indent),
+            );
+        }
+        tree
+    }
+
+    fn calculate_positions(
+        &self,
+        memoization: &mut HashMap<Expression, (usize, usize)>,
+        position: &mut Vec<(String, (usize, usize))>,
+        prev_pos: (usize, usize),
+    ) {
+        let height = self.term.get_height(memoization);
+        let below_height = self.term.get_below_height(memoization);
+        let parenthesis = self.needs_parenthesis();
+
+        let mut pos = prev_pos;
+        //  b
+        // ⌠
+        // ⎮
+        // ⌡
+        // a
+        if let Some((lower, _)) = &self.bounds {
+            lower.calculate_positions(memoization, position, pos);
+            pos.0 += lower.get_height(memoization);
+        }
+        position.push(("⌡".to_string(), pos));
+        for i in 1..=height {
+            position.push(("⎮".to_string(), (pos.0 + i, pos.1)));
+        }
+        position.push(("⌠".to_string(), (pos.0 + height + 1, pos.1)));
+        if let Some((_, upper)) = &self.bounds {
+            upper.calculate_positions(memoization, position, (pos.0 + height + 2, pos.1 + 1));
+        }
+
+        pos.0 += 1;
+        pos.1 += 2;
+        if parenthesis {
+            Self::calculate_parenthesis(position, pos, true, height);
+            pos.1 += 1;
+        }
+        self.term.calculate_positions(memoization, position, pos);
+        pos.1 += self.term.get_length(memoization);
+        if parenthesis {
+            Self::calculate_parenthesis(position, pos, false, height);
+            pos.1 += 1;
+        }
+
+        // d var
+        pos.0 += below_height;
+        pos.1 += 1;
+        for c in format!("d{}", self.variable).chars() {
+            position.push((c.to_string(), pos));
+            pos.1 += 1;
+        }
+    }
+
+    fn get_below_height(&self, memoization: &mut HashMap<Expression, (usize, usize)>) -> usize {
+        let lower = match &self.bounds {
+            Some((lower, _)) => lower.get_height(memoization),
+            None => 0,
+        };
+        self.term.get_below_height(memoization) + lower + 1
+    }
+
+    fn get_height(&self, memoization: &mut HashMap<Expression, (usize, usize)>) -> usize {
+        let bounds = match &self.bounds {
+            Some((lower, upper)) => lower.get_height(memoization) + upper.get_height(memoization),
+            None => 0,
+        };
+        self.term.get_height(memoization) + bounds + 2
+    }
+
+    fn get_length(&self, memoization: &mut HashMap<Expression, (usize, usize)>) -> usize {
+        let parenthesis = if self.needs_parenthesis() { 2 } else { 0 };
+        // The bounds are above and below the term
+        (self.term.get_length(memoization) + parenthesis + self.variable.len() + 4)
+            .max(self.bounds_length(memoization))
+    }
+}
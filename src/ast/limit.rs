@@ -0,0 +1,1122 @@
+use std::collections::HashMap;
+
+use crate::{ast::Expr, explanation::FormattingObserver, prints::PrettyPrints};
+
+use super::{
+    Expression, SimplifyError, SimplifyOptions, constant::Constant, evaluate::EvalError, function::FunctionType,
+    integral::cancel_factors,
+};
+
+/// Maximum number of L'Hôpital's rules and rewritings of an indeterminate form.
+const MAX_DEPTH: usize = 6;
+/// Highest order of the Taylor series compared for a `0/0` form.
+const MAX_ORDER: u32 = 6;
+/// Value given to the other variables when checking that a substitution is defined.
+pub(super) const GENERIC_VALUE: f64 = 0.577_215_664_901_532_9;
+
+#[derive(Debug, PartialEq, Copy, Clone, PartialOrd, Eq, Ord, Hash)]
+pub enum Direction {
+    /// From both sides, `x -> a`
+    Both,
+    /// From below, `x -> a-`
+    Left,
+    /// From above, `x -> a+`
+    Right,
+}
+
+impl std::fmt::Display for Direction {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Direction::Both => write!(f, ""),
+            Direction::Left => write!(f, "-"),
+            Direction::Right => write!(f, "+"),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, PartialOrd, Eq, Ord, Hash)]
+pub struct Limit {
+    pub term: Expression,
+    pub variable: String,
+    pub point: Expression,
+    pub direction: Direction,
+    pub simplified: bool,
+}
+
+// Constructor
+impl Limit {
+    pub fn new(
+        term: Expression,
+        variable: String,
+        point: Expression,
+        direction: Direction,
+        simplified: bool,
+    ) -> Self {
+        Self {
+            term,
+            variable,
+            point,
+            direction,
+            simplified,
+        }
+    }
+}
+
+impl Expr for Limit {
+    fn simplify(
+        &mut self,
+        options: &SimplifyOptions,
+        explanation: &mut Option<Box<FormattingObserver>>,
+    ) -> Result<Expression, SimplifyError> {
+        let term = self.term.simplify_with(options, explanation)?;
+        let point = self.point.simplify_with(options, explanation)?;
+        match term.find_limit_with(&self.variable, &point, self.direction, options, explanation) {
+            // The limit could not be found, it is kept
+            Err(SimplifyError::Unsupported) => Ok(Expression::Limit(Box::new(Limit::new(
+                term,
+                self.variable.clone(),
+                point,
+                self.direction,
+                true,
+            )))),
+            result => result,
+        }
+    }
+
+    fn is_equal(&self, other: &Limit) -> bool {
+        self.variable == other.variable
+            && self.direction == other.direction
+            && self.point.is_equal(&other.point)
+            && self.term.is_equal(&other.term)
+    }
+
+    fn contains_var(&self, variable: &str) -> bool {
+        // The variable of the limit is bound
+        self.point.contains_var(variable)
+            || (self.variable != variable && self.term.contains_var(variable))
+    }
+
+    fn is_single(&self) -> bool {
+        false
+    }
+}
+
+impl std::fmt::Display for Limit {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "lim({}->{}{}) ", self.variable, self.point, self.direction)?;
+        if self.term.is_single() {
+            write!(f, "{}", self.term)
+        } else {
+            write!(f, "({})", self.term)
+        }
+    }
+}
+
+/// Value of a limit, the infinities are kept apart from the expressions.
+#[derive(Debug, Clone)]
+enum Value {
+    Finite(Expression),
+    /// `true` for `+inf` and `false` for `-inf`
+    Infinite(bool),
+    /// The limit written as an expression, it stays between two bounds but
+    /// may have no limit, `true` if it is proven to oscillate
+    Bounded(Expression, bool),
+}
+
+impl Value {
+    fn from_expression(expr: &Expression) -> Value {
+        match expr {
+            Expression::Constant(Constant::Infinity) => Value::Infinite(true),
+            Expression::Negation(neg) if neg.term == Expression::infinity() => {
+                Value::Infinite(false)
+            }
+            _ => Value::Finite(expr.clone()),
+        }
+    }
+
+    fn to_expression(&self) -> Expression {
+        match self {
+            Value::Finite(expr) => expr.clone(),
+            Value::Infinite(true) => Expression::infinity(),
+            Value::Infinite(false) => Expression::negation(Expression::infinity()),
+            Value::Bounded(limit, _) => limit.clone(),
+        }
+    }
+}
+
+/// The variable going to a point.
+struct Approach<'a> {
+    variable: &'a str,
+    point: Value,
+    direction: Direction,
+    /// Options of every simplification on the way
+    options: &'a SimplifyOptions,
+}
+
+impl Approach<'_> {
+    /// `lim expr` written as an expression for the explanations.
+    fn limit(&self, expr: &Expression) -> Expression {
+        Expression::limit(
+            expr.clone(),
+            self.variable,
+            self.point.to_expression(),
+            self.direction,
+        )
+    }
+
+    /// Returns `expr` at the point if it is defined and continuous there.
+    fn substitute(&self, expr: &Expression) -> Option<Expression> {
+        let Value::Finite(point) = &self.point else {
+            return None;
+        };
+        if self.jumps(expr) {
+            return None;
+        }
+        defined(&expr.substitute(&Expression::variable(self.variable), point), self.options)
+    }
+
+    /// Returns `true` if `expr` has a floor or a ceil of an integer at the point,
+    /// they are not continuous there.
+    fn jumps(&self, expr: &Expression) -> bool {
+        if let Expression::Function(fun) = expr
+            && matches!(fun.name, FunctionType::Floor | FunctionType::Ceil)
+            && let [arg] = fun.args.as_slice()
+            && arg.contains_var(self.variable)
+            && self
+                .substitute(arg)
+                .is_some_and(|value| integer_value(&value).is_some())
+        {
+            return true;
+        }
+        let mut jumps = false;
+        let _ = expr.map_children(|child| {
+            jumps = jumps || self.jumps(child);
+            Ok(child.clone())
+        });
+        jumps
+    }
+
+    /// The approaches from each side of a finite point for a limit from both sides,
+    /// otherwise the approach itself.
+    fn sides(&self) -> Vec<Approach<'_>> {
+        let directions = match (&self.point, self.direction) {
+            (Value::Finite(_), Direction::Both) => vec![Direction::Left, Direction::Right],
+            _ => vec![self.direction],
+        };
+        directions
+            .into_iter()
+            .map(|direction| Approach {
+                variable: self.variable,
+                point: self.point.clone(),
+                direction,
+                options: self.options,
+            })
+            .collect()
+    }
+
+    /// Values of the variable close to the point, on each side it is approached from.
+    fn samples(&self) -> Result<Vec<Vec<f64>>, SimplifyError> {
+        let steps = [1e-3, 1e-5, 1e-7];
+        Ok(match &self.point {
+            Value::Finite(point) => {
+                let point = point
+                    .evaluate(&HashMap::new())
+                    .map_err(|_| SimplifyError::Unsupported)?;
+                let left = steps.iter().map(|step| point - step).collect();
+                let right = steps.iter().map(|step| point + step).collect();
+                match self.direction {
+                    Direction::Both => vec![left, right],
+                    Direction::Left => vec![left],
+                    Direction::Right => vec![right],
+                }
+            }
+            Value::Infinite(positive) => {
+                let sign = if *positive { 1.0 } else { -1.0 };
+                vec![steps.iter().map(|step| sign / step).collect()]
+            }
+            Value::Bounded(_, _) => return Err(SimplifyError::Unsupported),
+        })
+    }
+
+    /// Sign of `expr` close to the point, `true` if positive.
+    ///
+    /// Returns `SimplifyError::UndefinedLimit` when the sign differs on each side.
+    fn side_sign(&self, expr: &Expression) -> Result<bool, SimplifyError> {
+        let mut signs = Vec::new();
+        for side in self.samples()? {
+            let mut side_signs = side.iter().map(|x| {
+                let env = HashMap::from([(self.variable.to_string(), *x)]);
+                match expr.evaluate(&env) {
+                    Ok(value) if value > 0.0 => Ok(true),
+                    Ok(value) if value < 0.0 => Ok(false),
+                    _ => Err(SimplifyError::Unsupported),
+                }
+            });
+            let sign = side_signs.next().ok_or(SimplifyError::Unsupported)??;
+            for other in side_signs {
+                if other? != sign {
+                    return Err(SimplifyError::Unsupported);
+                }
+            }
+            signs.push(sign);
+        }
+        match signs.as_slice() {
+            [sign] => Ok(*sign),
+            [left, right] if left == right => Ok(*left),
+            _ => Err(SimplifyError::UndefinedLimit),
+        }
+    }
+}
+
+impl Expression {
+    /// Returns the limit of the expression when `variable` goes to `point`.
+    ///
+    /// The point can be `inf` or `-inf`, the direction is then ignored.
+    /// Indeterminate forms are solved with L'Hôpital's rule, or by comparing
+    /// the Taylor series of the numerator and of the denominator.
+    /// `SimplifyError::UndefinedLimit` is returned if the limit does not exist
+    /// and `SimplifyError::Unsupported` if it could not be found.
+    ///
+    /// # Examples
+    /// ```
+    /// use sym_rustic::ast::{Expression, limit::Direction};
+    ///
+    /// // lim(x->0) sin(x)/x = 1
+    /// let expr = Expression::division(
+    ///     Expression::sin(Expression::variable("x")),
+    ///     Expression::variable("x"),
+    /// );
+    /// let result = expr.find_limit("x", &Expression::integer(0), Direction::Both, &mut None);
+    /// assert_eq!(result, Ok(Expression::integer(1)));
+    /// ```
+    pub fn find_limit(
+        &self,
+        variable: &str,
+        point: &Expression,
+        direction: Direction,
+        explanation: &mut Option<Box<FormattingObserver>>,
+    ) -> Result<Expression, SimplifyError> {
+        self.find_limit_with(variable, point, direction, &SimplifyOptions::default(), explanation)
+    }
+
+    /// Returns the limit like `Expression::find_limit`, every step is simplified
+    /// with `options`.
+    pub fn find_limit_with(
+        &self,
+        variable: &str,
+        point: &Expression,
+        direction: Direction,
+        options: &SimplifyOptions,
+        explanation: &mut Option<Box<FormattingObserver>>,
+    ) -> Result<Expression, SimplifyError> {
+        let point = Value::from_expression(point);
+        // inf is only approached from one side
+        let direction = match point {
+            Value::Infinite(_) => Direction::Both,
+            _ => direction,
+        };
+        let approach = Approach {
+            variable,
+            point,
+            direction,
+            options,
+        };
+
+        // Steps are only kept when the limit is found
+        let mut trial = explanation.clone();
+        let term = self.clone().simplify_with(options, &mut trial)?;
+        let result = match term.limit_value(&approach, MAX_DEPTH, &mut trial)? {
+            Value::Bounded(_, true) => return Err(SimplifyError::UndefinedLimit),
+            Value::Bounded(_, false) => return Err(SimplifyError::Unsupported),
+            value => value.to_expression().simplify_with(options, &mut trial)?,
+        };
+        *explanation = trial;
+        Ok(result)
+    }
+
+    fn limit_value(
+        &self,
+        approach: &Approach,
+        depth: usize,
+        explanation: &mut Option<Box<FormattingObserver>>,
+    ) -> Result<Value, SimplifyError> {
+        let before = approach.limit(self);
+        if !self.contains_var(approach.variable) {
+            if let Some(explanation) = explanation {
+                explanation.rule_applied("Limit of a constant is given by\nlim a => a", &before, self);
+            }
+            return Ok(Value::Finite(self.clone()));
+        }
+        if let Some(after) = approach.substitute(self) {
+            if let Some(explanation) = explanation {
+                explanation.rule_applied(
+                    "The expression is defined at the point, substitute it\nlim(x->a) f(x) => f(a)",
+                    &before,
+                    &after,
+                );
+            }
+            return Ok(Value::Finite(after));
+        }
+
+        match self {
+            Expression::Variable(_) => {
+                if let Some(explanation) = explanation {
+                    explanation.rule_applied(
+                        "Limit of the variable is the point\nlim(x->a) x => a",
+                        &before,
+                        &approach.point.to_expression(),
+                    );
+                }
+                Ok(approach.point.clone())
+            }
+            Expression::Negation(neg) => {
+                if let Some(explanation) = explanation {
+                    let after = Expression::negation(approach.limit(&neg.term));
+                    explanation.rule_applied(
+                        "The negative constant is highlighted\nlim -f => -lim f",
+                        &before,
+                        &after,
+                    );
+                }
+                Ok(match neg.term.limit_value(approach, depth, explanation)? {
+                    Value::Finite(expr) => Value::Finite(Expression::negation(expr)),
+                    Value::Infinite(positive) => Value::Infinite(!positive),
+                    Value::Bounded(_, oscillates) => Value::Bounded(before, oscillates),
+                })
+            }
+            Expression::Addition(add) => Self::limit_sum(&add.terms, approach, depth, explanation),
+            Expression::Subtraction(sub) => Self::limit_sum(
+                &[sub.left.clone(), Expression::negation(sub.right.clone())],
+                approach,
+                depth,
+                explanation,
+            ),
+            Expression::Multiplication(mul) => {
+                Self::limit_product(&mul.terms, approach, depth, explanation)
+            }
+            Expression::Division(div) => {
+                Self::limit_quotient(&div.num, &div.den, approach, depth, explanation)
+            }
+            Expression::Exponentiation(exp) => {
+                Self::limit_power(&exp.base, &exp.expo, approach, depth, explanation)
+            }
+            Expression::Function(fun) => {
+                Self::limit_function(&fun.name, &fun.args, approach, depth, explanation)
+            }
+            _ => Err(SimplifyError::Unsupported),
+        }
+    }
+
+    fn limit_sum(
+        terms: &[Expression],
+        approach: &Approach,
+        depth: usize,
+        explanation: &mut Option<Box<FormattingObserver>>,
+    ) -> Result<Value, SimplifyError> {
+        let before = approach.limit(&Expression::addition(terms.to_vec()));
+        if let Some(explanation) = explanation {
+            let after = Expression::addition(terms.iter().map(|term| approach.limit(term)).collect());
+            explanation.rule_applied(
+                "Limit of sum is given by\nlim (f + g) => lim f + lim g",
+                &before,
+                &after,
+            );
+        }
+
+        let mut finite = Vec::new();
+        let mut infinite: Vec<(usize, bool)> = Vec::new();
+        let mut bounded = Vec::new();
+        for (i, term) in terms.iter().enumerate() {
+            match term.limit_value(approach, depth, explanation)? {
+                Value::Finite(expr) => finite.push(expr),
+                Value::Infinite(positive) => infinite.push((i, positive)),
+                Value::Bounded(_, oscillates) => bounded.push(oscillates),
+            }
+        }
+
+        match infinite.as_slice() {
+            // The sum of an oscillating expression and of converging ones oscillates
+            [] if !bounded.is_empty() => Ok(Value::Bounded(before, bounded == [true])),
+            [] => Ok(Value::Finite(Expression::addition(finite).simplify_with(approach.options, &mut None)?)),
+            // A bounded term doesn't change an infinite limit
+            [(_, positive), rest @ ..] if rest.iter().all(|(_, sign)| sign == positive) => {
+                Ok(Value::Infinite(*positive))
+            }
+            // inf - inf => f*(1 + g/f)
+            [(index, _), ..] => {
+                if depth == 0 {
+                    return Err(SimplifyError::Unsupported);
+                }
+                let dominant = &terms[*index];
+                let mut ratios = vec![Expression::integer(1)];
+                for (i, term) in terms.iter().enumerate() {
+                    if i != *index {
+                        ratios.push(cancel_factors(
+                            &Expression::division(term.clone(), dominant.clone()),
+                            approach.variable,
+                        )?);
+                    }
+                }
+                let after = Expression::multiplication(vec![
+                    dominant.clone(),
+                    Expression::addition(ratios),
+                ]);
+                if let Some(explanation) = explanation {
+                    explanation.rule_applied(
+                        "Factor out a term of the indeterminate form inf - inf\nf + g => f*(1 + g/f)",
+                        &before,
+                        &approach.limit(&after),
+                    );
+                }
+                after.limit_value(approach, depth - 1, explanation)
+            }
+        }
+    }
+
+    fn limit_product(
+        terms: &[Expression],
+        approach: &Approach,
+        depth: usize,
+        explanation: &mut Option<Box<FormattingObserver>>,
+    ) -> Result<Value, SimplifyError> {
+        let before = approach.limit(&Expression::multiplication(terms.to_vec()));
+        if let Some(explanation) = explanation {
+            let after =
+                Expression::multiplication(terms.iter().map(|term| approach.limit(term)).collect());
+            explanation.rule_applied(
+                "Limit of product is given by\nlim (f*g) => lim f * lim g",
+                &before,
+                &after,
+            );
+        }
+
+        let mut finite = Vec::new();
+        let mut infinite = Vec::new();
+        let mut bounded = Vec::new();
+        let mut positive = true;
+        for term in terms {
+            match term.limit_value(approach, depth, explanation)? {
+                Value::Finite(expr) => finite.push((term.clone(), expr)),
+                Value::Infinite(sign) => {
+                    infinite.push(term.clone());
+                    positive = positive == sign;
+                }
+                Value::Bounded(_, oscillates) => bounded.push(oscillates),
+            }
+        }
+
+        if !bounded.is_empty() {
+            if !infinite.is_empty() {
+                return Err(SimplifyError::Unsupported);
+            }
+            if finite.iter().any(|(_, value)| is_zero(value)) {
+                return Ok(Self::squeeze(&before, explanation));
+            }
+            // An oscillating expression times non zero numbers still oscillates
+            let oscillates =
+                bounded == [true] && finite.iter().all(|(_, value)| sign(value).is_ok());
+            return Ok(Value::Bounded(before, oscillates));
+        }
+        if infinite.is_empty() {
+            let product = finite.into_iter().map(|(_, value)| value).collect();
+            return Ok(Value::Finite(Expression::multiplication(product).simplify_with(approach.options, &mut None)?));
+        }
+        if !finite.iter().any(|(_, value)| is_zero(value)) {
+            for (_, value) in &finite {
+                positive = positive == sign(value)?;
+            }
+            return Ok(Value::Infinite(positive));
+        }
+
+        // 0*inf => f/(1/g)
+        if depth == 0 {
+            return Err(SimplifyError::Unsupported);
+        }
+        let finite = join(finite.into_iter().map(|(term, _)| term).collect());
+        let infinite = join(infinite);
+        for (num, den) in [(&infinite, &finite), (&finite, &infinite)] {
+            let den = cancel_factors(
+                &Expression::division(Expression::integer(1), den.clone()),
+                approach.variable,
+            )?;
+            let mut trial = explanation.clone();
+            if let Some(trial) = &mut trial {
+                let after = Expression::division(num.clone(), den.clone());
+                trial.rule_applied(
+                    "Write the indeterminate form 0*inf as a quotient\nf*g => f/(1/g)",
+                    &before,
+                    &approach.limit(&after),
+                );
+            }
+            if let Ok(value) = Self::limit_quotient(num, &den, approach, depth - 1, &mut trial) {
+                *explanation = trial;
+                return Ok(value);
+            }
+        }
+        Err(SimplifyError::Unsupported)
+    }
+
+    fn limit_quotient(
+        num: &Expression,
+        den: &Expression,
+        approach: &Approach,
+        depth: usize,
+        explanation: &mut Option<Box<FormattingObserver>>,
+    ) -> Result<Value, SimplifyError> {
+        let quotient = Expression::division(num.clone(), den.clone());
+        let before = approach.limit(&quotient);
+        if let Some(explanation) = explanation {
+            let after = Expression::division(approach.limit(num), approach.limit(den));
+            explanation.rule_applied(
+                "Limit of quotient is given by\nlim f/g => lim f / lim g",
+                &before,
+                &after,
+            );
+        }
+
+        let num_value = num.limit_value(approach, depth, explanation)?;
+        let den_value = den.limit_value(approach, depth, explanation)?;
+        match (num_value, den_value) {
+            (Value::Finite(n), Value::Finite(d)) if !is_zero(&d) => {
+                Ok(Value::Finite(Expression::division(n, d).simplify_with(approach.options, &mut None)?))
+            }
+            // a/0 => inf
+            (Value::Finite(n), Value::Finite(_)) if !is_zero(&n) => {
+                let positive = sign(&n)? == approach.side_sign(den)?;
+                let after = Value::Infinite(positive);
+                if let Some(explanation) = explanation {
+                    explanation.rule_applied(
+                        "A non zero number divided by a vanishing expression is infinite\nlim a/g => inf",
+                        &before,
+                        &after.to_expression(),
+                    );
+                }
+                Ok(after)
+            }
+            (Value::Finite(_), Value::Finite(_)) | (Value::Infinite(_), Value::Infinite(_)) => {
+                Self::lhopital(num, den, approach, depth, explanation)
+            }
+            (Value::Finite(_), Value::Infinite(_)) => Ok(Value::Finite(Expression::integer(0))),
+            (Value::Bounded(_, _), Value::Infinite(_)) => Ok(Self::squeeze(&before, explanation)),
+            (Value::Bounded(_, oscillates), Value::Finite(d)) if !is_zero(&d) => {
+                Ok(Value::Bounded(before, oscillates && sign(&d).is_ok()))
+            }
+            (Value::Bounded(_, _), _) | (_, Value::Bounded(_, _)) => Err(SimplifyError::Unsupported),
+            (Value::Infinite(positive), Value::Finite(d)) => {
+                let den_sign = if is_zero(&d) {
+                    approach.side_sign(den)?
+                } else {
+                    sign(&d)?
+                };
+                Ok(Value::Infinite(positive == den_sign))
+            }
+        }
+    }
+
+    /// A bounded expression times a vanishing one, or divided by an infinite one, goes to zero.
+    fn squeeze(before: &Expression, explanation: &mut Option<Box<FormattingObserver>>) -> Value {
+        let after = Expression::integer(0);
+        if let Some(explanation) = explanation {
+            explanation.rule_applied(
+                "A bounded expression times a vanishing one goes to zero\n|f| <= M and lim g = 0 => lim f*g = 0",
+                before,
+                &after,
+            );
+        }
+        Value::Finite(after)
+    }
+
+    /// `0/0` or `inf/inf` solved by L'Hôpital's rule, or by the Taylor series at a finite point.
+    fn lhopital(
+        num: &Expression,
+        den: &Expression,
+        approach: &Approach,
+        depth: usize,
+        explanation: &mut Option<Box<FormattingObserver>>,
+    ) -> Result<Value, SimplifyError> {
+        if depth == 0 {
+            return Err(SimplifyError::Unsupported);
+        }
+        let before = approach.limit(&Expression::division(num.clone(), den.clone()));
+
+        let mut trial = explanation.clone();
+        let mut lhopital = || {
+            let num = num.differentiate_n_with(approach.variable, 1, approach.options, &mut None)?;
+            let den = den.differentiate_n_with(approach.variable, 1, approach.options, &mut None)?;
+            let after = cancel_factors(&Expression::division(num, den), approach.variable)?
+                .simplify_with(approach.options, &mut None)?;
+            if let Some(trial) = &mut trial {
+                trial.rule_applied(
+                    "L'Hôpital's rule for an indeterminate form\nlim f/g => lim f'/g'",
+                    &before,
+                    &approach.limit(&after),
+                );
+            }
+            after.limit_value(approach, depth - 1, &mut trial)
+        };
+        match lhopital() {
+            Ok(value) => {
+                *explanation = trial;
+                Ok(value)
+            }
+            Err(SimplifyError::Unsupported) => {
+                Self::compare_series(num, den, approach, &before, explanation)
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    /// `f/g` at `a` from the first non zero terms of the Taylor series,
+    /// `f ~ f^(k)(a)/k! * (x - a)^k` and `g ~ g^(m)(a)/m! * (x - a)^m`.
+    fn compare_series(
+        num: &Expression,
+        den: &Expression,
+        approach: &Approach,
+        before: &Expression,
+        explanation: &mut Option<Box<FormattingObserver>>,
+    ) -> Result<Value, SimplifyError> {
+        let Value::Finite(point) = &approach.point else {
+            return Err(SimplifyError::Unsupported);
+        };
+        let (k, num_coeff) = leading_term(num, approach, point)?;
+        let (m, den_coeff) = leading_term(den, approach, point)?;
+
+        let after = if k > m {
+            Value::Finite(Expression::integer(0))
+        } else if k == m {
+            // The k! cancel
+            Value::Finite(Expression::division(num_coeff, den_coeff).simplify_with(approach.options, &mut None)?)
+        } else {
+            // (x - a)^(k - m) goes to inf, with the sign of the side for an odd power
+            let shift = Expression::subtraction(Expression::variable(approach.variable), point.clone());
+            let power = Expression::exponentiation(shift, Expression::integer(m - k));
+            let ratio = Expression::division(num_coeff, den_coeff);
+            Value::Infinite(sign(&ratio)? == approach.side_sign(&power)?)
+        };
+        if let Some(explanation) = explanation {
+            explanation.rule_applied(
+                "Compare the first terms of the Taylor series\nlim f/g => lim (f^(k)(a)*(x - a)^k/k!)/(g^(m)(a)*(x - a)^m/m!)",
+                before,
+                &after.to_expression(),
+            );
+        }
+        Ok(after)
+    }
+
+    fn limit_power(
+        base: &Expression,
+        expo: &Expression,
+        approach: &Approach,
+        depth: usize,
+        explanation: &mut Option<Box<FormattingObserver>>,
+    ) -> Result<Value, SimplifyError> {
+        let before = approach.limit(&Expression::exponentiation(base.clone(), expo.clone()));
+
+        // f^c
+        if !expo.contains_var(approach.variable) {
+            if let Some(explanation) = explanation {
+                let after = Expression::exponentiation(approach.limit(base), expo.clone());
+                explanation.rule_applied(
+                    "Limit of a power is given by\nlim f^c => (lim f)^c",
+                    &before,
+                    &after,
+                );
+            }
+            let positive_expo = sign(expo)?;
+            return match base.limit_value(approach, depth, explanation)? {
+                Value::Finite(b) if !is_zero(&b) => Ok(Value::Finite(
+                    Expression::exponentiation(b, expo.clone()).simplify_with(approach.options, &mut None)?,
+                )),
+                Value::Finite(_) if positive_expo => Ok(Value::Finite(Expression::integer(0))),
+                // f^(-c) => 1/f^c
+                Value::Finite(_) => Self::limit_quotient(
+                    &Expression::integer(1),
+                    &Expression::exponentiation(base.clone(), Expression::negation(expo.clone()))
+                        .simplify_with(approach.options, &mut None)?,
+                    approach,
+                    depth,
+                    explanation,
+                ),
+                Value::Infinite(_) if !positive_expo => Ok(Value::Finite(Expression::integer(0))),
+                Value::Infinite(true) => Ok(Value::Infinite(true)),
+                Value::Infinite(false) => match integer_value(expo) {
+                    Some(n) => Ok(Value::Infinite(n % 2 == 0)),
+                    None => Err(SimplifyError::Unsupported),
+                },
+                Value::Bounded(_, _) => Err(SimplifyError::Unsupported),
+            };
+        }
+
+        // c^g
+        if !base.contains_var(approach.variable) {
+            if let Some(explanation) = explanation {
+                let after = Expression::exponentiation(base.clone(), approach.limit(expo));
+                explanation.rule_applied(
+                    "Limit of an exponential is given by\nlim c^g => c^(lim g)",
+                    &before,
+                    &after,
+                );
+            }
+            let value = base
+                .evaluate(&HashMap::new())
+                .map_err(|_| SimplifyError::Unsupported)?;
+            return match expo.limit_value(approach, depth, explanation)? {
+                Value::Finite(g) => Ok(Value::Finite(
+                    Expression::exponentiation(base.clone(), g).simplify_with(approach.options, &mut None)?,
+                )),
+                _ if value == 1.0 => Ok(Value::Finite(Expression::integer(1))),
+                Value::Infinite(positive) if value > 1.0 => Ok(if positive {
+                    Value::Infinite(true)
+                } else {
+                    Value::Finite(Expression::integer(0))
+                }),
+                Value::Infinite(positive) if value > 0.0 => Ok(if positive {
+                    Value::Finite(Expression::integer(0))
+                } else {
+                    Value::Infinite(true)
+                }),
+                Value::Infinite(_) | Value::Bounded(_, _) => Err(SimplifyError::Unsupported),
+            };
+        }
+
+        // f^g => e^(g*ln(f))
+        if depth == 0 {
+            return Err(SimplifyError::Unsupported);
+        }
+        let after = Expression::exponentiation(
+            Expression::e(),
+            Expression::multiplication(vec![expo.clone(), Expression::ln(base.clone())]),
+        );
+        if let Some(explanation) = explanation {
+            explanation.rule_applied(
+                "Write the power as an exponential\nf^g => e^(g*ln(f))",
+                &before,
+                &approach.limit(&after),
+            );
+        }
+        after.limit_value(approach, depth - 1, explanation)
+    }
+
+    fn limit_function(
+        name: &FunctionType,
+        args: &[Expression],
+        approach: &Approach,
+        depth: usize,
+        explanation: &mut Option<Box<FormattingObserver>>,
+    ) -> Result<Value, SimplifyError> {
+        let power = match (name, args) {
+            (FunctionType::Sqrt, [arg]) => Some((arg.clone(), Expression::rational(1, 2))),
+            (FunctionType::Exp, [arg]) => Some((Expression::e(), arg.clone())),
+            // pow(o, f) => f^o and root(o, f) => f^(1/o)
+            (FunctionType::Pow, [order, arg]) => Some((arg.clone(), order.clone())),
+            (FunctionType::Root, [order, arg]) => Some((
+                arg.clone(),
+                Expression::division(Expression::integer(1), order.clone())
+                    .simplify_with(approach.options, &mut None)?,
+            )),
+            _ => None,
+        };
+        if let Some((base, expo)) = power {
+            return Self::limit_power(&base, &expo, approach, depth, explanation);
+        }
+        // log(b, f) => ln(f)/ln(b)
+        if let (FunctionType::Log, [base, arg]) = (name, args) {
+            return Self::limit_quotient(
+                &Expression::ln(arg.clone()),
+                &Expression::ln(base.clone()),
+                approach,
+                depth,
+                explanation,
+            );
+        }
+        let [arg] = args else {
+            return Err(SimplifyError::Unsupported);
+        };
+
+        let before = approach.limit(&Expression::function(name.clone(), args.to_vec()));
+        if let Some(explanation) = explanation {
+            let after = Expression::function(name.clone(), vec![approach.limit(arg)]);
+            explanation.rule_applied(
+                "The limit goes inside a continuous function\nlim f(g) => f(lim g)",
+                &before,
+                &after,
+            );
+        }
+        let bounded = matches!(name, FunctionType::Sin | FunctionType::Cos);
+        let value = match arg.limit_value(approach, depth, explanation) {
+            Ok(value) => value,
+            // sin and cos stay in [-1, 1] even when the argument has no limit,
+            // they oscillate if it goes to an infinity on each side
+            Err(err) if bounded => {
+                let oscillates = err == SimplifyError::UndefinedLimit
+                    && approach.sides().iter().all(|side| {
+                        matches!(arg.limit_value(side, depth, &mut None), Ok(Value::Infinite(_)))
+                    });
+                return Ok(Value::Bounded(before, oscillates));
+            }
+            Err(err) => return Err(err),
+        };
+        let after = match value {
+            // ln(0+) => -inf
+            Value::Finite(value)
+                if is_zero(&value)
+                    && matches!(
+                        name,
+                        FunctionType::Ln | FunctionType::Log2 | FunctionType::Log10
+                    ) =>
+            {
+                if approach.side_sign(arg)? {
+                    Value::Infinite(false)
+                } else {
+                    return Err(SimplifyError::Unsupported);
+                }
+            }
+            // floor and ceil jump at the integers, the sides can differ
+            Value::Finite(value)
+                if matches!(name, FunctionType::Floor | FunctionType::Ceil)
+                    && let Some(n) = integer_value(&value) =>
+            {
+                let offset = Expression::subtraction(arg.clone(), value);
+                let mut values = Vec::new();
+                for side in approach.sides() {
+                    values.push(match (name, side.side_sign(&offset)?) {
+                        (FunctionType::Floor, false) => n - 1,
+                        (FunctionType::Ceil, true) => n + 1,
+                        _ => n,
+                    });
+                }
+                values.dedup();
+                match values.as_slice() {
+                    [value] => Value::Finite(Expression::integer(*value)),
+                    _ => return Err(SimplifyError::UndefinedLimit),
+                }
+            }
+            Value::Finite(value) => match defined(
+                &Expression::function(name.clone(), vec![value]),
+                approach.options,
+            ) {
+                Some(value) => Value::Finite(value),
+                None => return Err(SimplifyError::Unsupported),
+            },
+            Value::Infinite(_) if bounded => return Ok(Value::Bounded(before, true)),
+            Value::Infinite(positive) => match name {
+                FunctionType::Atan => {
+                    let half_pi = Expression::division(Expression::pi(), Expression::integer(2));
+                    Value::Finite(if positive {
+                        half_pi
+                    } else {
+                        Expression::negation(half_pi)
+                    })
+                }
+                FunctionType::Tanh => Value::Finite(if positive {
+                    Expression::integer(1)
+                } else {
+                    Expression::negation(Expression::integer(1))
+                }),
+                FunctionType::Sinh
+                | FunctionType::Asinh
+                | FunctionType::Floor
+                | FunctionType::Ceil => Value::Infinite(positive),
+                FunctionType::Cosh | FunctionType::Abs => Value::Infinite(true),
+                FunctionType::Ln
+                | FunctionType::Log2
+                | FunctionType::Log10
+                | FunctionType::Acosh
+                    if positive =>
+                {
+                    Value::Infinite(true)
+                }
+                _ => return Err(SimplifyError::Unsupported),
+            },
+            Value::Bounded(_, _) => return Err(SimplifyError::Unsupported),
+        };
+        if let Some(explanation) = explanation {
+            explanation.rule_applied(
+                &format!("Limit of {} is known", name),
+                &before,
+                &after.to_expression(),
+            );
+        }
+        Ok(after)
+    }
+}
+
+/// Returns the simplified `expr` if it has a finite value.
+///
+/// The value is checked numerically, the variables are given a generic value.
+pub(super) fn defined(expr: &Expression, options: &SimplifyOptions) -> Option<Expression> {
+    let value = expr.clone().simplify_with(options, &mut None).ok()?;
+    let mut env = HashMap::new();
+    loop {
+        match value.evaluate(&env) {
+            Ok(number) if number.is_finite() => return Some(value),
+            Err(EvalError::UnboundVariable(name)) => {
+                env.insert(name, GENERIC_VALUE);
+            }
+            _ => return None,
+        }
+    }
+}
+
+/// Order and value of the first non zero derivative of `expr` at `point`.
+fn leading_term(
+    expr: &Expression,
+    approach: &Approach,
+    point: &Expression,
+) -> Result<(u32, Expression), SimplifyError> {
+    let mut derivative = expr.clone();
+    for order in 0..=MAX_ORDER {
+        let value = derivative
+            .substitute(&Expression::variable(approach.variable), point)
+            .simplify_with(approach.options, &mut None)
+            .map_err(|_| SimplifyError::Unsupported)?;
+        if value
+            .evaluate(&HashMap::new())
+            .map_err(|_| SimplifyError::Unsupported)?
+            .is_finite()
+            && !is_zero(&value)
+        {
+            return Ok((order, value));
+        }
+        derivative = derivative.differentiate_n_with(approach.variable, 1, approach.options, &mut None)?;
+    }
+    Err(SimplifyError::Unsupported)
+}
+
+/// Returns `true` if the expression is zero, numerically when it has no variables.
+fn is_zero(expr: &Expression) -> bool {
+    match expr {
+        Expression::Number(n) => n.is_zero(),
+        Expression::Negation(neg) => is_zero(&neg.term),
+        _ => expr
+            .evaluate(&HashMap::new())
+            .is_ok_and(|value| value.abs() < 1e-12),
+    }
+}
+
+/// Sign of a constant expression, `true` if positive.
+fn sign(expr: &Expression) -> Result<bool, SimplifyError> {
+    match expr.evaluate(&HashMap::new()) {
+        Ok(value) if value > 0.0 => Ok(true),
+        Ok(value) if value < 0.0 => Ok(false),
+        _ => Err(SimplifyError::Unsupported),
+    }
+}
+
+fn integer_value(expr: &Expression) -> Option<i64> {
+    let value = expr.evaluate(&HashMap::new()).ok()?;
+    (value.fract() == 0.0).then_some(value as i64)
+}
+
+/// Multiply the terms, an empty product is one.
+fn join(mut terms: Vec<Expression>) -> Expression {
+    match terms.len() {
+        0 => Expression::integer(1),
+        1 => terms.remove(0),
+        _ => Expression::multiplication(terms),
+    }
+}
+
+impl Limit {
+    /// `x->a` written under `lim`
+    fn subscript_length(&self, memoization: &mut HashMap<Expression, (usize, usize)>) -> usize {
+        self.variable.len()
+            + 2
+            + self.point.get_length(memoization)
+            + self.direction.to_string().len()
+    }
+
+    /// Columns taken by `lim` and the subscript
+    fn operator_length(&self, memoization: &mut HashMap<Expression, (usize, usize)>) -> usize {
+        self.subscript_length(memoization).max(3)
+    }
+
+    /// A sum is put in parenthesis so it is not read as the sum of a limit
+    fn needs_parenthesis(&self) -> bool {
+        matches!(self.term, Expression::Addition(_) | Expression::Subtraction(_))
+    }
+}
+
+impl PrettyPrints for Limit {
+    fn calculate_tree(&self, indent: usize) -> String {
+        let next_indent = indent + 2;
+        let next_indent_str = " ".repeat(next_indent);
+
+        format!(
+            "Limit{}:\n{}{}\n{}{} -> {}",
+            match self.direction {
+                Direction::Both => "",
+                Direction::Left => " from below",
+                Direction::Right => " from above",
+            },
+            next_indent_str,
+            self.term.calculate_tree(next_indent),
+            next_indent_str,
+            self.variable,
+            self.point.calculate_tree(next_indent),
+        )
+    }
+
+    fn calculate_positions(
+        &self,
+        memoization: &mut HashMap<Expression, (usize, usize)>,
+        position: &mut Vec<(String, (usize, usize))>,
+        prev_pos: (usize, usize),
+    ) {
+        let baseline = prev_pos.0 + self.get_below_height(memoization);
+        let operator_length = self.operator_length(memoization);
+
+        //  lim
+        // x->a
+        let column = prev_pos.1 + (operator_length - 3) / 2;
+        for (i, c) in "lim".chars().enumerate() {
+            position.push((c.to_string(), (baseline, column + i)));
+        }
+        let point_height = self.point.get_height(memoization);
+        let point_bottom = baseline - point_height;
+        let row = point_bottom + self.point.get_below_height(memoization);
+        let mut column = prev_pos.1;
+        for c in format!("{}->", self.variable).chars() {
+            position.push((c.to_string(), (row, column)));
+            column += 1;
+        }
+        self.point.calculate_positions(memoization, position, (point_bottom, column));
+        column += self.point.get_length(memoization);
+        for c in self.direction.to_string().chars() {
+            position.push((c.to_string(), (row, column)));
+        }
+
+        // term
+        let height = self.term.get_height(memoization);
+        let mut pos = (
+            baseline - self.term.get_below_height(memoization),
+            prev_pos.1 + operator_length + 1,
+        );
+        let parenthesis = self.needs_parenthesis();
+        if parenthesis {
+            Self::calculate_parenthesis(position, pos, true, height);
+            pos.1 += 1;
+        }
+        self.term.calculate_positions(memoization, position, pos);
+        pos.1 += self.term.get_length(memoization);
+        if parenthesis {
+            Self::calculate_parenthesis(position, pos, false, height);
+        }
+    }
+
+    fn get_below_height(&self, memoization: &mut HashMap<Expression, (usize, usize)>) -> usize {
+        self.term
+            .get_below_height(memoization)
+            .max(self.point.get_height(memoization))
+    }
+
+    fn get_height(&self, memoization: &mut HashMap<Expression, (usize, usize)>) -> usize {
+        let above = self.term.get_height(memoization) - self.term.get_below_height(memoization);
+        self.get_below_height(memoization) + above.max(1)
+    }
+
+    fn get_length(&self, memoization: &mut HashMap<Expression, (usize, usize)>) -> usize {
+        let parenthesis = if self.needs_parenthesis() { 2 } else { 0 };
+        self.operator_length(memoization) + 1 + self.term.get_length(memoization) + parenthesis
+    }
+}
@@ -0,0 +1,473 @@
+use std::collections::HashMap;
+
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::{One, ToPrimitive};
+
+use crate::{explanation::FormattingObserver, utils};
+
+use super::{
+    Expression, SimplifyError,
+    assumptions::Assumptions,
+    constant::Constant,
+    function::{Function, FunctionType},
+    numeral::Numeral,
+};
+
+impl Function {
+    /// Exact values of the logarithms and the exponential, like
+    /// `log10(1000) => 3`, `ln(exp(a)) => a` or `ln(a^3) => 3ln(a)`.
+    pub(super) fn log_value(
+        expr: &Expression,
+        assumptions: &Assumptions,
+    ) -> Option<(&'static str, Expression)> {
+        let Expression::Function(function) = expr else {
+            return None;
+        };
+        if function.name == FunctionType::Exp {
+            return Self::exp_of_log(&Expression::Constant(Constant::E), &function.args[0]).map(
+                |after| {
+                    (
+                        "Exponential and logarithm are inverse\nexp(c*ln(a)) => a^c",
+                        after,
+                    )
+                },
+            );
+        }
+        let (base, arg) = log_parts(function)?;
+        // The logarithms of base 0, 1 or a negative number are undefined
+        if !valid_base(&base) {
+            return None;
+        }
+        match arg {
+            arg if arg.is_equal(&base) => Some(("Logarithm of its base\nlog_b(b) => 1", Expression::integer(1))),
+            // ln(exp(a)) => a
+            Expression::Function(inner)
+                if inner.name == FunctionType::Exp
+                    && matches!(base, Expression::Constant(Constant::E)) =>
+            {
+                Some((
+                    "Logarithm and exponential are inverse\nlog_b(b^a) => a",
+                    inner.args[0].clone(),
+                ))
+            }
+            Expression::Exponentiation(exp) if exp.base.is_equal(&base) => Some((
+                "Logarithm and exponential are inverse\nlog_b(b^a) => a",
+                exp.expo.clone(),
+            )),
+            // ln(a^n) => n*ln(a), an even power loses the sign of a
+            Expression::Exponentiation(exp) => {
+                let even = matches!(&exp.expo, Expression::Number(Numeral::Integer(n)) if n.is_even());
+                if even && !exp.base.is_nonnegative(assumptions) {
+                    let abs = Expression::function(FunctionType::Abs, vec![exp.base.clone()]);
+                    Some((
+                        "Logarithm of an even power\nlog(a^n) => n*log(abs(a))",
+                        Expression::multiplication(vec![
+                            exp.expo.clone(),
+                            with_argument(function, abs),
+                        ]),
+                    ))
+                } else {
+                    Some((
+                        "Logarithm of a power\nlog(a^n) => n*log(a)",
+                        Expression::multiplication(vec![
+                            exp.expo.clone(),
+                            with_argument(function, exp.base.clone()),
+                        ]),
+                    ))
+                }
+            }
+            Expression::Number(n) => {
+                let n = positive_rational(n)?;
+                if let Expression::Number(Numeral::Integer(b)) = &base
+                    && let Some(value) = exact_log(b, &n)
+                {
+                    return Some(("Exact value of the logarithm\nlog_b(b^k) => k", value));
+                }
+                // ln(8) => 3ln(2)
+                let Numeral::Integer(n) = n else {
+                    return None;
+                };
+                let (root, k) = perfect_power(&n)?;
+                Some((
+                    "Logarithm of a power\nlog(a^n) => n*log(a)",
+                    Expression::multiplication(vec![
+                        Expression::integer(k),
+                        with_argument(function, Expression::integer(root)),
+                    ]),
+                ))
+            }
+            _ => None,
+        }
+    }
+
+    /// `b^(c*log_b(a)) => a^c`, the exponential of `base` cancels the logarithm
+    /// of the same base in `expo`.
+    pub(super) fn exp_of_log(base: &Expression, expo: &Expression) -> Option<Expression> {
+        let log_of = |expr: &Expression| match expr {
+            Expression::Function(function) => {
+                log_parts(function).filter(|(b, _)| b.is_equal(base)).map(|(_, arg)| arg.clone())
+            }
+            _ => None,
+        };
+        if let Some(arg) = log_of(expo) {
+            return Some(arg);
+        }
+        let Expression::Multiplication(mul) = expo else {
+            return None;
+        };
+        let i = mul.terms.iter().position(|term| log_of(term).is_some())?;
+        let mut coefficient = mul.terms.clone();
+        let arg = log_of(&coefficient.remove(i))?;
+        let coefficient = match coefficient.len() {
+            1 => coefficient.remove(0),
+            _ => Expression::multiplication(coefficient),
+        };
+        Some(Expression::exponentiation(arg, coefficient))
+    }
+}
+
+/// The base and the argument of a logarithm.
+fn log_parts(function: &Function) -> Option<(Expression, &Expression)> {
+    match (&function.name, function.args.as_slice()) {
+        (FunctionType::Ln, [arg]) => Some((Expression::Constant(Constant::E), arg)),
+        (FunctionType::Log2, [arg]) => Some((Expression::integer(2), arg)),
+        (FunctionType::Log10, [arg]) => Some((Expression::integer(10), arg)),
+        (FunctionType::Log, [base, arg]) => Some((base.clone(), arg)),
+        _ => None,
+    }
+}
+
+/// Returns `false` for the numbers that are not a base, `0`, `1` and the
+/// negative numbers.
+fn valid_base(base: &Expression) -> bool {
+    match base {
+        Expression::Number(n) => !n.is_negative() && !n.is_zero() && !n.is_one(),
+        Expression::Negation(neg) => !matches!(neg.term, Expression::Number(_)),
+        _ => true,
+    }
+}
+
+/// The same logarithm of another argument.
+fn with_argument(function: &Function, arg: Expression) -> Expression {
+    let mut args = function.args.clone();
+    if let Some(last) = args.last_mut() {
+        *last = arg;
+    }
+    Expression::function(function.name.clone(), args)
+}
+
+fn positive_rational(n: &Numeral) -> Option<Numeral> {
+    match n {
+        Numeral::Float(_) => None,
+        n if n.is_negative() || n.is_zero() => None,
+        n => Some(n.clone()),
+    }
+}
+
+/// The prime factors of `n` with their exponents, a rational has negative
+/// exponents for its denominator.
+fn factors(n: &Numeral) -> Option<HashMap<u64, i64>> {
+    let factor = |n: &BigInt| -> Option<HashMap<u64, u64>> {
+        if n.is_one() {
+            Some(HashMap::new())
+        } else {
+            utils::prime_factors(n.to_u64()?)
+        }
+    };
+    let (num, den) = match n {
+        Numeral::Integer(n) => (factor(n)?, HashMap::new()),
+        Numeral::Rational(n, d) => (factor(n)?, factor(d)?),
+        Numeral::Float(_) => return None,
+    };
+    let mut factors: HashMap<u64, i64> = HashMap::new();
+    for (prime, count) in num {
+        *factors.entry(prime).or_default() += count as i64;
+    }
+    for (prime, count) in den {
+        *factors.entry(prime).or_default() -= count as i64;
+    }
+    factors.retain(|_, count| *count != 0);
+    Some(factors)
+}
+
+/// `log_b(n) = k` when `n = b^k` with `k` rational, like `log(4, 8) = 3/2`.
+fn exact_log(base: &BigInt, n: &Numeral) -> Option<Expression> {
+    if base <= &BigInt::one() {
+        return None;
+    }
+    let base = factors(&Numeral::Integer(base.clone()))?;
+    let n = factors(n)?;
+    if n.is_empty() {
+        return Some(Expression::integer(0));
+    }
+    let (prime, expo) = base.iter().next()?;
+    let count = n.get(prime).copied()?;
+    // n = b^(count/expo) when every prime has the same ratio
+    let same_ratio = base.len() == n.len()
+        && base
+            .iter()
+            .all(|(p, e)| n.get(p).is_some_and(|c| c * expo == count * e));
+    if !same_ratio {
+        return None;
+    }
+    let value = Numeral::Integer(count.into()).div(&Numeral::Integer((*expo).into()));
+    Some(value.into_expression())
+}
+
+/// `n = m^k` with the largest `k >= 2`.
+fn perfect_power(n: &BigInt) -> Option<(BigInt, u64)> {
+    let factors = utils::prime_factors(n.to_u64()?)?;
+    let k = factors.values().fold(0, |acc, e| utils::gcd(acc, *e));
+    if k < 2 {
+        return None;
+    }
+    let root = factors
+        .iter()
+        .map(|(prime, e)| BigInt::from(*prime).pow((e / k) as u32))
+        .product();
+    Some((root, k))
+}
+
+impl Expression {
+    /// Split the logarithms of products, quotients and powers into sums of
+    /// logarithms, then simplify.
+    ///
+    /// A variable without assumption is a generic positive value, so
+    /// `ln(x*y) => ln(x) + ln(y)` is applied.
+    ///
+    /// # Examples
+    /// ```
+    /// use sym_rustic::ast::{Expression, function::FunctionType};
+    ///
+    /// let x = Expression::variable("x");
+    /// let y = Expression::variable("y");
+    /// let expr = Expression::function(
+    ///     FunctionType::Ln,
+    ///     vec![Expression::division(x, y)],
+    /// );
+    /// assert_eq!(expr.expand_log(&mut None).unwrap().to_string(), "ln(x) + -ln(y)");
+    /// ```
+    pub fn expand_log(
+        &self,
+        explanation: &mut Option<Box<FormattingObserver>>,
+    ) -> Result<Expression, SimplifyError> {
+        let simplified = self.clone().simplify(explanation)?;
+        simplified.split_logs(explanation)?.simplify(explanation)
+    }
+
+    /// Combine the sums and the multiples of logarithms of the same base into
+    /// a single logarithm, the inverse of `expand_log`.
+    ///
+    /// The combined logarithm is not simplified again since the simplification
+    /// takes the powers out of the logarithms.
+    ///
+    /// # Examples
+    /// ```
+    /// use sym_rustic::ast::{Expression, function::FunctionType};
+    ///
+    /// let ln = |name: &str| Expression::function(FunctionType::Ln, vec![Expression::variable(name)]);
+    /// let expr = Expression::subtraction(ln("x"), ln("y"));
+    /// assert_eq!(expr.combine_log(&mut None).unwrap().to_string(), "ln(x/y)");
+    /// ```
+    pub fn combine_log(
+        &self,
+        explanation: &mut Option<Box<FormattingObserver>>,
+    ) -> Result<Expression, SimplifyError> {
+        let simplified = self.clone().simplify(explanation)?;
+        simplified.join_logs(explanation)
+    }
+
+    fn split_logs(
+        &self,
+        explanation: &mut Option<Box<FormattingObserver>>,
+    ) -> Result<Expression, SimplifyError> {
+        let expr = self.map_children(|child| child.split_logs(explanation))?;
+        let Expression::Function(function) = &expr else {
+            return Ok(expr);
+        };
+        let Some((base, arg)) = log_parts(function) else {
+            return Ok(expr);
+        };
+        if !valid_base(&base) {
+            return Ok(expr);
+        }
+        let (rule, after) = match arg {
+            Expression::Multiplication(mul) => (
+                "Logarithm of a product\nlog(ab) => log(a) + log(b)",
+                Expression::addition(
+                    mul.terms
+                        .iter()
+                        .map(|term| with_argument(function, term.clone()))
+                        .collect(),
+                ),
+            ),
+            Expression::Division(div) => (
+                "Logarithm of a quotient\nlog(a/b) => log(a) - log(b)",
+                Expression::subtraction(
+                    with_argument(function, div.num.clone()),
+                    with_argument(function, div.den.clone()),
+                ),
+            ),
+            _ => return Ok(expr),
+        };
+        if let Some(explanation) = explanation {
+            explanation.rule_applied(rule, &expr, &after);
+        }
+        // The factors can be products again
+        after.split_logs(explanation)
+    }
+
+    fn join_logs(
+        &self,
+        explanation: &mut Option<Box<FormattingObserver>>,
+    ) -> Result<Expression, SimplifyError> {
+        let expr = self.map_children(|child| child.join_logs(explanation))?;
+        let terms = match &expr {
+            Expression::Addition(add) => add.terms.clone(),
+            // 2ln(a) => ln(a^2)
+            Expression::Multiplication(_) => match log_term(&expr) {
+                Some((function, arg, coefficient)) if !coefficient.is_negative() => {
+                    let after = combine(&function, vec![(arg, coefficient)], explanation)?;
+                    if let Some(explanation) = explanation {
+                        explanation.rule_applied(
+                            "Multiple of a logarithm\nn*log(a) => log(a^n)",
+                            &expr,
+                            &after,
+                        );
+                    }
+                    return Ok(after);
+                }
+                _ => return Ok(expr),
+            },
+            _ => return Ok(expr),
+        };
+
+        // Group the logarithms with the same base
+        let mut groups: Vec<(Function, Vec<(Expression, Numeral)>)> = Vec::new();
+        let mut others: Vec<Expression> = Vec::new();
+        for term in terms {
+            match log_term(&term) {
+                Some((function, arg, coefficient)) => {
+                    match groups
+                        .iter_mut()
+                        .find(|(other, _)| same_logarithm(other, &function))
+                    {
+                        Some((_, group)) => group.push((arg, coefficient)),
+                        None => groups.push((function, vec![(arg, coefficient)])),
+                    }
+                }
+                None => others.push(term),
+            }
+        }
+        if groups.iter().all(|(_, group)| group.len() < 2) {
+            return Ok(expr);
+        }
+        for (function, group) in groups {
+            match group.as_slice() {
+                [(arg, coefficient)] => others.push(multiple(
+                    with_argument(&function, arg.clone()),
+                    coefficient.clone(),
+                )),
+                _ => others.push(combine(&function, group, explanation)?),
+            }
+        }
+        let after = match others.len() {
+            1 => others.remove(0),
+            _ => Expression::addition(others),
+        };
+        if let Some(explanation) = explanation {
+            explanation.rule_applied("Sum of logarithms\nlog(a) + log(b) => log(ab)", &expr, &after);
+        }
+        Ok(after)
+    }
+}
+
+/// `c*log_b(a)` as the logarithm, `a` and `c`.
+fn log_term(expr: &Expression) -> Option<(Function, Expression, Numeral)> {
+    match expr {
+        Expression::Function(function) => {
+            let (_, arg) = log_parts(function)?;
+            Some((function.clone(), arg.clone(), Numeral::Integer(BigInt::one())))
+        }
+        Expression::Negation(neg) => log_term(&neg.term)
+            .map(|(function, arg, coefficient)| (function, arg, coefficient.neg())),
+        Expression::Multiplication(mul) => {
+            let mut coefficient = Numeral::Integer(BigInt::one());
+            let mut log = None;
+            for term in &mul.terms {
+                match term {
+                    Expression::Number(n) if n.is_exact() => coefficient = coefficient.mul(n),
+                    Expression::Negation(neg)
+                        if let Expression::Number(n) = &neg.term
+                            && n.is_exact() =>
+                    {
+                        coefficient = coefficient.mul(&n.neg())
+                    }
+                    term if log.is_none() => log = Some(log_term(term)?),
+                    _ => return None,
+                }
+            }
+            let (function, arg, inner) = log?;
+            Some((function, arg, coefficient.mul(&inner)))
+        }
+        _ => None,
+    }
+}
+
+fn same_logarithm(lhs: &Function, rhs: &Function) -> bool {
+    lhs.name == rhs.name
+        && match (log_parts(lhs), log_parts(rhs)) {
+            (Some((lhs, _)), Some((rhs, _))) => lhs.is_equal(&rhs),
+            _ => false,
+        }
+}
+
+/// `c*log_b(a)` as an expression.
+fn multiple(log: Expression, coefficient: Numeral) -> Expression {
+    if coefficient.is_one() {
+        log
+    } else if coefficient.is_integer(-1) {
+        Expression::negation(log)
+    } else {
+        Expression::multiplication(vec![coefficient.into_expression(), log])
+    }
+}
+
+/// `c1*log_b(a1) + c2*log_b(a2) => log_b(a1^c1 * a2^c2)`, the negative
+/// multiples go to the denominator.
+fn combine(
+    function: &Function,
+    group: Vec<(Expression, Numeral)>,
+    explanation: &mut Option<Box<FormattingObserver>>,
+) -> Result<Expression, SimplifyError> {
+    let power = |arg: Expression, coefficient: Numeral| {
+        if coefficient.is_one() {
+            arg
+        } else {
+            Expression::exponentiation(arg, Expression::Number(coefficient))
+        }
+    };
+    let (mut num, mut den) = (Vec::new(), Vec::new());
+    for (arg, coefficient) in group {
+        if coefficient.is_zero() {
+            continue;
+        }
+        if coefficient.is_negative() {
+            den.push(power(arg, coefficient.abs()));
+        } else {
+            num.push(power(arg, coefficient));
+        }
+    }
+    let product = |mut terms: Vec<Expression>| match terms.len() {
+        0 => Expression::integer(1),
+        1 => terms.remove(0),
+        _ => Expression::multiplication(terms),
+    };
+    let mut arg = if den.is_empty() {
+        product(num)
+    } else {
+        Expression::division(product(num), product(den))
+    };
+    Ok(with_argument(function, arg.simplify(explanation)?))
+}
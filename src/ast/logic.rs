@@ -0,0 +1,834 @@
+use std::collections::HashMap;
+
+use crate::{explanation::FormattingObserver, prints::PrettyPrints};
+
+use super::{Expression, SimplifyError, constant::Constant};
+
+/// Maximum number of clauses built while converting to a normal form.
+const MAX_CLAUSES: usize = 256;
+/// Maximum number of atoms of a truth table, it has `2^n` rows.
+const MAX_TABLE_ATOMS: usize = 16;
+
+/// The two multinary connectives, `&&` and `||`, they are dual of each other.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub(super) enum Connective {
+    And,
+    Or,
+}
+
+impl Connective {
+    fn dual(self) -> Connective {
+        match self {
+            Connective::And => Connective::Or,
+            Connective::Or => Connective::And,
+        }
+    }
+
+    pub(super) fn symbol(self) -> &'static str {
+        match self {
+            Connective::And => "&&",
+            Connective::Or => "||",
+        }
+    }
+
+    /// The constant that leaves the result unchanged, `a && true = a` and `a || false = a`.
+    /// Its negation absorbs everything.
+    fn identity(self) -> bool {
+        self == Connective::And
+    }
+
+    fn build(self, terms: Vec<Expression>) -> Expression {
+        match self {
+            Connective::And => Expression::and(terms),
+            Connective::Or => Expression::or(terms),
+        }
+    }
+
+    fn terms(self, expr: &Expression) -> Option<&[Expression]> {
+        match (self, expr) {
+            (Connective::And, Expression::And(and)) => Some(&and.terms),
+            (Connective::Or, Expression::Or(or)) => Some(&or.terms),
+            _ => None,
+        }
+    }
+
+    /// Apply the boolean algebra rules on already simplified terms.
+    pub(super) fn simplify_terms(
+        self,
+        terms: Vec<Expression>,
+        explanation: &mut Option<Box<FormattingObserver>>,
+    ) -> Result<Expression, SimplifyError> {
+        let symbol = self.symbol();
+        let identity = Expression::boolean(self.identity());
+        let absorbing = Expression::boolean(!self.identity());
+
+        let before = self.build(terms.clone());
+        if let Some(explanation) = explanation {
+            explanation.step_started(&before);
+        }
+
+        // (a && b) && c => a && b && c
+        let mut result = Vec::new();
+        let mut flattened = false;
+        for term in terms {
+            match self.terms(&term) {
+                Some(inner) => {
+                    result.extend(inner.iter().cloned());
+                    flattened = true;
+                }
+                None => result.push(term),
+            }
+        }
+        if flattened && let Some(explanation) = explanation {
+            explanation.rule_applied(
+                &format!("Associativity\n(a {0} b) {0} c => a {0} b {0} c", symbol),
+                &before,
+                &self.build(result.clone()),
+            );
+        }
+
+        // a && false => false
+        if result.iter().any(|term| term.is_equal(&absorbing)) {
+            if let Some(explanation) = explanation {
+                explanation.rule_applied(
+                    &format!("Annihilation\na {} {} => {}", symbol, absorbing, absorbing),
+                    &self.build(result),
+                    &absorbing,
+                );
+                explanation.step_completed(&absorbing);
+            }
+            return Ok(absorbing);
+        }
+
+        // a && true => a
+        if result.iter().any(|term| term.is_equal(&identity)) {
+            let before = self.build(result.clone());
+            result.retain(|term| !term.is_equal(&identity));
+            if let Some(explanation) = explanation {
+                explanation.rule_applied(
+                    &format!("Identity\na {} {} => a", symbol, identity),
+                    &before,
+                    &self.build(result.clone()),
+                );
+            }
+        }
+
+        // a && a => a
+        let mut unique: Vec<Expression> = Vec::new();
+        for term in &result {
+            if !unique.iter().any(|other| other.is_equal(term)) {
+                unique.push(term.clone());
+            }
+        }
+        if unique.len() != result.len() {
+            if let Some(explanation) = explanation {
+                explanation.rule_applied(
+                    &format!("Idempotence\na {} a => a", symbol),
+                    &self.build(result),
+                    &self.build(unique.clone()),
+                );
+            }
+            result = unique;
+        }
+
+        // a && !a => false
+        if result.iter().any(|term| {
+            result
+                .iter()
+                .any(|other| matches!(other, Expression::Not(not) if not.term.is_equal(term)))
+        }) {
+            if let Some(explanation) = explanation {
+                explanation.rule_applied(
+                    &format!("Complement\na {} !a => {}", symbol, absorbing),
+                    &self.build(result),
+                    &absorbing,
+                );
+                explanation.step_completed(&absorbing);
+            }
+            return Ok(absorbing);
+        }
+
+        // a && (a || b) => a
+        let dual = self.dual();
+        let absorbed: Vec<bool> = result
+            .iter()
+            .enumerate()
+            .map(|(i, term)| {
+                dual.terms(term).is_some_and(|inner| {
+                    result.iter().enumerate().any(|(j, other)| {
+                        i != j && inner.iter().any(|inner| inner.is_equal(other))
+                    })
+                })
+            })
+            .collect();
+        if absorbed.contains(&true) {
+            let before = self.build(result.clone());
+            let mut absorbed = absorbed.into_iter();
+            result.retain(|_| !absorbed.next().unwrap());
+            if let Some(explanation) = explanation {
+                explanation.rule_applied(
+                    &format!("Absorption\na {} (a {} b) => a", symbol, dual.symbol()),
+                    &before,
+                    &self.build(result.clone()),
+                );
+            }
+        }
+
+        let result = match result.len() {
+            0 => identity,
+            1 => result.pop().unwrap(),
+            _ => self.build(result),
+        };
+        if let Some(explanation) = explanation {
+            explanation.step_completed(&result);
+        }
+        Ok(result)
+    }
+}
+
+/// Print terms separated by an infix operator, non single terms are put in parentheses.
+pub(super) struct Infix<'a> {
+    pub(super) terms: Vec<&'a Expression>,
+    pub(super) symbol: &'static str,
+}
+
+impl Infix<'_> {
+    pub(super) fn display(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let terms: Vec<String> = self
+            .terms
+            .iter()
+            .map(|term| {
+                if term.is_single() {
+                    term.to_string()
+                } else {
+                    format!("({})", term)
+                }
+            })
+            .collect();
+        write!(f, "{}", terms.join(&format!(" {} ", self.symbol)))
+    }
+
+    pub(super) fn calculate_tree(&self, name: &str, indent: usize) -> String {
+        let next_indent = indent + 2;
+        let next_indent_str = " ".repeat(next_indent);
+
+        let mut result = format!("{}:", name);
+        for term in &self.terms {
+            result.push_str(&format!(
+                "\n{}{} {}",
+                next_indent_str,
+                self.symbol,
+                term.calculate_tree(next_indent)
+            ));
+        }
+        result
+    }
+
+    pub(super) fn calculate_positions(
+        &self,
+        memoization: &mut HashMap<Expression, (usize, usize)>,
+        position: &mut Vec<(String, (usize, usize))>,
+        prev_pos: (usize, usize),
+    ) {
+        let below_height = self.get_below_height(memoization);
+        let baseline = prev_pos.0 + below_height;
+        let mut column = prev_pos.1;
+
+        for (i, term) in self.terms.iter().enumerate() {
+            if i > 0 {
+                for c in format!(" {} ", self.symbol).chars() {
+                    position.push((c.to_string(), (baseline, column)));
+                    column += 1;
+                }
+            }
+            let height = term.get_height(memoization);
+            let mut pos = (baseline - term.get_below_height(memoization), column);
+            let parenthesis = !term.is_single();
+            if parenthesis {
+                Expression::calculate_parenthesis(position, pos, true, height);
+                pos.1 += 1;
+            }
+            term.calculate_positions(memoization, position, pos);
+            pos.1 += term.get_length(memoization);
+            if parenthesis {
+                Expression::calculate_parenthesis(position, pos, false, height);
+                pos.1 += 1;
+            }
+            column = pos.1;
+        }
+    }
+
+    pub(super) fn get_below_height(
+        &self,
+        memoization: &mut HashMap<Expression, (usize, usize)>,
+    ) -> usize {
+        self.terms
+            .iter()
+            .map(|term| term.get_below_height(memoization))
+            .max()
+            .unwrap_or(0)
+    }
+
+    pub(super) fn get_height(&self, memoization: &mut HashMap<Expression, (usize, usize)>) -> usize {
+        let above = self
+            .terms
+            .iter()
+            .map(|term| term.get_height(memoization) - term.get_below_height(memoization))
+            .max()
+            .unwrap_or(1);
+        above + self.get_below_height(memoization)
+    }
+
+    pub(super) fn get_length(&self, memoization: &mut HashMap<Expression, (usize, usize)>) -> usize {
+        let terms = self
+            .terms
+            .iter()
+            .map(|term| term.get_length(memoization) + if term.is_single() { 0 } else { 2 })
+            .sum::<usize>();
+        terms + (self.terms.len().max(1) - 1) * (self.symbol.chars().count() + 2)
+    }
+}
+
+/// An atom or its negation, the building block of the normal forms.
+#[derive(Debug, PartialEq, Clone)]
+struct Literal {
+    atom: Expression,
+    positive: bool,
+}
+
+impl Literal {
+    fn from_expression(expr: &Expression) -> Literal {
+        match expr {
+            Expression::Not(not) => Literal {
+                atom: not.term.clone(),
+                positive: false,
+            },
+            _ => Literal {
+                atom: expr.clone(),
+                positive: true,
+            },
+        }
+    }
+
+    fn to_expression(&self) -> Expression {
+        if self.positive {
+            self.atom.clone()
+        } else {
+            Expression::logical_not(self.atom.clone())
+        }
+    }
+
+    fn is_equal(&self, other: &Literal) -> bool {
+        self.positive == other.positive && self.atom.is_equal(&other.atom)
+    }
+
+    fn is_complement(&self, other: &Literal) -> bool {
+        self.positive != other.positive && self.atom.is_equal(&other.atom)
+    }
+}
+
+/// The literals joined by the inner connective of a normal form,
+/// a disjunction for the CNF and a conjunction for the DNF.
+type Clause = Vec<Literal>;
+
+/// The truth table of a logic expression, every assignment of its atoms
+/// with the resulting value.
+#[derive(Debug, PartialEq, Clone)]
+pub struct TruthTable {
+    pub atoms: Vec<Expression>,
+    pub expression: Expression,
+    pub rows: Vec<(Vec<bool>, bool)>,
+}
+
+impl std::fmt::Display for TruthTable {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let mut headers: Vec<String> = self.atoms.iter().map(|atom| atom.to_string()).collect();
+        headers.push(self.expression.to_string());
+        write!(f, "{}", headers.join(" | "))?;
+
+        for (values, result) in &self.rows {
+            let cells: Vec<String> = values
+                .iter()
+                .chain(std::iter::once(result))
+                .zip(&headers)
+                .map(|(value, header)| {
+                    format!(
+                        "{:<width$}",
+                        if *value { "T" } else { "F" },
+                        width = header.chars().count()
+                    )
+                })
+                .collect();
+            write!(f, "\n{}", cells.join(" | ").trim_end())?;
+        }
+        Ok(())
+    }
+}
+
+// Normal forms, truth tables and satisfiability
+impl Expression {
+    /// Returns the value of `true` and `false`
+    pub fn as_boolean(&self) -> Option<bool> {
+        match self {
+            Expression::Constant(Constant::True) => Some(true),
+            Expression::Constant(Constant::False) => Some(false),
+            _ => None,
+        }
+    }
+
+    /// Rewrite the expression as a conjunction of disjunctions of atoms or negated atoms,
+    /// `(a || !b) && (b || c)`.
+    ///
+    /// Fails with `SimplifyError::Unsupported` if the result would have too many clauses.
+    pub fn to_cnf(
+        &self,
+        explanation: &mut Option<Box<FormattingObserver>>,
+    ) -> Result<Expression, SimplifyError> {
+        let clauses = self.normal_form(Connective::And, explanation)?;
+        Ok(clauses_to_expression(&clauses, Connective::And))
+    }
+
+    /// Rewrite the expression as a disjunction of conjunctions of atoms or negated atoms,
+    /// `(a && !b) || (b && c)`.
+    ///
+    /// Fails with `SimplifyError::Unsupported` if the result would have too many terms.
+    pub fn to_dnf(
+        &self,
+        explanation: &mut Option<Box<FormattingObserver>>,
+    ) -> Result<Expression, SimplifyError> {
+        let clauses = self.normal_form(Connective::Or, explanation)?;
+        Ok(clauses_to_expression(&clauses, Connective::Or))
+    }
+
+    /// Evaluate the expression for every assignment of its atoms, `false` first.
+    ///
+    /// Fails with `SimplifyError::Unsupported` above 16 atoms.
+    pub fn truth_table(&self) -> Result<TruthTable, SimplifyError> {
+        let atoms = self.atoms();
+        if atoms.len() > MAX_TABLE_ATOMS {
+            return Err(SimplifyError::Unsupported);
+        }
+
+        let rows = (0..1usize << atoms.len())
+            .map(|row| {
+                let values: Vec<bool> = (0..atoms.len())
+                    .map(|i| row >> (atoms.len() - 1 - i) & 1 == 1)
+                    .collect();
+                let assignment: Vec<(Expression, bool)> =
+                    atoms.iter().cloned().zip(values.iter().copied()).collect();
+                (values, self.truth_value(&assignment))
+            })
+            .collect();
+
+        Ok(TruthTable {
+            atoms,
+            expression: self.clone(),
+            rows,
+        })
+    }
+
+    /// Search an assignment of the atoms that makes the expression true with the DPLL
+    /// algorithm on its CNF, returns `None` if there is none.
+    pub fn satisfiable(
+        &self,
+        explanation: &mut Option<Box<FormattingObserver>>,
+    ) -> Result<Option<Vec<(Expression, bool)>>, SimplifyError> {
+        let clauses = self.normal_form(Connective::And, explanation)?;
+        let mut assignment = Vec::new();
+        if !dpll(clauses, &mut assignment, explanation) {
+            return Ok(None);
+        }
+
+        // The atoms removed while simplifying can take any value
+        let model = self
+            .atoms()
+            .into_iter()
+            .map(|atom| {
+                let value = assignment
+                    .iter()
+                    .find(|(assigned, _)| assigned.is_equal(&atom))
+                    .is_none_or(|(_, value)| *value);
+                (atom, value)
+            })
+            .collect();
+        Ok(Some(model))
+    }
+
+    /// The distinct operands of the connectives, in order of appearance.
+    fn atoms(&self) -> Vec<Expression> {
+        fn collect(expr: &Expression, atoms: &mut Vec<Expression>) {
+            match expr {
+                Expression::And(and) => and.terms.iter().for_each(|term| collect(term, atoms)),
+                Expression::Or(or) => or.terms.iter().for_each(|term| collect(term, atoms)),
+                Expression::Not(not) => collect(&not.term, atoms),
+                Expression::Xor(xor) => {
+                    collect(&xor.left, atoms);
+                    collect(&xor.right, atoms);
+                }
+                _ if expr.as_boolean().is_some() => {}
+                _ => {
+                    if !atoms.iter().any(|atom| atom.is_equal(expr)) {
+                        atoms.push(expr.clone())
+                    }
+                }
+            }
+        }
+
+        let mut atoms = Vec::new();
+        collect(self, &mut atoms);
+        atoms
+    }
+
+    fn truth_value(&self, assignment: &[(Expression, bool)]) -> bool {
+        match self {
+            Expression::And(and) => and.terms.iter().all(|term| term.truth_value(assignment)),
+            Expression::Or(or) => or.terms.iter().any(|term| term.truth_value(assignment)),
+            Expression::Not(not) => !not.term.truth_value(assignment),
+            Expression::Xor(xor) => {
+                xor.left.truth_value(assignment) != xor.right.truth_value(assignment)
+            }
+            _ => self.as_boolean().unwrap_or_else(|| {
+                assignment
+                    .iter()
+                    .find(|(atom, _)| atom.is_equal(self))
+                    .is_some_and(|(_, value)| *value)
+            }),
+        }
+    }
+
+    /// Simplify, remove the `xor`, push the negations on the atoms and distribute
+    /// the inner connective over `outer`.
+    fn normal_form(
+        &self,
+        outer: Connective,
+        explanation: &mut Option<Box<FormattingObserver>>,
+    ) -> Result<Vec<Clause>, SimplifyError> {
+        let simplified = self.clone().simplify(explanation)?;
+        let expr = simplified.eliminate_xor(explanation);
+        let expr = expr.negation_normal_form(explanation);
+        let clauses = expr.clauses(outer, explanation)?;
+
+        let reduced = reduce_clauses(clauses.clone());
+        if reduced != clauses
+            && let Some(explanation) = explanation
+        {
+            let rule = match outer {
+                Connective::And => {
+                    "Remove the clauses always true or containing another clause\n(a || !a) && b => b\na && (a || b) => a"
+                }
+                Connective::Or => {
+                    "Remove the terms always false or containing another term\n(a && !a) || b => b\na || (a && b) => a"
+                }
+            };
+            explanation.rule_applied(
+                rule,
+                &clauses_to_expression(&clauses, outer),
+                &clauses_to_expression(&reduced, outer),
+            );
+        }
+        Ok(reduced)
+    }
+
+    fn eliminate_xor(&self, explanation: &mut Option<Box<FormattingObserver>>) -> Expression {
+        match self {
+            Expression::And(and) => Expression::and(
+                and.terms
+                    .iter()
+                    .map(|term| term.eliminate_xor(explanation))
+                    .collect(),
+            ),
+            Expression::Or(or) => Expression::or(
+                or.terms
+                    .iter()
+                    .map(|term| term.eliminate_xor(explanation))
+                    .collect(),
+            ),
+            Expression::Not(not) => Expression::logical_not(not.term.eliminate_xor(explanation)),
+            Expression::Xor(xor) => {
+                let left = xor.left.eliminate_xor(explanation);
+                let right = xor.right.eliminate_xor(explanation);
+                let after = Expression::or(vec![
+                    Expression::and(vec![left.clone(), Expression::logical_not(right.clone())]),
+                    Expression::and(vec![Expression::logical_not(left), right]),
+                ]);
+                if let Some(explanation) = explanation {
+                    explanation.rule_applied(
+                        "Exclusive or\na xor b => (a && !b) || (!a && b)",
+                        self,
+                        &after,
+                    );
+                }
+                after
+            }
+            _ => self.clone(),
+        }
+    }
+
+    /// Push the negations down to the atoms with De Morgan's laws.
+    fn negation_normal_form(&self, explanation: &mut Option<Box<FormattingObserver>>) -> Expression {
+        match self {
+            Expression::And(and) => Expression::and(
+                and.terms
+                    .iter()
+                    .map(|term| term.negation_normal_form(explanation))
+                    .collect(),
+            ),
+            Expression::Or(or) => Expression::or(
+                or.terms
+                    .iter()
+                    .map(|term| term.negation_normal_form(explanation))
+                    .collect(),
+            ),
+            Expression::Not(not) => {
+                let (rule, after) = match &not.term {
+                    Expression::And(and) => (
+                        "De Morgan's law\n!(a && b) => !a || !b",
+                        Expression::or(and.terms.iter().cloned().map(Expression::logical_not).collect()),
+                    ),
+                    Expression::Or(or) => (
+                        "De Morgan's law\n!(a || b) => !a && !b",
+                        Expression::and(or.terms.iter().cloned().map(Expression::logical_not).collect()),
+                    ),
+                    Expression::Not(inner) => ("Double negation\n!!a => a", inner.term.clone()),
+                    term => match term.as_boolean() {
+                        Some(value) => (
+                            "Negation of a constant\n!true => false",
+                            Expression::boolean(!value),
+                        ),
+                        None => return self.clone(),
+                    },
+                };
+                if let Some(explanation) = explanation {
+                    explanation.rule_applied(rule, self, &after);
+                }
+                after.negation_normal_form(explanation)
+            }
+            _ => self.clone(),
+        }
+    }
+
+    /// The clauses of an expression in negation normal form, joined by `outer`.
+    fn clauses(
+        &self,
+        outer: Connective,
+        explanation: &mut Option<Box<FormattingObserver>>,
+    ) -> Result<Vec<Clause>, SimplifyError> {
+        if let Some(terms) = outer.terms(self) {
+            let mut clauses = Vec::new();
+            for term in terms {
+                clauses.extend(term.clauses(outer, explanation)?);
+            }
+            return Ok(clauses);
+        }
+
+        let Some(terms) = outer.dual().terms(self) else {
+            return Ok(match self.as_boolean() {
+                // An empty outer connective is its identity
+                Some(value) if value == outer.identity() => vec![],
+                // An empty clause is the identity of the inner connective
+                Some(_) => vec![vec![]],
+                None => vec![vec![Literal::from_expression(self)]],
+            });
+        };
+
+        // (a && b) || c => (a || c) && (b || c)
+        let children = terms
+            .iter()
+            .map(|term| term.clauses(outer, explanation))
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut clauses: Vec<Clause> = vec![vec![]];
+        for child in &children {
+            if clauses.len() * child.len() > MAX_CLAUSES {
+                return Err(SimplifyError::Unsupported);
+            }
+            clauses = clauses
+                .iter()
+                .flat_map(|clause| {
+                    child.iter().map(move |literals| {
+                        let mut clause = clause.clone();
+                        clause.extend(literals.iter().cloned());
+                        clause
+                    })
+                })
+                .collect();
+        }
+
+        if children.iter().any(|child| child.len() > 1)
+            && let Some(explanation) = explanation
+        {
+            let rule = match outer {
+                Connective::And => "Distributivity\na || (b && c) => (a || b) && (a || c)",
+                Connective::Or => "Distributivity\na && (b || c) => (a && b) || (a && c)",
+            };
+            explanation.rule_applied(rule, self, &clauses_to_expression(&clauses, outer));
+        }
+        Ok(clauses)
+    }
+}
+
+/// Remove the repeated literals, the clauses containing a literal and its complement
+/// and the clauses containing another clause.
+fn reduce_clauses(clauses: Vec<Clause>) -> Vec<Clause> {
+    let mut reduced: Vec<Clause> = Vec::new();
+    for clause in clauses {
+        let mut literals: Clause = Vec::new();
+        for literal in clause {
+            if !literals.iter().any(|other| other.is_equal(&literal)) {
+                literals.push(literal);
+            }
+        }
+        // a || !a is always true in a CNF, a && !a always false in a DNF
+        if literals
+            .iter()
+            .any(|literal| literals.iter().any(|other| literal.is_complement(other)))
+        {
+            continue;
+        }
+        reduced.push(literals);
+    }
+
+    let contains = |clause: &Clause, other: &Clause| {
+        other
+            .iter()
+            .all(|literal| clause.iter().any(|l| l.is_equal(literal)))
+    };
+    let mut result: Vec<Clause> = Vec::new();
+    for (i, clause) in reduced.iter().enumerate() {
+        // Keep the first of two equal clauses
+        let redundant = reduced.iter().enumerate().any(|(j, other)| {
+            i != j
+                && contains(clause, other)
+                && (other.len() < clause.len() || j < i)
+        });
+        if !redundant {
+            result.push(clause.clone());
+        }
+    }
+    result
+}
+
+fn clauses_to_expression(clauses: &[Clause], outer: Connective) -> Expression {
+    let inner = outer.dual();
+    let mut terms: Vec<Expression> = clauses
+        .iter()
+        .map(|clause| {
+            let mut literals: Vec<Expression> = clause.iter().map(Literal::to_expression).collect();
+            match literals.len() {
+                0 => Expression::boolean(inner.identity()),
+                1 => literals.pop().unwrap(),
+                _ => inner.build(literals),
+            }
+        })
+        .collect();
+    match terms.len() {
+        0 => Expression::boolean(outer.identity()),
+        1 => terms.pop().unwrap(),
+        _ => outer.build(terms),
+    }
+}
+
+/// Give `literal` the value true in the clauses of a CNF.
+fn assign(clauses: &[Clause], literal: &Literal) -> Vec<Clause> {
+    clauses
+        .iter()
+        .filter(|clause| !clause.iter().any(|l| l.is_equal(literal)))
+        .map(|clause| {
+            clause
+                .iter()
+                .filter(|l| !l.is_complement(literal))
+                .cloned()
+                .collect()
+        })
+        .collect()
+}
+
+/// Davis–Putnam–Logemann–Loveland search on the clauses of a CNF.
+fn dpll(
+    mut clauses: Vec<Clause>,
+    assignment: &mut Vec<(Expression, bool)>,
+    explanation: &mut Option<Box<FormattingObserver>>,
+) -> bool {
+    loop {
+        if clauses.is_empty() {
+            return true;
+        }
+        if clauses.iter().any(|clause| clause.is_empty()) {
+            if let Some(explanation) = explanation {
+                explanation.rule_applied(
+                    "Conflict\nan empty clause can't be satisfied",
+                    &clauses_to_expression(&clauses, Connective::And),
+                    &Expression::boolean(false),
+                );
+            }
+            return false;
+        }
+
+        // a && (!a || b) => b
+        let unit = clauses
+            .iter()
+            .find(|clause| clause.len() == 1)
+            .map(|clause| (clause[0].clone(), "Unit propagation"));
+        // a only appears positively so it can be true
+        let pure = || {
+            clauses.iter().flatten().find_map(|literal| {
+                (!clauses
+                    .iter()
+                    .flatten()
+                    .any(|other| other.is_complement(literal)))
+                .then(|| (literal.clone(), "Pure literal"))
+            })
+        };
+        if let Some((literal, rule)) = unit.or_else(pure) {
+            let after = assign(&clauses, &literal);
+            if let Some(explanation) = explanation {
+                explanation.rule_applied(
+                    &format!("{}\n{} = {}", rule, literal.atom, literal.positive),
+                    &clauses_to_expression(&clauses, Connective::And),
+                    &clauses_to_expression(&after, Connective::And),
+                );
+            }
+            assignment.push((literal.atom, literal.positive));
+            clauses = after;
+            continue;
+        }
+
+        // Try both values of an atom
+        let atom = clauses[0][0].atom.clone();
+        let before = clauses_to_expression(&clauses, Connective::And);
+        let mut trial = explanation.clone();
+        let mut trial_assignment = assignment.clone();
+        let literal = Literal {
+            atom: atom.clone(),
+            positive: true,
+        };
+        let after = assign(&clauses, &literal);
+        if let Some(trial) = &mut trial {
+            trial.rule_applied(
+                &format!("Split\ntry {} = true", atom),
+                &before,
+                &clauses_to_expression(&after, Connective::And),
+            );
+        }
+        trial_assignment.push((atom.clone(), true));
+        if dpll(after, &mut trial_assignment, &mut trial) {
+            *explanation = trial;
+            *assignment = trial_assignment;
+            return true;
+        }
+
+        let literal = Literal {
+            atom: atom.clone(),
+            positive: false,
+        };
+        let after = assign(&clauses, &literal);
+        if let Some(explanation) = explanation {
+            explanation.rule_applied(
+                &format!("Split\n{0} = true leads to a conflict, try {0} = false", atom),
+                &before,
+                &clauses_to_expression(&after, Connective::And),
+            );
+        }
+        assignment.push((atom, false));
+        clauses = after;
+    }
+}
@@ -0,0 +1,1212 @@
+use std::collections::HashMap;
+
+use num_complex::Complex64;
+
+use crate::{
+    ast::Expr,
+    explanation::FormattingObserver,
+    prints::PrettyPrints,
+    solver::{Solution, Solver, SolverError, polynomial_coefficients, polynomial_expression},
+};
+
+use super::{
+    Expression, SimplifyError, SimplifyOptions, evaluate::EvalError, exponentiation::Exponentiation,
+    limit::GENERIC_VALUE, summation::integer_value, vector::Vector,
+};
+
+/// A matrix of symbolic entries, stored row by row.
+#[derive(Debug, PartialEq, Clone, PartialOrd, Eq, Ord, Hash)]
+pub struct Matrix {
+    pub rows: Vec<Vec<Expression>>,
+    pub simplified: bool,
+}
+
+// Constructor
+impl Matrix {
+    pub fn new(rows: Vec<Vec<Expression>>, simplified: bool) -> Self {
+        Self { rows, simplified }
+    }
+
+    /// The `n`x`n` identity matrix.
+    pub fn identity(n: usize) -> Self {
+        let rows = (0..n)
+            .map(|i| {
+                (0..n)
+                    .map(|j| Expression::integer(if i == j { 1 } else { 0 }))
+                    .collect()
+            })
+            .collect();
+        Self::new(rows, true)
+    }
+}
+
+impl Expr for Matrix {
+    fn simplify(
+        &mut self,
+        options: &SimplifyOptions,
+        explanation: &mut Option<Box<FormattingObserver>>,
+    ) -> Result<Expression, SimplifyError> {
+        let rows = self
+            .rows
+            .iter_mut()
+            .map(|row| {
+                row.iter_mut()
+                    .map(|entry| entry.simplify_with(options, explanation))
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Expression::Matrix(Box::new(Matrix::new(rows, true))))
+    }
+
+    fn is_equal(&self, other: &Matrix) -> bool {
+        self.dimensions() == other.dimensions()
+            && self
+                .rows
+                .iter()
+                .flatten()
+                .zip(other.rows.iter().flatten())
+                .all(|(lhs, rhs)| lhs.is_equal(rhs))
+    }
+
+    fn contains_var(&self, variable: &str) -> bool {
+        self.rows
+            .iter()
+            .flatten()
+            .any(|entry| entry.contains_var(variable))
+    }
+
+    fn is_single(&self) -> bool {
+        true
+    }
+}
+
+impl std::fmt::Display for Matrix {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let rows: Vec<String> = self
+            .rows
+            .iter()
+            .map(|row| {
+                let entries: Vec<String> = row.iter().map(|entry| entry.to_string()).collect();
+                format!("[{}]", entries.join(", "))
+            })
+            .collect();
+        write!(f, "[{}]", rows.join(", "))
+    }
+}
+
+impl Matrix {
+    /// Number of rows and columns.
+    pub fn dimensions(&self) -> (usize, usize) {
+        (self.rows.len(), self.rows.first().map_or(0, |row| row.len()))
+    }
+
+    pub fn is_square(&self) -> bool {
+        let (rows, columns) = self.dimensions();
+        rows == columns
+    }
+
+    pub fn transpose(&self) -> Matrix {
+        let (rows, columns) = self.dimensions();
+        Matrix::new(
+            (0..columns)
+                .map(|j| (0..rows).map(|i| self.rows[i][j].clone()).collect())
+                .collect(),
+            self.simplified,
+        )
+    }
+
+    /// Matrix product `self * other`, the entries are simplified.
+    ///
+    /// Returns `SimplifyError::DimensionMismatch` if the number of columns of `self`
+    /// is not the number of rows of `other`.
+    pub fn multiply(&self, other: &Matrix) -> Result<Matrix, SimplifyError> {
+        self.multiply_with(other, &SimplifyOptions::default())
+    }
+
+    /// Matrix product like `Matrix::multiply`, the entries are simplified with `options`.
+    pub fn multiply_with(
+        &self,
+        other: &Matrix,
+        options: &SimplifyOptions,
+    ) -> Result<Matrix, SimplifyError> {
+        let (rows, inner) = self.dimensions();
+        let (other_rows, columns) = other.dimensions();
+        if inner != other_rows {
+            return Err(SimplifyError::DimensionMismatch);
+        }
+        let rows = (0..rows)
+            .map(|i| {
+                (0..columns)
+                    .map(|j| {
+                        Expression::addition(
+                            (0..inner)
+                                .map(|k| {
+                                    Expression::multiplication(vec![
+                                        self.rows[i][k].clone(),
+                                        other.rows[k][j].clone(),
+                                    ])
+                                })
+                                .collect(),
+                        )
+                        .simplify_with(options, &mut None)
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Matrix::new(rows, true))
+    }
+
+    /// Every entry multiplied by `scalar`, the entries are simplified.
+    pub fn scale(&self, scalar: &Expression) -> Result<Matrix, SimplifyError> {
+        self.scale_with(scalar, &SimplifyOptions::default())
+    }
+
+    /// Every entry multiplied by `scalar`, the entries are simplified with `options`.
+    pub fn scale_with(
+        &self,
+        scalar: &Expression,
+        options: &SimplifyOptions,
+    ) -> Result<Matrix, SimplifyError> {
+        self.map(
+            |entry| Expression::multiplication(vec![scalar.clone(), entry.clone()]),
+            options,
+        )
+    }
+
+    /// Sum of the diagonal entries, simplified.
+    ///
+    /// Returns `SimplifyError::DimensionMismatch` if the matrix is not square.
+    pub fn trace(&self) -> Result<Expression, SimplifyError> {
+        if !self.is_square() {
+            return Err(SimplifyError::DimensionMismatch);
+        }
+        Expression::addition(
+            self.rows
+                .iter()
+                .enumerate()
+                .map(|(i, row)| row[i].clone())
+                .collect(),
+        )
+        .simplify(&mut None)
+    }
+
+    /// Determinant by cofactor expansion, simplified.
+    ///
+    /// Returns `SimplifyError::DimensionMismatch` if the matrix is not square.
+    pub fn determinant(&self) -> Result<Expression, SimplifyError> {
+        self.determinant_with(&SimplifyOptions::default())
+    }
+
+    /// Determinant like `Matrix::determinant`, simplified with `options`.
+    pub fn determinant_with(&self, options: &SimplifyOptions) -> Result<Expression, SimplifyError> {
+        if !self.is_square() || self.rows.is_empty() {
+            return Err(SimplifyError::DimensionMismatch);
+        }
+        cofactor_expansion(&self.rows).simplify_with(options, &mut None)
+    }
+
+    /// Inverse of the matrix, the adjugate divided by the determinant.
+    ///
+    /// Returns `SimplifyError::DimensionMismatch` if the matrix is not square and
+    /// `SimplifyError::SingularMatrix` if its determinant is zero.
+    pub fn inverse(&self) -> Result<Matrix, SimplifyError> {
+        self.inverse_with(&SimplifyOptions::default())
+    }
+
+    /// Inverse like `Matrix::inverse`, the entries are simplified with `options`.
+    pub fn inverse_with(&self, options: &SimplifyOptions) -> Result<Matrix, SimplifyError> {
+        let determinant = self.determinant_with(options)?;
+        if is_zero(&determinant) {
+            return Err(SimplifyError::SingularMatrix);
+        }
+        let n = self.rows.len();
+        if n == 1 {
+            return self.map(
+                |_| Expression::division(Expression::integer(1), determinant.clone()),
+                options,
+            );
+        }
+        let rows = (0..n)
+            .map(|i| {
+                (0..n)
+                    .map(|j| {
+                        // The adjugate is the transpose of the cofactor matrix
+                        let cofactor = cofactor_expansion(&minor(&self.rows, j, i));
+                        let cofactor = if (i + j) % 2 == 0 {
+                            cofactor
+                        } else {
+                            Expression::negation(cofactor)
+                        };
+                        Expression::division(cofactor, determinant.clone())
+                            .simplify_with(options, &mut None)
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Matrix::new(rows, true))
+    }
+
+    /// Number of linearly independent rows, found by fraction free Gaussian elimination.
+    ///
+    /// Symbolic entries are checked numerically at generic values, so the rank is the
+    /// one of the matrix for almost every value of its variables.
+    pub fn rank(&self) -> Result<usize, SimplifyError> {
+        let (_, columns) = self.dimensions();
+        let mut rows = self.map(|entry| entry.clone(), &SimplifyOptions::default())?.rows;
+        let mut rank = 0;
+        for column in 0..columns {
+            let Some(pivot) = (rank..rows.len()).find(|&i| !is_zero(&rows[i][column])) else {
+                continue;
+            };
+            rows.swap(rank, pivot);
+            for i in rank + 1..rows.len() {
+                if is_zero(&rows[i][column]) {
+                    continue;
+                }
+                // row_i => pivot * row_i - row_i[column] * row_rank
+                let factor = rows[i][column].clone();
+                let pivot = rows[rank][column].clone();
+                rows[i] = (0..columns)
+                    .map(|j| {
+                        Expression::subtraction(
+                            Expression::multiplication(vec![pivot.clone(), rows[i][j].clone()]),
+                            Expression::multiplication(vec![factor.clone(), rows[rank][j].clone()]),
+                        )
+                        .simplify(&mut None)
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+            }
+            rank += 1;
+        }
+        Ok(rank)
+    }
+
+    /// Characteristic polynomial `det(x*I - A)` in the given variable, written with
+    /// decreasing powers.
+    ///
+    /// Returns `SimplifyError::DimensionMismatch` if the matrix is not square and
+    /// `SimplifyError::Unsupported` if an entry contains `variable`.
+    pub fn characteristic_polynomial(&self, variable: &str) -> Result<Expression, SimplifyError> {
+        let coefficients = self.characteristic_coefficients(variable)?;
+        Ok(polynomial_expression(
+            &coefficients,
+            &Expression::variable(variable),
+        ))
+    }
+
+    /// Distinct eigenvalues, the exact roots of the characteristic polynomial.
+    ///
+    /// The diagonal entries that are roots are factored out first, so the eigenvalues
+    /// of a triangular matrix are its diagonal entries. Complex eigenvalues are given
+    /// as `Expression::Complex`.
+    ///
+    /// Returns `SimplifyError::Unsupported` if the roots can't be found exactly, like
+    /// for a polynomial of degree 5 without rational roots.
+    pub fn eigenvalues(
+        &self,
+        explanation: &mut Option<Box<FormattingObserver>>,
+    ) -> Result<Vec<Expression>, SimplifyError> {
+        let polynomial = self.characteristic_polynomial(EIGENVALUE_VARIABLE)?;
+        if let Some(explanation) = explanation {
+            explanation.rule_applied(
+                "The eigenvalues are the roots of the characteristic polynomial\np(λ) => det(λI - A)",
+                &Expression::Matrix(Box::new(self.clone())),
+                &polynomial,
+            );
+        }
+
+        let var = Expression::variable(EIGENVALUE_VARIABLE);
+        let mut factor = self.characteristic_coefficients(EIGENVALUE_VARIABLE)?;
+        let mut values: Vec<Expression> = Vec::new();
+        for (i, row) in self.rows.iter().enumerate() {
+            let value = row[i].clone().simplify(&mut None)?;
+            if values.iter().any(|other| other.is_equal(&value)) {
+                continue;
+            }
+            let before = polynomial_expression(&factor, &var);
+            let mut found = false;
+            while let Some(quotient) = deflate(&factor, &value)? {
+                factor = quotient;
+                found = true;
+            }
+            if !found {
+                continue;
+            }
+            if let Some(explanation) = explanation {
+                let root = Expression::subtraction(var.clone(), value.clone());
+                explanation.rule_applied(
+                    &format!("λ = {} is a root, factor out {}", value, root),
+                    &before,
+                    &Expression::multiplication(vec![root, polynomial_expression(&factor, &var)]),
+                );
+            }
+            values.push(value);
+        }
+        if factor.len() < 2 {
+            return Ok(values);
+        }
+
+        let roots = match Solver::solve_polynomial(
+            polynomial_expression(&factor, &var),
+            Expression::integer(0),
+            EIGENVALUE_VARIABLE,
+            explanation,
+        )
+        .map_err(from_solver)?
+        {
+            Solution::Single(value) => vec![value],
+            Solution::Multiple(values) => values,
+            Solution::NoSolution => vec![],
+            Solution::Infinite => return Err(SimplifyError::Unsupported),
+        };
+        for root in roots {
+            if !values.iter().any(|other| other.is_equal(&root)) {
+                values.push(root);
+            }
+        }
+        Ok(values)
+    }
+
+    /// Eigenvalues with a basis of their eigenvectors.
+    ///
+    /// The eigenvectors are the null space of `A - λI`, found by row reduction. When
+    /// the entries are numbers and the eigenvalues are all simple, the eigenvector of
+    /// an irrational or complex eigenvalue is instead a column of the adjugate of
+    /// `λI - A`, so that no division by `λ` is needed.
+    ///
+    /// Returns `SimplifyError::Unsupported` if the eigenvalues can't be found exactly.
+    pub fn eigenvectors(
+        &self,
+        explanation: &mut Option<Box<FormattingObserver>>,
+    ) -> Result<Vec<(Expression, Vec<Expression>)>, SimplifyError> {
+        let values = self.eigenvalues(explanation)?;
+
+        // The factor of the characteristic polynomial without the rational roots,
+        // every entry of the adjugate is reduced by it
+        let factor = if values.len() == self.rows.len() && self.rows.iter().flatten().all(is_numeric)
+        {
+            let mut factor = self.characteristic_coefficients(EIGENVALUE_VARIABLE)?;
+            for value in values.iter().filter(|value| is_rational(value)) {
+                while let Some(quotient) = deflate(&factor, value)? {
+                    factor = quotient;
+                }
+            }
+            Some(factor)
+        } else {
+            None
+        };
+
+        values
+            .into_iter()
+            .map(|value| {
+                let vector = match &factor {
+                    Some(factor) if !is_rational(&value) => {
+                        self.adjugate_eigenvector(&value, factor)?
+                    }
+                    _ => None,
+                };
+                let basis = match vector {
+                    Some(vector) => vec![vector],
+                    None => self.shifted(&value).nullspace()?,
+                };
+                // The eigenvalue was not recognized as a root when reducing A - λI
+                if basis.is_empty() {
+                    return Err(SimplifyError::Unsupported);
+                }
+                if let Some(explanation) = explanation {
+                    explanation.rule_applied(
+                        &format!(
+                            "The eigenvectors of λ = {} are the solutions of\n(A - λI)v = 0",
+                            value
+                        ),
+                        &Expression::Matrix(Box::new(self.shifted(&value))),
+                        &Expression::Matrix(Box::new(columns(&basis))),
+                    );
+                }
+                Ok((value, basis))
+            })
+            .collect()
+    }
+
+    /// Basis of the vectors `v` such that `Av = 0`, read from the reduced row echelon form.
+    ///
+    /// Symbolic entries are checked numerically at generic values, like for `Matrix::rank`.
+    pub fn nullspace(&self) -> Result<Vec<Expression>, SimplifyError> {
+        let (_, columns) = self.dimensions();
+        let mut rows = self.map(|entry| entry.clone(), &SimplifyOptions::default())?.rows;
+        let zero_if_zero = |entry: Expression| {
+            if is_zero(&entry) {
+                Expression::integer(0)
+            } else {
+                entry
+            }
+        };
+
+        let mut pivots = Vec::new();
+        for column in 0..columns {
+            let rank = pivots.len();
+            let Some(pivot) = (rank..rows.len()).find(|&i| !is_zero(&rows[i][column])) else {
+                continue;
+            };
+            rows.swap(rank, pivot);
+            let pivot = rows[rank][column].clone();
+            rows[rank] = rows[rank]
+                .iter()
+                .map(|entry| {
+                    Expression::division(entry.clone(), pivot.clone())
+                        .simplify(&mut None)
+                        .map(zero_if_zero)
+                })
+                .collect::<Result<_, _>>()?;
+            rows[rank][column] = Expression::integer(1);
+            for i in (0..rows.len()).filter(|&i| i != rank) {
+                let factor = rows[i][column].clone();
+                if is_zero(&factor) {
+                    continue;
+                }
+                rows[i] = (0..columns)
+                    .map(|j| {
+                        Expression::subtraction(
+                            rows[i][j].clone(),
+                            Expression::multiplication(vec![factor.clone(), rows[rank][j].clone()]),
+                        )
+                        .simplify(&mut None)
+                        .map(zero_if_zero)
+                    })
+                    .collect::<Result<_, _>>()?;
+                rows[i][column] = Expression::integer(0);
+            }
+            pivots.push(column);
+        }
+
+        // One vector for each free column, the pivot unknowns are solved for
+        (0..columns)
+            .filter(|column| !pivots.contains(column))
+            .map(|free| {
+                let mut entries = vec![Expression::integer(0); columns];
+                entries[free] = Expression::integer(1);
+                for (row, &column) in pivots.iter().enumerate() {
+                    entries[column] = Expression::negation(rows[row][free].clone())
+                        .simplify(&mut None)
+                        .map(zero_if_zero)?;
+                }
+                Ok(Expression::Vector(Box::new(Vector::new(entries, true))))
+            })
+            .collect()
+    }
+
+    /// Coefficients `[c0, c1, ..., cn]` of `det(x*I - A)`.
+    fn characteristic_coefficients(&self, variable: &str) -> Result<Vec<Expression>, SimplifyError> {
+        if !self.is_square() || self.rows.is_empty() {
+            return Err(SimplifyError::DimensionMismatch);
+        }
+        if self.contains_var(variable) {
+            return Err(SimplifyError::Unsupported);
+        }
+        let rows = self.shifted(&Expression::variable(variable)).rows;
+        // det(xI - A) = (-1)^n det(A - xI)
+        let determinant = if rows.len().is_multiple_of(2) {
+            cofactor_expansion(&rows)
+        } else {
+            Expression::negation(cofactor_expansion(&rows))
+        };
+        polynomial_coefficients(&determinant, variable).map_err(from_solver)
+    }
+
+    /// `A - value*I`
+    fn shifted(&self, value: &Expression) -> Matrix {
+        Matrix::new(
+            self.rows
+                .iter()
+                .enumerate()
+                .map(|(i, row)| {
+                    let mut row = row.clone();
+                    row[i] = Expression::subtraction(row[i].clone(), value.clone());
+                    row
+                })
+                .collect(),
+            false,
+        )
+    }
+
+    /// First non zero column of `adj(λI - A)` at the eigenvalue `value`.
+    ///
+    /// The entries are polynomials in `λ`, they are reduced by `factor`, a monic factor of
+    /// the characteristic polynomial that `value` is a root of. Returns `None` if every
+    /// column is zero, when the eigenvalue is not simple.
+    fn adjugate_eigenvector(
+        &self,
+        value: &Expression,
+        factor: &[Expression],
+    ) -> Result<Option<Expression>, SimplifyError> {
+        // λI - A = -(A - λI)
+        let rows: Vec<Vec<Expression>> = self
+            .shifted(&Expression::variable(EIGENVALUE_VARIABLE))
+            .rows
+            .into_iter()
+            .map(|row| row.into_iter().map(Expression::negation).collect())
+            .collect();
+        let n = rows.len();
+        for column in 0..n {
+            // adj(M)_ij = (-1)^(i+j) det(M_ji)
+            let entries = (0..n)
+                .map(|i| {
+                    let cofactor = cofactor_expansion(&minor(&rows, column, i));
+                    let cofactor = if (i + column) % 2 == 0 {
+                        cofactor
+                    } else {
+                        Expression::negation(cofactor)
+                    };
+                    let coefficients = remainder(
+                        polynomial_coefficients(&cofactor, EIGENVALUE_VARIABLE)
+                            .map_err(from_solver)?,
+                        factor,
+                    )?;
+                    polynomial_expression(&coefficients, value).simplify(&mut None)
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            if !entries.iter().all(is_zero) {
+                return Ok(Some(Expression::Vector(Box::new(Vector::new(entries, true)))));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Apply `f` to every entry and simplify the result with `options`.
+    fn map(
+        &self,
+        f: impl Fn(&Expression) -> Expression,
+        options: &SimplifyOptions,
+    ) -> Result<Matrix, SimplifyError> {
+        let rows = self
+            .rows
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|entry| f(entry).simplify_with(options, &mut None))
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Matrix::new(rows, true))
+    }
+
+    /// Back to an `Expression`, a single column is a `Vector` if `vector` is `true`.
+    fn into_expression(self, vector: bool) -> Expression {
+        if vector && self.dimensions().1 == 1 {
+            Expression::Vector(Box::new(Vector::new(
+                self.rows.into_iter().flatten().collect(),
+                self.simplified,
+            )))
+        } else {
+            Expression::Matrix(Box::new(self))
+        }
+    }
+}
+
+/// Variable of the characteristic polynomial when looking for eigenvalues
+const EIGENVALUE_VARIABLE: &str = "λ";
+
+fn from_solver(error: SolverError) -> SimplifyError {
+    match error {
+        SolverError::Simplification(error) => error,
+        _ => SimplifyError::Unsupported,
+    }
+}
+
+/// Returns `true` if `expr` has no variables.
+fn is_numeric(expr: &Expression) -> bool {
+    expr.evaluate_complex(&HashMap::new()).is_ok()
+}
+
+fn is_rational(expr: &Expression) -> bool {
+    match expr {
+        Expression::Number(_) => true,
+        Expression::Negation(neg) => is_rational(&neg.term),
+        _ => false,
+    }
+}
+
+/// Matrix whose columns are the vectors
+fn columns(vectors: &[Expression]) -> Matrix {
+    let vectors: Vec<Vec<Expression>> = vectors
+        .iter()
+        .filter_map(|vector| vector.to_matrix())
+        .map(|matrix| matrix.rows.into_iter().flatten().collect())
+        .collect();
+    Matrix::new(vectors, true).transpose()
+}
+
+/// Quotient of the polynomial by `x - root` if `root` is a root of it.
+fn deflate(
+    coefficients: &[Expression],
+    root: &Expression,
+) -> Result<Option<Vec<Expression>>, SimplifyError> {
+    if coefficients.len() < 2 {
+        return Ok(None);
+    }
+    // Synthetic division, from the leading coefficient down
+    let mut quotient = vec![Expression::integer(0); coefficients.len() - 1];
+    let mut carry = coefficients[coefficients.len() - 1].clone();
+    for k in (0..quotient.len()).rev() {
+        quotient[k] = carry.clone();
+        carry = Expression::addition(vec![
+            coefficients[k].clone(),
+            Expression::multiplication(vec![root.clone(), carry]),
+        ])
+        .simplify(&mut None)?;
+    }
+    Ok(is_zero(&carry).then_some(quotient))
+}
+
+/// Remainder of the polynomial divided by the monic polynomial `modulus`.
+fn remainder(
+    mut coefficients: Vec<Expression>,
+    modulus: &[Expression],
+) -> Result<Vec<Expression>, SimplifyError> {
+    let degree = modulus.len() - 1;
+    while coefficients.len() > degree {
+        let leading = coefficients.pop().unwrap();
+        let shift = coefficients.len() - degree;
+        for (k, coeff) in modulus[..degree].iter().enumerate() {
+            coefficients[shift + k] = Expression::subtraction(
+                coefficients[shift + k].clone(),
+                Expression::multiplication(vec![leading.clone(), coeff.clone()]),
+            )
+            .simplify(&mut None)?;
+        }
+    }
+    Ok(coefficients)
+}
+
+/// `rows` without the row `i` and the column `j`.
+fn minor(rows: &[Vec<Expression>], i: usize, j: usize) -> Vec<Vec<Expression>> {
+    rows.iter()
+        .enumerate()
+        .filter(|(k, _)| *k != i)
+        .map(|(_, row)| {
+            row.iter()
+                .enumerate()
+                .filter(|(k, _)| *k != j)
+                .map(|(_, entry)| entry.clone())
+                .collect()
+        })
+        .collect()
+}
+
+/// Determinant of a square matrix, expanded along the row with the most zeros.
+fn cofactor_expansion(rows: &[Vec<Expression>]) -> Expression {
+    match rows.len() {
+        0 => Expression::integer(1),
+        1 => rows[0][0].clone(),
+        2 => Expression::subtraction(
+            Expression::multiplication(vec![rows[0][0].clone(), rows[1][1].clone()]),
+            Expression::multiplication(vec![rows[0][1].clone(), rows[1][0].clone()]),
+        ),
+        _ => {
+            let zeros = |row: &Vec<Expression>| row.iter().filter(|entry| is_zero(entry)).count();
+            let (i, row) = rows
+                .iter()
+                .enumerate()
+                .max_by_key(|(i, row)| (zeros(row), std::cmp::Reverse(*i)))
+                .unwrap();
+            let terms: Vec<Expression> = row
+                .iter()
+                .enumerate()
+                .filter(|(_, entry)| !is_zero(entry))
+                .map(|(j, entry)| {
+                    let term = Expression::multiplication(vec![
+                        entry.clone(),
+                        cofactor_expansion(&minor(rows, i, j)),
+                    ]);
+                    if (i + j) % 2 == 0 {
+                        term
+                    } else {
+                        Expression::negation(term)
+                    }
+                })
+                .collect();
+            if terms.is_empty() {
+                Expression::integer(0)
+            } else {
+                Expression::addition(terms)
+            }
+        }
+    }
+}
+
+/// Returns `true` if `expr` is zero, the variables are given distinct generic values.
+///
+/// The value of a variable only depends on its name, so that all the entries of
+/// a matrix are checked at the same point. The tolerance is relative to the size
+/// of the terms of a sum.
+fn is_zero(expr: &Expression) -> bool {
+    match expr {
+        Expression::Number(n) => n.is_zero(),
+        Expression::Negation(neg) => is_zero(&neg.term),
+        _ => {
+            // Complex values so that eigenvalues with a square root of a negative number can be checked
+            let mut env = HashMap::new();
+            let mut evaluate = |expr: &Expression| loop {
+                match expr.evaluate_complex(&env) {
+                    Ok(value) => return Some(value.norm()),
+                    Err(EvalError::UnboundVariable(name)) => {
+                        let value = generic_value(&name);
+                        env.insert(name, Complex64::from(value));
+                    }
+                    Err(_) => return None,
+                }
+            };
+            let Some(value) = evaluate(expr) else {
+                return false;
+            };
+            let terms = match expr {
+                Expression::Addition(add) => add.terms.clone(),
+                Expression::Subtraction(sub) => vec![sub.left.clone(), sub.right.clone()],
+                _ => vec![],
+            };
+            let scale = terms.iter().filter_map(&mut evaluate).fold(1.0, f64::max);
+            value < 1e-9 * scale
+        }
+    }
+}
+
+/// Generic value of the variable `name`, different variables get different values.
+fn generic_value(name: &str) -> f64 {
+    let hash = name
+        .bytes()
+        .fold(0_u64, |hash, byte| (hash * 31 + byte as u64) % 997);
+    GENERIC_VALUE + (hash + 1) as f64 * std::f64::consts::SQRT_2 / 997.0
+}
+
+impl Expression {
+    /// Returns `true` if the expression is a `Matrix` or a `Vector`.
+    pub fn is_matrix(&self) -> bool {
+        matches!(self, Expression::Matrix(_) | Expression::Vector(_))
+    }
+
+    /// The expression as a matrix, a `Vector` is a single column.
+    pub fn to_matrix(&self) -> Option<Matrix> {
+        match self {
+            Expression::Matrix(matrix) => Some(matrix.as_ref().clone()),
+            Expression::Vector(vector) => Some(Matrix::new(
+                vector.entries.iter().map(|entry| vec![entry.clone()]).collect(),
+                vector.simplified,
+            )),
+            _ => None,
+        }
+    }
+
+    /// The simplified expression as a matrix.
+    fn simplified_matrix(&self) -> Result<Matrix, SimplifyError> {
+        self.clone()
+            .simplify(&mut None)?
+            .to_matrix()
+            .ok_or(SimplifyError::DimensionMismatch)
+    }
+
+    /// Transpose of a matrix, a `Vector` becomes a single row.
+    ///
+    /// Returns `SimplifyError::DimensionMismatch` if the expression is not a matrix.
+    pub fn transpose(&self) -> Result<Expression, SimplifyError> {
+        Ok(Expression::Matrix(Box::new(
+            self.simplified_matrix()?.transpose(),
+        )))
+    }
+
+    /// Trace of a square matrix.
+    ///
+    /// Returns `SimplifyError::DimensionMismatch` if the expression is not a square matrix.
+    pub fn trace(
+        &self,
+        explanation: &mut Option<Box<FormattingObserver>>,
+    ) -> Result<Expression, SimplifyError> {
+        let matrix = self.simplified_matrix()?;
+        let after = matrix.trace()?;
+        if let Some(explanation) = explanation {
+            explanation.rule_applied(
+                "The trace is the sum of the diagonal\ntr(A) => Σ A_ii",
+                &Expression::Matrix(Box::new(matrix)),
+                &after,
+            );
+        }
+        Ok(after)
+    }
+
+    /// Determinant of a square matrix, by cofactor expansion.
+    ///
+    /// Returns `SimplifyError::DimensionMismatch` if the expression is not a square matrix.
+    pub fn determinant(
+        &self,
+        explanation: &mut Option<Box<FormattingObserver>>,
+    ) -> Result<Expression, SimplifyError> {
+        let matrix = self.simplified_matrix()?;
+        let after = matrix.determinant()?;
+        if let Some(explanation) = explanation {
+            explanation.rule_applied(
+                "Cofactor expansion of the determinant\ndet(A) => Σ_j (-1)^(i+j) A_ij det(M_ij)",
+                &Expression::Matrix(Box::new(matrix)),
+                &after,
+            );
+        }
+        Ok(after)
+    }
+
+    /// Inverse of a square matrix, as its adjugate divided by its determinant.
+    ///
+    /// Returns `SimplifyError::DimensionMismatch` if the expression is not a square matrix
+    /// and `SimplifyError::SingularMatrix` if its determinant is zero.
+    pub fn inverse(
+        &self,
+        explanation: &mut Option<Box<FormattingObserver>>,
+    ) -> Result<Expression, SimplifyError> {
+        let matrix = self.simplified_matrix()?;
+        let after = Expression::Matrix(Box::new(matrix.inverse()?));
+        if let Some(explanation) = explanation {
+            explanation.rule_applied(
+                "The inverse is the adjugate over the determinant\nA^-1 => adj(A)/det(A)",
+                &Expression::Matrix(Box::new(matrix)),
+                &after,
+            );
+        }
+        Ok(after)
+    }
+
+    /// Rank of a matrix, see `Matrix::rank`.
+    ///
+    /// Returns `SimplifyError::DimensionMismatch` if the expression is not a matrix.
+    pub fn rank(&self) -> Result<usize, SimplifyError> {
+        self.simplified_matrix()?.rank()
+    }
+
+    /// Characteristic polynomial of a square matrix, see `Matrix::characteristic_polynomial`.
+    pub fn characteristic_polynomial(&self, variable: &str) -> Result<Expression, SimplifyError> {
+        self.simplified_matrix()?.characteristic_polynomial(variable)
+    }
+
+    /// Distinct eigenvalues of a square matrix, see `Matrix::eigenvalues`.
+    pub fn eigenvalues(
+        &self,
+        explanation: &mut Option<Box<FormattingObserver>>,
+    ) -> Result<Vec<Expression>, SimplifyError> {
+        self.simplified_matrix()?.eigenvalues(explanation)
+    }
+
+    /// Eigenvalues of a square matrix with a basis of their eigenvectors, see `Matrix::eigenvectors`.
+    pub fn eigenvectors(
+        &self,
+        explanation: &mut Option<Box<FormattingObserver>>,
+    ) -> Result<Vec<(Expression, Vec<Expression>)>, SimplifyError> {
+        self.simplified_matrix()?.eigenvectors(explanation)
+    }
+
+    /// Basis of the null space of a matrix, see `Matrix::nullspace`.
+    pub fn nullspace(&self) -> Result<Vec<Expression>, SimplifyError> {
+        self.simplified_matrix()?.nullspace()
+    }
+
+    /// Entry-wise sum of simplified matrices.
+    pub(super) fn add_matrices(
+        terms: Vec<Expression>,
+        options: &SimplifyOptions,
+        explanation: &mut Option<Box<FormattingObserver>>,
+    ) -> Result<Expression, SimplifyError> {
+        let before = Expression::addition(terms.clone());
+        let vector = terms.iter().any(|term| matches!(term, Expression::Vector(_)));
+        let matrices = terms
+            .iter()
+            .map(|term| term.to_matrix().ok_or(SimplifyError::DimensionMismatch))
+            .collect::<Result<Vec<_>, _>>()?;
+        let (rows, columns) = matrices[0].dimensions();
+        if matrices.iter().any(|matrix| matrix.dimensions() != (rows, columns)) {
+            return Err(SimplifyError::DimensionMismatch);
+        }
+        let sum = Matrix::new(
+            (0..rows)
+                .map(|i| {
+                    (0..columns)
+                        .map(|j| {
+                            Expression::addition(
+                                matrices.iter().map(|matrix| matrix.rows[i][j].clone()).collect(),
+                            )
+                            .simplify_with(options, &mut None)
+                        })
+                        .collect::<Result<Vec<_>, _>>()
+                })
+                .collect::<Result<Vec<_>, _>>()?,
+            true,
+        );
+        let after = sum.into_expression(vector);
+        if let Some(explanation) = explanation {
+            explanation.rule_applied(
+                "Matrices are added entry by entry\n(A + B)_ij => A_ij + B_ij",
+                &before,
+                &after,
+            );
+        }
+        Ok(after)
+    }
+
+    /// Product of simplified terms where at least one is a matrix.
+    ///
+    /// The matrices are multiplied in order and the scalars multiply every entry.
+    pub(super) fn multiply_matrices(
+        terms: Vec<Expression>,
+        options: &SimplifyOptions,
+        explanation: &mut Option<Box<FormattingObserver>>,
+    ) -> Result<Expression, SimplifyError> {
+        let before = Expression::multiplication(terms.clone());
+        let (matrices, scalars): (Vec<Expression>, Vec<Expression>) =
+            terms.into_iter().partition(Expression::is_matrix);
+        let vector = matches!(matrices.last(), Some(Expression::Vector(_)));
+
+        let mut product = matrices[0]
+            .to_matrix()
+            .ok_or(SimplifyError::DimensionMismatch)?;
+        for matrix in &matrices[1..] {
+            product = product.multiply_with(
+                &matrix.to_matrix().ok_or(SimplifyError::DimensionMismatch)?,
+                options,
+            )?;
+        }
+        let rule = if scalars.is_empty() {
+            "Matrix product\n(AB)_ij => Σ_k A_ik B_kj"
+        } else {
+            product = product.scale_with(&Expression::multiplication(scalars), options)?;
+            if matrices.len() == 1 {
+                "A scalar multiplies every entry\n(cA)_ij => c A_ij"
+            } else {
+                "Matrix product, the scalars multiply every entry\n(cAB)_ij => c Σ_k A_ik B_kj"
+            }
+        };
+
+        let after = product.into_expression(vector);
+        if let Some(explanation) = explanation {
+            explanation.rule_applied(rule, &before, &after);
+        }
+        Ok(after)
+    }
+
+    /// Integer power of a simplified square matrix, a negative power is a power of the inverse.
+    ///
+    /// Other exponents are kept.
+    pub(super) fn power_matrix(
+        base: Expression,
+        expo: Expression,
+        options: &SimplifyOptions,
+        explanation: &mut Option<Box<FormattingObserver>>,
+    ) -> Result<Expression, SimplifyError> {
+        let before = Expression::exponentiation(base.clone(), expo.clone());
+        let (Some(mut factor), Some(n)) = (base.to_matrix(), integer_value(&expo)) else {
+            return Ok(Expression::Exponentiation(Box::new(Exponentiation::new(
+                base, expo, true,
+            ))));
+        };
+        if !factor.is_square() {
+            return Err(SimplifyError::DimensionMismatch);
+        }
+
+        let rule = if n < 0 {
+            factor = factor.inverse_with(options)?;
+            "Negative power of a matrix\nA^-n => (A^-1)^n"
+        } else if n == 0 {
+            "A matrix to the power zero is the identity\nA^0 => I"
+        } else {
+            "Power of a matrix\nA^n => A*A*...*A"
+        };
+
+        // Exponentiation by squaring
+        let mut power = Matrix::identity(factor.rows.len());
+        let mut n = n.unsigned_abs();
+        while n > 0 {
+            if n % 2 == 1 {
+                power = power.multiply_with(&factor, options)?;
+            }
+            n /= 2;
+            if n > 0 {
+                factor = factor.multiply_with(&factor, options)?;
+            }
+        }
+
+        let after = Expression::Matrix(Box::new(power));
+        if let Some(explanation) = explanation {
+            explanation.rule_applied(rule, &before, &after);
+        }
+        Ok(after)
+    }
+}
+
+/// Opening and closing bracket of a `Grid` as (top, middle, bottom)
+const LEFT_BRACKET: (&str, &str, &str) = ("┌", "│", "└");
+const RIGHT_BRACKET: (&str, &str, &str) = ("┐", "│", "┘");
+
+/// Entries printed in aligned columns between brackets, shared by `Matrix` and `Vector`.
+pub(super) struct Grid<'a> {
+    pub rows: Vec<Vec<&'a Expression>>,
+}
+
+impl Grid<'_> {
+    /// Width of each column
+    fn column_lengths(&self, memoization: &mut HashMap<Expression, (usize, usize)>) -> Vec<usize> {
+        let columns = self.rows.first().map_or(0, |row| row.len());
+        (0..columns)
+            .map(|j| {
+                self.rows
+                    .iter()
+                    .map(|row| row[j].get_length(memoization))
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect()
+    }
+
+    /// Rows below and above the baseline of each row, the baseline included above
+    fn row_heights(
+        &self,
+        memoization: &mut HashMap<Expression, (usize, usize)>,
+    ) -> Vec<(usize, usize)> {
+        self.rows
+            .iter()
+            .map(|row| {
+                row.iter().fold((0, 1), |(below, above), entry| {
+                    let entry_below = entry.get_below_height(memoization);
+                    let entry_above = entry.get_height(memoization) - entry_below;
+                    (below.max(entry_below), above.max(entry_above))
+                })
+            })
+            .collect()
+    }
+
+    fn bracket(
+        position: &mut Vec<(String, (usize, usize))>,
+        prev_pos: (usize, usize),
+        (top, middle, bottom): (&str, &str, &str),
+        square: &str,
+        height: usize,
+    ) {
+        if height == 1 {
+            position.push((square.to_string(), prev_pos));
+            return;
+        }
+        position.push((bottom.to_string(), prev_pos));
+        for row in 1..height - 1 {
+            position.push((middle.to_string(), (prev_pos.0 + row, prev_pos.1)));
+        }
+        position.push((top.to_string(), (prev_pos.0 + height - 1, prev_pos.1)));
+    }
+
+    pub(super) fn calculate_tree(&self, name: &str, indent: usize) -> String {
+        let next_indent = indent + 2;
+        let next_indent_str = " ".repeat(next_indent);
+        let mut result = format!("{}:", name);
+        for row in &self.rows {
+            if row.len() == 1 {
+                result.push_str(&format!(
+                    "\n{}{}",
+                    next_indent_str,
+                    row[0].calculate_tree(next_indent)
+                ));
+            } else {
+                let entry_indent = next_indent + 2;
+                result.push_str(&format!("\n{}Row:", next_indent_str));
+                for entry in row {
+                    result.push_str(&format!(
+                        "\n{}{}",
+                        " ".repeat(entry_indent),
+                        entry.calculate_tree(entry_indent)
+                    ));
+                }
+            }
+        }
+        result
+    }
+
+    pub(super) fn calculate_positions(
+        &self,
+        memoization: &mut HashMap<Expression, (usize, usize)>,
+        position: &mut Vec<(String, (usize, usize))>,
+        prev_pos: (usize, usize),
+    ) {
+        let height = self.get_height(memoization);
+        let length = self.get_length(memoization);
+        Self::bracket(position, prev_pos, LEFT_BRACKET, "[", height);
+        Self::bracket(
+            position,
+            (prev_pos.0, prev_pos.1 + length - 1),
+            RIGHT_BRACKET,
+            "]",
+            height,
+        );
+
+        let column_lengths = self.column_lengths(memoization);
+        let row_heights = self.row_heights(memoization);
+        // The last row is at the bottom
+        let mut bottom = prev_pos.0;
+        for (row, (below, above)) in self.rows.iter().zip(row_heights).rev() {
+            let baseline = bottom + below;
+            let mut column = prev_pos.1 + 1;
+            for (entry, width) in row.iter().zip(&column_lengths) {
+                let offset = (width - entry.get_length(memoization)) / 2;
+                let entry_bottom = baseline - entry.get_below_height(memoization);
+                entry.calculate_positions(memoization, position, (entry_bottom, column + offset));
+                column += width + 2;
+            }
+            bottom += below + above;
+        }
+    }
+
+    pub(super) fn get_below_height(
+        &self,
+        memoization: &mut HashMap<Expression, (usize, usize)>,
+    ) -> usize {
+        self.get_height(memoization) / 2
+    }
+
+    pub(super) fn get_height(&self, memoization: &mut HashMap<Expression, (usize, usize)>) -> usize {
+        self.row_heights(memoization)
+            .iter()
+            .map(|(below, above)| below + above)
+            .sum::<usize>()
+            .max(1)
+    }
+
+    pub(super) fn get_length(&self, memoization: &mut HashMap<Expression, (usize, usize)>) -> usize {
+        let column_lengths = self.column_lengths(memoization);
+        column_lengths.iter().sum::<usize>() + 2 * column_lengths.len().saturating_sub(1) + 2
+    }
+}
+
+impl Matrix {
+    fn grid(&self) -> Grid<'_> {
+        Grid {
+            rows: self.rows.iter().map(|row| row.iter().collect()).collect(),
+        }
+    }
+}
+
+impl PrettyPrints for Matrix {
+    fn calculate_tree(&self, indent: usize) -> String {
+        self.grid().calculate_tree("Matrix", indent)
+    }
+
+    fn calculate_positions(
+        &self,
+        memoization: &mut HashMap<Expression, (usize, usize)>,
+        position: &mut Vec<(String, (usize, usize))>,
+        prev_pos: (usize, usize),
+    ) {
+        self.grid()
+            .calculate_positions(memoization, position, prev_pos)
+    }
+
+    fn get_below_height(&self, memoization: &mut HashMap<Expression, (usize, usize)>) -> usize {
+        self.grid().get_below_height(memoization)
+    }
+
+    fn get_height(&self, memoization: &mut HashMap<Expression, (usize, usize)>) -> usize {
+        self.grid().get_height(memoization)
+    }
+
+    fn get_length(&self, memoization: &mut HashMap<Expression, (usize, usize)>) -> usize {
+        self.grid().get_length(memoization)
+    }
+}
@@ -1,42 +1,93 @@
-use std::collections::HashMap;
-
 use function::Function;
+use num_bigint::BigInt;
 
 use crate::{
     ast::{
-        addition::Addition, complex::Complex, constant::Constant, derivative::Derivative,
+        addition::Addition, and::And, assumptions::Assumptions, complex::Complex, constant::Constant, derivative::Derivative,
         division::Division, equality::Equality, exponentiation::Exponentiation,
-        function::FunctionType, multiplication::Multiplication, negation::Negation,
-        numeral::Numeral, subtraction::Subtraction, variable::Variable,
+        function::FunctionType, inequality::{Inequality, Relation}, integral::Integral, limit::{Direction, Limit}, matrix::Matrix, multiplication::Multiplication, negation::Negation,
+        not::Not, numeral::Numeral, or::Or, product::Product, series::Series, subtraction::Subtraction,
+        summation::Summation, variable::Variable, vector::Vector, xor::Xor,
     },
     explanation::FormattingObserver,
 };
 
 mod addition;
+mod and;
+pub mod assumptions;
 mod complex;
 pub mod constant;
 mod derivative;
 mod division;
 mod equality;
+mod expand;
+pub mod evaluate;
 mod exponentiation;
 pub mod function;
+pub mod inequality;
+pub mod integral;
+pub mod limit;
+mod logarithm;
+pub mod logic;
+pub mod matrix;
 mod multiplication;
 mod negation;
+mod not;
 pub mod numeral;
+mod or;
+mod radical;
+pub mod product;
+pub mod series;
 mod subtraction;
+pub mod summation;
+mod trigonometry;
 mod variable;
+pub mod vector;
+mod xor;
+
+pub(crate) use exponentiation::MAX_NUMERIC_EXPONENT;
 
 #[derive(Debug, PartialEq, Clone)]
 pub enum SimplifyError {
     DivisionByZero,
     ZeroExponentiationZero,
     InvalidDerivative,
+    /// The limit does not exist, like `lim(1/x, x, 0)` or `lim(sin(x), x, inf)`
+    UndefinedLimit,
+    /// The dimensions don't fit the operation, like adding a 2x2 and a 3x3 matrix
+    /// or taking the determinant of a matrix that is not square
+    DimensionMismatch,
+    /// The matrix has no inverse, its determinant is zero
+    SingularMatrix,
+    /// The rewritings are nested deeper than `SimplifyOptions::max_depth`
+    DepthExceeded,
+    /// The improper integral diverges, like `int(1/x, x, 0, 1)`
+    DivergentIntegral,
     Unsupported,
 }
 
+/// Choose the rewritings done by `Expression::simplify_with`, the default
+/// options are the ones used by `Expression::simplify`.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct SimplifyOptions {
+    /// Expand the natural powers of sums, `(a + b)^2` becomes `a^2 + 2ab + b^2`
+    pub expand: bool,
+    /// Write the rationals as decimals, `1/4` becomes `0.25`
+    pub decimals: bool,
+    /// Round the decimals to this number of significant digits, `2/3` becomes
+    /// `0.6667` with `decimals` and 4 digits. `None` keeps every digit
+    pub precision: Option<usize>,
+    /// Maximum nesting of the rewritings, going deeper fails with
+    /// `SimplifyError::DepthExceeded`. `None` has no limit
+    pub max_depth: Option<usize>,
+    /// What is known about the variables, like `x > 0`
+    pub assumptions: Assumptions,
+}
+
 pub trait Expr: std::fmt::Display {
     fn simplify(
         &mut self,
+        options: &SimplifyOptions,
         explanation: &mut Option<Box<FormattingObserver>>,
     ) -> Result<Expression, SimplifyError>;
 
@@ -60,37 +111,42 @@ pub enum Expression {
     Division(Box<Division>),
     Exponentiation(Box<Exponentiation>),
     Equality(Box<Equality>),
+    Inequality(Box<Inequality>),
     Complex(Box<Complex>),
     // Function
     Function(Function),
     // Calculus
     Derivative(Box<Derivative>),
-    // Integral(Box<Integral>),
-    // Limit
+    Integral(Box<Integral>),
+    Limit(Box<Limit>),
+    Series(Box<Series>),
+    Summation(Box<Summation>),
+    Product(Box<Product>),
 
-    // Series
-    // Summation
-    // Product
-    // Matrix
-    // Vector
+    Matrix(Box<Matrix>),
+    Vector(Box<Vector>),
 
     // Logic
-    // And
-    // Or
-    // Not
-    // Xor
+    And(And),
+    Or(Or),
+    Not(Box<Not>),
+    Xor(Box<Xor>),
     // Binary
     // Hexadecimal
 }
 
 // Helper constructor for some expression.
 impl Expression {
-    pub fn integer(n: u64) -> Expression {
-        Expression::Number(numeral::Numeral::Integer(n))
+    pub fn integer(n: impl Into<BigInt>) -> Expression {
+        Expression::Number(numeral::Numeral::Integer(n.into()))
+    }
+
+    pub fn rational(n: impl Into<BigInt>, d: impl Into<BigInt>) -> Expression {
+        Expression::Number(numeral::Numeral::Rational(n.into(), d.into()))
     }
 
-    pub fn rational(n: u64, d: u64) -> Expression {
-        Expression::Number(numeral::Numeral::Rational(n, d))
+    pub fn float(x: f64) -> Expression {
+        Expression::Number(numeral::Numeral::Float(numeral::Float(x)))
     }
 
     pub fn subtraction(lhs: Expression, rhs: Expression) -> Expression {
@@ -101,6 +157,10 @@ impl Expression {
         Expression::Equality(Box::new(Equality::new(left, right, false)))
     }
 
+    pub fn inequality(left: Expression, relation: Relation, right: Expression) -> Expression {
+        Expression::Inequality(Box::new(Inequality::new(left, relation, right, false)))
+    }
+
     pub fn negation(arg: Expression) -> Expression {
         Expression::Negation(Box::new(Negation::new(arg, false)))
     }
@@ -138,6 +198,93 @@ impl Expression {
         )))
     }
 
+    pub fn integral(term: Expression, variable: &str) -> Expression {
+        Expression::Integral(Box::new(Integral::new(term, variable.to_owned(), None, false)))
+    }
+
+    pub fn definite_integral(
+        term: Expression,
+        variable: &str,
+        lower: Expression,
+        upper: Expression,
+    ) -> Expression {
+        Expression::Integral(Box::new(Integral::new(
+            term,
+            variable.to_owned(),
+            Some((lower, upper)),
+            false,
+        )))
+    }
+
+    pub fn summation(
+        term: Expression,
+        variable: &str,
+        lower: Expression,
+        upper: Expression,
+    ) -> Expression {
+        Expression::Summation(Box::new(Summation::new(
+            term,
+            variable.to_owned(),
+            lower,
+            upper,
+            false,
+        )))
+    }
+
+    pub fn product(
+        term: Expression,
+        variable: &str,
+        lower: Expression,
+        upper: Expression,
+    ) -> Expression {
+        Expression::Product(Box::new(Product::new(
+            term,
+            variable.to_owned(),
+            lower,
+            upper,
+            false,
+        )))
+    }
+
+    pub fn matrix(rows: Vec<Vec<Expression>>) -> Expression {
+        Expression::Matrix(Box::new(Matrix::new(rows, false)))
+    }
+
+    pub fn vector(entries: Vec<Expression>) -> Expression {
+        Expression::Vector(Box::new(Vector::new(entries, false)))
+    }
+
+    pub fn and(terms: Vec<Expression>) -> Expression {
+        Expression::And(And::new(terms, false))
+    }
+
+    pub fn or(terms: Vec<Expression>) -> Expression {
+        Expression::Or(Or::new(terms, false))
+    }
+
+    pub fn logical_not(term: Expression) -> Expression {
+        Expression::Not(Box::new(Not::new(term, false)))
+    }
+
+    pub fn xor(left: Expression, right: Expression) -> Expression {
+        Expression::Xor(Box::new(Xor::new(left, right, false)))
+    }
+
+    pub fn limit(
+        term: Expression,
+        variable: &str,
+        point: Expression,
+        direction: Direction,
+    ) -> Expression {
+        Expression::Limit(Box::new(Limit::new(
+            term,
+            variable.to_owned(),
+            point,
+            direction,
+            false,
+        )))
+    }
+
     pub fn function(name: FunctionType, args: Vec<Expression>) -> Expression {
         Expression::Function(Function::new(name, args, false))
     }
@@ -243,6 +390,12 @@ impl Expression {
     pub fn tau() -> Expression {
         Expression::Constant(Constant::Tau)
     }
+    pub fn infinity() -> Expression {
+        Expression::Constant(Constant::Infinity)
+    }
+    pub fn boolean(value: bool) -> Expression {
+        Expression::Constant(if value { Constant::True } else { Constant::False })
+    }
 }
 
 impl std::fmt::Display for Expression {
@@ -258,32 +411,140 @@ impl std::fmt::Display for Expression {
             Expression::Division(division) => write!(f, "{}", division),
             Expression::Exponentiation(exponentiation) => write!(f, "{}", exponentiation),
             Expression::Equality(equality) => write!(f, "{}", equality),
+            Expression::Inequality(inequality) => write!(f, "{}", inequality),
             Expression::Complex(complex) => write!(f, "{}", complex),
             Expression::Function(function) => write!(f, "{}", function),
             Expression::Derivative(derivative) => write!(f, "{}", derivative),
+            Expression::Integral(integral) => write!(f, "{}", integral),
+            Expression::Limit(limit) => write!(f, "{}", limit),
+            Expression::Series(series) => write!(f, "{}", series),
+            Expression::Summation(sum) => write!(f, "{}", sum),
+            Expression::Product(prod) => write!(f, "{}", prod),
+            Expression::Matrix(matrix) => write!(f, "{}", matrix),
+            Expression::Vector(vector) => write!(f, "{}", vector),
+            Expression::And(and) => write!(f, "{}", and),
+            Expression::Or(or) => write!(f, "{}", or),
+            Expression::Not(not) => write!(f, "{}", not),
+            Expression::Xor(xor) => write!(f, "{}", xor),
         }
     }
 }
 
 impl Expression {
+    /// Simplify the expression with the default `SimplifyOptions`.
     pub fn simplify(
         &mut self,
         explanation: &mut Option<Box<FormattingObserver>>,
     ) -> Result<Expression, SimplifyError> {
-        match self {
-            Expression::Addition(add) => add.simplify(explanation),
-            Expression::Subtraction(sub) => sub.simplify(explanation),
-            Expression::Multiplication(mul) => mul.simplify(explanation),
-            Expression::Division(div) => div.simplify(explanation),
-            Expression::Exponentiation(exp) => exp.simplify(explanation),
-            Expression::Negation(neg) => neg.simplify(explanation),
-            Expression::Complex(com) => com.simplify(explanation),
-            Expression::Equality(equ) => equ.simplify(explanation),
-            Expression::Function(fun) => fun.simplify(explanation),
-            Expression::Number(num) => num.simplify(explanation),
+        self.simplify_with(&SimplifyOptions::default(), explanation)
+    }
+
+    /// Simplify the expression with the rewritings chosen in `options`.
+    pub fn simplify_with(
+        &mut self,
+        options: &SimplifyOptions,
+        explanation: &mut Option<Box<FormattingObserver>>,
+    ) -> Result<Expression, SimplifyError> {
+        if options.max_depth == Some(0) {
+            return Err(SimplifyError::DepthExceeded);
+        }
+        // Each nested rewriting goes one level deeper, the floats are only rounded
+        // once the whole expression is simplified so the errors don't add up
+        let precision = options.precision;
+        let nested;
+        let options = if options.max_depth.is_some() || precision.is_some() {
+            nested = SimplifyOptions {
+                max_depth: options.max_depth.map(|depth| depth - 1),
+                precision: None,
+                ..options.clone()
+            };
+            &nested
+        } else {
+            options
+        };
+        let result = match self {
+            Expression::Addition(add) => add.simplify(options, explanation),
+            Expression::Subtraction(sub) => sub.simplify(options, explanation),
+            Expression::Multiplication(mul) => mul.simplify(options, explanation),
+            Expression::Division(div) => div.simplify(options, explanation),
+            Expression::Exponentiation(exp) => exp.simplify(options, explanation),
+            Expression::Negation(neg) => neg.simplify(options, explanation),
+            Expression::Complex(com) => com.simplify(options, explanation),
+            Expression::Equality(equ) => equ.simplify(options, explanation),
+            Expression::Inequality(ineq) => ineq.simplify(options, explanation),
+            Expression::Function(fun) => fun.simplify(options, explanation),
+            Expression::Number(num) => num.simplify(options, explanation),
             Expression::Variable(_) => Ok(self.clone()),
-            Expression::Constant(con) => con.simplify(explanation),
-            Expression::Derivative(der) => der.simplify(explanation),
+            Expression::Constant(con) => con.simplify(options, explanation),
+            Expression::Derivative(der) => der.simplify(options, explanation),
+            Expression::Integral(int) => int.simplify(options, explanation),
+            Expression::Limit(limit) => limit.simplify(options, explanation),
+            Expression::Series(series) => series.simplify(options, explanation),
+            Expression::Summation(sum) => sum.simplify(options, explanation),
+            Expression::Product(prod) => prod.simplify(options, explanation),
+            Expression::Matrix(matrix) => matrix.simplify(options, explanation),
+            Expression::Vector(vector) => vector.simplify(options, explanation),
+            Expression::And(and) => and.simplify(options, explanation),
+            Expression::Or(or) => or.simplify(options, explanation),
+            Expression::Not(not) => not.simplify(options, explanation),
+            Expression::Xor(xor) => xor.simplify(options, explanation),
+        };
+        match precision {
+            Some(digits) => result.map(|expr| expr.round_floats(digits)),
+            None => result,
+        }
+    }
+
+    /// Round the `Float` numbers to `digits` significant digits.
+    fn round_floats(&self, digits: usize) -> Expression {
+        let round = |expr: &Expression| expr.round_floats(digits);
+        match self {
+            Expression::Number(num) => Expression::Number(num.round(digits)),
+            Expression::Matrix(matrix) => Expression::Matrix(Box::new(Matrix::new(
+                matrix.rows.iter().map(|row| row.iter().map(round).collect()).collect(),
+                matrix.simplified,
+            ))),
+            Expression::Vector(vector) => Expression::Vector(Box::new(Vector::new(
+                vector.entries.iter().map(round).collect(),
+                vector.simplified,
+            ))),
+            Expression::Integral(int) => Expression::Integral(Box::new(Integral::new(
+                round(&int.term),
+                int.variable.clone(),
+                int.bounds.as_ref().map(|(lower, upper)| (round(lower), round(upper))),
+                int.simplified,
+            ))),
+            Expression::Summation(sum) => Expression::Summation(Box::new(Summation::new(
+                round(&sum.term),
+                sum.variable.clone(),
+                round(&sum.lower),
+                round(&sum.upper),
+                sum.simplified,
+            ))),
+            Expression::Product(prod) => Expression::Product(Box::new(Product::new(
+                round(&prod.term),
+                prod.variable.clone(),
+                round(&prod.lower),
+                round(&prod.upper),
+                prod.simplified,
+            ))),
+            Expression::Limit(limit) => Expression::Limit(Box::new(Limit::new(
+                round(&limit.term),
+                limit.variable.clone(),
+                round(&limit.point),
+                limit.direction,
+                limit.simplified,
+            ))),
+            Expression::Series(series) => Expression::Series(Box::new(Series::new(
+                round(&series.terms),
+                series.variable.clone(),
+                round(&series.point),
+                series.order,
+                series.simplified,
+            ))),
+            expr => expr
+                .map_children(|child| Ok(child.round_floats(digits)))
+                .unwrap_or_else(|_| expr.clone()),
         }
     }
 
@@ -301,8 +562,20 @@ impl Expression {
             (Expression::Negation(lhs), Expression::Negation(rhs)) => lhs.is_equal(rhs),
             (Expression::Complex(lhs), Expression::Complex(rhs)) => lhs.is_equal(rhs),
             (Expression::Equality(lhs), Expression::Equality(rhs)) => lhs.is_equal(rhs),
+            (Expression::Inequality(lhs), Expression::Inequality(rhs)) => lhs.is_equal(rhs),
             (Expression::Function(lhs), Expression::Function(rhs)) => lhs.is_equal(rhs),
             (Expression::Derivative(lhs), Expression::Derivative(rhs)) => lhs.is_equal(rhs),
+            (Expression::Integral(lhs), Expression::Integral(rhs)) => lhs.is_equal(rhs),
+            (Expression::Limit(lhs), Expression::Limit(rhs)) => lhs.is_equal(rhs),
+            (Expression::Series(lhs), Expression::Series(rhs)) => lhs.is_equal(rhs),
+            (Expression::Summation(lhs), Expression::Summation(rhs)) => lhs.is_equal(rhs),
+            (Expression::Product(lhs), Expression::Product(rhs)) => lhs.is_equal(rhs),
+            (Expression::Matrix(lhs), Expression::Matrix(rhs)) => lhs.is_equal(rhs),
+            (Expression::Vector(lhs), Expression::Vector(rhs)) => lhs.is_equal(rhs),
+            (Expression::And(lhs), Expression::And(rhs)) => lhs.is_equal(rhs),
+            (Expression::Or(lhs), Expression::Or(rhs)) => lhs.is_equal(rhs),
+            (Expression::Not(lhs), Expression::Not(rhs)) => lhs.is_equal(rhs),
+            (Expression::Xor(lhs), Expression::Xor(rhs)) => lhs.is_equal(rhs),
             _ => false,
         }
     }
@@ -320,9 +593,21 @@ impl Expression {
             Expression::Division(division) => division.is_single(),
             Expression::Exponentiation(exponentiation) => exponentiation.is_single(),
             Expression::Equality(equality) => equality.is_single(),
+            Expression::Inequality(inequality) => inequality.is_single(),
             Expression::Complex(complex) => complex.is_single(),
             Expression::Function(function) => function.is_single(),
             Expression::Derivative(derivative) => derivative.is_single(),
+            Expression::Integral(integral) => integral.is_single(),
+            Expression::Limit(limit) => limit.is_single(),
+            Expression::Series(series) => series.is_single(),
+            Expression::Summation(sum) => sum.is_single(),
+            Expression::Product(prod) => prod.is_single(),
+            Expression::Matrix(matrix) => matrix.is_single(),
+            Expression::Vector(vector) => vector.is_single(),
+            Expression::And(and) => and.is_single(),
+            Expression::Or(or) => or.is_single(),
+            Expression::Not(not) => not.is_single(),
+            Expression::Xor(xor) => xor.is_single(),
         }
     }
 
@@ -352,8 +637,8 @@ impl Expression {
     fn reduce_add_mult<'b>(
         terms: &[Expression],
         a: &'b Expression,
-    ) -> Option<(bool, bool, &'b Expression, u64)> {
-        let mut coeff = 1;
+    ) -> Option<(bool, bool, &'b Expression, BigInt)> {
+        let mut coeff = BigInt::from(1);
         let mut terms_neg = false;
 
         let (expr_neg, expr): (bool, &Expression) = if let Expression::Negation(expr) = a {
@@ -368,7 +653,7 @@ impl Expression {
             } else {
                 match term {
                     Expression::Negation(inner) => {
-                        if let Expression::Number(numeral::Numeral::Integer(b)) = inner.term {
+                        if let Expression::Number(numeral::Numeral::Integer(b)) = &inner.term {
                             coeff *= b;
                             terms_neg = !terms_neg;
                             true
@@ -419,9 +704,171 @@ impl Expression {
             Expression::Division(division) => division.contains_var(variable),
             Expression::Exponentiation(exponentiation) => exponentiation.contains_var(variable),
             Expression::Equality(equality) => equality.contains_var(variable),
+            Expression::Inequality(inequality) => inequality.contains_var(variable),
             Expression::Complex(complex) => complex.contains_var(variable),
             Expression::Function(function) => function.contains_var(variable),
             Expression::Derivative(derivative) => derivative.contains_var(variable),
+            Expression::Integral(integral) => integral.contains_var(variable),
+            Expression::Limit(limit) => limit.contains_var(variable),
+            Expression::Series(series) => series.contains_var(variable),
+            Expression::Summation(sum) => sum.contains_var(variable),
+            Expression::Product(prod) => prod.contains_var(variable),
+            Expression::Matrix(matrix) => matrix.contains_var(variable),
+            Expression::Vector(vector) => vector.contains_var(variable),
+            Expression::And(and) => and.contains_var(variable),
+            Expression::Or(or) => or.contains_var(variable),
+            Expression::Not(not) => not.contains_var(variable),
+            Expression::Xor(xor) => xor.contains_var(variable),
+        }
+    }
+
+    /// Rebuild the arithmetic expressions, the relations and the functions
+    /// with `f` applied on their operands, the other expressions are cloned.
+    pub(crate) fn map_children(
+        &self,
+        mut f: impl FnMut(&Expression) -> Result<Expression, SimplifyError>,
+    ) -> Result<Expression, SimplifyError> {
+        match self {
+            Expression::Negation(neg) => Ok(Expression::negation(f(&neg.term)?)),
+            Expression::Addition(add) => Ok(Expression::addition(
+                add.terms.iter().map(f).collect::<Result<_, _>>()?,
+            )),
+            Expression::Multiplication(mul) => Ok(Expression::multiplication(
+                mul.terms.iter().map(f).collect::<Result<_, _>>()?,
+            )),
+            Expression::Subtraction(sub) => Ok(Expression::subtraction(f(&sub.left)?, f(&sub.right)?)),
+            Expression::Division(div) => Ok(Expression::division(f(&div.num)?, f(&div.den)?)),
+            Expression::Exponentiation(exp) => {
+                Ok(Expression::exponentiation(f(&exp.base)?, f(&exp.expo)?))
+            }
+            Expression::Equality(equ) => Ok(Expression::equality(f(&equ.left)?, f(&equ.right)?)),
+            Expression::Inequality(ineq) => Ok(Expression::inequality(
+                f(&ineq.left)?,
+                ineq.relation,
+                f(&ineq.right)?,
+            )),
+            Expression::Complex(com) => Ok(Expression::complex(f(&com.real)?, f(&com.imag)?)),
+            Expression::Function(fun) => Ok(Expression::function(
+                fun.name.clone(),
+                fun.args.iter().map(f).collect::<Result<_, _>>()?,
+            )),
+            _ => Ok(self.clone()),
+        }
+    }
+
+    /// Returns the `Expression` with every occurrence of `target` replaced by `replacement`
+    pub fn substitute(&self, target: &Expression, replacement: &Expression) -> Expression {
+        if self.is_equal(target) {
+            return replacement.clone();
+        }
+        let substitute = |expr: &Expression| expr.substitute(target, replacement);
+        match self {
+            Expression::Number(_) | Expression::Variable(_) | Expression::Constant(_) => {
+                self.clone()
+            }
+            Expression::Negation(neg) => Expression::negation(substitute(&neg.term)),
+            Expression::Addition(add) => {
+                Expression::addition(add.terms.iter().map(substitute).collect())
+            }
+            Expression::Multiplication(mul) => {
+                Expression::multiplication(mul.terms.iter().map(substitute).collect())
+            }
+            Expression::Subtraction(sub) => {
+                Expression::subtraction(substitute(&sub.left), substitute(&sub.right))
+            }
+            Expression::Division(div) => {
+                Expression::division(substitute(&div.num), substitute(&div.den))
+            }
+            Expression::Exponentiation(exp) => {
+                Expression::exponentiation(substitute(&exp.base), substitute(&exp.expo))
+            }
+            Expression::Equality(equ) => {
+                Expression::equality(substitute(&equ.left), substitute(&equ.right))
+            }
+            Expression::Inequality(ineq) => Expression::inequality(
+                substitute(&ineq.left),
+                ineq.relation,
+                substitute(&ineq.right),
+            ),
+            Expression::Complex(com) => {
+                Expression::complex(substitute(&com.real), substitute(&com.imag))
+            }
+            Expression::Function(fun) => {
+                Expression::function(fun.name.clone(), fun.args.iter().map(substitute).collect())
+            }
+            Expression::Derivative(der) => {
+                Expression::derivative(substitute(&der.term), &der.variable, der.order)
+            }
+            Expression::Integral(int) => {
+                // The variable of a definite integral is bound
+                let term = match (&int.bounds, target) {
+                    (Some(_), Expression::Variable(var)) if var.name == int.variable => {
+                        int.term.clone()
+                    }
+                    _ => substitute(&int.term),
+                };
+                match &int.bounds {
+                    Some((lower, upper)) => Expression::definite_integral(
+                        term,
+                        &int.variable,
+                        substitute(lower),
+                        substitute(upper),
+                    ),
+                    None => Expression::integral(term, &int.variable),
+                }
+            }
+            Expression::Limit(limit) => {
+                // The variable of a limit is bound
+                let term = match target {
+                    Expression::Variable(var) if var.name == limit.variable => limit.term.clone(),
+                    _ => substitute(&limit.term),
+                };
+                Expression::limit(
+                    term,
+                    &limit.variable,
+                    substitute(&limit.point),
+                    limit.direction,
+                )
+            }
+            Expression::Series(series) => Expression::Series(Box::new(Series::new(
+                substitute(&series.terms),
+                series.variable.clone(),
+                substitute(&series.point),
+                series.order,
+                false,
+            ))),
+            Expression::Summation(sum) => {
+                // The index of a sum is bound
+                let term = match target {
+                    Expression::Variable(var) if var.name == sum.variable => sum.term.clone(),
+                    _ => substitute(&sum.term),
+                };
+                Expression::summation(term, &sum.variable, substitute(&sum.lower), substitute(&sum.upper))
+            }
+            Expression::Product(prod) => {
+                // The index of a product is bound
+                let term = match target {
+                    Expression::Variable(var) if var.name == prod.variable => prod.term.clone(),
+                    _ => substitute(&prod.term),
+                };
+                Expression::product(term, &prod.variable, substitute(&prod.lower), substitute(&prod.upper))
+            }
+            Expression::Matrix(matrix) => Expression::matrix(
+                matrix
+                    .rows
+                    .iter()
+                    .map(|row| row.iter().map(substitute).collect())
+                    .collect(),
+            ),
+            Expression::Vector(vector) => {
+                Expression::vector(vector.entries.iter().map(substitute).collect())
+            }
+            Expression::And(and) => Expression::and(and.terms.iter().map(substitute).collect()),
+            Expression::Or(or) => Expression::or(or.terms.iter().map(substitute).collect()),
+            Expression::Not(not) => Expression::logical_not(substitute(&not.term)),
+            Expression::Xor(xor) => {
+                Expression::xor(substitute(&xor.left), substitute(&xor.right))
+            }
         }
     }
 }
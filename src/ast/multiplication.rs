@@ -1,6 +1,6 @@
 use crate::{ast::Expr, explanation::FormattingObserver, prints::PrettyPrints};
 
-use super::{Expression, SimplifyError, numeral};
+use super::{Expression, SimplifyError, SimplifyOptions};
 
 #[derive(Debug, PartialEq, Clone, PartialOrd, Eq, Ord, Hash)]
 pub struct Multiplication {
@@ -17,13 +17,18 @@ impl Multiplication {
 impl Expr for Multiplication {
     fn simplify(
         &mut self,
+        options: &SimplifyOptions,
         explanation: &mut Option<Box<FormattingObserver>>,
     ) -> Result<Expression, SimplifyError> {
         let simplified_terms: Vec<Expression> = self.terms
         .iter_mut()
-        .map(|term| term.simplify(explanation))
+        .map(|term| term.simplify_with(options, explanation))
         .collect::<Result<Vec<Expression>, _>>()?;
-    self.simplify_multiplication(simplified_terms, explanation)
+    // The product of matrices doesn't commute
+    if simplified_terms.iter().any(Expression::is_matrix) {
+        return Expression::multiply_matrices(simplified_terms, options, explanation);
+    }
+    self.simplify_multiplication(simplified_terms, options, explanation)
     }
 
     fn is_equal(&self, other: &Multiplication) -> bool {
@@ -60,6 +65,7 @@ impl Multiplication {
     pub(crate) fn simplify_multiplication(
         &self,
         simplified_terms: Vec<Expression>,
+        options: &SimplifyOptions,
         explanation: &mut Option<Box<FormattingObserver>>,
     ) -> Result<Expression, SimplifyError> {
         let before = Expression::multiplication(simplified_terms.clone());
@@ -78,8 +84,9 @@ impl Multiplication {
                 let before = Expression::multiplication(result.clone());
                 match (&result[i], &result[j]) {
                     // a * 0 => 0
-                    (Expression::Number(numeral::Numeral::Integer(0)), _)
-                    | (_, Expression::Number(numeral::Numeral::Integer(0))) => {
+                    (Expression::Number(zero), _) | (_, Expression::Number(zero))
+                        if zero.is_zero() =>
+                    {
                         if let Some(explanation) = explanation {
                             explanation.rule_applied(
                                 "Multiplying by zero yield zero",
@@ -123,8 +130,9 @@ impl Multiplication {
                         result[i] = b.term.clone();
                     }
                     // 1 * a => a
-                    (Expression::Number(numeral::Numeral::Integer(1)), a)
-                    | (a, Expression::Number(numeral::Numeral::Integer(1))) => {
+                    (Expression::Number(one), a) | (a, Expression::Number(one))
+                        if one.is_integer(1) =>
+                    {
                         if let Some(explanation) = explanation {
                             explanation.rule_applied(
                                 "Multiply by one stay the same",
@@ -136,7 +144,7 @@ impl Multiplication {
                         result.swap_remove(j);
                     }
                     (Expression::Number(a), Expression::Number(b)) => {
-                        let after = Expression::Number(a.mul(b));
+                        let mut after = Expression::Number(a.mul(b));
                         if let Some(explanation) = explanation {
                             explanation.rule_applied(
                                 "Multiply numbers",
@@ -144,7 +152,7 @@ impl Multiplication {
                                 &after,
                             );
                         };
-                        result[i] = after;
+                        result[i] = after.simplify_with(options, explanation)?;
                         result.swap_remove(j);
                     }
                     // a * a => a^2
@@ -160,7 +168,7 @@ impl Multiplication {
                                 &after,
                             );
                         }
-                        result[i] = after.simplify(explanation)?;
+                        result[i] = after.simplify_with(options, explanation)?;
                         result.swap_remove(j);
                     }
                     // (a + b i)(c + d i) => ac - bd + ad i + bc i
@@ -197,7 +205,7 @@ impl Multiplication {
                                 &after
                             );
                         };
-                        result[i] = after.simplify(explanation)?;
+                        result[i] = after.simplify_with(options, explanation)?;
                         result.swap_remove(j);
                     }
                     // a(b + c i) => ab + ac i
@@ -213,7 +221,7 @@ impl Multiplication {
                                 &after
                             );
                         };
-                        result[i] = after.simplify(explanation)?;
+                        result[i] = after.simplify_with(options, explanation)?;
                         result.swap_remove(j);
                     }
                     // (a + b)(c + d) => ac + ad + bc + bd
@@ -239,7 +247,7 @@ impl Multiplication {
                                 &after,
                             );
                         };
-                        result[i] = after.simplify(explanation)?;
+                        result[i] = after.simplify_with(options, explanation)?;
                         result.swap_remove(j);
                     }
                     // a(b + c) => ab + ac
@@ -259,7 +267,7 @@ impl Multiplication {
                                 &after,
                             );
                         };
-                        result[i] = after.simplify(explanation)?;
+                        result[i] = after.simplify_with(options, explanation)?;
                         result.swap_remove(j);
                     }
                     // a^x * a^y => a^(x + y)
@@ -282,7 +290,7 @@ impl Multiplication {
                                     &after,
                                 );
                             };
-                            result[i] = after.simplify(explanation)?;
+                            result[i] = after.simplify_with(options, explanation)?;
                             result.swap_remove(j);
                         } else {
                             j += 1;
@@ -303,7 +311,7 @@ impl Multiplication {
                             );
                         }
                         result.swap_remove(j);
-                        result[i] = after.simplify(explanation)?;
+                        result[i] = after.simplify_with(options, explanation)?;
                     }
                     // a^x * a => a^(x + 1)
                     (Expression::Exponentiation(exp), a)
@@ -323,7 +331,7 @@ impl Multiplication {
                                     &after,
                                 );
                             }
-                            result[i] = after.simplify(explanation)?;
+                            result[i] = after.simplify_with(options, explanation)?;
                             result.swap_remove(j);
                         } else {
                             j += 1;
@@ -338,7 +346,7 @@ impl Multiplication {
         if result.len() <= 3 {
             result = result
                 .iter()
-                .filter(|term| !matches!(term, Expression::Number(numeral::Numeral::Integer(1))))
+                .filter(|term| !matches!(term, Expression::Number(one) if one.is_integer(1)))
                 .cloned()
                 .collect();
         }
@@ -356,7 +364,7 @@ impl Multiplication {
             if let Some(explanation) = explanation {
                 explanation.step_completed(&result);
             }
-            result.simplify(explanation)
+            result.simplify_with(options, explanation)
         } else {
             if let Some(explanation) = explanation {
                 explanation.step_completed(&sol);
@@ -1,5 +1,5 @@
 use crate::{
-    ast::{numeral::Numeral, Expr, SimplifyError},
+    ast::{numeral::Numeral, Expr, SimplifyError, SimplifyOptions},
     explanation::FormattingObserver, prints::PrettyPrints,
 };
 
@@ -24,24 +24,33 @@ impl Negation {
 impl Expr for Negation {
     fn simplify(
         &mut self,
+        options: &SimplifyOptions,
         explanation: &mut Option<Box<FormattingObserver>>,
     ) -> Result<Expression, SimplifyError> {
-        let expr = self.term.simplify(explanation)?;
+        let expr = self.term.simplify_with(options, explanation)?;
         match expr {
+            // -A => (-1)*A
+            _ if expr.is_matrix() => {
+                Expression::multiply_matrices(vec![Expression::integer(-1), expr], options, explanation)
+            }
             // --a => a
             Expression::Negation(a) => Ok(a.term),
             // -(a b) => -(a b)
             // -(Num b c) => -(Num) b c
+            // -(-(Num) b c) => Num b c
             Expression::Multiplication(mut a) => {
                 // Find a Expression::integer and transform it to Expression::Negation(Expression::integer)
-                if a.terms.iter_mut().any(|term| {
-                    if let Expression::Number(Numeral::Integer(n)) = term {
-                        *term = Expression::negation(Expression::integer(*n),
+                if a.terms.iter_mut().any(|term| match term {
+                    Expression::Number(Numeral::Integer(n)) => {
+                        *term = Expression::negation(Expression::integer(n.clone()),
                         );
                         true
-                    } else {
-                        false
                     }
+                    Expression::Negation(neg) if matches!(neg.term, Expression::Number(_)) => {
+                        *term = neg.term.clone();
+                        true
+                    }
+                    _ => false,
                 }) {
                     Ok(Expression::Multiplication(a))
                 } else {
@@ -55,17 +64,22 @@ impl Expr for Negation {
                 Expression::negation(comp.real),
                 Expression::negation(comp.imag),
             )
-            .simplify(explanation),
+            .simplify_with(options, explanation),
             // -(a + b) => -a - b
             Expression::Addition(add) => {
                 let terms = add.terms
                     .iter()
                     .map(|elem| Expression::negation(elem.clone()))
                     .collect();
-                Expression::addition(terms).simplify(explanation)
+                Expression::addition(terms).simplify_with(options, explanation)
+            }
+            // -((a + b)/c) => (-a - b)/c
+            Expression::Division(div) if matches!(div.num, Expression::Addition(_)) => {
+                Expression::division(Expression::negation(div.num), div.den)
+                    .simplify_with(options, explanation)
             }
             // -0 => 0
-            Expression::Number(Numeral::Integer(0)) => {
+            Expression::Number(n) if n.is_zero() => {
                 Ok(Expression::integer(0))
             }
             expr => Ok(Expression::Negation(Box::new(Negation::new(expr, true)))),
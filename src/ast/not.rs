@@ -0,0 +1,120 @@
+use std::collections::HashMap;
+
+use crate::{ast::Expr, explanation::FormattingObserver, prints::PrettyPrints};
+
+use super::{Expression, SimplifyError, SimplifyOptions};
+
+#[derive(Debug, PartialEq, Clone, PartialOrd, Eq, Ord, Hash)]
+pub struct Not {
+    pub term: Expression,
+    pub simplified: bool,
+}
+
+// Constructor
+impl Not {
+    pub fn new(term: Expression, simplified: bool) -> Self {
+        Self { term, simplified }
+    }
+}
+
+impl Expr for Not {
+    fn simplify(
+        &mut self,
+        options: &SimplifyOptions,
+        explanation: &mut Option<Box<FormattingObserver>>,
+    ) -> Result<Expression, SimplifyError> {
+        let term = self.term.simplify_with(options, explanation)?;
+        let before = Expression::logical_not(term.clone());
+        let (rule, after) = match term {
+            // !!a => a
+            Expression::Not(not) => ("Double negation\n!!a => a", not.term),
+            // !true => false
+            term => match term.as_boolean() {
+                Some(value) => ("Negation of a constant\n!true => false", Expression::boolean(!value)),
+                None => return Ok(Expression::Not(Box::new(Not::new(term, true)))),
+            },
+        };
+        if let Some(explanation) = explanation {
+            explanation.rule_applied(rule, &before, &after);
+        }
+        Ok(after)
+    }
+
+    fn is_equal(&self, other: &Not) -> bool {
+        self.term.is_equal(&other.term)
+    }
+
+    fn contains_var(&self, variable: &str) -> bool {
+        self.term.contains_var(variable)
+    }
+
+    fn is_single(&self) -> bool {
+        true
+    }
+}
+
+impl Not {
+    fn needs_parenthesis(&self) -> bool {
+        !self.term.is_single()
+    }
+}
+
+impl std::fmt::Display for Not {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        if self.needs_parenthesis() {
+            write!(f, "!({})", self.term)
+        } else {
+            write!(f, "!{}", self.term)
+        }
+    }
+}
+
+impl PrettyPrints for Not {
+    fn calculate_tree(&self, indent: usize) -> String {
+        let next_indent = indent + 2;
+        let next_indent_str = " ".repeat(next_indent);
+
+        format!(
+            "Not:\n{}! {}",
+            next_indent_str,
+            self.term.calculate_tree(next_indent)
+        )
+    }
+
+    fn calculate_positions(
+        &self,
+        memoization: &mut HashMap<Expression, (usize, usize)>,
+        position: &mut Vec<(String, (usize, usize))>,
+        prev_pos: (usize, usize),
+    ) {
+        let height = self.term.get_height(memoization);
+        let mut pos = prev_pos;
+        position.push((
+            "!".to_string(),
+            (pos.0 + self.term.get_below_height(memoization), pos.1),
+        ));
+        pos.1 += 1;
+        if self.needs_parenthesis() {
+            Expression::calculate_parenthesis(position, pos, true, height);
+            pos.1 += 1;
+        }
+        self.term.calculate_positions(memoization, position, pos);
+        pos.1 += self.term.get_length(memoization);
+        if self.needs_parenthesis() {
+            Expression::calculate_parenthesis(position, pos, false, height);
+        }
+    }
+
+    fn get_below_height(&self, memoization: &mut HashMap<Expression, (usize, usize)>) -> usize {
+        self.term.get_below_height(memoization)
+    }
+
+    fn get_height(&self, memoization: &mut HashMap<Expression, (usize, usize)>) -> usize {
+        self.term.get_height(memoization)
+    }
+
+    fn get_length(&self, memoization: &mut HashMap<Expression, (usize, usize)>) -> usize {
+        let parenthesis = if self.needs_parenthesis() { 2 } else { 0 };
+        self.term.get_length(memoization) + 1 + parenthesis
+    }
+}
@@ -1,26 +1,165 @@
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::{One, Signed, ToPrimitive, Zero};
+
 use crate::{
-    ast::Expr, explanation::FormattingObserver, prints::PrettyPrints, utils
+    ast::Expr, explanation::FormattingObserver, prints::PrettyPrints
 };
 
-use super::{Expression, SimplifyError};
+use super::{Expression, SimplifyError, SimplifyOptions};
 
-#[derive(Debug, PartialEq, Copy, Clone, PartialOrd, Eq, Ord, Hash)]
+/// Number backed by signed arbitrary-precision integers, so the exact
+/// arithmetic never overflows, or by a `Float` for inexact values.
+///
+/// The arithmetic of `Numeral` is signed, but once simplified a `Numeral` is
+/// always positive: the sign is carried by an `Expression::Negation` and a
+/// rational is reduced with a positive denominator. A negative number then has
+/// the same form as any other negative term, the rules written for `-a` apply
+/// to `-2` as well as to `-x`, and `-2` has a single form to compare against.
+///
+/// Mixing an exact and an inexact value gives an inexact value.
+#[derive(Debug, PartialEq, Clone, PartialOrd, Eq, Ord, Hash)]
 pub enum Numeral {
-    Integer(u64),
-    Rational(u64, u64),
+    Integer(BigInt),
+    Rational(BigInt, BigInt),
+    Float(Float),
+}
+
+/// Inexact value, compared by its bits so it can be hashed and ordered like
+/// the other numerals.
+#[derive(Debug, Clone, Copy)]
+pub struct Float(pub f64);
+
+impl PartialEq for Float {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.to_bits() == other.0.to_bits()
+    }
+}
+
+impl Eq for Float {}
+
+impl PartialOrd for Float {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Float {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+impl std::hash::Hash for Float {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.0.to_bits().hash(state);
+    }
 }
 
 impl Numeral {
     pub fn is_zero(&self) -> bool {
         match self {
-            Numeral::Integer(n) | Numeral::Rational(n, _) => *n == 0,
+            Numeral::Integer(n) | Numeral::Rational(n, _) => n.is_zero(),
+            Numeral::Float(x) => x.0 == 0.0,
         }
     }
 
     pub fn is_one(&self) -> bool {
         match self {
-            Numeral::Integer(n) => *n == 1,
-            Numeral::Rational(n, d) => *n == *d,
+            Numeral::Integer(n) => n.is_one(),
+            Numeral::Rational(n, d) => n == d,
+            Numeral::Float(x) => x.0 == 1.0,
+        }
+    }
+
+    /// Returns `true` if the numeral is exact, that is not a `Float`.
+    pub fn is_exact(&self) -> bool {
+        !matches!(self, Numeral::Float(_))
+    }
+
+    /// Returns `true` if the numeral is an integer equal to `value`.
+    pub fn is_integer(&self, value: impl Into<BigInt>) -> bool {
+        match self {
+            Numeral::Integer(n) => *n == value.into(),
+            Numeral::Rational(_, _) | Numeral::Float(_) => false,
+        }
+    }
+
+    pub fn is_negative(&self) -> bool {
+        match self {
+            Numeral::Integer(n) => n.is_negative(),
+            Numeral::Rational(n, d) => n.is_negative() != d.is_negative() && !n.is_zero(),
+            Numeral::Float(x) => x.0 < 0.0,
+        }
+    }
+
+    pub fn abs(&self) -> Numeral {
+        match self {
+            Numeral::Integer(n) => Numeral::Integer(n.abs()),
+            Numeral::Rational(n, d) => Numeral::Rational(n.abs(), d.abs()),
+            Numeral::Float(x) => Numeral::Float(Float(x.0.abs())),
+        }
+    }
+
+    pub fn neg(&self) -> Numeral {
+        match self {
+            Numeral::Integer(n) => Numeral::Integer(-n),
+            Numeral::Rational(n, d) => Numeral::Rational(-n, d.clone()),
+            Numeral::Float(x) => Numeral::Float(Float(-x.0)),
+        }
+    }
+
+    /// The numeral in lowest terms with a positive denominator, a rational
+    /// with a denominator of one becomes an integer.
+    pub fn reduced(&self) -> Numeral {
+        match self {
+            Numeral::Rational(n, d) if !d.is_zero() => {
+                let gcd = n.gcd(d);
+                let (mut n, mut d) = (n / &gcd, d / &gcd);
+                if d.is_negative() {
+                    (n, d) = (-n, -d);
+                }
+                if d.is_one() {
+                    Numeral::Integer(n)
+                } else {
+                    Numeral::Rational(n, d)
+                }
+            }
+            _ => self.clone(),
+        }
+    }
+
+    /// `1/self` in lowest terms, `None` for zero.
+    pub fn inverse(&self) -> Option<Numeral> {
+        (!self.is_zero()).then(|| Numeral::Integer(BigInt::one()).div(self).reduced())
+    }
+
+    /// Approximate the numeral by a `f64`, integers too large are infinite.
+    pub fn to_f64(&self) -> f64 {
+        match self {
+            Numeral::Integer(n) => n.to_f64().unwrap_or(f64::NAN),
+            Numeral::Rational(n, d) => {
+                // Scale down so huge numerators and denominators do not overflow
+                let shift = n.bits().max(d.bits()).saturating_sub(1000);
+                let (n, d) = (n >> shift, d >> shift);
+                n.to_f64().unwrap_or(f64::NAN) / d.to_f64().unwrap_or(f64::NAN)
+            }
+            Numeral::Float(x) => x.0,
+        }
+    }
+
+    /// The inexact value of the numeral, used to ask for a decimal answer.
+    pub fn to_float(&self) -> Numeral {
+        Numeral::Float(Float(self.to_f64()))
+    }
+
+    /// Write the numeral as an `Expression`, a negative numeral becomes the
+    /// negation of its absolute value.
+    pub fn into_expression(self) -> Expression {
+        if self.is_negative() {
+            Expression::negation(Expression::Number(self.abs()))
+        } else {
+            Expression::Number(self.abs())
         }
     }
 }
@@ -28,56 +167,77 @@ impl Numeral {
 impl Expr for Numeral {
     fn simplify(
         &mut self,
+        options: &SimplifyOptions,
         explanation: &mut Option<Box<FormattingObserver>>,
     ) -> Result<Expression, SimplifyError> {
         if let Numeral::Rational(n, d) = self {
-            let gcd = utils::gcd(*n, *d);
-            let result = if gcd == 1 {
-                Ok(Numeral::Rational(*n, *d))
-            } else if *d == gcd {
-                let simplified = *n / gcd;
+            if d.is_zero() {
+                return Err(SimplifyError::DivisionByZero);
+            }
+            // Keep the sign on the numerator
+            let (n, d) = if d.is_negative() {
+                (-&*n, -&*d)
+            } else {
+                (n.clone(), d.clone())
+            };
+            let gcd = n.gcd(&d);
+            let result = if d.is_one() {
+                Numeral::Integer(n)
+            } else if gcd.is_one() {
+                Numeral::Rational(n, d)
+            } else if d == gcd {
+                let simplified = &n / &gcd;
                 if let Some(explanation) = explanation {
                     let intermediary = Expression::division(
-                        Expression::integer(simplified),
+                        Expression::integer(simplified.clone()),
                         Expression::integer(1),
                     );
                     explanation.rule_applied(
                         &format!("Simplified by the common factor: {}", gcd),
-                        &Expression::rational(*n, *d),
+                        &Expression::rational(n.clone(), d.clone()),
                         &intermediary,
                     );
                     explanation.rule_applied(
                         "Something divided by one is unchanged",
                         &intermediary,
-                        &Expression::integer(simplified),
-                    );
-                }
-                Ok(Numeral::Integer(simplified))
-            } else if *n == 0 {
-                if let Some(explanation) = explanation {
-                    explanation.rule_applied(
-                        "Zero divided by something is Zero",
-                        &Expression::Number(*self),
-                        &Expression::integer(0),
+                        &Expression::integer(simplified.clone()),
                     );
                 }
-                Ok(Numeral::Integer(0))
-            } else if *d == 0 {
-                Err(SimplifyError::DivisionByZero)
+                Numeral::Integer(simplified)
             } else {
                 if let Some(explanation) = explanation {
                     explanation.rule_applied(
                         &format!("Simplified by the common factor: {}", gcd),
-                        &Expression::rational(*n, *d),
-                        &Expression::rational(*n / gcd, *d / gcd),
+                        &Expression::rational(n.clone(), d.clone()),
+                        &Expression::rational(&n / &gcd, &d / &gcd),
                     );
                 }
-                Ok(Numeral::Rational(*n / gcd, *d / gcd))
-            }?;
+                Numeral::Rational(&n / &gcd, &d / &gcd)
+            };
+            let result = match result {
+                Numeral::Rational(_, _) if options.decimals => {
+                    let decimal = Numeral::Float(Float(result.to_f64()));
+                    if let Some(explanation) = explanation {
+                        explanation.rule_applied(
+                            "Write the fraction as a decimal",
+                            &Expression::Number(result),
+                            &Expression::Number(decimal.clone()),
+                        );
+                    }
+                    decimal
+                }
+                result => result,
+            };
 
-            Ok(Expression::Number(result))
+            Ok(result.into_expression())
+        } else if let Numeral::Float(x) = self {
+            if x.0.is_finite() {
+                Ok(self.clone().into_expression())
+            } else {
+                Err(SimplifyError::Unsupported)
+            }
         } else {
-            Ok(Expression::Number(*self))
+            Ok(self.clone().into_expression())
         }
     }
     
@@ -87,6 +247,7 @@ impl Expr for Numeral {
             (Numeral::Rational(a, b), Numeral::Rational(c, d)) => {
                 a == c && b == d
             }
+            (Numeral::Float(a), Numeral::Float(b)) => a.0 == b.0,
             _ => false,
     }}
     
@@ -96,7 +257,7 @@ impl Expr for Numeral {
 
     fn is_single(&self) -> bool {
         match self {
-            Numeral::Integer(_) => true,
+            Numeral::Integer(_) | Numeral::Float(_) => true,
             Numeral::Rational(_, _) => false,
         }
     }
@@ -106,64 +267,62 @@ impl Expr for Numeral {
 impl Numeral {
     pub fn add(&self, other: &Numeral) -> Numeral {
         match (self, other) {
+            (Numeral::Float(_), _) | (_, Numeral::Float(_)) => {
+                Numeral::Float(Float(self.to_f64() + other.to_f64()))
+            }
             (Numeral::Integer(n), Numeral::Integer(m)) => Numeral::Integer(n + m),
             (Numeral::Rational(n, d), Numeral::Rational(m, p)) => {
                 Numeral::Rational(n * p + m * d, d * p)
             }
             (Numeral::Integer(m), Numeral::Rational(n, d))
-            | (Numeral::Rational(n, d), Numeral::Integer(m)) => Numeral::Rational(m * d + n, *d),
+            | (Numeral::Rational(n, d), Numeral::Integer(m)) => Numeral::Rational(m * d + n, d.clone()),
         }
     }
 
-    // TODO: Check for overflow
-    pub fn sub(&self, other: &Numeral) -> Expression {
-        match (self, other) {
-            (Numeral::Integer(n), Numeral::Integer(m)) => {
-                if m > n {
-                    Expression::negation(Expression::integer(m - n))
-                } else {
-                    Expression::integer(n - m)
-                }
-            }
-            (Numeral::Rational(n, d), Numeral::Rational(m, p)) => {
-                if m * d > n * p {
-                    Expression::negation(Expression::rational(m * d - n * p, d * p))
-                } else {
-                    Expression::rational(n * p - m * d, d * p)
-                }
-            }
-            (Numeral::Integer(m), Numeral::Rational(n, d)) => {
-                if m * d > *n {
-                    Expression::rational(m * d - n, *d)
-                } else {
-                    Expression::negation(Expression::rational(n - m * d, *d))
-                }
-            }
-            (Numeral::Rational(n, d), Numeral::Integer(m)) => {
-                if m * d > *n {
-                    Expression::negation(Expression::rational(m * d - n, *d))
-                } else {
-                    Expression::rational(n - m * d, *d)
-                }
-            }
-        }
+    pub fn sub(&self, other: &Numeral) -> Numeral {
+        self.add(&other.neg())
     }
 
     pub fn mul(&self, other: &Numeral) -> Numeral {
         match (self, other) {
+            (Numeral::Float(_), _) | (_, Numeral::Float(_)) => {
+                Numeral::Float(Float(self.to_f64() * other.to_f64()))
+            }
             (Numeral::Integer(n), Numeral::Integer(m)) => Numeral::Integer(n * m),
             (Numeral::Rational(n, d), Numeral::Rational(m, p)) => Numeral::Rational(n * m, d * p),
             (Numeral::Integer(m), Numeral::Rational(n, d))
-            | (Numeral::Rational(n, d), Numeral::Integer(m)) => Numeral::Rational(m * n, *d),
+            | (Numeral::Rational(n, d), Numeral::Integer(m)) => Numeral::Rational(m * n, d.clone()),
+        }
+    }
+
+    pub fn pow(&self, expo: u32) -> Numeral {
+        match self {
+            Numeral::Integer(n) => Numeral::Integer(n.pow(expo)),
+            Numeral::Rational(n, d) => Numeral::Rational(n.pow(expo), d.pow(expo)),
+            Numeral::Float(x) => Numeral::Float(Float(x.0.powf(expo as f64))),
+        }
+    }
+
+    /// A `Float` rounded to `digits` significant digits, the exact numbers are unchanged.
+    pub fn round(&self, digits: usize) -> Numeral {
+        match self {
+            Numeral::Float(x) if x.0.is_finite() => {
+                let rounded = format!("{:.*e}", digits.max(1) - 1, x.0);
+                Numeral::Float(Float(rounded.parse().unwrap_or(x.0)))
+            }
+            _ => self.clone(),
         }
     }
 
     pub fn div(&self, other: &Numeral) -> Numeral {
         match (self, other) {
-            (Numeral::Integer(n), Numeral::Integer(m)) => Numeral::Rational(*n, *m),
+            (Numeral::Float(_), _) | (_, Numeral::Float(_)) => {
+                Numeral::Float(Float(self.to_f64() / other.to_f64()))
+            }
+            (Numeral::Integer(n), Numeral::Integer(m)) => Numeral::Rational(n.clone(), m.clone()),
             (Numeral::Rational(n, d), Numeral::Rational(m, p)) => Numeral::Rational(n * p, m * d),
-            (Numeral::Integer(m), Numeral::Rational(n, d)) => Numeral::Rational(m * d, *n),
-            (Numeral::Rational(n, d), Numeral::Integer(m)) => Numeral::Rational(*n, m * d),
+            (Numeral::Integer(m), Numeral::Rational(n, d)) => Numeral::Rational(m * d, n.clone()),
+            (Numeral::Rational(n, d), Numeral::Integer(m)) => Numeral::Rational(n.clone(), m * d),
         }
     }
 }
@@ -173,6 +332,7 @@ impl std::fmt::Display for Numeral {
         match self {
             Numeral::Integer(n) => write!(f, "{}", n),
             Numeral::Rational(n, d) => write!(f, "{}/{}", n, d),
+            Numeral::Float(x) => write!(f, "{:?}", x.0),
         }
     }
 }
@@ -185,36 +345,44 @@ impl PrettyPrints for Numeral {
 
     fn calculate_positions(
         &self,
-        _memoization: &mut std::collections::HashMap<Expression, (usize, usize)>,
+        memoization: &mut std::collections::HashMap<Expression, (usize, usize)>,
         position: &mut Vec<(String, (usize, usize))>,
         prev_pos: (usize, usize),
     ) {
-        for (i, c) in self.to_string().chars().enumerate() {
-            position.push((c.to_string(), (prev_pos.0, prev_pos.1 + i)));
+        match self {
+            Numeral::Integer(_) | Numeral::Float(_) => {
+                for (i, c) in self.to_string().chars().enumerate() {
+                    position.push((c.to_string(), (prev_pos.0, prev_pos.1 + i)));
+                }
+            }
+            Numeral::Rational(num, den) => {
+                Expression::division(Expression::integer(num.clone()), Expression::integer(den.clone()))
+                    .calculate_positions(memoization, position, prev_pos)
+            }
         }
     }
 
     fn get_below_height(&self, _memoization: &mut std::collections::HashMap<Expression, (usize, usize)>) -> usize {
         match self {
-            Numeral::Integer(_) => 0,
+            Numeral::Integer(_) | Numeral::Float(_) => 0,
             Numeral::Rational(_num, _den) => 1,
         }
     }
 
     fn get_height(&self, _memoization: &mut std::collections::HashMap<Expression, (usize, usize)>) -> usize {
         match self {
-            Numeral::Integer(_) => 1,
+            Numeral::Integer(_) | Numeral::Float(_) => 1,
             Numeral::Rational(_num, _den) => 3,
         }
     }
 
     fn get_length(&self, memoization: &mut std::collections::HashMap<Expression, (usize, usize)>) -> usize {
         match self {
-            Numeral::Integer(num) => num.to_string().len(),
+            Numeral::Integer(_) | Numeral::Float(_) => self.to_string().len(),
             Numeral::Rational(num, den) => {
-                Expression::division(Expression::integer(*num), Expression::integer(*den))
+                Expression::division(Expression::integer(num.clone()), Expression::integer(den.clone()))
                     .get_length(memoization)
             }
         }
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,306 @@
+use std::collections::HashMap;
+
+use crate::{ast::Expr, explanation::FormattingObserver, prints::PrettyPrints};
+
+use super::{
+    Expression, SimplifyError, SimplifyOptions,
+    summation::{BigOperator, Bounds, MAX_EXPANDED_TERMS},
+};
+
+/// `Π(k=a..b) f(k)`, the product of `f(k)` for every integer `k` between the bounds.
+#[derive(Debug, PartialEq, Clone, PartialOrd, Eq, Ord, Hash)]
+pub struct Product {
+    pub term: Expression,
+    pub variable: String,
+    pub lower: Expression,
+    pub upper: Expression,
+    pub simplified: bool,
+}
+
+// Constructor
+impl Product {
+    pub fn new(
+        term: Expression,
+        variable: String,
+        lower: Expression,
+        upper: Expression,
+        simplified: bool,
+    ) -> Self {
+        Self {
+            term,
+            variable,
+            lower,
+            upper,
+            simplified,
+        }
+    }
+}
+
+impl Expr for Product {
+    fn simplify(
+        &mut self,
+        options: &SimplifyOptions,
+        explanation: &mut Option<Box<FormattingObserver>>,
+    ) -> Result<Expression, SimplifyError> {
+        let term = self.term.simplify_with(options, explanation)?;
+        let lower = self.lower.simplify_with(options, explanation)?;
+        let upper = self.upper.simplify_with(options, explanation)?;
+        match term.prod_with(&self.variable, &lower, &upper, options, explanation) {
+            // No closed form was found, the product is kept
+            Err(SimplifyError::Unsupported) => Ok(Expression::Product(Box::new(Product::new(
+                term,
+                self.variable.clone(),
+                lower,
+                upper,
+                true,
+            )))),
+            result => result,
+        }
+    }
+
+    fn is_equal(&self, other: &Product) -> bool {
+        self.variable == other.variable
+            && self.lower.is_equal(&other.lower)
+            && self.upper.is_equal(&other.upper)
+            && self.term.is_equal(&other.term)
+    }
+
+    fn contains_var(&self, variable: &str) -> bool {
+        // The index of the product is bound
+        self.lower.contains_var(variable)
+            || self.upper.contains_var(variable)
+            || (self.variable != variable && self.term.contains_var(variable))
+    }
+
+    fn is_single(&self) -> bool {
+        false
+    }
+}
+
+impl std::fmt::Display for Product {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "Π({}={}..{}) ", self.variable, self.lower, self.upper)?;
+        if self.term.is_single() {
+            write!(f, "{}", self.term)
+        } else {
+            write!(f, "({})", self.term)
+        }
+    }
+}
+
+impl Expression {
+    /// Product of the expression for every integer `variable` from `lower` to `upper`.
+    ///
+    /// The product is written out when the bounds are close numbers, otherwise a
+    /// closed form is looked for: constant terms, powers `c^f(k)` whose exponents
+    /// can be summed and telescoping quotients `f(k+1)/f(k)`.
+    ///
+    /// Returns `SimplifyError::Unsupported` if no closed form is found.
+    pub fn prod(
+        &self,
+        variable: &str,
+        lower: &Expression,
+        upper: &Expression,
+        explanation: &mut Option<Box<FormattingObserver>>,
+    ) -> Result<Expression, SimplifyError> {
+        self.prod_with(variable, lower, upper, &SimplifyOptions::default(), explanation)
+    }
+
+    /// Returns the product like `Expression::prod`, every step is simplified with `options`.
+    pub fn prod_with(
+        &self,
+        variable: &str,
+        lower: &Expression,
+        upper: &Expression,
+        options: &SimplifyOptions,
+        explanation: &mut Option<Box<FormattingObserver>>,
+    ) -> Result<Expression, SimplifyError> {
+        let bounds = Bounds {
+            variable,
+            lower,
+            upper,
+            options,
+        };
+        if lower.contains_var(variable) || upper.contains_var(variable) || !bounds.is_valid() {
+            return Err(SimplifyError::Unsupported);
+        }
+
+        // Steps are only kept when a closed form is found
+        let mut trial = explanation.clone();
+        let term = self.clone().simplify_with(options, &mut trial)?;
+        let result = term.product_terms(&bounds, &mut trial)?.simplify_with(options, &mut trial)?;
+        *explanation = trial;
+        Ok(result)
+    }
+
+    fn product_terms(
+        &self,
+        bounds: &Bounds,
+        explanation: &mut Option<Box<FormattingObserver>>,
+    ) -> Result<Expression, SimplifyError> {
+        let before = Expression::product(
+            self.clone(),
+            bounds.variable,
+            bounds.lower.clone(),
+            bounds.upper.clone(),
+        );
+        let product = |expr: &Expression| {
+            Expression::product(
+                expr.clone(),
+                bounds.variable,
+                bounds.lower.clone(),
+                bounds.upper.clone(),
+            )
+        };
+
+        let (rule, after) = if let Some(count) = bounds.count()
+            && count <= MAX_EXPANDED_TERMS
+        {
+            let after = match bounds.terms(self) {
+                Some(terms) if !terms.is_empty() => Expression::multiplication(terms),
+                _ => Expression::integer(1),
+            };
+            ("Expand the product", after)
+        } else if !self.contains_var(bounds.variable) {
+            if bounds.is_infinite() {
+                return Err(SimplifyError::Unsupported);
+            }
+            (
+                "Product of a constant\nΠ(k=a..b) c => c^(b - a + 1)",
+                Expression::exponentiation(self.clone(), bounds.length()),
+            )
+        } else {
+            match self {
+                // c^f(k)
+                Expression::Exponentiation(exp) if !exp.base.contains_var(bounds.variable) => {
+                    let expo = exp.expo.sum_terms(bounds, &mut None)?;
+                    (
+                        "Product of powers\nΠ(k=a..b) c^f(k) => c^(Σ(k=a..b) f(k))",
+                        Expression::exponentiation(exp.base.clone(), expo),
+                    )
+                }
+                Expression::Division(div) if telescopes(&div.num, &div.den, bounds)? => (
+                    "Telescoping product\nΠ(k=a..b) f(k+1)/f(k) => f(b+1)/f(a)",
+                    bounds.at_upper(
+                        &Expression::division(div.den.clone(), bounds.at_lower(&div.den, 0)),
+                        1,
+                    )?,
+                ),
+                Expression::Division(div) if telescopes(&div.den, &div.num, bounds)? => (
+                    "Telescoping product\nΠ(k=a..b) f(k)/f(k+1) => f(a)/f(b+1)",
+                    bounds.at_upper(
+                        &Expression::division(bounds.at_lower(&div.num, 0), div.num.clone()),
+                        1,
+                    )?,
+                ),
+                // The product of the limits can be an indeterminate form
+                _ if bounds.is_infinite() => return Err(SimplifyError::Unsupported),
+                Expression::Division(div) => {
+                    if let Some(explanation) = explanation {
+                        let after = Expression::division(product(&div.num), product(&div.den));
+                        explanation.rule_applied(
+                            "Product of a quotient is given by\nΠ(k=a..b) f/g => Π(k=a..b) f / Π(k=a..b) g",
+                            &before,
+                            &after,
+                        );
+                    }
+                    return Ok(Expression::division(
+                        div.num.product_terms(bounds, explanation)?,
+                        div.den.product_terms(bounds, explanation)?,
+                    ));
+                }
+                Expression::Multiplication(mul) => {
+                    if let Some(explanation) = explanation {
+                        let after = Expression::multiplication(mul.terms.iter().map(product).collect());
+                        explanation.rule_applied(
+                            "Product of a product is given by\nΠ(k=a..b) f*g => Π(k=a..b) f * Π(k=a..b) g",
+                            &before,
+                            &after,
+                        );
+                    }
+                    return Ok(Expression::multiplication(
+                        mul.terms
+                            .iter()
+                            .map(|term| term.product_terms(bounds, explanation))
+                            .collect::<Result<_, _>>()?,
+                    ));
+                }
+                Expression::Negation(neg) => {
+                    let sign = Expression::exponentiation(Expression::integer(-1), bounds.length());
+                    if let Some(explanation) = explanation {
+                        let after = Expression::multiplication(vec![sign.clone(), product(&neg.term)]);
+                        explanation.rule_applied(
+                            "The sign is taken out of the product\nΠ(k=a..b) -f => (-1)^(b - a + 1) * Π(k=a..b) f",
+                            &before,
+                            &after,
+                        );
+                    }
+                    return Ok(Expression::multiplication(vec![
+                        sign,
+                        neg.term.product_terms(bounds, explanation)?,
+                    ]));
+                }
+                _ => return Err(SimplifyError::Unsupported),
+            }
+        };
+
+        if let Some(explanation) = explanation {
+            explanation.rule_applied(rule, &before, &after);
+        }
+        Ok(after)
+    }
+}
+
+/// Returns `true` if `num` is `den(k+1)`.
+fn telescopes(num: &Expression, den: &Expression, bounds: &Bounds) -> Result<bool, SimplifyError> {
+    if !den.contains_var(bounds.variable) {
+        return Ok(false);
+    }
+    Ok(bounds
+        .shifted(den, 1)
+        .simplify_with(bounds.options, &mut None)?
+        .is_equal(&num.clone().simplify_with(bounds.options, &mut None)?))
+}
+
+const PI: [&str; 3] = ["┬─┬", "│ │", "│ │"];
+
+impl Product {
+    fn big_operator(&self) -> BigOperator<'_> {
+        BigOperator {
+            symbol: &PI,
+            baseline: 2,
+            term: &self.term,
+            variable: &self.variable,
+            lower: &self.lower,
+            upper: &self.upper,
+        }
+    }
+}
+
+impl PrettyPrints for Product {
+    fn calculate_tree(&self, indent: usize) -> String {
+        self.big_operator().calculate_tree("Product", indent)
+    }
+
+    fn calculate_positions(
+        &self,
+        memoization: &mut HashMap<Expression, (usize, usize)>,
+        position: &mut Vec<(String, (usize, usize))>,
+        prev_pos: (usize, usize),
+    ) {
+        self.big_operator()
+            .calculate_positions(memoization, position, prev_pos)
+    }
+
+    fn get_below_height(&self, memoization: &mut HashMap<Expression, (usize, usize)>) -> usize {
+        self.big_operator().get_below_height(memoization)
+    }
+
+    fn get_height(&self, memoization: &mut HashMap<Expression, (usize, usize)>) -> usize {
+        self.big_operator().get_height(memoization)
+    }
+
+    fn get_length(&self, memoization: &mut HashMap<Expression, (usize, usize)>) -> usize {
+        self.big_operator().get_length(memoization)
+    }
+}
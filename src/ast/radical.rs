@@ -0,0 +1,302 @@
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::{One, Signed, ToPrimitive, Zero};
+
+use crate::utils;
+
+use super::{
+    Expression,
+    function::{Function, FunctionType},
+    numeral::Numeral,
+};
+
+impl Function {
+    /// Simplify the square roots and the roots of exact numbers, like
+    /// `sqrt(12) => 2*sqrt(3)`, and denest `sqrt(a + b*sqrt(c))`.
+    pub(super) fn radical_value(expr: &Expression) -> Option<(&'static str, Expression)> {
+        let Expression::Function(function) = expr else {
+            return None;
+        };
+        let (index, radicand) = radical_parts(function)?;
+        match signed_numeral(radicand) {
+            Some(Numeral::Integer(n)) if n.is_positive() => {
+                let (outside, inside) = extract_power(&n, index)?;
+                Some((
+                    "Take the perfect powers out of the root\nroot(k, a^k*b) => a*root(k, b)",
+                    scaled((outside, BigInt::one()), root_factor(index, inside)),
+                ))
+            }
+            // root(k, p/q) => root(k, p*q^(k-1))/q
+            Some(n @ Numeral::Rational(_, _)) if !n.is_negative() && !n.is_zero() => {
+                let (p, q) = reduced(&n)?;
+                let radicand = p * q.pow(index - 1);
+                let (outside, inside) = extract_power(&radicand, index)
+                    .unwrap_or_else(|| (BigInt::one(), radicand));
+                Some((
+                    "Take the denominator out of the root\nroot(k, p/q) => root(k, p*q^(k-1))/q",
+                    scaled(reduce(outside, q), root_factor(index, inside)),
+                ))
+            }
+            Some(n) if n.is_negative() && index.is_odd() => Some((
+                "An odd root keeps the sign\nroot(k, -a) => -root(k, a)",
+                Expression::negation(radical(index, Expression::Number(n.abs()))),
+            )),
+            Some(_) => None,
+            None if index == 2 => denest(radicand).map(|after| {
+                (
+                    "Denest the square root\nsqrt(a + b*sqrt(c)) => sqrt((a + d)/2) + sqrt((a - d)/2) with d = sqrt(a^2 - b^2*c)",
+                    after,
+                )
+            }),
+            None => None,
+        }
+    }
+}
+
+impl Expression {
+    /// Take the roots of numbers out of the denominator of `num/den`, like
+    /// `1/sqrt(2) => sqrt(2)/2` or `1/(1 + sqrt(2)) => (1 - sqrt(2))/(1 - 2)`.
+    pub(super) fn rationalize(
+        num: &Expression,
+        den: &Expression,
+    ) -> Option<(&'static str, Expression)> {
+        // c*root(k, b) => the factor c, k and b
+        let scaled_root = |expr: &Expression| match expr {
+            Expression::Multiplication(mul) => match mul.terms.as_slice() {
+                [Expression::Number(c), root] | [root, Expression::Number(c)] => {
+                    numeric_root(root).map(|(index, b)| (c.clone(), index, b))
+                }
+                _ => None,
+            },
+            expr => numeric_root(expr).map(|(index, b)| (Numeral::Integer(BigInt::one()), index, b)),
+        };
+        if let Some((c, index, b)) = scaled_root(den) {
+            // a/(c*root(k, b)) => a*root(k, b^(k-1))/(c*b)
+            let (coefficient, mut rest) = numeric_factor(num);
+            let (p, q) = reduced(&coefficient.div(&c.mul(&Numeral::Integer(b.clone()))))?;
+            rest.push(radical(index, Expression::integer(b.pow(index - 1))));
+            return Some((
+                "Rationalize the denominator\na/root(k, b) => a*root(k, b^(k-1))/b",
+                scaled((p, q), rest),
+            ));
+        }
+        // a/(p + q*sqrt(c)) => a*(p - q*sqrt(c))/(p^2 - q^2*c)
+        let (p, q, c) = binomial_surd(den)?;
+        let norm = p.mul(&p).sub(&q.mul(&q).mul(&c));
+        if norm.is_zero() {
+            return None;
+        }
+        let conjugate = Expression::addition(vec![
+            p.into_expression(),
+            Expression::multiplication(vec![
+                q.neg().into_expression(),
+                Expression::sqrt(c.into_expression()),
+            ]),
+        ]);
+        Some((
+            "Multiply by the conjugate of the denominator\na/(p + q*sqrt(c)) => a*(p - q*sqrt(c))/(p^2 - q^2*c)",
+            Expression::division(
+                Expression::multiplication(vec![num.clone(), conjugate]),
+                norm.into_expression(),
+            ),
+        ))
+    }
+}
+
+/// The index and the radicand of `sqrt` and `root`.
+fn radical_parts(function: &Function) -> Option<(u32, &Expression)> {
+    match (&function.name, function.args.as_slice()) {
+        (FunctionType::Sqrt, [radicand]) => Some((2, radicand)),
+        (FunctionType::Root, [Expression::Number(Numeral::Integer(index)), radicand]) => {
+            index.to_u32().filter(|index| *index >= 2).map(|index| (index, radicand))
+        }
+        _ => None,
+    }
+}
+
+/// `sqrt(a)` when the index is 2 and `root(k, a)` otherwise.
+fn radical(index: u32, radicand: Expression) -> Expression {
+    if index == 2 {
+        Expression::sqrt(radicand)
+    } else {
+        Expression::root(Expression::integer(index), radicand)
+    }
+}
+
+/// The index and the radicand of the root of a positive integer.
+fn numeric_root(expr: &Expression) -> Option<(u32, BigInt)> {
+    let Expression::Function(function) = expr else {
+        return None;
+    };
+    match radical_parts(function)? {
+        (index, Expression::Number(Numeral::Integer(b))) if b.is_positive() => {
+            Some((index, b.clone()))
+        }
+        _ => None,
+    }
+}
+
+/// An exact number, possibly negated.
+fn signed_numeral(expr: &Expression) -> Option<Numeral> {
+    match expr {
+        Expression::Number(n) if n.is_exact() => Some(n.clone()),
+        Expression::Negation(neg) => signed_numeral(&neg.term).map(|n| n.neg()),
+        _ => None,
+    }
+}
+
+/// `n = a^k * b` with `a > 1` and `b` without `k`-th power factor.
+fn extract_power(n: &BigInt, index: u32) -> Option<(BigInt, BigInt)> {
+    let factors = utils::prime_factors(n.to_u64()?)?;
+    let (mut outside, mut inside) = (BigInt::one(), BigInt::one());
+    for (prime, count) in factors {
+        let prime = BigInt::from(prime);
+        let (quotient, remainder) = count.div_rem(&(index as u64));
+        outside *= prime.pow(quotient as u32);
+        inside *= prime.pow(remainder as u32);
+    }
+    (!outside.is_one()).then_some((outside, inside))
+}
+
+/// `q*sqrt(c)` as `q` and `c`.
+fn surd(expr: &Expression) -> Option<(Numeral, Numeral)> {
+    match expr {
+        Expression::Function(function) if function.name == FunctionType::Sqrt => {
+            match signed_numeral(&function.args[0])? {
+                c @ Numeral::Integer(_) if !c.is_negative() && !c.is_zero() => {
+                    Some((Numeral::Integer(BigInt::one()), c))
+                }
+                _ => None,
+            }
+        }
+        Expression::Negation(neg) => surd(&neg.term).map(|(q, c)| (q.neg(), c)),
+        Expression::Multiplication(mul) => match mul.terms.as_slice() {
+            [q, root] | [root, q] if let Some(q) = signed_numeral(q) => {
+                surd(root).map(|(r, c)| (q.mul(&r), c))
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// `p + q*sqrt(c)` as `p`, `q` and `c`.
+fn binomial_surd(expr: &Expression) -> Option<(Numeral, Numeral, Numeral)> {
+    let Expression::Addition(add) = expr else {
+        return None;
+    };
+    match add.terms.as_slice() {
+        [p, root] | [root, p] if let Some(p) = signed_numeral(p) => {
+            surd(root).map(|(q, c)| (p, q, c))
+        }
+        _ => None,
+    }
+}
+
+/// `sqrt(a + b*sqrt(c)) = sqrt((a + d)/2) + sqrt((a - d)/2)` when
+/// `d = sqrt(a^2 - b^2*c)` is rational, written `(sqrt(2(a + d)) + sqrt(2(a - d)))/2`
+/// when the halves are not integers.
+fn denest(radicand: &Expression) -> Option<Expression> {
+    let (a, b, c) = binomial_surd(radicand)?;
+    if a.is_negative() || a.is_zero() {
+        return None;
+    }
+    let d = rational_sqrt(&a.mul(&a).sub(&b.mul(&b).mul(&c)))?;
+    let sign = |rhs: Expression| {
+        if b.is_negative() {
+            Expression::negation(rhs)
+        } else {
+            rhs
+        }
+    };
+    let (sum, difference) = (a.add(&d), a.sub(&d));
+    let half = Numeral::Rational(BigInt::one(), BigInt::from(2));
+    let (x, y) = (sum.mul(&half), difference.mul(&half));
+    if reduced(&x)?.1.is_one() && reduced(&y)?.1.is_one() {
+        Some(Expression::addition(vec![
+            Expression::sqrt(x.into_expression()),
+            sign(Expression::sqrt(y.into_expression())),
+        ]))
+    } else {
+        let two = Numeral::Integer(BigInt::from(2));
+        Some(Expression::division(
+            Expression::addition(vec![
+                Expression::sqrt(sum.mul(&two).into_expression()),
+                sign(Expression::sqrt(difference.mul(&two).into_expression())),
+            ]),
+            Expression::integer(2),
+        ))
+    }
+}
+
+/// The square root of a nonnegative rational if it is rational.
+fn rational_sqrt(n: &Numeral) -> Option<Numeral> {
+    let root = |n: &BigInt| {
+        let root = n.sqrt();
+        (&root * &root == *n).then_some(root)
+    };
+    let (p, q) = reduced(n)?;
+    if p.is_negative() {
+        return None;
+    }
+    Some(Numeral::Integer(root(&p)?).div(&Numeral::Integer(root(&q)?)))
+}
+
+/// The numerator and the positive denominator of an exact number in lowest terms.
+fn reduced(n: &Numeral) -> Option<(BigInt, BigInt)> {
+    match n {
+        Numeral::Integer(n) => Some((n.clone(), BigInt::one())),
+        Numeral::Rational(p, q) if !q.is_zero() => Some(reduce(p.clone(), q.clone())),
+        _ => None,
+    }
+}
+
+fn reduce(p: BigInt, q: BigInt) -> (BigInt, BigInt) {
+    let gcd = p.gcd(&q);
+    let (p, q) = (p / &gcd, q / &gcd);
+    if q.is_negative() { (-p, -q) } else { (p, q) }
+}
+
+/// The number and the other factors of a product.
+fn numeric_factor(expr: &Expression) -> (Numeral, Vec<Expression>) {
+    let mut coefficient = Numeral::Integer(BigInt::one());
+    let mut rest = Vec::new();
+    let terms = match expr {
+        Expression::Multiplication(mul) => mul.terms.as_slice(),
+        expr => std::slice::from_ref(expr),
+    };
+    for term in terms {
+        match term {
+            Expression::Number(n) if n.is_exact() => coefficient = coefficient.mul(n),
+            term => rest.push(term.clone()),
+        }
+    }
+    (coefficient, rest)
+}
+
+/// `root(k, b)` as a factor, none when `b = 1`.
+fn root_factor(index: u32, radicand: BigInt) -> Vec<Expression> {
+    if radicand.is_one() {
+        vec![]
+    } else {
+        vec![radical(index, Expression::integer(radicand))]
+    }
+}
+
+/// `(p * f1 * ... * fn)/q` without the factors equal to 1.
+fn scaled((p, q): (BigInt, BigInt), factors: Vec<Expression>) -> Expression {
+    let mut terms = Vec::new();
+    if !p.is_one() || factors.is_empty() {
+        terms.push(Expression::integer(p));
+    }
+    terms.extend(factors);
+    let num = match terms.len() {
+        1 => terms.remove(0),
+        _ => Expression::multiplication(terms),
+    };
+    if q.is_one() {
+        num
+    } else {
+        Expression::division(num, Expression::integer(q))
+    }
+}
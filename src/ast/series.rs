@@ -0,0 +1,289 @@
+use std::collections::HashMap;
+
+use num_bigint::BigInt;
+
+use crate::{ast::Expr, explanation::FormattingObserver, prints::PrettyPrints};
+
+use super::{Expression, SimplifyError, SimplifyOptions, limit::defined, numeral::Numeral};
+
+/// Truncated power series `a0 + a1*(x - a) + ... + O((x - a)^n)`.
+#[derive(Debug, PartialEq, Clone, PartialOrd, Eq, Ord, Hash)]
+pub struct Series {
+    /// The polynomial part, without the remainder
+    pub terms: Expression,
+    pub variable: String,
+    pub point: Expression,
+    /// Power of the remainder `O((x - a)^order)`
+    pub order: u32,
+    pub simplified: bool,
+}
+
+// Constructor
+impl Series {
+    pub fn new(
+        terms: Expression,
+        variable: String,
+        point: Expression,
+        order: u32,
+        simplified: bool,
+    ) -> Self {
+        Self {
+            terms,
+            variable,
+            point,
+            order,
+            simplified,
+        }
+    }
+
+    /// The `(x - a)^order` inside the big-O.
+    pub fn remainder(&self) -> Expression {
+        power_of_distance(&self.variable, &self.point, self.order)
+    }
+
+    /// Returns `true` if every term was truncated, the series is only `O((x - a)^n)`.
+    fn is_remainder(&self) -> bool {
+        matches!(&self.terms, Expression::Number(n) if n.is_zero())
+    }
+}
+
+impl Expr for Series {
+    fn simplify(
+        &mut self,
+        options: &SimplifyOptions,
+        explanation: &mut Option<Box<FormattingObserver>>,
+    ) -> Result<Expression, SimplifyError> {
+        if self.simplified {
+            return Ok(Expression::Series(Box::new(self.clone())));
+        }
+        Ok(Expression::Series(Box::new(Series::new(
+            self.terms.simplify_with(options, explanation)?,
+            self.variable.clone(),
+            self.point.simplify_with(options, explanation)?,
+            self.order,
+            true,
+        ))))
+    }
+
+    fn is_equal(&self, other: &Series) -> bool {
+        self.variable == other.variable
+            && self.order == other.order
+            && self.point.is_equal(&other.point)
+            && self.terms.is_equal(&other.terms)
+    }
+
+    fn contains_var(&self, variable: &str) -> bool {
+        self.variable == variable
+            || self.terms.contains_var(variable)
+            || self.point.contains_var(variable)
+    }
+
+    fn is_single(&self) -> bool {
+        false
+    }
+}
+
+impl std::fmt::Display for Series {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        if !self.is_remainder() {
+            write!(f, "{} + ", self.terms)?;
+        }
+        write!(f, "O({})", self.remainder())
+    }
+}
+
+impl Expression {
+    /// Taylor series of the expression around `point`, up to the power `order` excluded.
+    ///
+    /// The term of degree `k` is `f^(k)(a)/k! * (x - a)^k`, the truncated terms
+    /// are kept in the remainder `O((x - a)^order)`.
+    ///
+    /// Returns `SimplifyError::Unsupported` if a derivative is not defined at the point.
+    ///
+    /// ```
+    /// use sym_rustic::ast::Expression;
+    ///
+    /// let x = Expression::variable("x");
+    /// let series = Expression::exp(x.clone())
+    ///     .series("x", &Expression::integer(0), 3)
+    ///     .unwrap();
+    /// assert_eq!(series.to_string(), "1 + x + ((x^2)/2) + O(x^3)");
+    /// ```
+    pub fn series(
+        &self,
+        variable: &str,
+        point: &Expression,
+        order: u32,
+    ) -> Result<Expression, SimplifyError> {
+        let point = point.clone().simplify(&mut None)?;
+        if point.contains_var(variable) || point.is_infinite() {
+            return Err(SimplifyError::Unsupported);
+        }
+
+        let mut terms = Vec::new();
+        let mut derivative = self.clone().simplify(&mut None)?;
+        let mut factorial = BigInt::from(1);
+        for k in 0..order {
+            if k > 0 {
+                derivative = derivative.differentiate_n(variable, 1, &mut None)?;
+                factorial *= k;
+            }
+            let at_point = derivative.substitute(&Expression::variable(variable), &point);
+            let Some(value) = defined(&at_point, &SimplifyOptions::default()) else {
+                return Err(SimplifyError::Unsupported);
+            };
+            if matches!(&value, Expression::Number(n) if n.is_zero()) {
+                continue;
+            }
+            let coefficient = Expression::division(value, Expression::integer(factorial.clone()))
+                .simplify(&mut None)?;
+            terms.push(monomial(coefficient, variable, &point, k));
+        }
+
+        // The terms are not simplified, it would expand the powers of `x - a`
+        let mut sum: Vec<Expression> = Vec::new();
+        for term in terms {
+            match term {
+                Expression::Negation(neg) if !sum.is_empty() => {
+                    sum = vec![Expression::subtraction(join(sum), neg.term)];
+                }
+                term => sum.push(term),
+            }
+        }
+        let terms = match sum.len() {
+            0 => Expression::integer(0),
+            _ => join(sum),
+        };
+        Ok(Expression::Series(Box::new(Series::new(
+            terms,
+            variable.to_owned(),
+            point,
+            order,
+            true,
+        ))))
+    }
+
+    /// Returns `true` for `inf` and `-inf`.
+    fn is_infinite(&self) -> bool {
+        match self {
+            Expression::Constant(super::constant::Constant::Infinity) => true,
+            Expression::Negation(neg) => neg.term.is_infinite(),
+            _ => false,
+        }
+    }
+}
+
+/// Add the terms, a single term is returned as is.
+fn join(mut terms: Vec<Expression>) -> Expression {
+    match terms.len() {
+        1 => terms.remove(0),
+        _ => Expression::addition(terms),
+    }
+}
+
+/// `c*(x - a)^k`, a rational coefficient is written as a quotient `(p*(x - a)^k)/q`.
+fn monomial(coefficient: Expression, variable: &str, point: &Expression, k: u32) -> Expression {
+    if k == 0 {
+        return coefficient;
+    }
+    let power = power_of_distance(variable, point, k);
+    match coefficient {
+        Expression::Number(Numeral::Integer(n)) if n == BigInt::from(1) => power,
+        Expression::Number(Numeral::Rational(num, den)) => Expression::division(
+            monomial(Expression::integer(num), variable, point, k),
+            Expression::integer(den),
+        ),
+        Expression::Negation(neg) => {
+            Expression::negation(monomial(neg.term, variable, point, k))
+        }
+        coefficient => Expression::multiplication(vec![coefficient, power]),
+    }
+}
+
+/// `(x - a)^k`, written `x^k` when the point is zero.
+fn power_of_distance(variable: &str, point: &Expression, k: u32) -> Expression {
+    let distance = match point {
+        Expression::Number(n) if n.is_zero() => Expression::variable(variable),
+        _ => Expression::subtraction(Expression::variable(variable), point.clone()),
+    };
+    match k {
+        1 => distance,
+        _ => Expression::exponentiation(distance, Expression::integer(k)),
+    }
+}
+
+impl PrettyPrints for Series {
+    fn calculate_tree(&self, indent: usize) -> String {
+        let next_indent = indent + 2;
+        let next_indent_str = " ".repeat(next_indent);
+
+        format!(
+            "Series:\n{}{}\n{}O {}",
+            next_indent_str,
+            self.terms.calculate_tree(next_indent),
+            next_indent_str,
+            self.remainder().calculate_tree(next_indent),
+        )
+    }
+
+    fn calculate_positions(
+        &self,
+        memoization: &mut HashMap<Expression, (usize, usize)>,
+        position: &mut Vec<(String, (usize, usize))>,
+        prev_pos: (usize, usize),
+    ) {
+        let baseline = prev_pos.0 + self.get_below_height(memoization);
+        let mut column = prev_pos.1;
+
+        // a0 + a1*x +
+        if !self.is_remainder() {
+            let bottom = baseline - self.terms.get_below_height(memoization);
+            self.terms
+                .calculate_positions(memoization, position, (bottom, column));
+            column += self.terms.get_length(memoization);
+            position.push(("+".to_string(), (baseline, column + 1)));
+            column += 3;
+        }
+
+        // O(x^n)
+        let remainder = self.remainder();
+        let height = remainder.get_height(memoization);
+        position.push(("O".to_string(), (baseline, column)));
+        column += 1;
+        let bottom = baseline - remainder.get_below_height(memoization);
+        Self::calculate_parenthesis(position, (bottom, column), true, height);
+        column += 1;
+        remainder.calculate_positions(memoization, position, (bottom, column));
+        column += remainder.get_length(memoization);
+        Self::calculate_parenthesis(position, (bottom, column), false, height);
+    }
+
+    fn get_below_height(&self, memoization: &mut HashMap<Expression, (usize, usize)>) -> usize {
+        let remainder = self.remainder().get_below_height(memoization);
+        if self.is_remainder() {
+            remainder
+        } else {
+            remainder.max(self.terms.get_below_height(memoization))
+        }
+    }
+
+    fn get_height(&self, memoization: &mut HashMap<Expression, (usize, usize)>) -> usize {
+        let remainder = self.remainder();
+        let mut above =
+            remainder.get_height(memoization) - remainder.get_below_height(memoization);
+        if !self.is_remainder() {
+            above = above
+                .max(self.terms.get_height(memoization) - self.terms.get_below_height(memoization));
+        }
+        self.get_below_height(memoization) + above
+    }
+
+    fn get_length(&self, memoization: &mut HashMap<Expression, (usize, usize)>) -> usize {
+        let terms = if self.is_remainder() {
+            0
+        } else {
+            self.terms.get_length(memoization) + 3
+        };
+        terms + 1 + self.remainder().get_length(memoization) + 2
+    }
+}
@@ -1,5 +1,5 @@
 use crate::{
-    ast::{numeral, Expr, SimplifyError},
+    ast::{Expr, SimplifyError, SimplifyOptions},
     explanation::FormattingObserver, prints::PrettyPrints,
 };
 
@@ -26,51 +26,58 @@ impl Subtraction {
 impl Expr for Subtraction {
     fn simplify(
         &mut self,
+        options: &SimplifyOptions,
         explanation: &mut Option<Box<FormattingObserver>>,
     ) -> Result<Expression, SimplifyError> {
-        let left = self.left.simplify(explanation)?;
-        let right = self.right.simplify(explanation)?;
+        let left = self.left.simplify_with(options, explanation)?;
+        let right = self.right.simplify_with(options, explanation)?;
+
+        // A - B => A + (-1)*B
+        if left.is_matrix() || right.is_matrix() {
+            let right = Expression::multiply_matrices(vec![Expression::integer(-1), right], options, explanation)?;
+            return Expression::add_matrices(vec![left, right], options, explanation);
+        }
 
         let before = &Expression::subtraction(left.clone(), right.clone());
         match (left, right) {
             // a - 0
-            (lhs, Expression::Number(numeral::Numeral::Integer(0))) => {
+            (lhs, Expression::Number(rhs)) if rhs.is_zero() => {
                 if let Some(explanation) = explanation {
                     explanation.rule_applied("Subtracting zero stay the same", before, &lhs);
                 }
                 Ok(lhs)},
             // 0 - a
-            (Expression::Number(numeral::Numeral::Integer(0)), rhs) => {
-                let after  = Expression::negation(rhs);
+            (Expression::Number(lhs), rhs) if lhs.is_zero() => {
+                let mut after  = Expression::negation(rhs);
                 if let Some(explanation) = explanation {
                     explanation.rule_applied("Adding zero stay the same", before, &after);
                 }
-                Ok(after)
+                after.simplify_with(options, explanation)
             }
             // a - b => c 
             (Expression::Number(lhs), Expression::Number(rhs)) => {
-                let after  = lhs.sub(&rhs);
+                let mut after  = lhs.sub(&rhs).into_expression();
                 if let Some(explanation) = explanation {
                     explanation.rule_applied("Subtracting numbers", before, &after);
                 }
-                Ok(after)
+                after.simplify_with(options, explanation)
             }
             // -a - b => -(c)
             (Expression::Negation(lhs), Expression::Number(rhs)) => {
                 if let Expression::Number(inner_lhs) = lhs.term {
-                    let after  = Expression::negation(Expression::Number(inner_lhs.add(&rhs)));
+                    let mut after  = Expression::negation(Expression::Number(inner_lhs.add(&rhs)));
                     if let Some(explanation) = explanation {
                         explanation.rule_applied("Subtracting numbers", before, &after);
                     }
-                    Ok(after)
+                    after.simplify_with(options, explanation)
                 } else {
                     Expression::addition(vec![Expression::Negation(lhs), Expression::negation(Expression::Number(rhs))])
-                    .simplify(explanation)
+                    .simplify_with(options, explanation)
                 }
             }
             (lhs, rhs) => {
                 Expression::addition(vec![lhs, Expression::negation(rhs)])
-                    .simplify(explanation)
+                    .simplify_with(options, explanation)
             }
         }
     }
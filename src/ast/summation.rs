@@ -0,0 +1,921 @@
+use std::collections::HashMap;
+
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::{Signed, ToPrimitive, Zero};
+
+use crate::{
+    ast::Expr,
+    explanation::FormattingObserver,
+    polynomial::Polynomial,
+    prints::PrettyPrints,
+    solver::{Fraction, partial_fractions, polynomial_coefficients},
+};
+
+use super::{
+    Expression, SimplifyError, SimplifyOptions, constant::Constant, function::FunctionType, limit::Direction,
+    numeral::Numeral,
+};
+
+/// Largest number of terms written out when both bounds are numbers.
+pub(super) const MAX_EXPANDED_TERMS: i64 = 16;
+/// Highest power summed with Faulhaber's formula.
+const MAX_POWER_SUM_DEGREE: usize = 32;
+
+/// `Σ(k=a..b) f(k)`, the sum of `f(k)` for every integer `k` between the bounds.
+#[derive(Debug, PartialEq, Clone, PartialOrd, Eq, Ord, Hash)]
+pub struct Summation {
+    pub term: Expression,
+    pub variable: String,
+    pub lower: Expression,
+    /// Can be `inf` for a series
+    pub upper: Expression,
+    pub simplified: bool,
+}
+
+// Constructor
+impl Summation {
+    pub fn new(
+        term: Expression,
+        variable: String,
+        lower: Expression,
+        upper: Expression,
+        simplified: bool,
+    ) -> Self {
+        Self {
+            term,
+            variable,
+            lower,
+            upper,
+            simplified,
+        }
+    }
+}
+
+impl Expr for Summation {
+    fn simplify(
+        &mut self,
+        options: &SimplifyOptions,
+        explanation: &mut Option<Box<FormattingObserver>>,
+    ) -> Result<Expression, SimplifyError> {
+        let term = self.term.simplify_with(options, explanation)?;
+        let lower = self.lower.simplify_with(options, explanation)?;
+        let upper = self.upper.simplify_with(options, explanation)?;
+        match term.sum_with(&self.variable, &lower, &upper, options, explanation) {
+            // No closed form was found, the sum is kept
+            Err(SimplifyError::Unsupported) => Ok(Expression::Summation(Box::new(
+                Summation::new(term, self.variable.clone(), lower, upper, true),
+            ))),
+            result => result,
+        }
+    }
+
+    fn is_equal(&self, other: &Summation) -> bool {
+        self.variable == other.variable
+            && self.lower.is_equal(&other.lower)
+            && self.upper.is_equal(&other.upper)
+            && self.term.is_equal(&other.term)
+    }
+
+    fn contains_var(&self, variable: &str) -> bool {
+        // The index of the sum is bound
+        self.lower.contains_var(variable)
+            || self.upper.contains_var(variable)
+            || (self.variable != variable && self.term.contains_var(variable))
+    }
+
+    fn is_single(&self) -> bool {
+        false
+    }
+}
+
+impl std::fmt::Display for Summation {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "Σ({}={}..{}) ", self.variable, self.lower, self.upper)?;
+        if self.term.is_single() {
+            write!(f, "{}", self.term)
+        } else {
+            write!(f, "({})", self.term)
+        }
+    }
+}
+
+/// The index of a sum or a product and its bounds.
+pub(super) struct Bounds<'a> {
+    pub(super) variable: &'a str,
+    pub(super) lower: &'a Expression,
+    pub(super) upper: &'a Expression,
+    /// Options of every simplification on the way
+    pub(super) options: &'a SimplifyOptions,
+}
+
+impl Bounds<'_> {
+    /// Returns `true` if the lower bound is finite, the upper bound is not `-inf`
+    /// and both bounds are integers.
+    pub(super) fn is_valid(&self) -> bool {
+        let minus_infinity =
+            |expr: &Expression| matches!(expr, Expression::Negation(neg) if neg.term == Expression::infinity());
+        !matches!(self.lower, Expression::Constant(Constant::Infinity))
+            && !minus_infinity(self.lower)
+            && !minus_infinity(self.upper)
+            && is_integer_bound(self.lower)
+            && (self.is_infinite() || is_integer_bound(self.upper))
+    }
+
+    /// Returns `true` for a series, the upper bound is `inf`.
+    pub(super) fn is_infinite(&self) -> bool {
+        matches!(self.upper, Expression::Constant(Constant::Infinity))
+    }
+
+    /// Number of terms when both bounds are integers.
+    pub(super) fn count(&self) -> Option<i64> {
+        Some(integer_value(self.upper)? - integer_value(self.lower)? + 1)
+    }
+
+    /// `b - a + 1`
+    pub(super) fn length(&self) -> Expression {
+        Expression::addition(vec![
+            Expression::subtraction(self.upper.clone(), self.lower.clone()),
+            Expression::integer(1),
+        ])
+    }
+
+    /// Every term of the sum or product, if both bounds are integers.
+    pub(super) fn terms(&self, expr: &Expression) -> Option<Vec<Expression>> {
+        let lower = integer_value(self.lower)?;
+        let upper = integer_value(self.upper)?;
+        Some(
+            (lower..=upper)
+                .map(|k| self.at(expr, &Expression::integer(k)))
+                .collect(),
+        )
+    }
+
+    pub(super) fn at(&self, expr: &Expression, value: &Expression) -> Expression {
+        expr.substitute(&Expression::variable(self.variable), value)
+    }
+
+    /// `expr(k + n)`
+    pub(super) fn shifted(&self, expr: &Expression, n: i64) -> Expression {
+        self.at(
+            expr,
+            &Expression::addition(vec![Expression::variable(self.variable), Expression::integer(n)]),
+        )
+    }
+
+    /// `expr(b + n)`, the limit at infinity for a series.
+    pub(super) fn at_upper(&self, expr: &Expression, n: i64) -> Result<Expression, SimplifyError> {
+        if self.is_infinite() {
+            expr.find_limit_with(self.variable, self.upper, Direction::Both, self.options, &mut None)
+        } else {
+            Ok(self.at(
+                expr,
+                &Expression::addition(vec![self.upper.clone(), Expression::integer(n)]),
+            ))
+        }
+    }
+
+    /// `expr(a + n)`
+    pub(super) fn at_lower(&self, expr: &Expression, n: i64) -> Expression {
+        self.at(
+            expr,
+            &Expression::addition(vec![self.lower.clone(), Expression::integer(n)]),
+        )
+    }
+}
+
+impl Expression {
+    /// Sum of the expression for every integer `variable` from `lower` to `upper`.
+    ///
+    /// The sum is written out when the bounds are close numbers, otherwise a closed
+    /// form is looked for: constant, arithmetic and polynomial terms (Faulhaber's
+    /// formula), geometric series and telescoping sums. `upper` can be `inf` for a
+    /// geometric or a telescoping series.
+    ///
+    /// Returns `SimplifyError::Unsupported` if no closed form is found.
+    ///
+    /// ```
+    /// use sym_rustic::ast::Expression;
+    ///
+    /// let k = Expression::variable("k");
+    /// let sum = k
+    ///     .sum("k", &Expression::integer(1), &Expression::variable("n"), &mut None)
+    ///     .unwrap();
+    /// assert_eq!(sum.to_string(), "(n + (n^2))/2");
+    /// ```
+    pub fn sum(
+        &self,
+        variable: &str,
+        lower: &Expression,
+        upper: &Expression,
+        explanation: &mut Option<Box<FormattingObserver>>,
+    ) -> Result<Expression, SimplifyError> {
+        self.sum_with(variable, lower, upper, &SimplifyOptions::default(), explanation)
+    }
+
+    /// Returns the sum like `Expression::sum`, every step is simplified with `options`.
+    pub fn sum_with(
+        &self,
+        variable: &str,
+        lower: &Expression,
+        upper: &Expression,
+        options: &SimplifyOptions,
+        explanation: &mut Option<Box<FormattingObserver>>,
+    ) -> Result<Expression, SimplifyError> {
+        let bounds = Bounds {
+            variable,
+            lower,
+            upper,
+            options,
+        };
+        if lower.contains_var(variable) || upper.contains_var(variable) || !bounds.is_valid() {
+            return Err(SimplifyError::Unsupported);
+        }
+
+        // Steps are only kept when a closed form is found
+        let mut trial = explanation.clone();
+        let term = self.clone().simplify_with(options, &mut trial)?;
+        let result = term.sum_terms(&bounds, &mut trial)?.simplify_with(options, &mut trial)?;
+        *explanation = trial;
+        Ok(result)
+    }
+
+    pub(super) fn sum_terms(
+        &self,
+        bounds: &Bounds,
+        explanation: &mut Option<Box<FormattingObserver>>,
+    ) -> Result<Expression, SimplifyError> {
+        let before = Expression::summation(
+            self.clone(),
+            bounds.variable,
+            bounds.lower.clone(),
+            bounds.upper.clone(),
+        );
+        let (rule, after) = if let Some(count) = bounds.count()
+            && count <= MAX_EXPANDED_TERMS
+        {
+            let after = match bounds.terms(self) {
+                Some(terms) if !terms.is_empty() => Expression::addition(terms),
+                _ => Expression::integer(0),
+            };
+            ("Expand the sum", after)
+        } else if !self.contains_var(bounds.variable) {
+            if bounds.is_infinite() {
+                return Err(SimplifyError::Unsupported);
+            }
+            (
+                "Sum of a constant\nΣ(k=a..b) c => (b - a + 1)*c",
+                Expression::multiplication(vec![bounds.length(), self.clone()]),
+            )
+        } else if let Some(result) = self.polynomial_sum(bounds)? {
+            result
+        } else if let Some(result) = self.geometric_sum(bounds)? {
+            result
+        } else if let Some(after) = self.telescoping_sum(bounds)? {
+            ("Telescoping sum\nΣ(k=a..b) (f(k) - f(k+1)) => f(a) - f(b+1)", after)
+        } else {
+            return self.split_sum(bounds, explanation);
+        };
+
+        if let Some(explanation) = explanation {
+            explanation.rule_applied(rule, &before, &after);
+        }
+        Ok(after)
+    }
+
+    /// Sum of a polynomial in the index, with Faulhaber's formula for each power.
+    fn polynomial_sum(
+        &self,
+        bounds: &Bounds,
+    ) -> Result<Option<(&'static str, Expression)>, SimplifyError> {
+        if bounds.is_infinite() {
+            return Ok(None);
+        }
+        let Ok(coefficients) = polynomial_coefficients(self, bounds.variable) else {
+            return Ok(None);
+        };
+        if coefficients.len() > MAX_POWER_SUM_DEGREE + 1 {
+            return Ok(None);
+        }
+
+        if coefficients.len() <= 2 {
+            let after = Expression::division(
+                Expression::multiplication(vec![
+                    bounds.length(),
+                    Expression::addition(vec![
+                        bounds.at(self, bounds.lower),
+                        bounds.at(self, bounds.upper),
+                    ]),
+                ]),
+                Expression::integer(2),
+            );
+            return Ok(Some((
+                "Sum of an arithmetic series\nΣ(k=a..b) f(k) => (b - a + 1)*(f(a) + f(b))/2",
+                after,
+            )));
+        }
+
+        // Σ(k=a..b) k^p = S_p(b) - S_p(a - 1)
+        let bernoulli = bernoulli_numbers(coefficients.len());
+        let before_lower =
+            Expression::subtraction(bounds.lower.clone(), Expression::integer(1));
+        let terms = coefficients
+            .into_iter()
+            .enumerate()
+            .map(|(power, coefficient)| {
+                Expression::multiplication(vec![
+                    coefficient,
+                    Expression::subtraction(
+                        power_sum(power, bounds.upper, &bernoulli),
+                        power_sum(power, &before_lower, &bernoulli),
+                    ),
+                ])
+            })
+            .collect();
+        Ok(Some((
+            "Faulhaber's formula for the sums of powers\nΣ(k=1..n) k^p => Σ(j=0..p) C(p+1, j)*B_j*n^(p+1-j)/(p+1)",
+            Expression::addition(terms),
+        )))
+    }
+
+    /// Sum of `c*r^k`, the ratio `r` of two consecutive terms is constant.
+    fn geometric_sum(
+        &self,
+        bounds: &Bounds,
+    ) -> Result<Option<(&'static str, Expression)>, SimplifyError> {
+        let Some(mut ratio) = geometric_ratio(self, bounds.variable) else {
+            return Ok(None);
+        };
+        let ratio = ratio.simplify_with(bounds.options, &mut None)?;
+        if matches!(&ratio, Expression::Number(n) if n.is_one()) {
+            return Ok(None);
+        }
+        let first = bounds.at(self, bounds.lower);
+
+        if bounds.is_infinite() {
+            // Only a ratio smaller than one converges
+            let converges = ratio
+                .evaluate(&HashMap::new())
+                .is_ok_and(|value| value.abs() < 1.0);
+            if !converges {
+                return Ok(None);
+            }
+            let after = Expression::division(
+                first,
+                Expression::subtraction(Expression::integer(1), ratio),
+            );
+            return Ok(Some((
+                "Sum of a geometric series\nΣ(k=a..inf) c*r^k => c*r^a/(1 - r)",
+                after,
+            )));
+        }
+
+        let after = Expression::division(
+            Expression::multiplication(vec![
+                first,
+                Expression::subtraction(
+                    Expression::exponentiation(ratio.clone(), bounds.length()),
+                    Expression::integer(1),
+                ),
+            ]),
+            Expression::subtraction(ratio, Expression::integer(1)),
+        );
+        Ok(Some((
+            "Sum of a geometric series\nΣ(k=a..b) c*r^k => c*r^a*(r^(b - a + 1) - 1)/(r - 1)",
+            after,
+        )))
+    }
+
+    /// Sum of `f(k) - f(k+1)`, every term but the first and the last cancel.
+    ///
+    /// A rational term is first written as partial fractions, `1/(k*(k + 1))` is
+    /// `1/k - 1/(k + 1)`.
+    fn telescoping_sum(&self, bounds: &Bounds) -> Result<Option<Expression>, SimplifyError> {
+        if let Some((left, right)) = difference(self) {
+            let shifted = |expr: &Expression| bounds.shifted(expr, 1).simplify_with(bounds.options, &mut None);
+            // f(k) - f(k+1)
+            if shifted(&left)?.is_equal(&right.clone().simplify_with(bounds.options, &mut None)?) {
+                return Ok(Some(Expression::subtraction(
+                    bounds.at_lower(&left, 0),
+                    bounds.at_upper(&left, 1)?,
+                )));
+            }
+            // f(k+1) - f(k)
+            if shifted(&right)?.is_equal(&left.clone().simplify_with(bounds.options, &mut None)?) {
+                return Ok(Some(Expression::subtraction(
+                    bounds.at_upper(&right, 1)?,
+                    bounds.at_lower(&right, 0),
+                )));
+            }
+        }
+
+        let Some(fractions) = simple_fractions(self, bounds.variable) else {
+            return Ok(None);
+        };
+        // c_i/(k - r_i) = c_i*g(k + s_i) with g(k) = 1/(k - r_0)
+        let root = fractions[0].1.clone();
+        let mut shifts = Vec::new();
+        for (_, other) in &fractions {
+            let shift = Expression::subtraction(root.clone(), other.clone()).simplify_with(bounds.options, &mut None)?;
+            match integer_value(&shift) {
+                Some(shift) if shift.abs() <= MAX_EXPANDED_TERMS => shifts.push(shift),
+                _ => return Ok(None),
+            }
+        }
+        let total = Expression::addition(fractions.iter().map(|(coeff, _)| coeff.clone()).collect())
+            .simplify_with(bounds.options, &mut None)?;
+        if !matches!(&total, Expression::Number(n) if n.is_zero()) {
+            return Ok(None);
+        }
+
+        // Σ(k=a..b) g(k + s) - g(k + m) with m the smallest shift keeps the terms
+        // g(b + m + 1), ..., g(b + s) and -g(a + m), ..., -g(a + s - 1)
+        let g = Expression::division(
+            Expression::integer(1),
+            match root {
+                Expression::Number(n) if n.is_zero() => Expression::variable(bounds.variable),
+                root => Expression::subtraction(Expression::variable(bounds.variable), root),
+            },
+        );
+        let smallest = shifts.iter().copied().min().unwrap_or_default();
+        let mut terms = Vec::new();
+        for ((coeff, _), shift) in fractions.iter().zip(&shifts) {
+            for t in 0..(shift - smallest) {
+                terms.push(Expression::multiplication(vec![
+                    coeff.clone(),
+                    Expression::subtraction(
+                        bounds.at_upper(&g, smallest + 1 + t)?,
+                        bounds.at_lower(&g, smallest + t),
+                    ),
+                ]));
+            }
+        }
+        Ok(Some(match terms.len() {
+            0 => Expression::integer(0),
+            _ => Expression::addition(terms),
+        }))
+    }
+
+    /// The sum is linear, constant factors are taken out and a sum of terms is split.
+    fn split_sum(
+        &self,
+        bounds: &Bounds,
+        explanation: &mut Option<Box<FormattingObserver>>,
+    ) -> Result<Expression, SimplifyError> {
+        let before = Expression::summation(
+            self.clone(),
+            bounds.variable,
+            bounds.lower.clone(),
+            bounds.upper.clone(),
+        );
+        let sum = |expr: &Expression| {
+            Expression::summation(
+                expr.clone(),
+                bounds.variable,
+                bounds.lower.clone(),
+                bounds.upper.clone(),
+            )
+        };
+
+        let (factor, rest) = match self {
+            Expression::Negation(neg) => (Expression::integer(-1), neg.term.clone()),
+            Expression::Multiplication(mul) => {
+                let (constant, rest): (Vec<_>, Vec<_>) = mul
+                    .terms
+                    .iter()
+                    .cloned()
+                    .partition(|term| !term.contains_var(bounds.variable));
+                if constant.is_empty() {
+                    return Err(SimplifyError::Unsupported);
+                }
+                (
+                    Expression::multiplication(constant),
+                    Expression::multiplication(rest),
+                )
+            }
+            Expression::Division(div) if !div.den.contains_var(bounds.variable) => (
+                Expression::division(Expression::integer(1), div.den.clone()),
+                div.num.clone(),
+            ),
+            Expression::Addition(add) => {
+                if let Some(explanation) = explanation {
+                    let after = Expression::addition(add.terms.iter().map(sum).collect());
+                    explanation.rule_applied(
+                        "Sum of a sum is given by\nΣ(k=a..b) (f + g) => Σ(k=a..b) f + Σ(k=a..b) g",
+                        &before,
+                        &after,
+                    );
+                }
+                return Ok(Expression::addition(
+                    add.terms
+                        .iter()
+                        .map(|term| term.sum_terms(bounds, explanation))
+                        .collect::<Result<_, _>>()?,
+                ));
+            }
+            Expression::Subtraction(sub) => {
+                if let Some(explanation) = explanation {
+                    let after = Expression::subtraction(sum(&sub.left), sum(&sub.right));
+                    explanation.rule_applied(
+                        "Sum of a sum is given by\nΣ(k=a..b) (f - g) => Σ(k=a..b) f - Σ(k=a..b) g",
+                        &before,
+                        &after,
+                    );
+                }
+                return Ok(Expression::subtraction(
+                    sub.left.sum_terms(bounds, explanation)?,
+                    sub.right.sum_terms(bounds, explanation)?,
+                ));
+            }
+            _ => return Err(SimplifyError::Unsupported),
+        };
+
+        if let Some(explanation) = explanation {
+            let after = Expression::multiplication(vec![factor.clone(), sum(&rest)]);
+            explanation.rule_applied(
+                "A constant factor is taken out of the sum\nΣ(k=a..b) c*f => c*Σ(k=a..b) f",
+                &before,
+                &after,
+            );
+        }
+        Ok(Expression::multiplication(vec![
+            factor,
+            rest.sum_terms(bounds, explanation)?,
+        ]))
+    }
+}
+
+/// Value of an integer expression.
+pub(super) fn integer_value(expr: &Expression) -> Option<i64> {
+    match expr {
+        Expression::Number(Numeral::Integer(n)) => n.to_i64(),
+        Expression::Negation(neg) => integer_value(&neg.term).map(|n| -n),
+        _ => None,
+    }
+}
+
+/// Returns `true` for an integer or a polynomial with integer coefficients in
+/// variables, like `2*n - 1`, the variables of a bound stand for integers.
+fn is_integer_bound(expr: &Expression) -> bool {
+    if integer_value(expr).is_some() {
+        return true;
+    }
+    Polynomial::from_expression(expr).is_ok_and(|polynomial| {
+        polynomial
+            .generators()
+            .iter()
+            .all(|generator| matches!(generator, Expression::Variable(_)))
+            && polynomial
+                .terms()
+                .all(|(_, coefficient)| matches!(coefficient, Numeral::Integer(_)))
+    })
+}
+
+/// Ratio `f(k+1)/f(k)` if it doesn't depend on `k`, like `f(k) = c*r^(2*k)`.
+pub(super) fn geometric_ratio(expr: &Expression, variable: &str) -> Option<Expression> {
+    match expr {
+        _ if !expr.contains_var(variable) => Some(Expression::integer(1)),
+        Expression::Exponentiation(exp) if !exp.base.contains_var(variable) => {
+            match polynomial_coefficients(&exp.expo, variable).ok()?.as_slice() {
+                [_, slope] => Some(Expression::exponentiation(exp.base.clone(), slope.clone())),
+                _ => None,
+            }
+        }
+        Expression::Function(fun) if fun.name == FunctionType::Exp => {
+            match polynomial_coefficients(&fun.args[0], variable).ok()?.as_slice() {
+                [_, slope] => Some(Expression::exp(slope.clone())),
+                _ => None,
+            }
+        }
+        Expression::Negation(neg) => geometric_ratio(&neg.term, variable),
+        Expression::Multiplication(mul) => Some(Expression::multiplication(
+            mul.terms
+                .iter()
+                .map(|term| geometric_ratio(term, variable))
+                .collect::<Option<_>>()?,
+        )),
+        Expression::Division(div) => Some(Expression::division(
+            geometric_ratio(&div.num, variable)?,
+            geometric_ratio(&div.den, variable)?,
+        )),
+        _ => None,
+    }
+}
+
+/// `f - g` as the pair `(f, g)`.
+fn difference(expr: &Expression) -> Option<(Expression, Expression)> {
+    match expr {
+        Expression::Subtraction(sub) => Some((sub.left.clone(), sub.right.clone())),
+        Expression::Addition(add) => match add.terms.as_slice() {
+            [left, Expression::Negation(right)] | [Expression::Negation(right), left] => {
+                Some((left.clone(), right.term.clone()))
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// The term as a sum of `c_i/(k - r_i)`, returns the pairs `(c_i, r_i)`.
+fn simple_fractions(expr: &Expression, variable: &str) -> Option<Vec<(Expression, Expression)>> {
+    let negate = |fractions: Vec<(Expression, Expression)>| {
+        fractions
+            .into_iter()
+            .map(|(coeff, root)| (Expression::negation(coeff), root))
+            .collect::<Vec<_>>()
+    };
+    let fractions = match expr {
+        Expression::Addition(add) => add
+            .terms
+            .iter()
+            .map(|term| simple_fractions(term, variable))
+            .collect::<Option<Vec<_>>>()?
+            .concat(),
+        Expression::Subtraction(sub) => [
+            simple_fractions(&sub.left, variable)?,
+            negate(simple_fractions(&sub.right, variable)?),
+        ]
+        .concat(),
+        Expression::Negation(neg) => negate(simple_fractions(&neg.term, variable)?),
+        Expression::Multiplication(mul) => {
+            let (constant, rest): (Vec<_>, Vec<_>) = mul
+                .terms
+                .iter()
+                .cloned()
+                .partition(|term| !term.contains_var(variable));
+            if constant.is_empty() {
+                return None;
+            }
+            let constant = Expression::multiplication(constant);
+            simple_fractions(&Expression::multiplication(rest), variable)?
+                .into_iter()
+                .map(|(coeff, root)| {
+                    (Expression::multiplication(vec![constant.clone(), coeff]), root)
+                })
+                .collect()
+        }
+        Expression::Division(div) if div.den.contains_var(variable) => {
+            let decomposition = partial_fractions(&div.num, &div.den, variable)?;
+            let no_polynomial = decomposition
+                .polynomial
+                .iter()
+                .all(|coeff| matches!(coeff, Expression::Number(n) if n.is_zero()));
+            if !no_polynomial {
+                return None;
+            }
+            decomposition
+                .fractions
+                .into_iter()
+                .map(|fraction| match fraction {
+                    Fraction::Linear {
+                        coeff,
+                        root,
+                        power: 1,
+                    } => Some((coeff, root)),
+                    _ => None,
+                })
+                .collect::<Option<_>>()?
+        }
+        _ => return None,
+    };
+    (!fractions.is_empty()).then_some(fractions)
+}
+
+/// Bernoulli numbers `B_0, ..., B_(count-1)` with `B_1 = 1/2`, as reduced fractions.
+fn bernoulli_numbers(count: usize) -> Vec<(BigInt, BigInt)> {
+    let mut numbers: Vec<(BigInt, BigInt)> = Vec::with_capacity(count);
+    for m in 0..count {
+        // B_m = 1 - Σ(j=0..m-1) C(m, j)*B_j/(m - j + 1)
+        let mut value = (BigInt::from(1), BigInt::from(1));
+        for (j, (num, den)) in numbers.iter().enumerate() {
+            let coefficient = binomial(m, j);
+            let term = (coefficient * num, den * BigInt::from(m - j + 1));
+            value = reduce(
+                &value.0 * &term.1 - &term.0 * &value.1,
+                &value.1 * &term.1,
+            );
+        }
+        numbers.push(value);
+    }
+    numbers
+}
+
+/// `S_p(n) = 1^p + 2^p + ... + n^p` as a polynomial in `n`.
+fn power_sum(power: usize, n: &Expression, bernoulli: &[(BigInt, BigInt)]) -> Expression {
+    let terms = (0..=power)
+        .filter(|j| !bernoulli[*j].0.is_zero())
+        .map(|j| {
+            let (num, den) = reduce(
+                binomial(power + 1, j) * &bernoulli[j].0,
+                &bernoulli[j].1 * BigInt::from(power + 1),
+            );
+            Expression::multiplication(vec![
+                fraction(num, den),
+                Expression::exponentiation(n.clone(), Expression::integer(power + 1 - j)),
+            ])
+        })
+        .collect();
+    Expression::addition(terms)
+}
+
+fn binomial(n: usize, k: usize) -> BigInt {
+    (0..k).fold(BigInt::from(1), |acc, i| acc * (n - i) / (i + 1))
+}
+
+fn reduce(num: BigInt, den: BigInt) -> (BigInt, BigInt) {
+    let gcd = num.gcd(&den);
+    let (num, den) = (num / &gcd, den / &gcd);
+    if den.is_negative() {
+        (-num, -den)
+    } else {
+        (num, den)
+    }
+}
+
+fn fraction(num: BigInt, den: BigInt) -> Expression {
+    if den == BigInt::from(1) {
+        Numeral::Integer(num).into_expression()
+    } else {
+        Numeral::Rational(num, den).into_expression()
+    }
+}
+
+/// Positions of `Σ` or `Π` with the bounds below and above, and the term on its right.
+pub(super) struct BigOperator<'a> {
+    /// Rows of the symbol from the top
+    pub(super) symbol: &'a [&'a str],
+    /// Row of the symbol on the baseline of the term, from the top
+    pub(super) baseline: usize,
+    pub(super) term: &'a Expression,
+    pub(super) variable: &'a str,
+    pub(super) lower: &'a Expression,
+    pub(super) upper: &'a Expression,
+}
+
+impl BigOperator<'_> {
+    fn symbol_length(&self) -> usize {
+        self.symbol
+            .iter()
+            .map(|row| row.chars().count())
+            .max()
+            .unwrap_or_default()
+    }
+
+    fn operator_length(&self, memoization: &mut HashMap<Expression, (usize, usize)>) -> usize {
+        let lower = self.variable.chars().count() + 1 + self.lower.get_length(memoization);
+        self.symbol_length()
+            .max(lower)
+            .max(self.upper.get_length(memoization))
+    }
+
+    /// A sum is put in parenthesis so it is not read as a sum with the operator
+    fn needs_parenthesis(&self) -> bool {
+        matches!(self.term, Expression::Addition(_) | Expression::Subtraction(_))
+    }
+
+    pub(super) fn calculate_tree(&self, name: &str, indent: usize) -> String {
+        let next_indent = indent + 2;
+        let next_indent_str = " ".repeat(next_indent);
+
+        format!(
+            "{}:\n{}{}\n{}{} from {}\n{}to {}",
+            name,
+            next_indent_str,
+            self.term.calculate_tree(next_indent),
+            next_indent_str,
+            self.variable,
+            self.lower.calculate_tree(next_indent),
+            next_indent_str,
+            self.upper.calculate_tree(next_indent),
+        )
+    }
+
+    pub(super) fn calculate_positions(
+        &self,
+        memoization: &mut HashMap<Expression, (usize, usize)>,
+        position: &mut Vec<(String, (usize, usize))>,
+        prev_pos: (usize, usize),
+    ) {
+        let baseline = prev_pos.0 + self.get_below_height(memoization);
+        let operator_length = self.operator_length(memoization);
+
+        //  n
+        // ___
+        // ╲
+        // ╱
+        // ‾‾‾
+        // k=1
+        let column = prev_pos.1 + (operator_length - self.symbol_length()) / 2;
+        for (i, row) in self.symbol.iter().enumerate() {
+            for (j, c) in row.chars().enumerate() {
+                if c != ' ' {
+                    position.push((c.to_string(), (baseline + self.baseline - i, column + j)));
+                }
+            }
+        }
+
+        let upper_length = self.upper.get_length(memoization);
+        self.upper.calculate_positions(
+            memoization,
+            position,
+            (
+                baseline + self.baseline + 1,
+                prev_pos.1 + (operator_length - upper_length) / 2,
+            ),
+        );
+
+        let lower_length = self.variable.chars().count() + 1 + self.lower.get_length(memoization);
+        let symbol_below = self.symbol.len() - 1 - self.baseline;
+        let lower_bottom = baseline - symbol_below - self.lower.get_height(memoization);
+        let row = lower_bottom + self.lower.get_below_height(memoization);
+        let mut column = prev_pos.1 + (operator_length - lower_length) / 2;
+        for c in format!("{}=", self.variable).chars() {
+            position.push((c.to_string(), (row, column)));
+            column += 1;
+        }
+        self.lower
+            .calculate_positions(memoization, position, (lower_bottom, column));
+
+        // term
+        let height = self.term.get_height(memoization);
+        let mut pos = (
+            baseline - self.term.get_below_height(memoization),
+            prev_pos.1 + operator_length + 1,
+        );
+        let parenthesis = self.needs_parenthesis();
+        if parenthesis {
+            Expression::calculate_parenthesis(position, pos, true, height);
+            pos.1 += 1;
+        }
+        self.term.calculate_positions(memoization, position, pos);
+        pos.1 += self.term.get_length(memoization);
+        if parenthesis {
+            Expression::calculate_parenthesis(position, pos, false, height);
+        }
+    }
+
+    pub(super) fn get_below_height(
+        &self,
+        memoization: &mut HashMap<Expression, (usize, usize)>,
+    ) -> usize {
+        let symbol_below = self.symbol.len() - 1 - self.baseline;
+        self.term
+            .get_below_height(memoization)
+            .max(symbol_below + self.lower.get_height(memoization))
+    }
+
+    pub(super) fn get_height(&self, memoization: &mut HashMap<Expression, (usize, usize)>) -> usize {
+        let term_above =
+            self.term.get_height(memoization) - self.term.get_below_height(memoization);
+        let symbol_above = self.baseline + 1 + self.upper.get_height(memoization);
+        self.get_below_height(memoization) + term_above.max(symbol_above)
+    }
+
+    pub(super) fn get_length(&self, memoization: &mut HashMap<Expression, (usize, usize)>) -> usize {
+        let parenthesis = if self.needs_parenthesis() { 2 } else { 0 };
+        self.operator_length(memoization) + 1 + self.term.get_length(memoization) + parenthesis
+    }
+}
+
+const SIGMA: [&str; 4] = ["___", "╲  ", "╱  ", "‾‾‾"];
+
+impl Summation {
+    fn big_operator(&self) -> BigOperator<'_> {
+        BigOperator {
+            symbol: &SIGMA,
+            baseline: 2,
+            term: &self.term,
+            variable: &self.variable,
+            lower: &self.lower,
+            upper: &self.upper,
+        }
+    }
+}
+
+impl PrettyPrints for Summation {
+    fn calculate_tree(&self, indent: usize) -> String {
+        self.big_operator().calculate_tree("Summation", indent)
+    }
+
+    fn calculate_positions(
+        &self,
+        memoization: &mut HashMap<Expression, (usize, usize)>,
+        position: &mut Vec<(String, (usize, usize))>,
+        prev_pos: (usize, usize),
+    ) {
+        self.big_operator()
+            .calculate_positions(memoization, position, prev_pos)
+    }
+
+    fn get_below_height(&self, memoization: &mut HashMap<Expression, (usize, usize)>) -> usize {
+        self.big_operator().get_below_height(memoization)
+    }
+
+    fn get_height(&self, memoization: &mut HashMap<Expression, (usize, usize)>) -> usize {
+        self.big_operator().get_height(memoization)
+    }
+
+    fn get_length(&self, memoization: &mut HashMap<Expression, (usize, usize)>) -> usize {
+        self.big_operator().get_length(memoization)
+    }
+}
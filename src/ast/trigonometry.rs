@@ -0,0 +1,690 @@
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::{One, ToPrimitive};
+
+use crate::{explanation::FormattingObserver, polynomial::Polynomial};
+
+use super::{
+    Expression, SimplifyError,
+    constant::Constant,
+    function::{Function, FunctionType},
+    numeral::Numeral,
+};
+
+/// Maximum `n` rewritten as a sum of angles by `expand_trig` in `sin(n*a)`.
+const MAX_ANGLE_MULTIPLE: u32 = 16;
+
+impl Function {
+    /// Exact value of `sin`, `cos` and `tan` at the multiples of `pi/6` and
+    /// `pi/4`, like `sin(pi/6) => 1/2`.
+    pub(super) fn trig_value(expr: &Expression) -> Option<(&'static str, Expression)> {
+        let Expression::Function(function) = expr else {
+            return None;
+        };
+        let [arg] = function.args.as_slice() else {
+            return None;
+        };
+        if !matches!(
+            function.name,
+            FunctionType::Sin | FunctionType::Cos | FunctionType::Tan
+        ) {
+            return None;
+        }
+        let twelfths = twelfths_of_pi(arg)?;
+        let value = match function.name {
+            FunctionType::Sin => sine(twelfths),
+            FunctionType::Cos => sine((twelfths + 6) % 24),
+            _ => tangent(twelfths % 12)?,
+        };
+        Some(("Exact value of the trigonometric function at a known angle", value))
+    }
+
+    /// `sin(-a) => -sin(a)` and `cos(-a) => cos(a)`.
+    pub(super) fn parity(expr: &Expression) -> Option<(&'static str, Expression)> {
+        let Expression::Function(function) = expr else {
+            return None;
+        };
+        let [Expression::Negation(neg)] = function.args.as_slice() else {
+            return None;
+        };
+        let positive = Expression::function(function.name.clone(), vec![neg.term.clone()]);
+        match function.name {
+            FunctionType::Sin
+            | FunctionType::Tan
+            | FunctionType::Asin
+            | FunctionType::Atan
+            | FunctionType::Sinh
+            | FunctionType::Tanh
+            | FunctionType::Asinh
+            | FunctionType::Atanh => Some((
+                "Odd function\nf(-a) => -f(a)",
+                Expression::negation(positive),
+            )),
+            FunctionType::Cos | FunctionType::Cosh | FunctionType::Abs => {
+                Some(("Even function\nf(-a) => f(a)", positive))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// The angle `arg` as a number of twelfths of `pi` in `[0, 24)`, if it is a
+/// multiple of `pi/6` or of `pi/4`.
+fn twelfths_of_pi(arg: &Expression) -> Option<u32> {
+    let pi = Expression::Constant(Constant::Pi);
+    let polynomial = Polynomial::from_expression(arg).ok()?;
+    if polynomial.generators() != std::slice::from_ref(&pi)
+        || polynomial.degree(&pi) != Some(1)
+        || !polynomial.coefficient(&pi, 0).is_zero()
+    {
+        return None;
+    }
+    let (num, den) = match polynomial.coefficient(&pi, 1).as_constant()? {
+        Numeral::Integer(n) => (n, BigInt::one()),
+        Numeral::Rational(n, d) => (n, d),
+        Numeral::Float(_) => return None,
+    };
+    let den = den.to_u32()?;
+    if ![1, 2, 3, 4, 6].contains(&den) {
+        return None;
+    }
+    (num * (12 / den)).mod_floor(&BigInt::from(24)).to_u32()
+}
+
+/// `sin(k*pi/12)` for `k` in `[0, 24)` a multiple of 2 or 3.
+fn sine(twelfths: u32) -> Expression {
+    let (negative, angle) = if twelfths < 12 {
+        (false, twelfths)
+    } else {
+        (true, twelfths - 12)
+    };
+    // sin(pi - a) = sin(a)
+    let value = match angle.min(12 - angle) {
+        0 => Expression::integer(0),
+        2 => Expression::rational(1, 2),
+        3 => half_root(2),
+        4 => half_root(3),
+        _ => Expression::integer(1),
+    };
+    if negative {
+        Expression::negation(value)
+    } else {
+        value
+    }
+}
+
+/// `tan(k*pi/12)` for `k` in `[0, 12)` a multiple of 2 or 3, `tan(pi/2)` is undefined.
+fn tangent(twelfths: u32) -> Option<Expression> {
+    let (negative, angle) = if twelfths <= 6 {
+        (false, twelfths)
+    } else {
+        (true, 12 - twelfths)
+    };
+    let root_three = Expression::function(FunctionType::Sqrt, vec![Expression::integer(3)]);
+    let value = match angle {
+        0 => Expression::integer(0),
+        2 => Expression::division(root_three, Expression::integer(3)),
+        3 => Expression::integer(1),
+        4 => root_three,
+        _ => return None,
+    };
+    Some(if negative {
+        Expression::negation(value)
+    } else {
+        value
+    })
+}
+
+/// `sqrt(n)/2`
+fn half_root(n: u32) -> Expression {
+    Expression::division(
+        Expression::function(FunctionType::Sqrt, vec![Expression::integer(n)]),
+        Expression::integer(2),
+    )
+}
+
+impl Expression {
+    /// `c*sin(a)^2 + c*cos(a)^2 => c`, the two terms are already simplified.
+    pub(super) fn pythagorean(lhs: &Expression, rhs: &Expression) -> Option<Expression> {
+        let (lhs_negative, lhs_name, lhs_arg, lhs_rest) = trig_square(lhs)?;
+        let (rhs_negative, rhs_name, rhs_arg, rhs_rest) = trig_square(rhs)?;
+        if lhs_negative != rhs_negative
+            || lhs_name == rhs_name
+            || !lhs_arg.is_equal(&rhs_arg)
+            || !Expression::compare_expression_vectors(&lhs_rest, &rhs_rest)
+        {
+            return None;
+        }
+        let coefficient = match lhs_rest.len() {
+            0 => Expression::integer(1),
+            1 => lhs_rest[0].clone(),
+            _ => Expression::multiplication(lhs_rest),
+        };
+        Some(if lhs_negative {
+            Expression::negation(coefficient)
+        } else {
+            coefficient
+        })
+    }
+
+    /// Rewrite the sines, cosines and tangents of sums and of integer
+    /// multiples with the angle addition formulas, then simplify.
+    ///
+    /// # Examples
+    /// ```
+    /// use sym_rustic::ast::{Expression, function::FunctionType};
+    ///
+    /// let x = Expression::variable("x");
+    /// let y = Expression::variable("y");
+    /// let expr = Expression::function(FunctionType::Sin, vec![Expression::addition(vec![x, y])]);
+    /// assert_eq!(
+    ///     expr.expand_trig(&mut None).unwrap().to_string(),
+    ///     "(sin(x) * cos(y)) + (cos(x) * sin(y))"
+    /// );
+    /// ```
+    pub fn expand_trig(
+        &self,
+        explanation: &mut Option<Box<FormattingObserver>>,
+    ) -> Result<Expression, SimplifyError> {
+        let simplified = self.clone().simplify(explanation)?;
+        simplified.expand_angles(explanation)?.simplify(explanation)
+    }
+
+    /// Combine the products and the sums of sines and cosines with the
+    /// Pythagorean identity, the double angle and the angle addition formulas,
+    /// the inverse of `expand_trig`.
+    ///
+    /// # Examples
+    /// ```
+    /// use sym_rustic::ast::{Expression, function::FunctionType};
+    ///
+    /// let x = Expression::variable("x");
+    /// let sin = Expression::function(FunctionType::Sin, vec![x.clone()]);
+    /// let cos = Expression::function(FunctionType::Cos, vec![x]);
+    /// let expr = Expression::multiplication(vec![Expression::integer(2), sin, cos]);
+    /// assert_eq!(expr.trigsimp(&mut None).unwrap().to_string(), "sin(2 * x)");
+    /// ```
+    pub fn trigsimp(
+        &self,
+        explanation: &mut Option<Box<FormattingObserver>>,
+    ) -> Result<Expression, SimplifyError> {
+        let simplified = self.clone().simplify(explanation)?;
+        simplified.combine_trig(explanation)?.simplify(explanation)
+    }
+
+    fn expand_angles(
+        &self,
+        explanation: &mut Option<Box<FormattingObserver>>,
+    ) -> Result<Expression, SimplifyError> {
+        let expr = self.map_children(|child| child.expand_angles(explanation))?;
+        let Expression::Function(function) = &expr else {
+            return Ok(expr);
+        };
+        let [arg] = function.args.as_slice() else {
+            return Ok(expr);
+        };
+        let name = function.name.clone();
+        if !matches!(name, FunctionType::Sin | FunctionType::Cos | FunctionType::Tan) {
+            return Ok(expr);
+        }
+        let (rule, after) = match split_angle(arg) {
+            Some(Angle::Double(a)) => double_angle(name, a),
+            Some(Angle::Sum(a, b)) => angle_addition(name, a, b),
+            None => return Ok(expr),
+        };
+        if let Some(explanation) = explanation {
+            explanation.rule_applied(rule, &expr, &after);
+        }
+        // The angles of the result can be sums again
+        after.expand_angles(explanation)
+    }
+
+    fn combine_trig(
+        &self,
+        explanation: &mut Option<Box<FormattingObserver>>,
+    ) -> Result<Expression, SimplifyError> {
+        let expr = self.map_children(|child| child.combine_trig(explanation))?;
+        match &expr {
+            // sin(a)/cos(a) => tan(a)
+            Expression::Division(div)
+                if let (Some((FunctionType::Sin, num)), Some((FunctionType::Cos, den))) =
+                    (trig_argument(&div.num), trig_argument(&div.den))
+                    && num.is_equal(den) =>
+            {
+                let after = Expression::function(FunctionType::Tan, vec![num.clone()]);
+                if let Some(explanation) = explanation {
+                    explanation.rule_applied("Quotient identity\nsin(a)/cos(a) => tan(a)", &expr, &after);
+                }
+                Ok(after)
+            }
+            Expression::Addition(_)
+            | Expression::Subtraction(_)
+            | Expression::Multiplication(_)
+            | Expression::Negation(_) => match Polynomial::from_expression(&expr) {
+                Ok(polynomial) => Ok(combine_polynomial(polynomial, &expr, explanation)),
+                Err(_) => Ok(expr),
+            },
+            _ => Ok(expr),
+        }
+    }
+}
+
+/// The sign, the function, the angle and the other factors of `sin(a)^2` or
+/// `cos(a)^2` times some factors.
+fn trig_square(expr: &Expression) -> Option<(bool, FunctionType, Expression, Vec<Expression>)> {
+    let square = |expr: &Expression| match expr {
+        Expression::Exponentiation(exp)
+            if matches!(&exp.expo, Expression::Number(n) if n.is_integer(2)) =>
+        {
+            match trig_argument(&exp.base) {
+                Some((name @ (FunctionType::Sin | FunctionType::Cos), arg)) => {
+                    Some((name, arg.clone()))
+                }
+                _ => None,
+            }
+        }
+        _ => None,
+    };
+    match expr {
+        Expression::Negation(neg) => trig_square(&neg.term)
+            .map(|(negative, name, arg, rest)| (!negative, name, arg, rest)),
+        Expression::Multiplication(mul) => mul.terms.iter().enumerate().find_map(|(i, term)| {
+            let (name, arg) = square(term)?;
+            let mut rest = mul.terms.clone();
+            rest.remove(i);
+            Some((false, name, arg, rest))
+        }),
+        expr => square(expr).map(|(name, arg)| (false, name, arg, vec![])),
+    }
+}
+
+/// The name and the argument of a function of one argument.
+fn trig_argument(expr: &Expression) -> Option<(FunctionType, &Expression)> {
+    match expr {
+        Expression::Function(function) => match function.args.as_slice() {
+            [arg] => Some((function.name.clone(), arg)),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+enum Angle {
+    /// `2*a`
+    Double(Expression),
+    /// `a + b`
+    Sum(Expression, Expression),
+}
+
+/// Split `a + b` in `a` and `b`, `a - b` in `a` and `-b` and `n*a` in
+/// `(n - 1)*a` and `a`.
+fn split_angle(arg: &Expression) -> Option<Angle> {
+    match arg {
+        Expression::Addition(add) if add.terms.len() >= 2 => {
+            let rest = match &add.terms[1..] {
+                [term] => term.clone(),
+                terms => Expression::addition(terms.to_vec()),
+            };
+            Some(Angle::Sum(add.terms[0].clone(), rest))
+        }
+        Expression::Subtraction(sub) => Some(Angle::Sum(
+            sub.left.clone(),
+            Expression::negation(sub.right.clone()),
+        )),
+        Expression::Multiplication(mul) => {
+            let (i, n) = mul.terms.iter().enumerate().find_map(|(i, term)| match term {
+                Expression::Number(Numeral::Integer(n)) => n
+                    .to_u32()
+                    .filter(|n| (2..=MAX_ANGLE_MULTIPLE).contains(n))
+                    .map(|n| (i, n)),
+                _ => None,
+            })?;
+            let mut rest = mul.terms.clone();
+            rest.remove(i);
+            let angle = match rest.len() {
+                1 => rest.remove(0),
+                _ => Expression::multiplication(rest),
+            };
+            if n == 2 {
+                Some(Angle::Double(angle))
+            } else {
+                Some(Angle::Sum(
+                    Expression::multiplication(vec![Expression::integer(n - 1), angle.clone()]),
+                    angle,
+                ))
+            }
+        }
+        _ => None,
+    }
+}
+
+fn sin(arg: Expression) -> Expression {
+    Expression::function(FunctionType::Sin, vec![arg])
+}
+
+fn cos(arg: Expression) -> Expression {
+    Expression::function(FunctionType::Cos, vec![arg])
+}
+
+fn tan(arg: Expression) -> Expression {
+    Expression::function(FunctionType::Tan, vec![arg])
+}
+
+fn double_angle(name: FunctionType, a: Expression) -> (&'static str, Expression) {
+    let square = |expr: Expression| Expression::exponentiation(expr, Expression::integer(2));
+    match name {
+        FunctionType::Sin => (
+            "Double angle\nsin(2a) => 2sin(a)cos(a)",
+            Expression::multiplication(vec![Expression::integer(2), sin(a.clone()), cos(a)]),
+        ),
+        FunctionType::Cos => (
+            "Double angle\ncos(2a) => cos(a)^2 - sin(a)^2",
+            Expression::subtraction(square(cos(a.clone())), square(sin(a))),
+        ),
+        _ => (
+            "Double angle\ntan(2a) => 2tan(a)/(1 - tan(a)^2)",
+            Expression::division(
+                Expression::multiplication(vec![Expression::integer(2), tan(a.clone())]),
+                Expression::subtraction(Expression::integer(1), square(tan(a))),
+            ),
+        ),
+    }
+}
+
+fn angle_addition(name: FunctionType, a: Expression, b: Expression) -> (&'static str, Expression) {
+    match name {
+        FunctionType::Sin => (
+            "Angle addition\nsin(a + b) => sin(a)cos(b) + cos(a)sin(b)",
+            Expression::addition(vec![
+                Expression::multiplication(vec![sin(a.clone()), cos(b.clone())]),
+                Expression::multiplication(vec![cos(a), sin(b)]),
+            ]),
+        ),
+        FunctionType::Cos => (
+            "Angle addition\ncos(a + b) => cos(a)cos(b) - sin(a)sin(b)",
+            Expression::subtraction(
+                Expression::multiplication(vec![cos(a.clone()), cos(b.clone())]),
+                Expression::multiplication(vec![sin(a), sin(b)]),
+            ),
+        ),
+        _ => (
+            "Angle addition\ntan(a + b) => (tan(a) + tan(b))/(1 - tan(a)tan(b))",
+            Expression::division(
+                Expression::addition(vec![tan(a.clone()), tan(b.clone())]),
+                Expression::subtraction(
+                    Expression::integer(1),
+                    Expression::multiplication(vec![tan(a), tan(b)]),
+                ),
+            ),
+        ),
+    }
+}
+
+/// A term of a polynomial, its exponents and its coefficient.
+type Term = (Vec<u32>, Numeral);
+
+/// Apply the trigonometric identities on a polynomial in sines and cosines,
+/// `expr` is the polynomial written as an expression.
+fn combine_polynomial(
+    mut polynomial: Polynomial,
+    expr: &Expression,
+    explanation: &mut Option<Box<FormattingObserver>>,
+) -> Expression {
+    let mut current = expr.clone();
+    let mut log = |rule: &str, after: Expression, explanation: &mut Option<Box<FormattingObserver>>| {
+        if let Some(explanation) = explanation {
+            explanation.rule_applied(rule, &current, &after);
+        }
+        current = after;
+    };
+
+    // sin(a)^2 => 1 - cos(a)^2 or cos(a)^2 => 1 - sin(a)^2 when there are fewer terms
+    for angle in angles(&polynomial) {
+        let reduced = pythagorean_reduction(&polynomial, &angle);
+        if reduced.terms().count() < polynomial.terms().count() {
+            polynomial = reduced;
+            log(
+                "Pythagorean identity\nsin(a)^2 + cos(a)^2 => 1",
+                polynomial.to_expression(),
+                explanation,
+            );
+        }
+    }
+
+    // Combine the pairs of terms, then the products of a sine and a cosine
+    loop {
+        let combined = angles(&polynomial).iter().find_map(|a| {
+            angles(&polynomial)
+                .iter()
+                .find_map(|b| combine_pair(&polynomial, a, b))
+        });
+        match combined {
+            Some((rule, combined)) => {
+                polynomial = combined;
+                log(rule, polynomial.to_expression(), explanation);
+            }
+            None => break,
+        }
+    }
+    for angle in angles(&polynomial) {
+        if let Some(combined) = double_angle_product(&polynomial, &angle) {
+            polynomial = combined;
+            log(
+                "Double angle\n2sin(a)cos(a) => sin(2a)",
+                polynomial.to_expression(),
+                explanation,
+            );
+        }
+    }
+    current
+}
+
+/// The arguments of the sines and cosines among the generators.
+fn angles(polynomial: &Polynomial) -> Vec<Expression> {
+    let mut angles: Vec<Expression> = Vec::new();
+    for generator in polynomial.generators() {
+        if let Some((FunctionType::Sin | FunctionType::Cos, arg)) = trig_argument(generator)
+            && !angles.iter().any(|angle| angle.is_equal(arg))
+        {
+            angles.push(arg.clone());
+        }
+    }
+    angles
+}
+
+/// The generator `name(angle)` of the polynomial, or a new one.
+fn trig_generator(polynomial: &Polynomial, name: FunctionType, angle: &Expression) -> Expression {
+    polynomial
+        .generators()
+        .iter()
+        .find(|generator| {
+            trig_argument(generator).is_some_and(|(n, arg)| n == name && arg.is_equal(angle))
+        })
+        .cloned()
+        .unwrap_or_else(|| Expression::function(name, vec![angle.clone()]))
+}
+
+/// The index of the generator `name(angle)`.
+fn trig_index(polynomial: &Polynomial, name: FunctionType, angle: &Expression) -> Option<usize> {
+    polynomial.generators().iter().position(|generator| {
+        trig_argument(generator).is_some_and(|(n, arg)| n == name && arg.is_equal(angle))
+    })
+}
+
+/// The polynomial with the fewest terms among the ones where `sin(a)^2` is
+/// replaced by `1 - cos(a)^2` and where `cos(a)^2` is replaced by `1 - sin(a)^2`.
+fn pythagorean_reduction(polynomial: &Polynomial, angle: &Expression) -> Polynomial {
+    let sin = trig_generator(polynomial, FunctionType::Sin, angle);
+    let cos = trig_generator(polynomial, FunctionType::Cos, angle);
+    let replace = |square: &Expression, other: &Expression| {
+        let one_minus = Polynomial::integer(1).sub(&Polynomial::generator(other.clone()).pow(2));
+        let power = Polynomial::generator(square.clone());
+        polynomial
+            .coefficients(square)
+            .iter()
+            .enumerate()
+            .fold(Polynomial::zero(), |acc, (k, coeff)| {
+                let k = k as u32;
+                acc.add(&coeff.mul(&power.pow(k % 2)).mul(&one_minus.pow(k / 2)))
+            })
+    };
+    let without_sin = replace(&sin, &cos);
+    let without_cos = replace(&cos, &sin);
+    if without_cos.terms().count() < without_sin.terms().count() {
+        without_cos
+    } else {
+        without_sin
+    }
+}
+
+/// Find two terms `m * first` and `±m * second` and replace them by `m * replacement`.
+fn find_pair(
+    polynomial: &Polynomial,
+    first: &[usize],
+    second: &[usize],
+    opposite: bool,
+    replacement: Expression,
+) -> Option<Polynomial> {
+    let terms: Vec<Term> = polynomial
+        .terms()
+        .map(|(exponents, coeff)| (exponents.to_vec(), coeff.clone()))
+        .collect();
+    let base = |exponents: &[u32], indices: &[usize]| {
+        let mut base = exponents.to_vec();
+        for &i in indices {
+            base[i] = base[i].checked_sub(1)?;
+        }
+        Some(base)
+    };
+    for (i, (lhs, lhs_coeff)) in terms.iter().enumerate() {
+        let Some(lhs_base) = base(lhs, first) else {
+            continue;
+        };
+        let expected = if opposite {
+            lhs_coeff.neg()
+        } else {
+            lhs_coeff.clone()
+        };
+        let partner = terms.iter().enumerate().position(|(j, (rhs, rhs_coeff))| {
+            i != j && *rhs_coeff == expected && base(rhs, second).as_ref() == Some(&lhs_base)
+        });
+        if let Some(j) = partner {
+            let rest = terms
+                .iter()
+                .enumerate()
+                .filter(|(k, _)| *k != i && *k != j)
+                .fold(Polynomial::zero(), |acc, (_, (exponents, coeff))| {
+                    acc.add(&monomial(polynomial, exponents, coeff))
+                });
+            let combined = monomial(polynomial, &lhs_base, lhs_coeff)
+                .mul(&Polynomial::generator(replacement));
+            return Some(rest.add(&combined));
+        }
+    }
+    None
+}
+
+/// `coeff * g1^e1 * ... * gn^en` over the generators of `polynomial`.
+fn monomial(polynomial: &Polynomial, exponents: &[u32], coeff: &Numeral) -> Polynomial {
+    polynomial
+        .generators()
+        .iter()
+        .zip(exponents)
+        .fold(Polynomial::integer(1), |acc, (generator, expo)| {
+            acc.mul(&Polynomial::generator(generator.clone()).pow(*expo))
+        })
+        .scale(coeff)
+}
+
+/// The double angle and the angle addition formulas read from right to left
+/// on a pair of terms.
+fn combine_pair(
+    polynomial: &Polynomial,
+    a: &Expression,
+    b: &Expression,
+) -> Option<(&'static str, Polynomial)> {
+    let sin_a = trig_index(polynomial, FunctionType::Sin, a)?;
+    let cos_a = trig_index(polynomial, FunctionType::Cos, a)?;
+    if a.is_equal(b) {
+        return find_pair(
+            polynomial,
+            &[cos_a, cos_a],
+            &[sin_a, sin_a],
+            true,
+            cos(Expression::multiplication(vec![Expression::integer(2), a.clone()])),
+        )
+        .map(|combined| ("Double angle\ncos(a)^2 - sin(a)^2 => cos(2a)", combined));
+    }
+    let sin_b = trig_index(polynomial, FunctionType::Sin, b)?;
+    let cos_b = trig_index(polynomial, FunctionType::Cos, b)?;
+    let sum = Expression::addition(vec![a.clone(), b.clone()]);
+    let difference = Expression::subtraction(a.clone(), b.clone());
+    let rules = [
+        (
+            "Angle addition\nsin(a)cos(b) + cos(a)sin(b) => sin(a + b)",
+            [sin_a, cos_b],
+            [cos_a, sin_b],
+            false,
+            sin(sum.clone()),
+        ),
+        (
+            "Angle addition\nsin(a)cos(b) - cos(a)sin(b) => sin(a - b)",
+            [sin_a, cos_b],
+            [cos_a, sin_b],
+            true,
+            sin(difference.clone()),
+        ),
+        (
+            "Angle addition\ncos(a)cos(b) - sin(a)sin(b) => cos(a + b)",
+            [cos_a, cos_b],
+            [sin_a, sin_b],
+            true,
+            cos(sum),
+        ),
+        (
+            "Angle addition\ncos(a)cos(b) + sin(a)sin(b) => cos(a - b)",
+            [cos_a, cos_b],
+            [sin_a, sin_b],
+            false,
+            cos(difference),
+        ),
+    ];
+    rules
+        .into_iter()
+        .find_map(|(rule, first, second, opposite, replacement)| {
+            find_pair(polynomial, &first, &second, opposite, replacement)
+                .map(|combined| (rule, combined))
+        })
+}
+
+/// `sin(a)^k * cos(a)^k => (sin(2a)/2)^k` in every term, when no other sine
+/// or cosine of `a` is left.
+fn double_angle_product(polynomial: &Polynomial, angle: &Expression) -> Option<Polynomial> {
+    let sin_a = trig_index(polynomial, FunctionType::Sin, angle)?;
+    let cos_a = trig_index(polynomial, FunctionType::Cos, angle)?;
+    let double = Polynomial::generator(sin(Expression::multiplication(vec![
+        Expression::integer(2),
+        angle.clone(),
+    ])));
+    let mut changed = false;
+    let mut left = false;
+    let result = polynomial
+        .terms()
+        .fold(Polynomial::zero(), |acc, (exponents, coeff)| {
+            let k = exponents[sin_a].min(exponents[cos_a]);
+            left |= exponents[sin_a] != exponents[cos_a];
+            if k == 0 {
+                return acc.add(&monomial(polynomial, exponents, coeff));
+            }
+            changed = true;
+            let mut exponents = exponents.to_vec();
+            exponents[sin_a] -= k;
+            exponents[cos_a] -= k;
+            let half = Numeral::Rational(BigInt::one(), BigInt::from(2).pow(k));
+            acc.add(
+                &monomial(polynomial, &exponents, &coeff.mul(&half))
+                    .mul(&double.pow(k)),
+            )
+        });
+    (changed && !left).then_some(result)
+}
@@ -1,6 +1,6 @@
 use std::fmt;
 
-use crate::{ast::{Expr, Expression, SimplifyError}, explanation::FormattingObserver, prints::PrettyPrints};
+use crate::{ast::{Expr, Expression, SimplifyError, SimplifyOptions}, explanation::FormattingObserver, prints::PrettyPrints};
 
 #[derive(Debug, PartialEq, Clone, PartialOrd, Eq, Ord, Hash)]
 pub struct Variable {
@@ -16,6 +16,7 @@ impl Variable {
 impl Expr for Variable {
     fn simplify(
         &mut self,
+        _options: &SimplifyOptions,
         _explanation: &mut Option<Box<FormattingObserver>>,
     ) -> Result<Expression, SimplifyError> {
         Ok(Expression::Variable(self.clone()))
@@ -61,7 +62,7 @@ impl PrettyPrints for Variable {
     }
 
     fn get_height(&self, _memoization: &mut std::collections::HashMap<Expression, (usize, usize)>) -> usize {
-        0
+        1
     }
 
     fn get_length(&self, _memoization: &mut std::collections::HashMap<Expression, (usize, usize)>) -> usize {
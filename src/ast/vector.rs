@@ -0,0 +1,97 @@
+use std::collections::HashMap;
+
+use crate::{ast::Expr, explanation::FormattingObserver, prints::PrettyPrints};
+
+use super::{Expression, SimplifyError, SimplifyOptions, matrix::Grid};
+
+/// A column vector of symbolic entries.
+#[derive(Debug, PartialEq, Clone, PartialOrd, Eq, Ord, Hash)]
+pub struct Vector {
+    pub entries: Vec<Expression>,
+    pub simplified: bool,
+}
+
+// Constructor
+impl Vector {
+    pub fn new(entries: Vec<Expression>, simplified: bool) -> Self {
+        Self {
+            entries,
+            simplified,
+        }
+    }
+}
+
+impl Expr for Vector {
+    fn simplify(
+        &mut self,
+        options: &SimplifyOptions,
+        explanation: &mut Option<Box<FormattingObserver>>,
+    ) -> Result<Expression, SimplifyError> {
+        let entries = self
+            .entries
+            .iter_mut()
+            .map(|entry| entry.simplify_with(options, explanation))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Expression::Vector(Box::new(Vector::new(entries, true))))
+    }
+
+    fn is_equal(&self, other: &Vector) -> bool {
+        self.entries.len() == other.entries.len()
+            && self
+                .entries
+                .iter()
+                .zip(&other.entries)
+                .all(|(lhs, rhs)| lhs.is_equal(rhs))
+    }
+
+    fn contains_var(&self, variable: &str) -> bool {
+        self.entries.iter().any(|entry| entry.contains_var(variable))
+    }
+
+    fn is_single(&self) -> bool {
+        true
+    }
+}
+
+impl std::fmt::Display for Vector {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let entries: Vec<String> = self.entries.iter().map(|entry| entry.to_string()).collect();
+        write!(f, "[{}]", entries.join(", "))
+    }
+}
+
+impl Vector {
+    fn grid(&self) -> Grid<'_> {
+        Grid {
+            rows: self.entries.iter().map(|entry| vec![entry]).collect(),
+        }
+    }
+}
+
+impl PrettyPrints for Vector {
+    fn calculate_tree(&self, indent: usize) -> String {
+        self.grid().calculate_tree("Vector", indent)
+    }
+
+    fn calculate_positions(
+        &self,
+        memoization: &mut HashMap<Expression, (usize, usize)>,
+        position: &mut Vec<(String, (usize, usize))>,
+        prev_pos: (usize, usize),
+    ) {
+        self.grid()
+            .calculate_positions(memoization, position, prev_pos)
+    }
+
+    fn get_below_height(&self, memoization: &mut HashMap<Expression, (usize, usize)>) -> usize {
+        self.grid().get_below_height(memoization)
+    }
+
+    fn get_height(&self, memoization: &mut HashMap<Expression, (usize, usize)>) -> usize {
+        self.grid().get_height(memoization)
+    }
+
+    fn get_length(&self, memoization: &mut HashMap<Expression, (usize, usize)>) -> usize {
+        self.grid().get_length(memoization)
+    }
+}
@@ -0,0 +1,117 @@
+use std::collections::HashMap;
+
+use crate::{ast::Expr, explanation::FormattingObserver, prints::PrettyPrints};
+
+use super::{Expression, SimplifyError, SimplifyOptions, logic::Infix};
+
+#[derive(Debug, PartialEq, Clone, PartialOrd, Eq, Ord, Hash)]
+pub struct Xor {
+    pub left: Expression,
+    pub right: Expression,
+    pub simplified: bool,
+}
+
+// Constructor
+impl Xor {
+    pub fn new(left: Expression, right: Expression, simplified: bool) -> Self {
+        Self {
+            left,
+            right,
+            simplified,
+        }
+    }
+}
+
+impl Expr for Xor {
+    fn simplify(
+        &mut self,
+        options: &SimplifyOptions,
+        explanation: &mut Option<Box<FormattingObserver>>,
+    ) -> Result<Expression, SimplifyError> {
+        let left = self.left.simplify_with(options, explanation)?;
+        let right = self.right.simplify_with(options, explanation)?;
+        let before = Expression::xor(left.clone(), right.clone());
+
+        let is_complement = |a: &Expression, b: &Expression| {
+            matches!(b, Expression::Not(not) if not.term.is_equal(a))
+        };
+        let (rule, mut after) = match (left.as_boolean(), right.as_boolean()) {
+            // true xor false => true
+            (Some(a), Some(b)) => ("Exclusive or of constants", Expression::boolean(a != b)),
+            // a xor false => a
+            (Some(false), _) => ("Identity\nfalse xor a => a", right),
+            (_, Some(false)) => ("Identity\na xor false => a", left),
+            // a xor true => !a
+            (Some(true), _) => ("Negation\ntrue xor a => !a", Expression::logical_not(right)),
+            (_, Some(true)) => ("Negation\na xor true => !a", Expression::logical_not(left)),
+            // a xor a => false
+            _ if left.is_equal(&right) => ("Self exclusion\na xor a => false", Expression::boolean(false)),
+            // a xor !a => true
+            _ if is_complement(&left, &right) || is_complement(&right, &left) => {
+                ("Complement\na xor !a => true", Expression::boolean(true))
+            }
+            _ => return Ok(Expression::Xor(Box::new(Xor::new(left, right, true)))),
+        };
+        if let Some(explanation) = explanation {
+            explanation.rule_applied(rule, &before, &after);
+        }
+        after.simplify_with(options, explanation)
+    }
+
+    fn is_equal(&self, other: &Xor) -> bool {
+        // xor is commutative
+        (self.left.is_equal(&other.left) && self.right.is_equal(&other.right))
+            || (self.left.is_equal(&other.right) && self.right.is_equal(&other.left))
+    }
+
+    fn contains_var(&self, variable: &str) -> bool {
+        self.left.contains_var(variable) || self.right.contains_var(variable)
+    }
+
+    fn is_single(&self) -> bool {
+        false
+    }
+}
+
+impl Xor {
+    fn infix(&self) -> Infix<'_> {
+        Infix {
+            terms: vec![&self.left, &self.right],
+            symbol: "xor",
+        }
+    }
+}
+
+impl std::fmt::Display for Xor {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        self.infix().display(f)
+    }
+}
+
+impl PrettyPrints for Xor {
+    fn calculate_tree(&self, indent: usize) -> String {
+        self.infix().calculate_tree("Xor", indent)
+    }
+
+    fn calculate_positions(
+        &self,
+        memoization: &mut HashMap<Expression, (usize, usize)>,
+        position: &mut Vec<(String, (usize, usize))>,
+        prev_pos: (usize, usize),
+    ) {
+        self.infix()
+            .calculate_positions(memoization, position, prev_pos)
+    }
+
+    fn get_below_height(&self, memoization: &mut HashMap<Expression, (usize, usize)>) -> usize {
+        self.infix().get_below_height(memoization)
+    }
+
+    fn get_height(&self, memoization: &mut HashMap<Expression, (usize, usize)>) -> usize {
+        self.infix().get_height(memoization)
+    }
+
+    fn get_length(&self, memoization: &mut HashMap<Expression, (usize, usize)>) -> usize {
+        self.infix().get_length(memoization)
+    }
+}
@@ -11,6 +11,16 @@ pub enum Token {
     Divide,
     Caret,
     Equals,
+    Less,
+    LessEqual,
+    Greater,
+    GreaterEqual,
+    NotEqual,
+
+    And,
+    Or,
+    Not,
+    Xor,
 
     Percent,
     Dot,
@@ -43,6 +53,15 @@ impl fmt::Display for Token {
             Token::Divide => write!(f, "/"),
             Token::Caret => write!(f, "^"),
             Token::Equals => write!(f, "="),
+            Token::Less => write!(f, "<"),
+            Token::LessEqual => write!(f, "<="),
+            Token::Greater => write!(f, ">"),
+            Token::GreaterEqual => write!(f, ">="),
+            Token::NotEqual => write!(f, "!="),
+            Token::And => write!(f, "&&"),
+            Token::Or => write!(f, "||"),
+            Token::Not => write!(f, "!"),
+            Token::Xor => write!(f, "xor"),
             Token::Percent => write!(f, "%"),
             Token::Dot => write!(f, "."),
             Token::Comma => write!(f, ","),
@@ -70,12 +89,11 @@ pub struct Lexer<'a> {
 
 impl<'a> Lexer<'a> {
     pub fn new(input: &'a str) -> Self {
-        let lexer = Lexer {
+        Lexer {
             input,
             position: 0,
             current_char: input.chars().next(),
-        };
-        lexer
+        }
     }
 
     fn advance(&mut self) {
@@ -83,8 +101,11 @@ impl<'a> Lexer<'a> {
         self.current_char = self.input.chars().nth(self.position);
     }
 
-    fn number(&mut self) -> Token {
-        let start = self.position;
+    fn peek(&self, offset: usize) -> Option<char> {
+        self.input.chars().nth(self.position + offset)
+    }
+
+    fn digits(&mut self) {
         while let Some(c) = self.current_char {
             if c.is_ascii_digit() {
                 self.advance();
@@ -92,6 +113,29 @@ impl<'a> Lexer<'a> {
                 break;
             }
         }
+    }
+
+    /// Read an integer with an optional scientific exponent like `15e-3`.
+    ///
+    /// The exponent is only read when digits follow the `e`, so `2e` and `2ex`
+    /// are still a number followed by a literal.
+    fn number(&mut self) -> Token {
+        let start = self.position;
+        self.digits();
+        if matches!(self.current_char, Some('e' | 'E')) {
+            let exponent = match self.peek(1) {
+                Some(c) if c.is_ascii_digit() => true,
+                Some('+' | '-') => self.peek(2).is_some_and(|c| c.is_ascii_digit()),
+                _ => false,
+            };
+            if exponent {
+                self.advance();
+                if matches!(self.current_char, Some('+' | '-')) {
+                    self.advance();
+                }
+                self.digits();
+            }
+        }
         Token::Number(self.input[start..self.position].to_string())
     }
 
@@ -105,7 +149,10 @@ impl<'a> Lexer<'a> {
                 break;
             }
         }
-        Token::Literal(self.input[start..self.position].to_string())
+        match &self.input[start..self.position] {
+            "xor" => Token::Xor,
+            literal => Token::Literal(literal.to_string()),
+        }
     }
 
     pub fn next_token(&mut self) -> Option<Token> {
@@ -168,6 +215,32 @@ impl<'a> Lexer<'a> {
                 self.advance();
                 Some(Token::SingleQuote)
             }
+            Some('&') if self.peek(1) == Some('&') => {
+                self.advance();
+                self.advance();
+                Some(Token::And)
+            }
+            Some('|') if self.peek(1) == Some('|') => {
+                self.advance();
+                self.advance();
+                Some(Token::Or)
+            }
+            Some('<') | Some('>') | Some('!') => {
+                let c = self.current_char;
+                self.advance();
+                let equal = self.current_char == Some('=');
+                if equal {
+                    self.advance();
+                }
+                Some(match (c, equal) {
+                    (Some('<'), false) => Token::Less,
+                    (Some('<'), true) => Token::LessEqual,
+                    (Some('>'), false) => Token::Greater,
+                    (Some('>'), true) => Token::GreaterEqual,
+                    (_, true) => Token::NotEqual,
+                    (_, false) => Token::Not,
+                })
+            }
             Some('%') => {
                 self.advance();
                 Some(Token::Percent)
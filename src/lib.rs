@@ -3,5 +3,6 @@ pub mod parser;
 pub mod lexer;
 pub mod utils;
 pub mod solver;
+pub mod polynomial;
 pub mod prints;
 pub mod explanation;
\ No newline at end of file
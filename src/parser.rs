@@ -1,4 +1,6 @@
-use crate::ast::Expression;
+use num_bigint::BigInt;
+
+use crate::ast::{Expression, inequality::Relation, limit::Direction};
 use crate::lexer::Token;
 #[derive(Debug, PartialEq)]
 pub enum ParseError {
@@ -8,6 +10,8 @@ pub enum ParseError {
     InvalidVariableFormat(usize),
     // Name of fct, args_number, position
     InvalidFunctionFormat(String, usize, usize),
+    /// The rows of a matrix don't have the same length, or an entry is a matrix
+    InvalidMatrixFormat(usize),
     DerivativeFailed,
 }
 
@@ -78,14 +82,22 @@ impl<'a> Parser<'a> {
 
         while let Some(token) = self.current_token().cloned() {
             let precedence = match token {
-                Token::Equals => 1,
-                Token::Plus | Token::Minus => 2,
-                Token::Multiply | Token::Divide => 3,
-                Token::Caret => 5,
+                Token::Or => 1,
+                Token::Xor => 2,
+                Token::And => 3,
+                Token::Equals
+                | Token::Less
+                | Token::LessEqual
+                | Token::Greater
+                | Token::GreaterEqual
+                | Token::NotEqual => 4,
+                Token::Plus | Token::Minus => 5,
+                Token::Multiply | Token::Divide => 6,
+                Token::Caret => 8,
                 _ => break,
             };
 
-            if precedence < past_precedence || (precedence != 5 && precedence == past_precedence) {
+            if precedence < past_precedence || (precedence != 8 && precedence == past_precedence) {
                 break;
             }
 
@@ -102,6 +114,18 @@ impl<'a> Parser<'a> {
                     Expression::exponentiation(left_expr, right_expr)
                 }
                 Token::Equals => Expression::equality(left_expr, right_expr),
+                Token::Less => Expression::inequality(left_expr, Relation::Less, right_expr),
+                Token::LessEqual => {
+                    Expression::inequality(left_expr, Relation::LessEqual, right_expr)
+                }
+                Token::Greater => Expression::inequality(left_expr, Relation::Greater, right_expr),
+                Token::GreaterEqual => {
+                    Expression::inequality(left_expr, Relation::GreaterEqual, right_expr)
+                }
+                Token::NotEqual => Expression::inequality(left_expr, Relation::NotEqual, right_expr),
+                Token::And => Expression::and(vec![left_expr, right_expr]),
+                Token::Or => Expression::or(vec![left_expr, right_expr]),
+                Token::Xor => Expression::xor(left_expr, right_expr),
                 _ => {
                     return Err(ParseError::UnexpectedToken(
                         format!("{}", token),
@@ -132,7 +156,7 @@ impl<'a> Parser<'a> {
                 ) {
                     Ok(Expression::multiplication(vec![
                         expr,
-                        self.parse_binary(None, 3)?,
+                        self.parse_binary(None, 6)?,
                     ]))
                 } else {
                     Ok(expr)
@@ -140,7 +164,7 @@ impl<'a> Parser<'a> {
             }
             Some(Token::Minus) => {
                 self.advance();
-                let expr = self.parse_binary(None, 4)?;
+                let expr = self.parse_binary(None, 7)?;
                 while let Some(Token::WhiteSpace) = self.current_token() {
                     self.advance();
                 }
@@ -151,7 +175,7 @@ impl<'a> Parser<'a> {
                 ) {
                     Ok(Expression::multiplication(vec![
                         expr,
-                        self.parse_binary(None, 3)?,
+                        self.parse_binary(None, 6)?,
                     ]))
                 } else {
                     Ok(Expression::negation(expr))
@@ -170,7 +194,7 @@ impl<'a> Parser<'a> {
                 ) {
                     Ok(Expression::multiplication(vec![
                         expr,
-                        self.parse_binary(None, 3)?,
+                        self.parse_binary(None, 6)?,
                     ]))
                 } else {
                     Ok(expr)
@@ -193,7 +217,7 @@ impl<'a> Parser<'a> {
                     ) {
                         Ok(Expression::multiplication(vec![
                             expr,
-                            self.parse_binary(None, 3)?,
+                            self.parse_binary(None, 6)?,
                         ]))
                     } else {
                         Ok(expr)
@@ -205,20 +229,20 @@ impl<'a> Parser<'a> {
                     ))
                 }
             }
+            Some(Token::Not) => {
+                self.advance();
+                Ok(Expression::logical_not(self.parse_binary(None, 7)?))
+            }
+            Some(Token::LeftBracket) => self.parse_brackets(),
             Some(Token::Dot) => {
                 // Handle decimal number with no leading zero like .55
                 self.advance();
                 match self.current_token() {
-                    Some(Token::Number(value)) => match value.parse::<u64>() {
-                        Ok(denominator) => {
-                            self.advance();
-                            Ok(Expression::rational(
-                                denominator,
-                                10_u64.pow(denominator.to_string().len() as u32),
-                            ))
-                        }
-                        Err(_) => Err(ParseError::InvalidNumberFormat(self.position)),
-                    },
+                    Some(Token::Number(value)) => {
+                        let number = Self::decimal("", value);
+                        self.advance();
+                        number.ok_or(ParseError::InvalidNumberFormat(self.position))
+                    }
                     Some(token) => {
                         Err(ParseError::UnexpectedToken(
                             format!("{}", token),
@@ -242,21 +266,86 @@ impl<'a> Parser<'a> {
         parsed
     }
 
+    /// Parse `[a, b, c]` into a `Vector` and `[[a, b], [c, d]]` into a `Matrix`
+    /// whose rows are the inner brackets.
+    fn parse_brackets(&mut self) -> Result<Expression, ParseError> {
+        let start = self.position;
+        self.advance();
+        let mut entries = vec![self.parse_binary(None, 0)?];
+        while let Some(Token::Comma) = self.current_token() {
+            self.advance();
+            entries.push(self.parse_binary(None, 0)?);
+        }
+        match self.current_token() {
+            Some(Token::RightBracket) => self.advance(),
+            Some(token) => {
+                return Err(ParseError::UnexpectedToken(
+                    format!("{}", token),
+                    self.position,
+                ));
+            }
+            None => return Err(ParseError::UnexpectedEndOfInput(self.position)),
+        }
+
+        if !entries.iter().any(Expression::is_matrix) {
+            return Ok(Expression::vector(entries));
+        }
+        // Each inner bracket is a row
+        let rows = entries
+            .into_iter()
+            .map(|entry| match entry {
+                Expression::Vector(row) if !row.entries.iter().any(Expression::is_matrix) => {
+                    Ok(row.entries)
+                }
+                _ => Err(ParseError::InvalidMatrixFormat(start)),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if rows.iter().any(|row| row.len() != rows[0].len()) {
+            return Err(ParseError::InvalidMatrixFormat(start));
+        }
+        Ok(Expression::matrix(rows))
+    }
+
+    /// Build the number `integer.fraction`, a decimal is kept exact as a rational
+    /// unless it is written in scientific notation.
+    fn decimal(integer: &str, fraction: &str) -> Option<Expression> {
+        let scientific = |part: &str| part.contains(['e', 'E']);
+        if scientific(integer) && !fraction.is_empty() {
+            // 1e5.3
+            return None;
+        }
+        if scientific(integer) || scientific(fraction) {
+            let text = if fraction.is_empty() {
+                format!("0{}", integer)
+            } else {
+                format!("0{}.{}", integer, fraction)
+            };
+            let value: f64 = text.parse().ok()?;
+            return value.is_finite().then(|| Expression::float(value));
+        }
+
+        let numerator = format!("{}{}", integer, fraction).parse::<BigInt>().ok()?;
+        Some(if fraction.is_empty() {
+            Expression::integer(numerator)
+        } else {
+            Expression::rational(numerator, BigInt::from(10).pow(fraction.len() as u32))
+        })
+    }
+
     fn parse_number(&mut self, variable: String) -> Result<Expression, ParseError> {
         self.advance();
-        match variable.parse::<u64>() {
-            Ok(numerator) => {
+        match Self::decimal(&variable, "") {
+            Some(integer) => {
                 // Handle decimal point
                 let number = if let Some(Token::Dot) = self.current_token() {
                     self.advance();
                     match self.current_token() {
-                        Some(Token::Number(value)) => match value.parse::<u64>() {
-                            Ok(denominator) => {
+                        Some(Token::Number(value)) => match Self::decimal(&variable, value) {
+                            Some(number) => {
                                 self.advance();
-                                let power = 10_u64.pow(denominator.to_string().len() as u32);
-                                Expression::rational(numerator * power + denominator, power)
+                                number
                             }
-                            Err(_) => return Err(ParseError::InvalidNumberFormat(self.position)),
+                            None => return Err(ParseError::InvalidNumberFormat(self.position)),
                         },
                         Some(token) => {
                             return Err(ParseError::UnexpectedToken(
@@ -267,7 +356,7 @@ impl<'a> Parser<'a> {
                         None => return Err(ParseError::UnexpectedEndOfInput(self.position)),
                     }
                 } else {
-                    Expression::integer(numerator)
+                    integer
                 };
 
                 while let Some(Token::WhiteSpace) = self.current_token() {
@@ -280,13 +369,13 @@ impl<'a> Parser<'a> {
                 ) {
                     Ok(Expression::multiplication(vec![
                         number,
-                        self.parse_binary(None, 3)?,
+                        self.parse_binary(None, 6)?,
                     ]))
                 } else {
                     Ok(number)
                 }
             }
-            Err(_) => Err(ParseError::InvalidNumberFormat(self.position)),
+            None => Err(ParseError::InvalidNumberFormat(self.position)),
         }
     }
 
@@ -313,6 +402,9 @@ impl<'a> Parser<'a> {
                 "tau" => Ok(Expression::tau()),
                 "pi" => Ok(Expression::pi()),
                 "e" => Ok(Expression::e()),
+                "inf" => Ok(Expression::infinity()),
+                "true" => Ok(Expression::boolean(true)),
+                "false" => Ok(Expression::boolean(false)),
                 "i" => Ok(Expression::complex(
                     Expression::integer(0),
                     Expression::integer(1),
@@ -496,12 +588,74 @@ impl<'a> Parser<'a> {
                     "log" => Ok(Expression::log(args[0].clone(), args[1].clone())),
 
                     "pow" => Ok(Expression::pow(args[0].clone(), args[1].clone())),
+
+                    "int" => match &args[1] {
+                        Expression::Variable(var) => Ok(Expression::integral(args[0].clone(), &var.name)),
+                        _ => Err(ParseError::InvalidFunctionFormat(
+                            variable,
+                            2,
+                            self.position,
+                        )),
+                    },
                     _ => Err(ParseError::InvalidFunctionFormat(
                         variable,
                         2,
                         self.position,
                     )),
                 },
+                3 => match (variable.as_str(), &args[1]) {
+                    ("lim", Expression::Variable(var)) => Ok(Expression::limit(
+                        args[0].clone(),
+                        &var.name,
+                        args[2].clone(),
+                        Direction::Both,
+                    )),
+                    _ => Err(ParseError::InvalidFunctionFormat(
+                        variable,
+                        3,
+                        self.position,
+                    )),
+                },
+                4 => match (variable.as_str(), &args[1], &args[3]) {
+                    ("int", Expression::Variable(var), _) => Ok(Expression::definite_integral(
+                        args[0].clone(),
+                        &var.name,
+                        args[2].clone(),
+                        args[3].clone(),
+                    )),
+                    ("sum", Expression::Variable(var), _) => Ok(Expression::summation(
+                        args[0].clone(),
+                        &var.name,
+                        args[2].clone(),
+                        args[3].clone(),
+                    )),
+                    ("prod", Expression::Variable(var), _) => Ok(Expression::product(
+                        args[0].clone(),
+                        &var.name,
+                        args[2].clone(),
+                        args[3].clone(),
+                    )),
+                    // lim(f, x, a, left) and lim(f, x, a, right) are one-sided
+                    ("lim", Expression::Variable(var), Expression::Variable(side))
+                        if side.name == "left" || side.name == "right" =>
+                    {
+                        Ok(Expression::limit(
+                            args[0].clone(),
+                            &var.name,
+                            args[2].clone(),
+                            if side.name == "left" {
+                                Direction::Left
+                            } else {
+                                Direction::Right
+                            },
+                        ))
+                    }
+                    _ => Err(ParseError::InvalidFunctionFormat(
+                        variable,
+                        4,
+                        self.position,
+                    )),
+                },
                 x => Err(ParseError::InvalidFunctionFormat(
                     variable,
                     x,
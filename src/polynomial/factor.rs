@@ -0,0 +1,390 @@
+use std::collections::BTreeMap;
+
+use num_bigint::BigInt;
+use num_traits::{One, Zero};
+
+use crate::{
+    ast::{Expression, SimplifyError, numeral::Numeral},
+    explanation::FormattingObserver,
+};
+
+use super::{
+    Polynomial, PolynomialError,
+    modular::{self, Dense, MAX_MODULAR_FACTORS, combinations},
+    rational_function::{RationalFunction, factor_name},
+    reduce,
+};
+
+/// Above this degree the Kronecker substitution of a multivariate polynomial is not factored.
+const MAX_KRONECKER_DEGREE: u64 = 4096;
+
+impl Polynomial {
+    /// Factor the polynomial into irreducible polynomials over the rationals.
+    ///
+    /// Returns a constant and the factors with their multiplicity, the factors
+    /// are primitive polynomials with a positive greatest term sorted by degree.
+    ///
+    /// # Examples
+    /// ```
+    /// use sym_rustic::{ast::{Expression, numeral::Numeral}, polynomial::Polynomial};
+    ///
+    /// let x = Polynomial::generator(Expression::variable("x"));
+    /// let (constant, factors) = x.pow(3).sub(&x).scale(&Numeral::Integer(2.into())).factor_list().unwrap();
+    /// assert_eq!(constant, Numeral::Integer(2.into()));
+    /// assert_eq!(
+    ///     factors.iter().map(|(factor, _)| factor.to_string()).collect::<Vec<_>>(),
+    ///     vec!["x", "x + -1", "x + 1"]
+    /// );
+    /// ```
+    pub fn factor_list(&self) -> Result<(Numeral, Vec<(Polynomial, u32)>), PolynomialError> {
+        self.factor_with(&mut None)
+    }
+
+    /// The square-free decomposition in `variable` of a polynomial whose
+    /// factors all contain `variable`, with Yun's algorithm.
+    ///
+    /// Returns the square-free and pairwise coprime parts `a_i` with their
+    /// multiplicity `i`, the polynomial is the product of the `a_i^i` up to a constant.
+    pub fn square_free(&self, variable: &Expression) -> Vec<(Polynomial, u32)> {
+        let derivative = self.derivative(variable);
+        let common = self.gcd(&derivative);
+        let mut rest = self.div_exact(&common);
+        let mut difference = derivative
+            .div_exact(&common)
+            .sub(&rest.derivative(variable));
+
+        let mut parts = Vec::new();
+        let mut multiplicity = 1;
+        while rest.total_degree().unwrap_or(0) > 0 {
+            let part = rest.gcd(&difference);
+            rest = rest.div_exact(&part);
+            let quotient = difference.div_exact(&part);
+            difference = quotient.sub(&rest.derivative(variable));
+            if part.total_degree().unwrap_or(0) > 0 {
+                parts.push((part, multiplicity));
+            }
+            multiplicity += 1;
+        }
+        parts
+    }
+
+    fn factor_with(
+        &self,
+        explanation: &mut Option<Box<FormattingObserver>>,
+    ) -> Result<(Numeral, Vec<(Polynomial, u32)>), PolynomialError> {
+        if self.is_zero() {
+            return Ok((Numeral::Integer(BigInt::zero()), Vec::new()));
+        }
+        let (content, mut rest) = self.primitive();
+        let mut factors = Vec::new();
+
+        // x^a*y^b divides every term
+        let monomial = rest
+            .generators
+            .iter()
+            .enumerate()
+            .map(|(index, generator)| {
+                let expo = rest.terms.keys().map(|exponents| exponents[index]).min();
+                (generator, expo.unwrap_or(0))
+            })
+            .filter(|(_, expo)| *expo > 0)
+            .fold(Self::integer(1), |acc, (generator, expo)| {
+                factors.push((Self::generator(generator.clone()), expo));
+                acc.mul(&Self::generator(generator.clone()).pow(expo))
+            });
+        let common = monomial.scale(&content);
+        if !common.is_one() {
+            rest = rest.div_exact(&monomial);
+            if let Some(explanation) = explanation {
+                explanation.rule_applied(
+                    &format!("Take out the common factor {}", factor_name(&common)),
+                    &self.to_expression(),
+                    &Expression::multiplication(vec![common.to_expression(), rest.to_expression()]),
+                );
+            }
+        }
+
+        if let Some(variable) = rest.generators.first().cloned() {
+            // The gcd of the coefficients in the first generator does not contain it
+            let content = rest.content(&variable);
+            if content.total_degree().unwrap_or(0) > 0 {
+                let primitive = rest.div_exact(&content);
+                if let Some(explanation) = explanation {
+                    explanation.rule_applied(
+                        &format!(
+                            "Take out the factor {} common to the coefficients of the powers of {}",
+                            factor_name(&content),
+                            variable
+                        ),
+                        &rest.to_expression(),
+                        &Expression::multiplication(vec![
+                            content.to_expression(),
+                            primitive.to_expression(),
+                        ]),
+                    );
+                }
+                factors.extend(content.factor_with(explanation)?.1);
+                rest = primitive;
+            }
+
+            let parts = rest.square_free(&variable);
+            if let Some(explanation) = explanation
+                && parts.iter().any(|(_, multiplicity)| *multiplicity > 1)
+            {
+                explanation.rule_applied(
+                    "Split into square-free factors using the gcd with the derivative",
+                    &rest.to_expression(),
+                    &product_expression(&Numeral::Integer(BigInt::one()), &parts),
+                );
+            }
+            for (part, multiplicity) in parts {
+                for factor in part.irreducible_factors(&variable, explanation)? {
+                    factors.push((factor, multiplicity));
+                }
+            }
+        }
+
+        let mut merged: Vec<(Polynomial, u32)> = Vec::new();
+        for (factor, multiplicity) in factors {
+            match merged.iter_mut().find(|(other, _)| *other == factor) {
+                Some(entry) => entry.1 += multiplicity,
+                None => merged.push((factor, multiplicity)),
+            }
+        }
+        merged.sort_by_cached_key(|(factor, _)| (factor.total_degree(), factor.to_expression()));
+
+        // The greatest term of a product is the product of the greatest terms
+        let lead = merged
+            .iter()
+            .fold(Numeral::Integer(BigInt::one()), |acc, (factor, multiplicity)| {
+                acc.mul(&factor.leading_numeral().pow(*multiplicity))
+            });
+        Ok((reduce(self.leading_numeral().div(&lead)), merged))
+    }
+
+    /// The irreducible factors of a square-free polynomial whose factors all contain `variable`.
+    fn irreducible_factors(
+        &self,
+        variable: &Expression,
+        explanation: &mut Option<Box<FormattingObserver>>,
+    ) -> Result<Vec<Polynomial>, PolynomialError> {
+        let primitive = self.primitive().1;
+        if primitive.degree(variable) == Some(1) {
+            return Ok(vec![primitive]);
+        }
+        let (factors, rule) = if primitive.generators.len() == 1 {
+            let (factors, prime) = modular::factor_square_free(&primitive.to_dense(variable))?;
+            let factors = factors
+                .iter()
+                .map(|factor| Self::from_dense(factor, variable))
+                .collect();
+            let rule = prime.map(|prime| {
+                format!(
+                    "Factor modulo {} with Berlekamp's algorithm, lift the factors with Hensel's lemma and combine them",
+                    prime
+                )
+            });
+            (factors, rule)
+        } else {
+            (
+                primitive.kronecker_factors()?,
+                Some("Substitute powers of a single variable for the variables and factor the univariate polynomial".to_string()),
+            )
+        };
+
+        if let Some(explanation) = explanation
+            && let Some(rule) = rule
+            && factors.len() > 1
+        {
+            let parts: Vec<(Polynomial, u32)> =
+                factors.iter().map(|factor| (factor.clone(), 1)).collect();
+            explanation.rule_applied(
+                &rule,
+                &primitive.to_expression(),
+                &product_expression(&Numeral::Integer(BigInt::one()), &parts),
+            );
+        }
+        Ok(factors)
+    }
+
+    /// Factor a square-free primitive polynomial in several generators.
+    ///
+    /// With `d` above every degree, `x_i = t^(d^i)` maps the polynomial to a
+    /// univariate polynomial without merging terms, every factor is mapped to
+    /// a product of the irreducible factors of the image.
+    fn kronecker_factors(&self) -> Result<Vec<Polynomial>, PolynomialError> {
+        let base = self
+            .generators
+            .iter()
+            .filter_map(|generator| self.degree(generator))
+            .max()
+            .unwrap_or(0)
+            + 1;
+        let count = self.generators.len() as u32;
+        (base as u64)
+            .checked_pow(count)
+            .filter(|degree| *degree <= MAX_KRONECKER_DEGREE)
+            .ok_or(PolynomialError::DegreeTooLarge)?;
+        let weights: Vec<u32> = (0..count).rev().map(|i| base.pow(i)).collect();
+
+        let image = Self {
+            generators: vec![Expression::variable("t")],
+            terms: self
+                .terms
+                .iter()
+                .map(|(exponents, coeff)| {
+                    let expo = exponents.iter().zip(&weights).map(|(e, w)| e * w).sum();
+                    (vec![expo], coeff.clone())
+                })
+                .collect(),
+        };
+        let mut candidates: Vec<Polynomial> = image
+            .factor_with(&mut None)?
+            .1
+            .into_iter()
+            .flat_map(|(factor, multiplicity)| std::iter::repeat_n(factor, multiplicity as usize))
+            .collect();
+        if candidates.len() > MAX_MODULAR_FACTORS {
+            return Err(PolynomialError::TooManyFactors);
+        }
+
+        let mut rest = self.clone();
+        let mut factors = Vec::new();
+        let mut size = 1;
+        'sizes: while 2 * size <= candidates.len() {
+            for subset in combinations(candidates.len(), size) {
+                let product = subset
+                    .iter()
+                    .fold(Self::integer(1), |acc, &i| acc.mul(&candidates[i]));
+                let candidate = self.unsubstitute(&product, &weights).primitive().1;
+                if candidate.total_degree().unwrap_or(0) == 0 {
+                    continue;
+                }
+                let (quotient, remainder) = rest.div_rem(&candidate)?;
+                if remainder.is_zero() {
+                    factors.push(candidate);
+                    rest = quotient;
+                    for i in subset.into_iter().rev() {
+                        candidates.remove(i);
+                    }
+                    continue 'sizes;
+                }
+            }
+            size += 1;
+        }
+        factors.push(rest.primitive().1);
+        Ok(factors)
+    }
+
+    /// Map back a polynomial in `t` with `t^(weights[i])` read as the generator `i`.
+    fn unsubstitute(&self, image: &Polynomial, weights: &[u32]) -> Polynomial {
+        let terms = image
+            .terms
+            .iter()
+            .map(|(exponents, coeff)| {
+                let mut expo = exponents.first().copied().unwrap_or(0);
+                let exponents = weights
+                    .iter()
+                    .map(|weight| {
+                        let digit = expo / weight;
+                        expo %= weight;
+                        digit
+                    })
+                    .collect();
+                (exponents, coeff.clone())
+            })
+            .collect();
+        Self {
+            generators: self.generators.clone(),
+            terms,
+        }
+        .normalize()
+    }
+
+    /// The integer coefficients of a primitive polynomial in the single generator `variable`.
+    fn to_dense(&self, variable: &Expression) -> Dense {
+        self.coefficients(variable)
+            .iter()
+            .map(|coeff| match coeff.as_constant() {
+                Some(Numeral::Integer(n)) => n,
+                _ => unreachable!("a primitive polynomial has integer coefficients"),
+            })
+            .collect()
+    }
+
+    fn from_dense(coefficients: &Dense, variable: &Expression) -> Polynomial {
+        let terms: BTreeMap<Vec<u32>, Numeral> = coefficients
+            .iter()
+            .enumerate()
+            .filter(|(_, coeff)| !coeff.is_zero())
+            .map(|(degree, coeff)| (vec![degree as u32], Numeral::Integer(coeff.clone())))
+            .collect();
+        Self {
+            generators: vec![variable.clone()],
+            terms,
+        }
+        .normalize()
+    }
+}
+
+impl Expression {
+    /// Factor a polynomial, or the numerator and the denominator of a
+    /// rational expression, into irreducible polynomials over the rationals.
+    ///
+    /// The result is a product of powers of the factors, with the constant first.
+    ///
+    /// # Examples
+    /// ```
+    /// use sym_rustic::ast::Expression;
+    ///
+    /// let x = Expression::variable("x");
+    /// let expr = Expression::subtraction(
+    ///     Expression::exponentiation(x.clone(), Expression::integer(3)),
+    ///     x.clone(),
+    /// );
+    /// assert_eq!(expr.factor(&mut None).unwrap().to_string(), "x * (x + -1) * (x + 1)");
+    /// ```
+    pub fn factor(
+        &self,
+        explanation: &mut Option<Box<FormattingObserver>>,
+    ) -> Result<Expression, SimplifyError> {
+        let (num, den) = RationalFunction::from_expression(self)?.integer_parts();
+        let (content, factors) = num.factor_with(explanation)?;
+        let num = product_expression(&content, &factors);
+        let after = if den.is_one() {
+            num
+        } else {
+            let (content, factors) = den.factor_with(explanation)?;
+            Expression::division(num, product_expression(&content, &factors))
+        };
+        if let Some(explanation) = explanation {
+            explanation.rule_applied("Factor over the rationals", self, &after);
+        }
+        Ok(after)
+    }
+}
+
+/// `constant * factor_1^m_1 * ... * factor_n^m_n`, a negative constant becomes a negation.
+fn product_expression(constant: &Numeral, factors: &[(Polynomial, u32)]) -> Expression {
+    let mut terms: Vec<Expression> = factors
+        .iter()
+        .map(|(factor, multiplicity)| match multiplicity {
+            1 => factor_name(factor),
+            multiplicity => {
+                Expression::exponentiation(factor_name(factor), Expression::integer(*multiplicity))
+            }
+        })
+        .collect();
+    if terms.is_empty() || !constant.abs().is_one() {
+        terms.insert(0, Expression::Number(constant.abs()));
+    }
+    let product = match terms.len() {
+        1 => terms.remove(0),
+        _ => Expression::multiplication(terms),
+    };
+    if constant.is_negative() {
+        Expression::negation(product)
+    } else {
+        product
+    }
+}
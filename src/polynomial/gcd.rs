@@ -0,0 +1,157 @@
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::{One, Zero};
+
+use crate::ast::{Expression, numeral::Numeral};
+
+use super::{Polynomial, reduce};
+
+impl Polynomial {
+    /// Split the polynomial into its content and its primitive part, so that
+    /// `self = content * primitive`.
+    ///
+    /// The primitive part has integer coefficients without common factor and
+    /// a positive greatest term. The zero polynomial has a zero content.
+    ///
+    /// # Examples
+    /// ```
+    /// use sym_rustic::{ast::{Expression, numeral::Numeral}, polynomial::Polynomial};
+    ///
+    /// let x = Polynomial::generator(Expression::variable("x"));
+    /// let (content, primitive) = x.scale(&Numeral::Integer((-4).into())).add(&Polynomial::integer(6)).primitive();
+    /// assert_eq!(content, Numeral::Integer((-2).into()));
+    /// assert_eq!(primitive.to_string(), "(2 * x) + -3");
+    /// ```
+    pub fn primitive(&self) -> (Numeral, Polynomial) {
+        if self.is_zero() {
+            return (Numeral::Integer(BigInt::zero()), Self::zero());
+        }
+        let (mut num, mut den) = (BigInt::zero(), BigInt::one());
+        for (_, coeff) in self.terms() {
+            let (n, d) = match coeff {
+                Numeral::Integer(n) => (n.clone(), BigInt::one()),
+                Numeral::Rational(n, d) => (n.clone(), d.clone()),
+                Numeral::Float(_) => unreachable!("the coefficients are exact"),
+            };
+            num = num.gcd(&n);
+            den = den.lcm(&d);
+        }
+        if self.leading_numeral().is_negative() {
+            num = -num;
+        }
+        let content = reduce(Numeral::Rational(num, den));
+        let inverse = reduce(Numeral::Integer(BigInt::one()).div(&content));
+        (content, self.scale(&inverse))
+    }
+
+    /// The greatest common divisor, written as a primitive polynomial.
+    ///
+    /// The coefficients are rationals so the divisor is only defined up to a
+    /// constant factor, the gcd of two nonzero constants is `1`.
+    ///
+    /// # Examples
+    /// ```
+    /// use sym_rustic::{ast::Expression, polynomial::Polynomial};
+    ///
+    /// let x = Polynomial::generator(Expression::variable("x"));
+    /// let one = Polynomial::integer(1);
+    /// let gcd = x.pow(2).sub(&one).gcd(&x.pow(2).sub(&x.mul(&Polynomial::integer(2))).add(&one));
+    /// assert_eq!(gcd.to_string(), "x + -1");
+    /// ```
+    pub fn gcd(&self, other: &Polynomial) -> Polynomial {
+        if self.is_zero() {
+            return other.primitive().1;
+        }
+        if other.is_zero() {
+            return self.primitive().1;
+        }
+        // Work on the most significant generator, the coefficients in it
+        // have fewer generators so the recursion ends with constants
+        let variable = match (self.generators.first(), other.generators.first()) {
+            (None, None) => return Self::integer(1),
+            (Some(lhs), Some(rhs)) => lhs.min(rhs),
+            (lhs, rhs) => lhs.or(rhs).unwrap(),
+        }
+        .clone();
+
+        let lhs_content = self.content(&variable);
+        let rhs_content = other.content(&variable);
+        let content = lhs_content.gcd(&rhs_content);
+        if self.degree(&variable) == Some(0) || other.degree(&variable) == Some(0) {
+            return content;
+        }
+
+        // Primitive remainder sequence on the primitive parts
+        let mut lhs = self.div_exact(&lhs_content);
+        let mut rhs = other.div_exact(&rhs_content);
+        if lhs.degree(&variable) < rhs.degree(&variable) {
+            std::mem::swap(&mut lhs, &mut rhs);
+        }
+        while !rhs.is_zero() {
+            let remainder = lhs.pseudo_remainder(&rhs, &variable);
+            lhs = rhs;
+            rhs = if remainder.is_zero() {
+                remainder
+            } else {
+                remainder.div_exact(&remainder.content(&variable))
+            };
+        }
+        lhs.mul(&content).primitive().1
+    }
+
+    /// The least common multiple, written as a primitive polynomial.
+    pub fn lcm(&self, other: &Polynomial) -> Polynomial {
+        if self.is_zero() || other.is_zero() {
+            return Self::zero();
+        }
+        self.div_exact(&self.gcd(other)).mul(other).primitive().1
+    }
+
+    /// Returns the gcd and the quotients of both polynomials by it.
+    pub fn cofactors(&self, other: &Polynomial) -> (Polynomial, Polynomial, Polynomial) {
+        let gcd = self.gcd(other);
+        let lhs = self.div_exact(&gcd);
+        let rhs = other.div_exact(&gcd);
+        (gcd, lhs, rhs)
+    }
+
+    /// The gcd of the coefficients in `variable`, that divides the polynomial.
+    pub(super) fn content(&self, variable: &Expression) -> Polynomial {
+        self.coefficients(variable)
+            .iter()
+            .fold(Self::zero(), |acc, coeff| acc.gcd(coeff))
+    }
+
+    /// The quotient by a divisor known to divide the polynomial.
+    pub(super) fn div_exact(&self, divisor: &Polynomial) -> Polynomial {
+        if divisor.is_one() {
+            return self.clone();
+        }
+        let (quotient, remainder) = self
+            .div_rem(divisor)
+            .expect("the divisor is not zero");
+        debug_assert!(remainder.is_zero(), "{} does not divide {}", divisor, self);
+        quotient
+    }
+
+    /// The remainder of the division in `variable` by `divisor`, up to a
+    /// constant factor and a power of the leading coefficient of `divisor`.
+    ///
+    /// No coefficient is divided so it stays a polynomial in the other generators.
+    fn pseudo_remainder(&self, divisor: &Polynomial, variable: &Expression) -> Polynomial {
+        let degree = divisor.degree(variable).unwrap_or(0);
+        let lead = divisor.leading_coefficient(variable);
+        let power = Self::generator(variable.clone());
+
+        let mut remainder = self.clone();
+        while let Some(current) = remainder.degree(variable)
+            && current >= degree
+        {
+            let term = remainder
+                .leading_coefficient(variable)
+                .mul(&power.pow(current - degree));
+            remainder = remainder.mul(&lead).sub(&term.mul(divisor)).primitive().1;
+        }
+        remainder
+    }
+}
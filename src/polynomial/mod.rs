@@ -0,0 +1,560 @@
+use std::collections::BTreeMap;
+
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::{One, Signed, ToPrimitive, Zero};
+
+use crate::ast::{Expression, constant::Constant, numeral::Numeral};
+
+mod factor;
+mod gcd;
+mod modular;
+mod rational_function;
+
+/// Exponents above this bound are not expanded, `(x + 1)^100000` has too many terms.
+const MAX_DEGREE: u32 = 1000;
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum PolynomialError {
+    /// The expression is not a polynomial, like `1/x`, `x^(-2)` or `x = 1`
+    NotPolynomial,
+    /// A coefficient is a float, the polynomial arithmetic is exact
+    InexactCoefficient,
+    /// Dividing by zero, the zero polynomial or a zero constant
+    DivisionByZero,
+    /// An exponent is above the degree that is expanded
+    DegreeTooLarge,
+    /// The factors modulo a prime have too many combinations to try
+    TooManyFactors,
+}
+
+/// Sparse multivariate polynomial with exact rational coefficients.
+///
+/// Every expression that is not built with the polynomial operations is a
+/// generator, like `x`, `pi`, `sin(x)` or `x^(1/2)`. The generators are sorted
+/// and each of them appears in at least one term.
+///
+/// A term maps the exponents of the generators to its nonzero coefficient.
+/// The terms are sorted in the lexicographic order, the first generator being
+/// the most significant, so two equal polynomials have the same representation.
+///
+/// # Examples
+/// ```
+/// use sym_rustic::{ast::Expression, polynomial::Polynomial};
+///
+/// let x = Polynomial::generator(Expression::variable("x"));
+/// let one = Polynomial::integer(1);
+/// let square = x.add(&one).pow(2);
+/// assert_eq!(square.to_string(), "(x^2) + (2 * x) + 1");
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Polynomial {
+    generators: Vec<Expression>,
+    terms: BTreeMap<Vec<u32>, Numeral>,
+}
+
+// Constructor
+impl Polynomial {
+    pub fn zero() -> Self {
+        Self {
+            generators: Vec::new(),
+            terms: BTreeMap::new(),
+        }
+    }
+
+    pub fn integer(n: impl Into<BigInt>) -> Self {
+        Self::constant(Numeral::Integer(n.into()))
+    }
+
+    /// The constant polynomial `value`, the numeral must be exact.
+    fn constant(value: Numeral) -> Self {
+        let value = reduce(value);
+        let mut terms = BTreeMap::new();
+        if !value.is_zero() {
+            terms.insert(Vec::new(), value);
+        }
+        Self {
+            generators: Vec::new(),
+            terms,
+        }
+    }
+
+    /// The polynomial made of the single generator `expr`.
+    pub fn generator(expr: Expression) -> Self {
+        Self {
+            generators: vec![expr],
+            terms: BTreeMap::from([(vec![1], Numeral::Integer(BigInt::one()))]),
+        }
+    }
+
+    /// Read an expression as a polynomial in the expressions that are not
+    /// sums, products, quotients by a constant or natural powers.
+    ///
+    /// The expression is read as it is, so it should be simplified first for
+    /// `sin(2*x)` and `sin(x*2)` to be the same generator.
+    ///
+    /// # Examples
+    /// ```
+    /// use sym_rustic::{ast::Expression, polynomial::Polynomial};
+    ///
+    /// let x = Expression::variable("x");
+    /// let expr = Expression::multiplication(vec![
+    ///     Expression::addition(vec![x.clone(), Expression::integer(1)]),
+    ///     Expression::subtraction(x.clone(), Expression::integer(1)),
+    /// ]);
+    /// let polynomial = Polynomial::from_expression(&expr).unwrap();
+    /// assert_eq!(polynomial.to_string(), "(x^2) + -1");
+    /// assert_eq!(polynomial.degree(&x), Some(2));
+    /// ```
+    pub fn from_expression(expr: &Expression) -> Result<Self, PolynomialError> {
+        match expr {
+            Expression::Number(Numeral::Float(_)) => Err(PolynomialError::InexactCoefficient),
+            Expression::Number(Numeral::Rational(_, d)) if d.is_zero() => {
+                Err(PolynomialError::DivisionByZero)
+            }
+            Expression::Number(n) => Ok(Self::constant(n.clone())),
+            Expression::Negation(neg) => Ok(Self::from_expression(&neg.term)?.neg()),
+            Expression::Addition(add) => add.terms.iter().try_fold(Self::zero(), |acc, term| {
+                Ok(acc.add(&Self::from_expression(term)?))
+            }),
+            Expression::Subtraction(sub) => Ok(Self::from_expression(&sub.left)?
+                .sub(&Self::from_expression(&sub.right)?)),
+            Expression::Multiplication(mul) => {
+                mul.terms.iter().try_fold(Self::integer(1), |acc, term| {
+                    Ok(acc.mul(&Self::from_expression(term)?))
+                })
+            }
+            Expression::Division(div) => {
+                let den = Self::from_expression(&div.den)?
+                    .as_constant()
+                    .ok_or(PolynomialError::NotPolynomial)?;
+                if den.is_zero() {
+                    return Err(PolynomialError::DivisionByZero);
+                }
+                let inverse = Numeral::Integer(BigInt::one()).div(&den);
+                Ok(Self::from_expression(&div.num)?.scale(&inverse))
+            }
+            Expression::Exponentiation(exp) => match integer_value(&exp.expo) {
+                Some(expo) => {
+                    let degree = expo
+                        .abs()
+                        .to_u32()
+                        .filter(|degree| *degree <= MAX_DEGREE)
+                        .ok_or(PolynomialError::DegreeTooLarge)?;
+                    let base = Self::from_expression(&exp.base)?;
+                    if !expo.is_negative() {
+                        return Ok(base.pow(degree));
+                    }
+                    // Only a constant can be raised to a negative power
+                    let base = base.as_constant().ok_or(PolynomialError::NotPolynomial)?;
+                    if base.is_zero() {
+                        return Err(PolynomialError::DivisionByZero);
+                    }
+                    Ok(Self::constant(
+                        Numeral::Integer(BigInt::one()).div(&base).pow(degree),
+                    ))
+                }
+                None => Ok(Self::generator(expr.clone())),
+            },
+            Expression::Constant(Constant::True | Constant::False) => {
+                Err(PolynomialError::NotPolynomial)
+            }
+            Expression::Variable(_) | Expression::Constant(_) | Expression::Function(_) => {
+                Ok(Self::generator(expr.clone()))
+            }
+            _ => Err(PolynomialError::NotPolynomial),
+        }
+    }
+}
+
+impl Polynomial {
+    /// The generators sorted, the exponents of a term are given in this order.
+    pub fn generators(&self) -> &[Expression] {
+        &self.generators
+    }
+
+    /// The exponents and the coefficient of each term, in increasing order.
+    pub fn terms(&self) -> impl DoubleEndedIterator<Item = (&[u32], &Numeral)> {
+        self.terms
+            .iter()
+            .map(|(exponents, coeff)| (exponents.as_slice(), coeff))
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    pub fn is_one(&self) -> bool {
+        self.as_constant().is_some_and(|value| value.is_one())
+    }
+
+    /// Returns the value of a polynomial without generators.
+    pub fn as_constant(&self) -> Option<Numeral> {
+        match self.terms.len() {
+            0 => Some(Numeral::Integer(BigInt::zero())),
+            1 if self.generators.is_empty() => self.terms.values().next().cloned(),
+            _ => None,
+        }
+    }
+
+    /// The degree in `variable`, `None` for the zero polynomial.
+    pub fn degree(&self, variable: &Expression) -> Option<u32> {
+        let index = self.index_of(variable);
+        self.terms
+            .keys()
+            .map(|exponents| index.map_or(0, |index| exponents[index]))
+            .max()
+    }
+
+    /// The greatest sum of the exponents of a term, `None` for the zero polynomial.
+    pub fn total_degree(&self) -> Option<u32> {
+        self.terms
+            .keys()
+            .map(|exponents| exponents.iter().sum())
+            .max()
+    }
+
+    /// The polynomial in the other generators that multiplies `variable^n`.
+    pub fn coefficient(&self, variable: &Expression, n: u32) -> Polynomial {
+        let Some(index) = self.index_of(variable) else {
+            return if n == 0 { self.clone() } else { Self::zero() };
+        };
+        let terms = self
+            .terms
+            .iter()
+            .filter(|(exponents, _)| exponents[index] == n)
+            .map(|(exponents, coeff)| {
+                let mut exponents = exponents.clone();
+                exponents[index] = 0;
+                (exponents, coeff.clone())
+            })
+            .collect();
+        Self {
+            generators: self.generators.clone(),
+            terms,
+        }
+        .normalize()
+    }
+
+    /// The coefficients `[c0, c1, ..., cn]` such that
+    /// `self = c0 + c1*variable + ... + cn*variable^n`.
+    ///
+    /// The zero polynomial gives `[0]`.
+    pub fn coefficients(&self, variable: &Expression) -> Vec<Polynomial> {
+        (0..=self.degree(variable).unwrap_or(0))
+            .map(|n| self.coefficient(variable, n))
+            .collect()
+    }
+
+    /// The coefficient of the highest power of `variable`.
+    pub fn leading_coefficient(&self, variable: &Expression) -> Polynomial {
+        self.coefficient(variable, self.degree(variable).unwrap_or(0))
+    }
+
+    /// The coefficient of the greatest term in the lexicographic order.
+    pub fn leading_numeral(&self) -> Numeral {
+        self.terms
+            .values()
+            .next_back()
+            .cloned()
+            .unwrap_or(Numeral::Integer(BigInt::zero()))
+    }
+
+    /// The derivative with respect to the generator `variable`.
+    pub fn derivative(&self, variable: &Expression) -> Polynomial {
+        let Some(index) = self.index_of(variable) else {
+            return Self::zero();
+        };
+        let terms = self
+            .terms
+            .iter()
+            .filter(|(exponents, _)| exponents[index] > 0)
+            .map(|(exponents, coeff)| {
+                let mut exponents = exponents.clone();
+                let factor = Numeral::Integer(BigInt::from(exponents[index]));
+                exponents[index] -= 1;
+                (exponents, coeff.mul(&factor))
+            })
+            .collect();
+        Self {
+            generators: self.generators.clone(),
+            terms,
+        }
+        .normalize()
+    }
+
+    pub fn neg(&self) -> Polynomial {
+        self.scale(&Numeral::Integer(-BigInt::one()))
+    }
+
+    /// Multiply every coefficient by the exact numeral `factor`.
+    pub fn scale(&self, factor: &Numeral) -> Polynomial {
+        if factor.is_zero() {
+            return Self::zero();
+        }
+        Self {
+            generators: self.generators.clone(),
+            terms: self
+                .terms
+                .iter()
+                .map(|(exponents, coeff)| (exponents.clone(), reduce(coeff.mul(factor))))
+                .collect(),
+        }
+    }
+
+    pub fn add(&self, other: &Polynomial) -> Polynomial {
+        let (generators, mut terms, other_terms) = self.unify(other);
+        for (exponents, coeff) in other_terms {
+            add_term(&mut terms, exponents, coeff);
+        }
+        Self { generators, terms }.normalize()
+    }
+
+    pub fn sub(&self, other: &Polynomial) -> Polynomial {
+        self.add(&other.neg())
+    }
+
+    pub fn mul(&self, other: &Polynomial) -> Polynomial {
+        let (generators, lhs, rhs) = self.unify(other);
+        let mut terms = BTreeMap::new();
+        for (a, x) in &lhs {
+            for (b, y) in &rhs {
+                let exponents = a.iter().zip(b).map(|(a, b)| a + b).collect();
+                add_term(&mut terms, exponents, x.mul(y));
+            }
+        }
+        Self { generators, terms }.normalize()
+    }
+
+    pub fn pow(&self, expo: u32) -> Polynomial {
+        let mut base = self.clone();
+        let mut expo = expo;
+        let mut result = Self::integer(1);
+        // Exponentiation by squaring
+        while expo > 0 {
+            if expo % 2 == 1 {
+                result = result.mul(&base);
+            }
+            expo /= 2;
+            if expo > 0 {
+                base = base.mul(&base);
+            }
+        }
+        result
+    }
+
+    /// Divide by `divisor` and returns the quotient and the remainder.
+    ///
+    /// The division follows the lexicographic order of the generators: the
+    /// greatest term of the divisor divides every term of the quotient and no
+    /// term of the remainder. With a single generator it is the long division.
+    ///
+    /// # Examples
+    /// ```
+    /// use sym_rustic::{ast::Expression, polynomial::Polynomial};
+    ///
+    /// let x = Polynomial::generator(Expression::variable("x"));
+    /// let dividend = x.pow(3).add(&Polynomial::integer(2));
+    /// let (quotient, remainder) = dividend.div_rem(&x.add(&Polynomial::integer(1))).unwrap();
+    /// assert_eq!(quotient.to_string(), "(x^2) + -x + 1");
+    /// assert_eq!(remainder.to_string(), "1");
+    /// ```
+    pub fn div_rem(&self, divisor: &Polynomial) -> Result<(Polynomial, Polynomial), PolynomialError> {
+        let (generators, mut dividend, divisor) = self.unify(divisor);
+        let Some((lead, lead_coeff)) = divisor.iter().next_back() else {
+            return Err(PolynomialError::DivisionByZero);
+        };
+
+        let mut quotient = BTreeMap::new();
+        let mut remainder = BTreeMap::new();
+        while let Some((exponents, coeff)) = dividend.pop_last() {
+            if !exponents.iter().zip(lead).all(|(a, b)| a >= b) {
+                remainder.insert(exponents, coeff);
+                continue;
+            }
+            let shift: Vec<u32> = exponents.iter().zip(lead).map(|(a, b)| a - b).collect();
+            let factor = reduce(coeff.div(lead_coeff));
+            // Remove the product of the term and the divisor, its greatest term cancels
+            for (exponents, coeff) in divisor.iter().rev().skip(1) {
+                let exponents = exponents.iter().zip(&shift).map(|(a, b)| a + b).collect();
+                add_term(&mut dividend, exponents, coeff.mul(&factor).neg());
+            }
+            quotient.insert(shift, factor);
+        }
+
+        let quotient = Self {
+            generators: generators.clone(),
+            terms: quotient,
+        };
+        let remainder = Self {
+            generators,
+            terms: remainder,
+        };
+        Ok((quotient.normalize(), remainder.normalize()))
+    }
+
+    /// Write the polynomial as an `Expression`, the greatest term first.
+    pub fn to_expression(&self) -> Expression {
+        let mut terms: Vec<Expression> = self
+            .terms
+            .iter()
+            .rev()
+            .map(|(exponents, coeff)| {
+                let mut factors: Vec<Expression> = self
+                    .generators
+                    .iter()
+                    .zip(exponents)
+                    .filter(|(_, expo)| **expo > 0)
+                    .map(|(generator, expo)| match expo {
+                        1 => generator.clone(),
+                        expo => Expression::exponentiation(
+                            generator.clone(),
+                            Expression::integer(*expo),
+                        ),
+                    })
+                    .collect();
+                if factors.is_empty() || !coeff.abs().is_one() {
+                    factors.insert(0, Expression::Number(coeff.abs()));
+                }
+                let term = match factors.len() {
+                    1 => factors.remove(0),
+                    _ => Expression::multiplication(factors),
+                };
+                if coeff.is_negative() {
+                    Expression::negation(term)
+                } else {
+                    term
+                }
+            })
+            .collect();
+
+        match terms.len() {
+            0 => Expression::integer(0),
+            1 => terms.remove(0),
+            _ => Expression::addition(terms),
+        }
+    }
+
+    fn index_of(&self, variable: &Expression) -> Option<usize> {
+        self.generators.binary_search(variable).ok()
+    }
+
+    /// Write both polynomials over the union of their generators.
+    fn unify(&self, other: &Polynomial) -> (Vec<Expression>, Terms, Terms) {
+        if self.generators == other.generators {
+            return (
+                self.generators.clone(),
+                self.terms.clone(),
+                other.terms.clone(),
+            );
+        }
+        let mut generators = self.generators.clone();
+        generators.extend(other.generators.iter().cloned());
+        generators.sort();
+        generators.dedup();
+        (
+            generators.clone(),
+            self.terms_over(&generators),
+            other.terms_over(&generators),
+        )
+    }
+
+    /// The terms with the exponents of `generators`, that contains the generators of `self`.
+    fn terms_over(&self, generators: &[Expression]) -> Terms {
+        let indices: Vec<usize> = self
+            .generators
+            .iter()
+            .map(|generator| generators.binary_search(generator).unwrap())
+            .collect();
+        self.terms
+            .iter()
+            .map(|(exponents, coeff)| {
+                let mut spread = vec![0; generators.len()];
+                for (index, expo) in indices.iter().zip(exponents) {
+                    spread[*index] = *expo;
+                }
+                (spread, coeff.clone())
+            })
+            .collect()
+    }
+
+    /// Remove the generators that no longer appear in a term.
+    fn normalize(self) -> Self {
+        let used: Vec<bool> = (0..self.generators.len())
+            .map(|index| self.terms.keys().any(|exponents| exponents[index] > 0))
+            .collect();
+        if used.iter().all(|used| *used) {
+            return self;
+        }
+        let generators = self
+            .generators
+            .into_iter()
+            .zip(&used)
+            .filter(|(_, used)| **used)
+            .map(|(generator, _)| generator)
+            .collect();
+        let terms = self
+            .terms
+            .into_iter()
+            .map(|(exponents, coeff)| {
+                let exponents = exponents
+                    .into_iter()
+                    .zip(&used)
+                    .filter(|(_, used)| **used)
+                    .map(|(expo, _)| expo)
+                    .collect();
+                (exponents, coeff)
+            })
+            .collect();
+        Self { generators, terms }
+    }
+}
+
+impl std::fmt::Display for Polynomial {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.to_expression())
+    }
+}
+
+type Terms = BTreeMap<Vec<u32>, Numeral>;
+
+/// Add `coeff * generators^exponents` to the terms, removing it if it cancels.
+fn add_term(terms: &mut Terms, exponents: Vec<u32>, coeff: Numeral) {
+    let sum = match terms.remove(&exponents) {
+        Some(previous) => reduce(previous.add(&coeff)),
+        None => reduce(coeff),
+    };
+    if !sum.is_zero() {
+        terms.insert(exponents, sum);
+    }
+}
+
+/// Keep the rationals reduced with a positive denominator, an integer
+/// denominator is written as an `Integer`.
+fn reduce(value: Numeral) -> Numeral {
+    match value {
+        Numeral::Rational(n, d) => {
+            let gcd = n.gcd(&d);
+            let gcd = if d.is_negative() { -gcd } else { gcd };
+            let (n, d) = (n / &gcd, d / gcd);
+            if d.is_one() {
+                Numeral::Integer(n)
+            } else {
+                Numeral::Rational(n, d)
+            }
+        }
+        value => value,
+    }
+}
+
+/// The value of an integer written as a number or its negation.
+fn integer_value(expr: &Expression) -> Option<BigInt> {
+    match expr {
+        Expression::Number(Numeral::Integer(n)) => Some(n.clone()),
+        Expression::Number(Numeral::Rational(n, d)) if !d.is_zero() && n.is_multiple_of(d) => {
+            Some(n / d)
+        }
+        Expression::Negation(neg) => integer_value(&neg.term).map(|n| -n),
+        _ => None,
+    }
+}
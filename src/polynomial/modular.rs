@@ -0,0 +1,481 @@
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::{One, Signed, ToPrimitive, Zero};
+
+use super::PolynomialError;
+
+/// Coefficients `[c0, c1, ..., cn]` of a univariate polynomial with integer
+/// coefficients, the last one is not zero.
+pub(super) type Dense = Vec<BigInt>;
+
+/// Coefficients of a univariate polynomial modulo a prime, the last one is not zero.
+type Modular = Vec<u64>;
+
+/// The primes tried to factor a polynomial modulo a prime.
+const PRIMES: [u64; 30] = [
+    3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71, 73, 79, 83, 89, 97,
+    101, 103, 107, 109, 113, 127,
+];
+/// Number of suitable primes compared to keep the one with the fewest factors.
+const PRIME_TRIALS: usize = 5;
+/// Above this number of factors modulo the prime, the combinations are not tried.
+pub(super) const MAX_MODULAR_FACTORS: usize = 16;
+
+/// Factor a square-free primitive polynomial with a positive leading
+/// coefficient into irreducible polynomials over the integers.
+///
+/// Zassenhaus' algorithm: the polynomial is factored modulo a prime with
+/// Berlekamp's algorithm, the factors are lifted modulo a power of the prime
+/// large enough to bound the coefficients of the true factors and then combined.
+///
+/// Returns the factors and the prime used.
+pub(super) fn factor_square_free(f: &Dense) -> Result<(Vec<Dense>, Option<u64>), PolynomialError> {
+    let degree = f.len() - 1;
+    if degree <= 1 {
+        return Ok((vec![f.clone()], None));
+    }
+
+    let (p, factors) = PRIMES
+        .iter()
+        .filter_map(|p| {
+            let lead = (f[degree].mod_floor(&BigInt::from(*p))).to_u64()?;
+            let reduced = reduce(f, *p);
+            // The prime must keep the degree and the polynomial square-free
+            let square_free = gcd(&reduced, &derivative(&reduced, *p), *p).len() == 1;
+            (lead != 0 && square_free).then(|| (*p, berlekamp(&monic(&reduced, *p), *p)))
+        })
+        .take(PRIME_TRIALS)
+        .min_by_key(|(_, factors)| factors.len())
+        .ok_or(PolynomialError::TooManyFactors)?;
+    if factors.len() == 1 {
+        return Ok((vec![f.clone()], Some(p)));
+    }
+    if factors.len() > MAX_MODULAR_FACTORS {
+        return Err(PolynomialError::TooManyFactors);
+    }
+
+    // Any factor of lead*f has coefficients below 2^degree * |f| * lead
+    let lead = f[degree].clone();
+    let norm = f.iter().map(|coeff| coeff.abs()).max().unwrap_or_default();
+    let bound = BigInt::from(2) * (BigInt::one() << degree) * (degree + 1) * norm * &lead;
+    let mut k = 1;
+    let mut modulus = BigInt::from(p);
+    while modulus <= bound {
+        modulus *= p;
+        k += 1;
+    }
+
+    let lifted = hensel_lift(f, &factors, p, k);
+    Ok((recombine(f.clone(), lifted, &modulus), Some(p)))
+}
+
+/// Combine the lifted factors, the product of a subset times the leading
+/// coefficient is a factor over the integers when it divides the polynomial.
+fn recombine(mut f: Dense, mut lifted: Vec<Dense>, modulus: &BigInt) -> Vec<Dense> {
+    let mut factors = Vec::new();
+    let mut size = 1;
+    'sizes: while 2 * size <= lifted.len() {
+        for subset in combinations(lifted.len(), size) {
+            let lead = f.last().unwrap().clone();
+            let product = subset
+                .iter()
+                .fold(vec![lead], |acc, &i| symmetric(&mul(&acc, &lifted[i]), modulus));
+            let candidate = primitive(&product);
+            if let Some(quotient) = div_exact(&f, &candidate) {
+                factors.push(candidate);
+                f = quotient;
+                for i in subset.into_iter().rev() {
+                    lifted.remove(i);
+                }
+                continue 'sizes;
+            }
+        }
+        size += 1;
+    }
+    factors.push(primitive(&f));
+    factors
+}
+
+/// Lift `f = lead * factors[0] * ... (mod p)` to the monic factors modulo `p^k`.
+fn hensel_lift(f: &Dense, factors: &[Modular], p: u64, k: u32) -> Vec<Dense> {
+    let modulus = BigInt::from(p).pow(k);
+    let lead = f.last().unwrap();
+    if factors.len() == 1 {
+        let inverse = mod_inverse(lead, &modulus);
+        return vec![residue(&scale(f, &inverse), &modulus)];
+    }
+
+    let g = &factors[0];
+    let lead_p = lead.mod_floor(&BigInt::from(p)).to_u64().unwrap_or_default();
+    let h = factors[1..]
+        .iter()
+        .fold(vec![lead_p], |acc, factor| mul_mod(&acc, factor, p));
+    let (lifted_g, lifted_h) = lift_pair(f, g, &h, p, &modulus);
+
+    let mut lifted = vec![lifted_g];
+    lifted.extend(hensel_lift(&lifted_h, &factors[1..], p, k));
+    lifted
+}
+
+/// Linear Hensel lifting of `f = g * h (mod p)` with `g` monic to `f = G * H (mod modulus)`.
+fn lift_pair(f: &Dense, g: &Modular, h: &Modular, p: u64, modulus: &BigInt) -> (Dense, Dense) {
+    // s*g + t*h = 1 (mod p)
+    let (_, s, t) = extended_gcd(g, h, p);
+    let mut lifted_g: Dense = g.iter().map(|coeff| BigInt::from(*coeff)).collect();
+    let mut lifted_h: Dense = h.iter().map(|coeff| BigInt::from(*coeff)).collect();
+    let mut power = BigInt::from(p);
+    while &power < modulus {
+        let error = residue(&sub(f, &mul(&lifted_g, &lifted_h)), modulus);
+        let error: Modular = trim_modular(
+            error
+                .iter()
+                .map(|coeff| (coeff / &power).mod_floor(&BigInt::from(p)).to_u64().unwrap_or_default())
+                .collect(),
+        );
+        // delta_g*h + g*delta_h = error (mod p) with deg(delta_g) < deg(g)
+        let (quotient, delta_g) = div_rem_mod(&mul_mod(&t, &error, p), g, p);
+        let delta_h = add_mod(&mul_mod(&s, &error, p), &mul_mod(&quotient, h, p), p);
+        lifted_g = add(&lifted_g, &scale(&lift(&delta_g), &power));
+        lifted_h = add(&lifted_h, &scale(&lift(&delta_h), &power));
+        power *= p;
+    }
+    (residue(&lifted_g, modulus), residue(&lifted_h, modulus))
+}
+
+/// Berlekamp's algorithm, the factors of a monic square-free polynomial modulo `p`.
+fn berlekamp(f: &Modular, p: u64) -> Vec<Modular> {
+    let n = f.len() - 1;
+    // Row i of Q is x^(p*i) mod f
+    let x_p = pow_mod(&[0, 1], p, f, p);
+    let mut rows = vec![vec![1]];
+    for i in 1..n {
+        rows.push(div_rem_mod(&mul_mod(&rows[i - 1], &x_p, p), f, p).1);
+    }
+    // The polynomials v with v^p = v (mod f) are the kernel of (Q - I)^T
+    let mut matrix = vec![vec![0; n]; n];
+    for (i, row) in rows.iter().enumerate() {
+        for (j, entry) in matrix.iter_mut().enumerate() {
+            entry[i] = row.get(j).copied().unwrap_or(0);
+        }
+        matrix[i][i] = (matrix[i][i] + p - 1) % p;
+    }
+    let basis = kernel(matrix, p);
+
+    let mut factors = vec![f.clone()];
+    for v in basis.iter().filter(|v| v.len() > 1) {
+        if factors.len() == basis.len() {
+            break;
+        }
+        factors = factors
+            .into_iter()
+            .flat_map(|factor| {
+                if factor.len() <= 2 {
+                    return vec![factor];
+                }
+                // factor is the product of the gcd(factor, v - s)
+                (0..p)
+                    .filter_map(|s| {
+                        let mut shifted = v.clone();
+                        shifted[0] = (shifted[0] + p - s) % p;
+                        let divisor = gcd(&factor, &trim_modular(shifted), p);
+                        (divisor.len() > 1).then_some(divisor)
+                    })
+                    .collect()
+            })
+            .collect();
+    }
+    factors
+}
+
+/// A basis of the vectors `v` with `matrix * v = 0 (mod p)`.
+fn kernel(mut matrix: Vec<Vec<u64>>, p: u64) -> Vec<Modular> {
+    let n = matrix.len();
+    let mut pivots = Vec::new();
+    let mut row = 0;
+    for col in 0..n {
+        let Some(pivot) = (row..n).find(|&i| matrix[i][col] != 0) else {
+            continue;
+        };
+        matrix.swap(row, pivot);
+        let inverse = inverse_mod(matrix[row][col], p);
+        for entry in matrix[row].iter_mut() {
+            *entry = *entry * inverse % p;
+        }
+        let pivot_row = matrix[row].clone();
+        for (i, other) in matrix.iter_mut().enumerate() {
+            let factor = other[col];
+            if i != row && factor != 0 {
+                for (entry, pivot) in other.iter_mut().zip(&pivot_row) {
+                    *entry = (*entry + p * p - factor * pivot) % p;
+                }
+            }
+        }
+        pivots.push(col);
+        row += 1;
+    }
+
+    (0..n)
+        .filter(|col| !pivots.contains(col))
+        .map(|free| {
+            let mut v = vec![0; n];
+            v[free] = 1;
+            for (row, pivot) in pivots.iter().enumerate() {
+                v[*pivot] = (p - matrix[row][free]) % p;
+            }
+            trim_modular(v)
+        })
+        .collect()
+}
+
+/// The subsets of `size` indices below `n`, in increasing order.
+pub(super) fn combinations(n: usize, size: usize) -> Vec<Vec<usize>> {
+    if size == 0 {
+        return vec![Vec::new()];
+    }
+    (size - 1..n)
+        .flat_map(|last| {
+            combinations(last, size - 1).into_iter().map(move |mut subset| {
+                subset.push(last);
+                subset
+            })
+        })
+        .collect()
+}
+
+// Integer polynomials
+
+fn trim(mut f: Dense) -> Dense {
+    while f.last().is_some_and(|coeff| coeff.is_zero()) {
+        f.pop();
+    }
+    f
+}
+
+fn add(lhs: &Dense, rhs: &Dense) -> Dense {
+    let mut sum = vec![BigInt::zero(); lhs.len().max(rhs.len())];
+    for (i, coeff) in lhs.iter().enumerate() {
+        sum[i] += coeff;
+    }
+    for (i, coeff) in rhs.iter().enumerate() {
+        sum[i] += coeff;
+    }
+    trim(sum)
+}
+
+fn sub(lhs: &Dense, rhs: &Dense) -> Dense {
+    add(lhs, &scale(rhs, &-BigInt::one()))
+}
+
+fn mul(lhs: &Dense, rhs: &Dense) -> Dense {
+    if lhs.is_empty() || rhs.is_empty() {
+        return Vec::new();
+    }
+    let mut product = vec![BigInt::zero(); lhs.len() + rhs.len() - 1];
+    for (i, a) in lhs.iter().enumerate() {
+        for (j, b) in rhs.iter().enumerate() {
+            product[i + j] += a * b;
+        }
+    }
+    trim(product)
+}
+
+fn scale(f: &Dense, factor: &BigInt) -> Dense {
+    trim(f.iter().map(|coeff| coeff * factor).collect())
+}
+
+/// Divide by the gcd of the coefficients and make the leading coefficient positive.
+fn primitive(f: &Dense) -> Dense {
+    let content = f.iter().fold(BigInt::zero(), |acc, coeff| acc.gcd(coeff));
+    let content = if f.last().is_some_and(|lead| lead.is_negative()) {
+        -content
+    } else {
+        content
+    };
+    f.iter().map(|coeff| coeff / &content).collect()
+}
+
+/// The quotient of `f` by `divisor` if the division over the integers is exact.
+fn div_exact(f: &Dense, divisor: &Dense) -> Option<Dense> {
+    if divisor.len() > f.len() {
+        return None;
+    }
+    let mut rest = f.clone();
+    let mut quotient = vec![BigInt::zero(); f.len() - divisor.len() + 1];
+    let lead = divisor.last()?;
+    for i in (0..quotient.len()).rev() {
+        let (coeff, remainder) = rest[i + divisor.len() - 1].div_rem(lead);
+        if !remainder.is_zero() {
+            return None;
+        }
+        for (j, d) in divisor.iter().enumerate() {
+            rest[i + j] -= &coeff * d;
+        }
+        quotient[i] = coeff;
+    }
+    rest.iter().all(|coeff| coeff.is_zero()).then(|| trim(quotient))
+}
+
+/// The coefficients reduced in `[0, modulus)`.
+fn residue(f: &Dense, modulus: &BigInt) -> Dense {
+    trim(f.iter().map(|coeff| coeff.mod_floor(modulus)).collect())
+}
+
+/// The coefficients reduced in `(-modulus/2, modulus/2]`.
+fn symmetric(f: &Dense, modulus: &BigInt) -> Dense {
+    let half = modulus / 2;
+    trim(
+        f.iter()
+            .map(|coeff| {
+                let coeff = coeff.mod_floor(modulus);
+                if coeff > half { coeff - modulus } else { coeff }
+            })
+            .collect(),
+    )
+}
+
+fn lift(f: &Modular) -> Dense {
+    f.iter().map(|coeff| BigInt::from(*coeff)).collect()
+}
+
+fn reduce(f: &Dense, p: u64) -> Modular {
+    let p = BigInt::from(p);
+    trim_modular(
+        f.iter()
+            .map(|coeff| coeff.mod_floor(&p).to_u64().unwrap_or_default())
+            .collect(),
+    )
+}
+
+/// The inverse of `a` modulo `modulus`, they are coprime.
+fn mod_inverse(a: &BigInt, modulus: &BigInt) -> BigInt {
+    let extended = a.mod_floor(modulus).extended_gcd(modulus);
+    extended.x.mod_floor(modulus)
+}
+
+// Polynomials modulo p
+
+fn trim_modular(mut f: Modular) -> Modular {
+    while f.last() == Some(&0) {
+        f.pop();
+    }
+    f
+}
+
+fn inverse_mod(a: u64, p: u64) -> u64 {
+    // Fermat's little theorem
+    let (mut base, mut expo, mut result) = (a % p, p - 2, 1);
+    while expo > 0 {
+        if expo % 2 == 1 {
+            result = result * base % p;
+        }
+        base = base * base % p;
+        expo /= 2;
+    }
+    result
+}
+
+fn add_mod(lhs: &Modular, rhs: &Modular, p: u64) -> Modular {
+    let mut sum = vec![0; lhs.len().max(rhs.len())];
+    for (i, coeff) in lhs.iter().enumerate() {
+        sum[i] = coeff % p;
+    }
+    for (i, coeff) in rhs.iter().enumerate() {
+        sum[i] = (sum[i] + coeff) % p;
+    }
+    trim_modular(sum)
+}
+
+fn mul_mod(lhs: &Modular, rhs: &Modular, p: u64) -> Modular {
+    if lhs.is_empty() || rhs.is_empty() {
+        return Vec::new();
+    }
+    let mut product = vec![0; lhs.len() + rhs.len() - 1];
+    for (i, a) in lhs.iter().enumerate() {
+        for (j, b) in rhs.iter().enumerate() {
+            product[i + j] = (product[i + j] + a * b) % p;
+        }
+    }
+    trim_modular(product)
+}
+
+fn div_rem_mod(f: &Modular, divisor: &Modular, p: u64) -> (Modular, Modular) {
+    if f.len() < divisor.len() {
+        return (Vec::new(), f.clone());
+    }
+    let mut rest = f.clone();
+    let mut quotient = vec![0; f.len() - divisor.len() + 1];
+    let inverse = inverse_mod(*divisor.last().unwrap(), p);
+    for i in (0..quotient.len()).rev() {
+        let coeff = rest[i + divisor.len() - 1] * inverse % p;
+        for (j, d) in divisor.iter().enumerate() {
+            rest[i + j] = (rest[i + j] + p * p - coeff * d) % p;
+        }
+        quotient[i] = coeff;
+    }
+    (trim_modular(quotient), trim_modular(rest))
+}
+
+fn monic(f: &Modular, p: u64) -> Modular {
+    let inverse = inverse_mod(*f.last().unwrap(), p);
+    f.iter().map(|coeff| coeff * inverse % p).collect()
+}
+
+/// The monic gcd modulo `p`.
+fn gcd(lhs: &Modular, rhs: &Modular, p: u64) -> Modular {
+    let (mut a, mut b) = (lhs.clone(), rhs.clone());
+    while !b.is_empty() {
+        let remainder = div_rem_mod(&a, &b, p).1;
+        a = b;
+        b = remainder;
+    }
+    monic(&a, p)
+}
+
+/// Returns `(gcd, s, t)` with `s*lhs + t*rhs = gcd (mod p)` and the gcd monic.
+fn extended_gcd(lhs: &Modular, rhs: &Modular, p: u64) -> (Modular, Modular, Modular) {
+    let (mut a, mut b) = (lhs.clone(), rhs.clone());
+    let (mut s0, mut s1) = (vec![1], Vec::new());
+    let (mut t0, mut t1) = (Vec::new(), vec![1]);
+    while !b.is_empty() {
+        let (quotient, remainder) = div_rem_mod(&a, &b, p);
+        let s2 = add_mod(&s0, &negate_mod(&mul_mod(&quotient, &s1, p), p), p);
+        let t2 = add_mod(&t0, &negate_mod(&mul_mod(&quotient, &t1, p), p), p);
+        (a, b) = (b, remainder);
+        (s0, s1) = (s1, s2);
+        (t0, t1) = (t1, t2);
+    }
+    let inverse = vec![inverse_mod(*a.last().unwrap(), p)];
+    (
+        mul_mod(&a, &inverse, p),
+        mul_mod(&s0, &inverse, p),
+        mul_mod(&t0, &inverse, p),
+    )
+}
+
+fn negate_mod(f: &Modular, p: u64) -> Modular {
+    f.iter().map(|coeff| (p - coeff) % p).collect()
+}
+
+fn derivative(f: &Modular, p: u64) -> Modular {
+    trim_modular(
+        f.iter()
+            .enumerate()
+            .skip(1)
+            .map(|(i, coeff)| (i as u64 % p) * coeff % p)
+            .collect(),
+    )
+}
+
+/// `base^expo mod (modulus, p)` by squaring.
+fn pow_mod(base: &[u64], mut expo: u64, modulus: &Modular, p: u64) -> Modular {
+    let mut base = div_rem_mod(&base.to_vec(), modulus, p).1;
+    let mut result = vec![1];
+    while expo > 0 {
+        if expo % 2 == 1 {
+            result = div_rem_mod(&mul_mod(&result, &base, p), modulus, p).1;
+        }
+        base = div_rem_mod(&mul_mod(&base, &base, p), modulus, p).1;
+        expo /= 2;
+    }
+    result
+}
@@ -0,0 +1,248 @@
+use num_bigint::BigInt;
+use num_traits::{One, Signed, ToPrimitive};
+
+use crate::{
+    ast::{Expression, SimplifyError, numeral::Numeral},
+    explanation::FormattingObserver,
+};
+
+use super::{MAX_DEGREE, Polynomial, PolynomialError, integer_value};
+
+/// Quotient of two polynomials, the denominator is primitive and never zero.
+#[derive(Debug, Clone)]
+pub(super) struct RationalFunction {
+    num: Polynomial,
+    den: Polynomial,
+}
+
+impl RationalFunction {
+    fn new(num: Polynomial, den: Polynomial) -> Result<Self, PolynomialError> {
+        if den.is_zero() {
+            return Err(PolynomialError::DivisionByZero);
+        }
+        // Move the content of the denominator to the numerator
+        let (content, den) = den.primitive();
+        let num = num.scale(&Numeral::Integer(BigInt::one()).div(&content));
+        Ok(Self { num, den })
+    }
+
+    fn polynomial(num: Polynomial) -> Self {
+        Self {
+            num,
+            den: Polynomial::integer(1),
+        }
+    }
+
+    /// Read an expression as a quotient of polynomials, the sums are put over
+    /// their least common denominator but no common factor is cancelled.
+    pub(super) fn from_expression(expr: &Expression) -> Result<Self, PolynomialError> {
+        match expr {
+            Expression::Negation(neg) => {
+                let inner = Self::from_expression(&neg.term)?;
+                Ok(Self {
+                    num: inner.num.neg(),
+                    den: inner.den,
+                })
+            }
+            Expression::Addition(add) => add
+                .terms
+                .iter()
+                .try_fold(Self::polynomial(Polynomial::zero()), |acc, term| {
+                    acc.add(&Self::from_expression(term)?)
+                }),
+            Expression::Subtraction(sub) => {
+                let right = Self::from_expression(&sub.right)?;
+                Self::from_expression(&sub.left)?.add(&Self {
+                    num: right.num.neg(),
+                    den: right.den,
+                })
+            }
+            Expression::Multiplication(mul) => mul
+                .terms
+                .iter()
+                .try_fold(Self::polynomial(Polynomial::integer(1)), |acc, term| {
+                    acc.mul(&Self::from_expression(term)?)
+                }),
+            Expression::Division(div) => {
+                Self::from_expression(&div.num)?.mul(&Self::from_expression(&div.den)?.inverse()?)
+            }
+            Expression::Exponentiation(exp) => {
+                let Some(expo) = integer_value(&exp.expo) else {
+                    return Ok(Self::polynomial(Polynomial::from_expression(expr)?));
+                };
+                let degree = expo
+                    .abs()
+                    .to_u32()
+                    .filter(|degree| *degree <= MAX_DEGREE)
+                    .ok_or(PolynomialError::DegreeTooLarge)?;
+                let base = Self::from_expression(&exp.base)?;
+                let power = Self::new(base.num.pow(degree), base.den.pow(degree))?;
+                if expo.is_negative() {
+                    power.inverse()
+                } else {
+                    Ok(power)
+                }
+            }
+            expr => Ok(Self::polynomial(Polynomial::from_expression(expr)?)),
+        }
+    }
+
+    fn add(&self, other: &Self) -> Result<Self, PolynomialError> {
+        let (_, lhs, rhs) = self.den.cofactors(&other.den);
+        Self::new(
+            self.num.mul(&rhs).add(&other.num.mul(&lhs)),
+            self.den.mul(&rhs),
+        )
+    }
+
+    fn mul(&self, other: &Self) -> Result<Self, PolynomialError> {
+        Self::new(self.num.mul(&other.num), self.den.mul(&other.den))
+    }
+
+    fn inverse(&self) -> Result<Self, PolynomialError> {
+        Self::new(self.den.clone(), self.num.clone())
+    }
+
+    /// Divide the numerator and the denominator by their gcd, returns the
+    /// cancelled factor if it is not a constant.
+    fn cancel(&mut self) -> Option<Polynomial> {
+        let (gcd, num, den) = self.num.cofactors(&self.den);
+        if gcd.total_degree().unwrap_or(0) == 0 {
+            return None;
+        }
+        *self = Self::new(num, den).expect("a divisor of a nonzero polynomial is not zero");
+        Some(gcd)
+    }
+
+    fn to_expression(&self) -> Expression {
+        let (num, den) = self.integer_parts();
+        if den.is_one() {
+            num.to_expression()
+        } else {
+            Expression::division(num.to_expression(), den.to_expression())
+        }
+    }
+
+    /// The numerator and the denominator with integer coefficients, `(1/2)*x`
+    /// becomes `x/2`.
+    pub(super) fn integer_parts(&self) -> (Polynomial, Polynomial) {
+        let (content, num) = self.num.primitive();
+        let (factor, multiple) = match content {
+            Numeral::Rational(n, d) => (n, d),
+            Numeral::Integer(n) => (n, BigInt::one()),
+            Numeral::Float(_) => unreachable!("the coefficients are exact"),
+        };
+        let num = num.scale(&Numeral::Integer(factor));
+        let den = self.den.scale(&Numeral::Integer(multiple));
+        (num, den)
+    }
+}
+
+impl From<PolynomialError> for SimplifyError {
+    fn from(error: PolynomialError) -> Self {
+        match error {
+            PolynomialError::DivisionByZero => SimplifyError::DivisionByZero,
+            PolynomialError::NotPolynomial
+            | PolynomialError::InexactCoefficient
+            | PolynomialError::DegreeTooLarge
+            | PolynomialError::TooManyFactors => SimplifyError::Unsupported,
+        }
+    }
+}
+
+impl Expression {
+    /// Write the expression as a single fraction over the least common
+    /// denominator, the numerator and the denominator are expanded.
+    ///
+    /// The common factors of the numerator and the denominator are kept, use
+    /// `cancel` to remove them.
+    ///
+    /// # Examples
+    /// ```
+    /// use sym_rustic::ast::Expression;
+    ///
+    /// let x = Expression::variable("x");
+    /// let expr = Expression::addition(vec![
+    ///     Expression::division(Expression::integer(1), x.clone()),
+    ///     Expression::division(Expression::integer(1), Expression::integer(2)),
+    /// ]);
+    /// assert_eq!(expr.together(&mut None).unwrap().to_string(), "(x + 2)/(2 * x)");
+    /// ```
+    pub fn together(
+        &self,
+        explanation: &mut Option<Box<FormattingObserver>>,
+    ) -> Result<Expression, SimplifyError> {
+        let after = RationalFunction::from_expression(self)?.to_expression();
+        if let Some(explanation) = explanation {
+            explanation.rule_applied("Put the terms over a common denominator", self, &after);
+        }
+        Ok(after)
+    }
+
+    /// Write the expression as a fraction of expanded polynomials without
+    /// common factor.
+    ///
+    /// # Examples
+    /// ```
+    /// use sym_rustic::ast::Expression;
+    ///
+    /// let x = Expression::variable("x");
+    /// let expr = Expression::division(
+    ///     Expression::subtraction(
+    ///         Expression::exponentiation(x.clone(), Expression::integer(2)),
+    ///         Expression::integer(1),
+    ///     ),
+    ///     Expression::subtraction(x.clone(), Expression::integer(1)),
+    /// );
+    /// assert_eq!(expr.cancel(&mut None).unwrap().to_string(), "x + 1");
+    /// ```
+    pub fn cancel(
+        &self,
+        explanation: &mut Option<Box<FormattingObserver>>,
+    ) -> Result<Expression, SimplifyError> {
+        let mut fraction = RationalFunction::from_expression(self)?;
+        let together = fraction.to_expression();
+        if let Some(explanation) = explanation
+            && !together.is_equal(self)
+        {
+            explanation.rule_applied("Put the terms over a common denominator", self, &together);
+        }
+        match fraction.cancel() {
+            Some(gcd) => {
+                let after = fraction.to_expression();
+                if let Some(explanation) = explanation {
+                    explanation.rule_applied(
+                        &format!("Simplify by the common factor {}", factor_name(&gcd)),
+                        &together,
+                        &after,
+                    );
+                }
+                Ok(after)
+            }
+            None => Ok(together),
+        }
+    }
+
+    /// Cancel the common factor of a numerator and a denominator that are
+    /// polynomials, returns `None` when they have none.
+    pub(crate) fn cancel_fraction(
+        num: &Expression,
+        den: &Expression,
+    ) -> Option<(Expression, Expression, Expression)> {
+        let num = Polynomial::from_expression(num).ok()?;
+        let den = Polynomial::from_expression(den).ok()?;
+        if den.is_zero() {
+            return None;
+        }
+        let mut fraction = RationalFunction { num, den };
+        let gcd = fraction.cancel()?;
+        let (num, den) = fraction.integer_parts();
+        Some((num.to_expression(), den.to_expression(), factor_name(&gcd)))
+    }
+}
+
+/// The simplified factor to name it in the explanation.
+pub(super) fn factor_name(factor: &Polynomial) -> Expression {
+    let expr = factor.to_expression();
+    expr.clone().simplify(&mut None).unwrap_or(expr)
+}
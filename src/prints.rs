@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use crate::ast::{Expression, constant, numeral::Numeral};
+use crate::ast::Expression;
 
 pub trait PrettyPrints: std::fmt::Display {
     // Printing methods
@@ -21,11 +21,11 @@ pub trait PrettyPrints: std::fmt::Display {
     ///
     /// # Exemple
     /// ```
-    /// use sym_rustic::ast::Expression;
+    /// use sym_rustic::{ast::Expression, prints::PrettyPrints};
     ///
-    /// let expr = Expression::Addition(vec![
-    ///         Expression::Multiplication(vec![
-    ///             Expression::Variable("x".to_string()),
+    /// let expr = Expression::addition(vec![
+    ///         Expression::multiplication(vec![
+    ///             Expression::variable("x"),
     ///             Expression::integer(2)
     ///         ]),
     ///         Expression::integer(5)
@@ -46,17 +46,17 @@ pub trait PrettyPrints: std::fmt::Display {
     ///
     /// # Exemple
     /// ```
-    /// use sym_rustic::ast::Expression;
+    /// use sym_rustic::{ast::Expression, prints::PrettyPrints};
     ///
-    /// let expr = Expression::Addition(vec![
-    ///         Expression::Exponentiation(
-    ///             Box::new(Expression::Variable("x".to_string())),
-    ///             Box::new(Expression::integer(2))
+    /// let expr = Expression::addition(vec![
+    ///         Expression::exponentiation(
+    ///             Expression::variable("x"),
+    ///             Expression::integer(2)
     ///         ),
     ///         Expression::integer(5)
     ///     ]);
     /// expr.print_console();
-    /// let result = " 2    \nx  + 5";
+    /// assert_eq!(expr.get_processed(), " 2    \nx  + 5");
     /// ```
     fn print_console(&self) {
         println!("{}\n", self.get_processed())
@@ -138,9 +138,21 @@ impl PrettyPrints for Expression {
             Expression::Division(division) =>division.calculate_tree(indent),
             Expression::Exponentiation(exponentiation) => exponentiation.calculate_tree(indent),
             Expression::Equality(equality) => equality.calculate_tree(indent),
+            Expression::Inequality(inequality) => inequality.calculate_tree(indent),
             Expression::Complex(complex) => complex.calculate_tree(indent),
             Expression::Function(function) => function.calculate_tree(indent),
             Expression::Derivative(derivative) => derivative.calculate_tree(indent),
+            Expression::Integral(integral) => integral.calculate_tree(indent),
+            Expression::Limit(limit) => limit.calculate_tree(indent),
+            Expression::Series(series) => series.calculate_tree(indent),
+            Expression::Summation(sum) => sum.calculate_tree(indent),
+            Expression::Product(prod) => prod.calculate_tree(indent),
+            Expression::Matrix(matrix) => matrix.calculate_tree(indent),
+            Expression::Vector(vector) => vector.calculate_tree(indent),
+            Expression::And(and) => and.calculate_tree(indent),
+            Expression::Or(or) => or.calculate_tree(indent),
+            Expression::Not(not) => not.calculate_tree(indent),
+            Expression::Xor(xor) => xor.calculate_tree(indent),
         }
     }
 
@@ -161,9 +173,21 @@ impl PrettyPrints for Expression {
             Expression::Division(division) => division.calculate_positions(memoization, position, prev_pos),
             Expression::Exponentiation(exponentiation) => exponentiation.calculate_positions(memoization, position, prev_pos),
             Expression::Equality(equality) => equality.calculate_positions(memoization, position, prev_pos),
+            Expression::Inequality(inequality) => inequality.calculate_positions(memoization, position, prev_pos),
             Expression::Complex(complex) => complex.calculate_positions(memoization, position, prev_pos),
             Expression::Function(function) => function.calculate_positions(memoization, position, prev_pos),
             Expression::Derivative(derivative) => derivative.calculate_positions(memoization, position, prev_pos),
+            Expression::Integral(integral) => integral.calculate_positions(memoization, position, prev_pos),
+            Expression::Limit(limit) => limit.calculate_positions(memoization, position, prev_pos),
+            Expression::Series(series) => series.calculate_positions(memoization, position, prev_pos),
+            Expression::Summation(sum) => sum.calculate_positions(memoization, position, prev_pos),
+            Expression::Product(prod) => prod.calculate_positions(memoization, position, prev_pos),
+            Expression::Matrix(matrix) => matrix.calculate_positions(memoization, position, prev_pos),
+            Expression::Vector(vector) => vector.calculate_positions(memoization, position, prev_pos),
+            Expression::And(and) => and.calculate_positions(memoization, position, prev_pos),
+            Expression::Or(or) => or.calculate_positions(memoization, position, prev_pos),
+            Expression::Not(not) => not.calculate_positions(memoization, position, prev_pos),
+            Expression::Xor(xor) => xor.calculate_positions(memoization, position, prev_pos),
         }
     }
 
@@ -179,17 +203,29 @@ impl PrettyPrints for Expression {
             Expression::Division(division) => division.get_below_height(memoization),
             Expression::Exponentiation(exponentiation) => exponentiation.get_below_height(memoization),
             Expression::Equality(equality) => equality.get_below_height(memoization),
+            Expression::Inequality(inequality) => inequality.get_below_height(memoization),
             Expression::Complex(complex) => complex.get_below_height(memoization),
             Expression::Function(function) => function.get_below_height(memoization),
             Expression::Derivative(derivative) => derivative.get_below_height(memoization),
+            Expression::Integral(integral) => integral.get_below_height(memoization),
+            Expression::Limit(limit) => limit.get_below_height(memoization),
+            Expression::Series(series) => series.get_below_height(memoization),
+            Expression::Summation(sum) => sum.get_below_height(memoization),
+            Expression::Product(prod) => prod.get_below_height(memoization),
+            Expression::Matrix(matrix) => matrix.get_below_height(memoization),
+            Expression::Vector(vector) => vector.get_below_height(memoization),
+            Expression::And(and) => and.get_below_height(memoization),
+            Expression::Or(or) => or.get_below_height(memoization),
+            Expression::Not(not) => not.get_below_height(memoization),
+            Expression::Xor(xor) => xor.get_below_height(memoization),
         }
     }
 
     fn get_height(&self, memoization: &mut HashMap<Expression, (usize, usize)>) -> usize {
-        if let Some((height, _length)) = memoization.get(self) {
-            if *height != 0 {
-                return *height;
-            }
+        if let Some((height, _length)) = memoization.get(self)
+            && *height != 0
+        {
+            return *height;
         }
         
         let height = match self {
@@ -203,9 +239,21 @@ impl PrettyPrints for Expression {
             Expression::Division(division) => division.get_height(memoization),
             Expression::Exponentiation(exponentiation) => exponentiation.get_height(memoization),
             Expression::Equality(equality) => equality.get_height(memoization),
+            Expression::Inequality(inequality) => inequality.get_height(memoization),
             Expression::Complex(complex) => complex.get_height(memoization),
             Expression::Function(function) => function.get_height(memoization),
             Expression::Derivative(derivative) => derivative.get_height(memoization),
+            Expression::Integral(integral) => integral.get_height(memoization),
+            Expression::Limit(limit) => limit.get_height(memoization),
+            Expression::Series(series) => series.get_height(memoization),
+            Expression::Summation(sum) => sum.get_height(memoization),
+            Expression::Product(prod) => prod.get_height(memoization),
+            Expression::Matrix(matrix) => matrix.get_height(memoization),
+            Expression::Vector(vector) => vector.get_height(memoization),
+            Expression::And(and) => and.get_height(memoization),
+            Expression::Or(or) => or.get_height(memoization),
+            Expression::Not(not) => not.get_height(memoization),
+            Expression::Xor(xor) => xor.get_height(memoization),
         };
 
         if let Some((h, _l)) = memoization.get_mut(self) {
@@ -218,10 +266,10 @@ impl PrettyPrints for Expression {
     }
 
     fn get_length(&self, memoization: &mut HashMap<Expression, (usize, usize)>) -> usize {
-        if let Some((_height, length)) = memoization.get(self) {
-            if *length != 0 {
-                return *length;
-            }
+        if let Some((_height, length)) = memoization.get(self)
+            && *length != 0
+        {
+            return *length;
         }
         
         let length = match self {
@@ -235,9 +283,21 @@ impl PrettyPrints for Expression {
             Expression::Division(division) => division.get_length(memoization),
             Expression::Exponentiation(exponentiation) => exponentiation.get_length(memoization),
             Expression::Equality(equality) => equality.get_length(memoization),
+            Expression::Inequality(inequality) => inequality.get_length(memoization),
             Expression::Complex(complex) => complex.get_length(memoization),
             Expression::Function(function) => function.get_length(memoization),
             Expression::Derivative(derivative) => derivative.get_length(memoization),
+            Expression::Integral(integral) => integral.get_length(memoization),
+            Expression::Limit(limit) => limit.get_length(memoization),
+            Expression::Series(series) => series.get_length(memoization),
+            Expression::Summation(sum) => sum.get_length(memoization),
+            Expression::Product(prod) => prod.get_length(memoization),
+            Expression::Matrix(matrix) => matrix.get_length(memoization),
+            Expression::Vector(vector) => vector.get_length(memoization),
+            Expression::And(and) => and.get_length(memoization),
+            Expression::Or(or) => or.get_length(memoization),
+            Expression::Not(not) => not.get_length(memoization),
+            Expression::Xor(xor) => xor.get_length(memoization),
         };
 
         if let Some((_h, l)) = memoization.get_mut(self) {
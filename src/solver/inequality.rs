@@ -0,0 +1,594 @@
+use std::{cmp::Ordering, collections::HashMap};
+
+use num_complex::Complex64;
+use num_traits::Signed;
+
+use crate::{
+    ast::{Expression, SimplifyError, numeral::Numeral},
+    explanation::FormattingObserver,
+};
+
+use super::{
+    Solution, Solver, SolverError, add_polynomials, exact_value, fold, is_one, is_zero,
+    mul_polynomials, neg_coefficient, polynomial_coefficients, polynomial_expression,
+};
+
+/// Imaginary parts smaller than this are dropped when deciding if a root is real.
+const REAL_TOLERANCE: f64 = 1e-9;
+
+/// An end of an interval of real numbers.
+#[derive(Debug, PartialEq, Clone)]
+pub enum Bound {
+    /// `-inf` for a lower bound and `inf` for an upper bound
+    Unbounded,
+    Open(Expression),
+    Closed(Expression),
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Interval {
+    pub lower: Bound,
+    pub upper: Bound,
+}
+
+impl Interval {
+    /// Returns `true` if `value` is in the interval, the bounds are evaluated numerically.
+    pub fn contains(&self, value: f64) -> bool {
+        let env = HashMap::new();
+        let above = match &self.lower {
+            Bound::Unbounded => true,
+            Bound::Open(lower) => lower.evaluate(&env).is_ok_and(|lower| value > lower),
+            Bound::Closed(lower) => lower.evaluate(&env).is_ok_and(|lower| value >= lower),
+        };
+        let below = match &self.upper {
+            Bound::Unbounded => true,
+            Bound::Open(upper) => upper.evaluate(&env).is_ok_and(|upper| value < upper),
+            Bound::Closed(upper) => upper.evaluate(&env).is_ok_and(|upper| value <= upper),
+        };
+        above && below
+    }
+}
+
+impl std::fmt::Display for Interval {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match (&self.lower, &self.upper) {
+            (Bound::Closed(lower), Bound::Closed(upper)) if lower == upper => {
+                write!(f, "{{{}}}", lower)
+            }
+            (lower, upper) => {
+                match lower {
+                    Bound::Unbounded => write!(f, "(-inf")?,
+                    Bound::Open(lower) => write!(f, "({}", lower)?,
+                    Bound::Closed(lower) => write!(f, "[{}", lower)?,
+                }
+                match upper {
+                    Bound::Unbounded => write!(f, ", inf)"),
+                    Bound::Open(upper) => write!(f, ", {})", upper),
+                    Bound::Closed(upper) => write!(f, ", {}]", upper),
+                }
+            }
+        }
+    }
+}
+
+/// Solution of an inequality, a union of disjoint intervals in increasing order.
+#[derive(Debug, PartialEq, Clone)]
+pub struct SolutionSet {
+    pub intervals: Vec<Interval>,
+}
+
+impl SolutionSet {
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    pub fn contains(&self, value: f64) -> bool {
+        self.intervals.iter().any(|interval| interval.contains(value))
+    }
+}
+
+impl std::fmt::Display for SolutionSet {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        if self.intervals.is_empty() {
+            return write!(f, "∅");
+        }
+        let intervals: Vec<String> = self
+            .intervals
+            .iter()
+            .map(|interval| interval.to_string())
+            .collect();
+        write!(f, "{}", intervals.join(" ∪ "))
+    }
+}
+
+/// A zero of the numerator or of the denominator, where the sign can change.
+struct CriticalPoint {
+    value: Expression,
+    approximation: f64,
+    /// Zero of the denominator, the expression is undefined there
+    pole: bool,
+}
+
+// Inequalities
+impl Solver {
+    /// Solves the inequality for the specified variable.
+    ///
+    /// Every term is moved to the left side and written as a single fraction `p/q`.
+    /// The zeros of `p` and `q` cut the real line in intervals where the sign is
+    /// constant, a sign chart gives the intervals that satisfy the inequality.
+    ///
+    /// The variable must be `Expression::Variable` type and the inequality
+    /// `Expression::Inequality` type. The coefficients must be numerical.
+    ///
+    /// # Examples
+    /// ```
+    /// use sym_rustic::ast::{Expression, inequality::Relation};
+    /// use sym_rustic::solver::Solver;
+    ///
+    /// // x^2 < 4
+    /// let inequality = Expression::inequality(
+    ///     Expression::exponentiation(Expression::variable("x"), Expression::integer(2)),
+    ///     Relation::Less,
+    ///     Expression::integer(4),
+    /// );
+    ///
+    /// let solution = Solver::solve_inequality(inequality, Expression::variable("x"), &mut None);
+    /// assert_eq!(solution.unwrap().to_string(), "(-2, 2)");
+    /// ```
+    pub fn solve_inequality(
+        inequality: Expression,
+        variable: Expression,
+        explanation: &mut Option<Box<FormattingObserver>>,
+    ) -> Result<SolutionSet, SolverError> {
+        let Expression::Variable(var) = variable else {
+            return Err(SolverError::InvalidVariable);
+        };
+        if !inequality.contains_var(&var.name) {
+            return Err(SolverError::VariableNotFound);
+        }
+        let Expression::Inequality(mut inequality) = inequality else {
+            return Err(SolverError::InvalidEquation);
+        };
+        let relation = inequality.relation;
+        let var_expr = Expression::variable(&var.name);
+
+        if let Some(explanation) = explanation {
+            explanation.open_explaination(format!(
+                "Solving {} for {}",
+                Expression::Inequality(inequality.clone()),
+                var.name
+            ));
+        }
+
+        // lhs - rhs = p/q, the fraction is built before simplifying so the values
+        // where a denominator is zero stay excluded
+        let original = Expression::subtraction(inequality.left.clone(), inequality.right.clone());
+        let lhs = inequality.left.simplify(explanation)?;
+        let rhs = inequality.right.simplify(explanation)?;
+        let (mut numerator, denominator) = match rational_function(&original, &var.name) {
+            Ok(fraction) => fraction,
+            Err(_) => {
+                rational_function(&Expression::subtraction(lhs.clone(), rhs.clone()), &var.name)?
+            }
+        };
+        let denominator = match denominator.as_slice() {
+            [constant] if is_zero(constant) => {
+                return Err(SimplifyError::DivisionByZero.into());
+            }
+            // p/c has the sign of p/c, the constant is put in the coefficients
+            [constant] => {
+                if !is_one(constant) {
+                    numerator = numerator
+                        .into_iter()
+                        .map(|coeff| fold(Expression::division(coeff, constant.clone())))
+                        .collect::<Result<_, _>>()?;
+                }
+                vec![Expression::integer(1)]
+            }
+            _ => denominator,
+        };
+
+        let before = Expression::inequality(lhs, relation, rhs);
+        let function = if denominator.len() == 1 {
+            polynomial_expression(&numerator, &var_expr)
+        } else {
+            Expression::division(
+                polynomial_expression(&numerator, &var_expr),
+                polynomial_expression(&denominator, &var_expr),
+            )
+        };
+        let after = Expression::inequality(function.clone(), relation, Expression::integer(0));
+        if let Some(explanation) = explanation
+            && !before.is_equal(&after)
+        {
+            explanation.rule_applied("Move every term to the left side", &before, &after);
+        }
+
+        // The sign can only change at the zeros of the numerator and the denominator
+        let numerator_values = numerical_coefficients(&numerator)?;
+        let denominator_values = numerical_coefficients(&denominator)?;
+        let mut points: Vec<CriticalPoint> = Vec::new();
+        for (coefficients, pole) in [(&denominator, true), (&numerator, false)] {
+            for (value, approximation) in real_roots(coefficients, &var.name, explanation)? {
+                if !points.iter().any(|point| same_value(point.approximation, approximation)) {
+                    points.push(CriticalPoint {
+                        value,
+                        approximation,
+                        pole,
+                    });
+                }
+            }
+        }
+        points.sort_by(|a, b| a.approximation.total_cmp(&b.approximation));
+
+        if let Some(explanation) = explanation {
+            let critical: Vec<String> = points
+                .iter()
+                .map(|point| {
+                    if point.pole {
+                        format!("{} = {} (excluded)", var.name, point.value)
+                    } else {
+                        format!("{} = {}", var.name, point.value)
+                    }
+                })
+                .collect();
+            explanation.open_explaination(if critical.is_empty() {
+                "There is no critical point, the sign is constant".to_string()
+            } else {
+                format!(
+                    "The critical points are the zeros of the numerator and of the denominator\n{}",
+                    critical.join(", ")
+                )
+            });
+        }
+
+        // Sign in each region, the test point is between two critical points
+        let test_points: Vec<f64> = (0..=points.len())
+            .map(|k| match (k.checked_sub(1).map(|k| &points[k]), points.get(k)) {
+                (None, None) => 0.0,
+                (None, Some(next)) => next.approximation - 1.0,
+                (Some(previous), None) => previous.approximation + 1.0,
+                (Some(previous), Some(next)) => (previous.approximation + next.approximation) / 2.0,
+            })
+            .collect();
+        let numerator_signs: Vec<Ordering> = test_points
+            .iter()
+            .map(|x| sign(horner(&numerator_values, *x)))
+            .collect();
+        let denominator_signs: Vec<Ordering> = test_points
+            .iter()
+            .map(|x| sign(horner(&denominator_values, *x)))
+            .collect();
+        let region_signs: Vec<Ordering> = numerator_signs
+            .iter()
+            .zip(&denominator_signs)
+            .map(|(n, d)| if *d == Ordering::Less { n.reverse() } else { *n })
+            .collect();
+
+        if let Some(explanation) = explanation {
+            // The sign of a fraction is given by the signs of its numerator and denominator
+            let factors = if denominator.len() > 1 {
+                vec![
+                    (
+                        polynomial_expression(&numerator, &var_expr),
+                        numerator_signs.as_slice(),
+                        numerator_values.as_slice(),
+                    ),
+                    (
+                        polynomial_expression(&denominator, &var_expr),
+                        denominator_signs.as_slice(),
+                        denominator_values.as_slice(),
+                    ),
+                ]
+            } else {
+                vec![]
+            };
+            explanation.open_explaination(sign_chart(
+                &var.name,
+                &points,
+                &factors,
+                &function,
+                &region_signs,
+                &numerator_values,
+            ));
+        }
+
+        // The regions and the points where the inequality holds are joined in intervals
+        let mut intervals = Vec::new();
+        let mut lower: Option<Bound> = None;
+        for k in 0..=points.len() {
+            if relation.holds(region_signs[k]) {
+                if lower.is_none() {
+                    lower = Some(match k {
+                        0 => Bound::Unbounded,
+                        k => Bound::Open(points[k - 1].value.clone()),
+                    });
+                }
+            } else if let Some(lower) = lower.take() {
+                intervals.push(Interval {
+                    lower,
+                    upper: Bound::Closed(points[k - 1].value.clone()),
+                });
+            }
+
+            let Some(point) = points.get(k) else {
+                break;
+            };
+            let included = !point.pole && relation.holds(point_sign(point, &numerator_values));
+            if included {
+                if lower.is_none() {
+                    lower = Some(Bound::Closed(point.value.clone()));
+                }
+            } else if let Some(lower) = lower.take() {
+                intervals.push(Interval {
+                    lower,
+                    upper: Bound::Open(point.value.clone()),
+                });
+            }
+        }
+        if let Some(lower) = lower {
+            intervals.push(Interval {
+                lower,
+                upper: Bound::Unbounded,
+            });
+        }
+
+        let solution = SolutionSet { intervals };
+        if let Some(explanation) = explanation {
+            explanation.open_explaination(format!("Solution: {} ∈ {}", var.name, solution));
+        }
+        Ok(solution)
+    }
+}
+
+/// Returns the coefficients of `p` and `q` such that `expr = p/q`.
+fn rational_function(
+    expr: &Expression,
+    variable: &str,
+) -> Result<(Vec<Expression>, Vec<Expression>), SolverError> {
+    let one = || vec![Expression::integer(1)];
+    if !expr.contains_var(variable) {
+        return Ok((vec![fold(expr.clone())?], one()));
+    }
+
+    match expr {
+        // (a/b)/(c/d) => (a d)/(b c)
+        Expression::Division(div) => {
+            let (a, b) = rational_function(&div.num, variable)?;
+            let (c, d) = rational_function(&div.den, variable)?;
+            Ok((mul_polynomials(&a, &d)?, mul_polynomials(&b, &c)?))
+        }
+        // a/b + c/d => (a d + c b)/(b d)
+        Expression::Addition(add) => {
+            add.terms
+                .iter()
+                .try_fold((vec![Expression::integer(0)], one()), |(a, b), term| {
+                    let (c, d) = rational_function(term, variable)?;
+                    if b == d {
+                        return Ok((add_polynomials(a, c)?, b));
+                    }
+                    Ok((
+                        add_polynomials(mul_polynomials(&a, &d)?, mul_polynomials(&c, &b)?)?,
+                        mul_polynomials(&b, &d)?,
+                    ))
+                })
+        }
+        Expression::Subtraction(sub) => rational_function(
+            &Expression::addition(vec![
+                sub.left.clone(),
+                Expression::negation(sub.right.clone()),
+            ]),
+            variable,
+        ),
+        Expression::Negation(neg) => {
+            let (a, b) = rational_function(&neg.term, variable)?;
+            Ok((
+                a.into_iter().map(neg_coefficient).collect::<Result<_, _>>()?,
+                b,
+            ))
+        }
+        Expression::Multiplication(mul) => {
+            mul.terms
+                .iter()
+                .try_fold((one(), one()), |(a, b), term| {
+                    let (c, d) = rational_function(term, variable)?;
+                    Ok((mul_polynomials(&a, &c)?, mul_polynomials(&b, &d)?))
+                })
+        }
+        // (a/b)^-n => b^n/a^n
+        Expression::Exponentiation(exp) if !exp.expo.contains_var(variable) => {
+            match exact_value(&exp.expo) {
+                Some(Numeral::Integer(expo)) if expo.is_negative() => {
+                    let (a, b) = rational_function(
+                        &Expression::exponentiation(
+                            exp.base.clone(),
+                            Expression::integer(-expo),
+                        ),
+                        variable,
+                    )?;
+                    Ok((b, a))
+                }
+                _ if matches!(exp.base, Expression::Variable(_)) => {
+                    Ok((polynomial_coefficients(expr, variable)?, one()))
+                }
+                _ => {
+                    let (a, b) = rational_function(&exp.base, variable)?;
+                    let expo = exp.expo.clone();
+                    let power = |coefficients: Vec<Expression>| {
+                        polynomial_coefficients(
+                            &Expression::exponentiation(
+                                polynomial_expression(
+                                    &coefficients,
+                                    &Expression::variable(variable),
+                                ),
+                                expo.clone(),
+                            ),
+                            variable,
+                        )
+                    };
+                    Ok((power(a)?, power(b)?))
+                }
+            }
+        }
+        _ => Ok((polynomial_coefficients(expr, variable)?, one())),
+    }
+}
+
+/// The real roots of the polynomial with their numerical value.
+fn real_roots(
+    coefficients: &[Expression],
+    variable: &str,
+    explanation: &mut Option<Box<FormattingObserver>>,
+) -> Result<Vec<(Expression, f64)>, SolverError> {
+    if coefficients.len() <= 1 {
+        return Ok(vec![]);
+    }
+    let roots = match Solver::solve_polynomial(
+        polynomial_expression(coefficients, &Expression::variable(variable)),
+        Expression::integer(0),
+        variable,
+        explanation,
+    )? {
+        Solution::Single(root) => vec![root],
+        Solution::Multiple(roots) => roots,
+        Solution::Infinite | Solution::NoSolution => vec![],
+    };
+
+    let env = HashMap::new();
+    let mut real = Vec::new();
+    for root in roots {
+        let value: Complex64 = root
+            .evaluate_complex(&env)
+            .map_err(|_| SolverError::UnsupportedEquationType)?;
+        if value.im.abs() < REAL_TOLERANCE {
+            let root = match root {
+                Expression::Complex(complex) => complex.real,
+                root => root,
+            };
+            real.push((root, value.re));
+        }
+    }
+    Ok(real)
+}
+
+fn numerical_coefficients(coefficients: &[Expression]) -> Result<Vec<f64>, SolverError> {
+    let env = HashMap::new();
+    coefficients
+        .iter()
+        .map(|coeff| {
+            coeff
+                .evaluate(&env)
+                .map_err(|_| SolverError::UnsupportedEquationType)
+        })
+        .collect()
+}
+
+fn horner(coefficients: &[f64], x: f64) -> f64 {
+    coefficients
+        .iter()
+        .rev()
+        .fold(0.0, |value, coeff| value * x + coeff)
+}
+
+fn sign(value: f64) -> Ordering {
+    value.partial_cmp(&0.0).unwrap_or(Ordering::Equal)
+}
+
+fn same_value(lhs: f64, rhs: f64) -> bool {
+    (lhs - rhs).abs() <= REAL_TOLERANCE * lhs.abs().max(1.0)
+}
+
+/// Sign of the numerator at a critical point, the numerator is zero at its roots.
+fn point_sign(point: &CriticalPoint, numerator: &[f64]) -> Ordering {
+    let value = horner(numerator, point.approximation);
+    if same_value(value, 0.0) {
+        Ordering::Equal
+    } else {
+        sign(value)
+    }
+}
+
+/// Lay out the sign of the numerator, the denominator and the fraction on each
+/// region and at each critical point.
+fn sign_chart(
+    variable: &str,
+    points: &[CriticalPoint],
+    factors: &[(Expression, &[Ordering], &[f64])],
+    function: &Expression,
+    region_signs: &[Ordering],
+    numerator: &[f64],
+) -> String {
+    let symbol = |ordering: &Ordering| match ordering {
+        Ordering::Less => "-".to_string(),
+        Ordering::Equal => "0".to_string(),
+        Ordering::Greater => "+".to_string(),
+    };
+    let bound = |k: usize| -> String {
+        match (k.checked_sub(1).map(|k| &points[k]), points.get(k)) {
+            (None, None) => "(-inf, inf)".to_string(),
+            (None, Some(next)) => format!("(-inf, {})", next.value),
+            (Some(previous), None) => format!("({}, inf)", previous.value),
+            (Some(previous), Some(next)) => format!("({}, {})", previous.value, next.value),
+        }
+    };
+
+    let mut rows: Vec<Vec<String>> = Vec::new();
+    let mut header = vec![variable.to_string()];
+    for k in 0..=points.len() {
+        header.push(bound(k));
+        if let Some(point) = points.get(k) {
+            header.push(point.value.to_string());
+        }
+    }
+    rows.push(header);
+
+    for (factor, signs, values) in factors {
+        let mut row = vec![factor.to_string()];
+        for (k, region) in signs.iter().enumerate() {
+            row.push(symbol(region));
+            if let Some(point) = points.get(k) {
+                let value = horner(values, point.approximation);
+                row.push(if same_value(value, 0.0) {
+                    "0".to_string()
+                } else {
+                    symbol(&sign(value))
+                });
+            }
+        }
+        rows.push(row);
+    }
+
+    let mut row = vec![function.to_string()];
+    for (k, region) in region_signs.iter().enumerate() {
+        row.push(symbol(region));
+        if let Some(point) = points.get(k) {
+            row.push(if point.pole {
+                "undefined".to_string()
+            } else {
+                symbol(&point_sign(point, numerator))
+            });
+        }
+    }
+    rows.push(row);
+
+    let widths: Vec<usize> = (0..rows[0].len())
+        .map(|column| {
+            rows.iter()
+                .map(|row| row[column].chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+    let lines: Vec<String> = rows
+        .iter()
+        .map(|row| {
+            let cells: Vec<String> = row
+                .iter()
+                .zip(&widths)
+                .map(|(cell, width)| format!("{:^width$}", cell, width = width))
+                .collect();
+            cells.join(" | ").trim_end().to_string()
+        })
+        .collect();
+    format!("Sign chart\n{}", lines.join("\n"))
+}
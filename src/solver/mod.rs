@@ -8,66 +8,463 @@
 // // Implementing methods for solving differential equations
 // // Implementing methods for solving integral equations
 
-// use crate::ast::Expression;
-
-// pub enum SolverError {
-//     InvalidEquation,
-//     UnsupportedEquationType,
-//     VariableNotFound,
-//     InvalidVariable,
-// }
-
-// pub enum Solution {
-//     Single(Expression),
-//     Multiple(Vec<Expression>),
-//     NoSolution,
-// }
-
-// // Add steps for solving equations
-// pub struct Solver;
-
-// impl Solver {
-
-//     /// Solves the given equation for the specified variable.
-//     /// 
-//     /// The variable must be `Expression::Variable` type.
-//     /// 
-//     /// The equation must be `Expression::Equality` type.
-//     pub fn solve_for(equation: Expression, variable: Expression) -> Result<Solution, SolverError> {
-//         if let Expression::Equality(lhs, rhs) = equation {
-//             if let Expression::Variable(var) = variable {
-//                 // Implement the logic to solve the equation for the variable
-//                 // This is a placeholder implementation
-//                 Ok(Solution::Single(Expression::Equality(lhs, rhs)))
-//             } else {
-//                 Err(SolverError::InvalidVariable)
-//             }
-//         } else {
-//             Err(SolverError::InvalidEquation)
-//         }
-//     }
-// }
-
-// // Linear equations
-// impl Solver {
-
-// /*     /// Solves a linear equation for the specified variable.
-//     /// 
-//     /// The variable must be `Expression::Variable` type.
-//     /// 
-//     /// The equation must be `Expression::Equality` type. 
-// */
-//     // pub fn solve_linear(equation: Expression, variable: Expression) -> Result<Solution, SolverError> {
-//     //     if let Expression::Equality(lhs, rhs) = equation {
-//     //         if let Expression::Variable(var) = variable {
-                
-
-
-//     //         } else {
-//     //             Err(SolverError::InvalidVariable)
-//     //         }
-//     //     } else {
-//     //         Err(SolverError::InvalidEquation)
-//     //     }
-//     // }
-// }
\ No newline at end of file
+mod inequality;
+mod partial_fraction;
+mod polynomial;
+mod system;
+
+use std::cmp::Ordering;
+
+use num_bigint::BigInt;
+use num_traits::{Signed, ToPrimitive, Zero};
+
+use crate::{
+    ast::{Expression, MAX_NUMERIC_EXPONENT, SimplifyError, numeral::Numeral},
+    explanation::FormattingObserver,
+};
+
+pub use inequality::{Bound, Interval, SolutionSet};
+pub(crate) use partial_fraction::{Fraction, partial_fractions};
+pub(crate) use polynomial::{polynomial_expression, square_root};
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum SolverError {
+    InvalidEquation,
+    UnsupportedEquationType,
+    VariableNotFound,
+    InvalidVariable,
+    Simplification(SimplifyError),
+}
+
+impl From<SimplifyError> for SolverError {
+    fn from(error: SimplifyError) -> Self {
+        SolverError::Simplification(error)
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Solution {
+    Single(Expression),
+    Multiple(Vec<Expression>),
+    /// Every value of the variable is a solution (e.g. `x = x`)
+    Infinite,
+    NoSolution,
+}
+
+/// Solution of a system of equations, the values are given in the order of the unknowns.
+#[derive(Debug, PartialEq, Clone)]
+pub enum SystemSolution {
+    Unique(Vec<Expression>),
+    /// The `parameters` are free unknowns, the other values are expressed with them
+    Infinite {
+        parameters: Vec<Expression>,
+        values: Vec<Expression>,
+    },
+    NoSolution,
+    /// The solution depends on the symbols of the coefficients, each condition
+    /// on them is given with the solution when it holds
+    Cases(Vec<(Expression, SystemSolution)>),
+}
+
+pub struct Solver;
+
+impl Solver {
+    /// Solves the given equation for the specified variable.
+    ///
+    /// The variable must be `Expression::Variable` type.
+    ///
+    /// The equation must be `Expression::Equality` type.
+    ///
+    /// Linear equations are solved by isolating the variable, polynomial
+    /// equations up to degree 4 with `Solver::solve_polynomial`.
+    ///
+    /// # Examples
+    /// ```
+    /// use sym_rustic::ast::Expression;
+    /// use sym_rustic::solver::{Solution, Solver};
+    ///
+    /// // 3x + 2 = 5x - 7
+    /// let equation = Expression::equality(
+    ///     Expression::addition(vec![
+    ///         Expression::multiplication(vec![Expression::integer(3), Expression::variable("x")]),
+    ///         Expression::integer(2),
+    ///     ]),
+    ///     Expression::subtraction(
+    ///         Expression::multiplication(vec![Expression::integer(5), Expression::variable("x")]),
+    ///         Expression::integer(7),
+    ///     ),
+    /// );
+    ///
+    /// let solution = Solver::solve_for(equation, Expression::variable("x"), &mut None);
+    /// assert_eq!(solution, Ok(Solution::Single(Expression::rational(9, 2))));
+    /// ```
+    pub fn solve_for(
+        equation: Expression,
+        variable: Expression,
+        explanation: &mut Option<Box<FormattingObserver>>,
+    ) -> Result<Solution, SolverError> {
+        let Expression::Variable(var) = variable else {
+            return Err(SolverError::InvalidVariable);
+        };
+        let Expression::Equality(equality) = equation else {
+            return Err(SolverError::InvalidEquation);
+        };
+        if !equality.left.contains_var(&var.name) && !equality.right.contains_var(&var.name) {
+            return Err(SolverError::VariableNotFound);
+        }
+
+        let is_linear = |side: &Expression| {
+            polynomial_coefficients(side, &var.name).is_ok_and(|coeffs| coeffs.len() <= 2)
+        };
+        if is_linear(&equality.left) && is_linear(&equality.right) {
+            Self::solve_linear(equality.left, equality.right, &var.name, explanation)
+        } else {
+            Self::solve_polynomial(equality.left, equality.right, &var.name, explanation)
+        }
+    }
+}
+
+// Linear equations
+impl Solver {
+    /// Solves the linear equation `lhs = rhs` for the specified variable.
+    ///
+    /// Both sides are simplified and written as `a*x + b`, then the variable
+    /// is isolated by moving the terms from one side to the other.
+    pub fn solve_linear(
+        mut lhs: Expression,
+        mut rhs: Expression,
+        variable: &str,
+        explanation: &mut Option<Box<FormattingObserver>>,
+    ) -> Result<Solution, SolverError> {
+        let var = Expression::variable(variable);
+
+        if let Some(explanation) = explanation {
+            explanation.open_explaination(format!(
+                "Solving {} for {}",
+                Expression::equality(lhs.clone(), rhs.clone()),
+                variable
+            ));
+        }
+
+        let lhs = lhs.simplify(explanation)?;
+        let rhs = rhs.simplify(explanation)?;
+
+        let (lhs_coeff, lhs_const) = linear_coefficients(&lhs, variable)?;
+        let (rhs_coeff, rhs_const) = linear_coefficients(&rhs, variable)?;
+
+        let mut before = Expression::equality(lhs, rhs);
+
+        // a x + b = c x + d => (a - c) x + b = d
+        let coeff = if is_zero(&rhs_coeff) {
+            lhs_coeff
+        } else {
+            let coeff = fold(Expression::subtraction(lhs_coeff, rhs_coeff.clone()))?;
+            let after =
+                Expression::equality(linear_form(&coeff, &var, &lhs_const), rhs_const.clone());
+            if let Some(explanation) = explanation {
+                explanation.rule_applied(
+                    &format!(
+                        "Subtract {} from both sides",
+                        linear_form(&rhs_coeff, &var, &Expression::integer(0))
+                    ),
+                    &before,
+                    &after,
+                );
+            }
+            before = after;
+            coeff
+        };
+
+        // a x + b = d => a x = d - b
+        let constant = if is_zero(&lhs_const) {
+            rhs_const
+        } else {
+            let constant = fold(Expression::subtraction(rhs_const, lhs_const.clone()))?;
+            let after = Expression::equality(
+                linear_form(&coeff, &var, &Expression::integer(0)),
+                constant.clone(),
+            );
+            if let Some(explanation) = explanation {
+                let rule = match &lhs_const {
+                    Expression::Negation(neg) => format!("Add {} to both sides", neg.term),
+                    lhs_const => format!("Subtract {} from both sides", lhs_const),
+                };
+                explanation.rule_applied(&rule, &before, &after);
+            }
+            before = after;
+            constant
+        };
+
+        // 0 = d has no solution unless d is also zero
+        if is_zero(&coeff) {
+            let after = Expression::equality(Expression::integer(0), constant.clone());
+            return Ok(if is_zero(&constant) {
+                if let Some(explanation) = explanation {
+                    explanation.rule_applied(
+                        "The equation is always true, every value is a solution",
+                        &before,
+                        &after,
+                    );
+                }
+                Solution::Infinite
+            } else {
+                if let Some(explanation) = explanation {
+                    explanation.rule_applied(
+                        "The equation is never true, there is no solution",
+                        &before,
+                        &after,
+                    );
+                }
+                Solution::NoSolution
+            });
+        }
+
+        // a x = d => x = d / a
+        let solution = if is_one(&coeff) {
+            constant
+        } else {
+            let solution = fold(Expression::division(constant, coeff.clone()))?;
+            if let Some(explanation) = explanation {
+                explanation.rule_applied(
+                    &format!("Divide both sides by {}", coeff),
+                    &before,
+                    &Expression::equality(var.clone(), solution.clone()),
+                );
+            }
+            solution
+        };
+
+        Ok(Solution::Single(solution))
+    }
+}
+
+/// Returns the coefficients `(a, b)` such that `expr = a*variable + b`.
+fn linear_coefficients(
+    expr: &Expression,
+    variable: &str,
+) -> Result<(Expression, Expression), SolverError> {
+    match polynomial_coefficients(expr, variable)?.as_slice() {
+        [constant] => Ok((Expression::integer(0), constant.clone())),
+        [constant, coeff] => Ok((coeff.clone(), constant.clone())),
+        _ => Err(SolverError::UnsupportedEquationType),
+    }
+}
+
+/// Maximum exponent expanded when collecting the coefficients of a polynomial.
+const MAX_EXPANDED_DEGREE: u32 = 64;
+
+/// Returns the coefficients `[c0, c1, ..., cn]` such that
+/// `expr = c0 + c1*variable + ... + cn*variable^n`.
+///
+/// The coefficients are reduced and the list never ends with a zero, except for
+/// the zero polynomial which is `[0]`.
+pub(crate) fn polynomial_coefficients(
+    expr: &Expression,
+    variable: &str,
+) -> Result<Vec<Expression>, SolverError> {
+    if !expr.contains_var(variable) {
+        return Ok(vec![fold(expr.clone())?]);
+    }
+
+    let coefficients = match expr {
+        Expression::Variable(_) => vec![Expression::integer(0), Expression::integer(1)],
+        Expression::Negation(neg) => polynomial_coefficients(&neg.term, variable)?
+            .into_iter()
+            .map(neg_coefficient)
+            .collect::<Result<_, _>>()?,
+        Expression::Addition(add) => add
+            .terms
+            .iter()
+            .try_fold(vec![Expression::integer(0)], |acc, term| {
+                add_polynomials(acc, polynomial_coefficients(term, variable)?)
+            })?,
+        Expression::Subtraction(sub) => add_polynomials(
+            polynomial_coefficients(&sub.left, variable)?,
+            polynomial_coefficients(&Expression::negation(sub.right.clone()), variable)?,
+        )?,
+        Expression::Multiplication(mul) => mul
+            .terms
+            .iter()
+            .try_fold(vec![Expression::integer(1)], |acc, term| {
+                mul_polynomials(&acc, &polynomial_coefficients(term, variable)?)
+            })?,
+        Expression::Division(div) if !div.den.contains_var(variable) => {
+            polynomial_coefficients(&div.num, variable)?
+                .into_iter()
+                .map(|coeff| fold(Expression::division(coeff, div.den.clone())))
+                .collect::<Result<_, _>>()?
+        }
+        // a^n with n a positive integer
+        Expression::Exponentiation(exp) if !exp.expo.contains_var(variable) => {
+            let expo = match exact_value(&exp.expo) {
+                Some(Numeral::Integer(expo)) => expo.to_u32(),
+                _ => None,
+            };
+            let expo = expo
+                .filter(|expo| *expo <= MAX_EXPANDED_DEGREE)
+                .ok_or(SolverError::UnsupportedEquationType)?;
+            let base = polynomial_coefficients(&exp.base, variable)?;
+            (0..expo).try_fold(vec![Expression::integer(1)], |acc, _| {
+                mul_polynomials(&acc, &base)
+            })?
+        }
+        _ => return Err(SolverError::UnsupportedEquationType),
+    };
+
+    Ok(trim_polynomial(coefficients))
+}
+
+fn add_polynomials(
+    lhs: Vec<Expression>,
+    rhs: Vec<Expression>,
+) -> Result<Vec<Expression>, SolverError> {
+    let (mut long, short) = if lhs.len() >= rhs.len() {
+        (lhs, rhs)
+    } else {
+        (rhs, lhs)
+    };
+    for (i, coeff) in short.into_iter().enumerate() {
+        long[i] = add_coefficients(long[i].clone(), coeff)?;
+    }
+    Ok(trim_polynomial(long))
+}
+
+fn mul_polynomials(lhs: &[Expression], rhs: &[Expression]) -> Result<Vec<Expression>, SolverError> {
+    let mut product = vec![Expression::integer(0); lhs.len() + rhs.len() - 1];
+    for (i, a) in lhs.iter().enumerate() {
+        for (j, b) in rhs.iter().enumerate() {
+            let term = mul_coefficients(a.clone(), b.clone())?;
+            product[i + j] = add_coefficients(product[i + j].clone(), term)?;
+        }
+    }
+    Ok(trim_polynomial(product))
+}
+
+/// Remove the leading zeros so the last coefficient gives the degree.
+fn trim_polynomial(mut coefficients: Vec<Expression>) -> Vec<Expression> {
+    while coefficients.len() > 1 && coefficients.last().is_some_and(is_zero) {
+        coefficients.pop();
+    }
+    coefficients
+}
+
+fn add_coefficients(lhs: Expression, rhs: Expression) -> Result<Expression, SolverError> {
+    if is_zero(&lhs) {
+        Ok(rhs)
+    } else if is_zero(&rhs) {
+        Ok(lhs)
+    } else {
+        fold(Expression::addition(vec![lhs, rhs]))
+    }
+}
+
+fn mul_coefficients(lhs: Expression, rhs: Expression) -> Result<Expression, SolverError> {
+    if is_zero(&lhs) || is_zero(&rhs) {
+        Ok(Expression::integer(0))
+    } else if is_one(&lhs) {
+        Ok(rhs)
+    } else if is_one(&rhs) {
+        Ok(lhs)
+    } else {
+        fold(Expression::multiplication(vec![lhs, rhs]))
+    }
+}
+
+fn neg_coefficient(coeff: Expression) -> Result<Expression, SolverError> {
+    match coeff {
+        Expression::Negation(neg) => Ok(neg.term),
+        coeff => fold(Expression::negation(coeff)),
+    }
+}
+
+/// Build `coeff*variable + constant` leaving out the parts that are zero or one.
+fn linear_form(coeff: &Expression, variable: &Expression, constant: &Expression) -> Expression {
+    let term = if is_zero(coeff) {
+        None
+    } else if is_one(coeff) {
+        Some(variable.clone())
+    } else {
+        Some(Expression::multiplication(vec![
+            coeff.clone(),
+            variable.clone(),
+        ]))
+    };
+
+    match term {
+        None => constant.clone(),
+        Some(term) if is_zero(constant) => term,
+        Some(term) => Expression::addition(vec![term, constant.clone()]),
+    }
+}
+
+/// Reduce a coefficient, numerical coefficients are computed exactly
+/// and the others are simplified.
+fn fold(mut expr: Expression) -> Result<Expression, SolverError> {
+    match exact_value(&expr) {
+        Some(value) => Ok(value.into_expression()),
+        None => Ok(expr.simplify(&mut None)?),
+    }
+}
+
+/// Evaluate an `Expression` made only of exact numbers in lowest terms, returns
+/// `None` if it contains anything else or if the value is undefined.
+fn exact_value(expr: &Expression) -> Option<Numeral> {
+    let value = match expr {
+        Expression::Number(Numeral::Rational(_, d)) if d.is_zero() => return None,
+        Expression::Number(n) if n.is_exact() => n.clone(),
+        Expression::Negation(neg) => exact_value(&neg.term)?.neg(),
+        Expression::Addition(add) => add
+            .terms
+            .iter()
+            .try_fold(Numeral::Integer(BigInt::zero()), |acc, term| {
+                Some(acc.add(&exact_value(term)?))
+            })?,
+        Expression::Multiplication(mul) => mul
+            .terms
+            .iter()
+            .try_fold(Numeral::Integer(BigInt::from(1)), |acc, term| {
+                Some(acc.mul(&exact_value(term)?))
+            })?,
+        Expression::Subtraction(sub) => exact_value(&sub.left)?.sub(&exact_value(&sub.right)?),
+        Expression::Division(div) => {
+            exact_value(&div.num)?.mul(&exact_value(&div.den)?.inverse()?)
+        }
+        // Same bound on the exponent as the simplification of a^n
+        Expression::Exponentiation(exp) => match exact_value(&exp.expo)? {
+            Numeral::Integer(expo) => {
+                let power = expo
+                    .magnitude()
+                    .to_u32()
+                    .filter(|power| *power <= MAX_NUMERIC_EXPONENT)?;
+                let base = exact_value(&exp.base)?;
+                if expo.is_negative() {
+                    base.inverse()?.pow(power)
+                } else {
+                    base.pow(power)
+                }
+            }
+            _ => return None,
+        },
+        _ => return None,
+    };
+    Some(value.reduced())
+}
+
+/// Sign of an exact number.
+fn signum(value: &Numeral) -> Ordering {
+    if value.is_zero() {
+        Ordering::Equal
+    } else if value.is_negative() {
+        Ordering::Less
+    } else {
+        Ordering::Greater
+    }
+}
+
+fn is_zero(expr: &Expression) -> bool {
+    exact_value(expr).is_some_and(|value| value.is_zero())
+}
+
+fn is_one(expr: &Expression) -> bool {
+    exact_value(expr).is_some_and(|value| value.is_one())
+}
@@ -0,0 +1,201 @@
+use num_bigint::BigInt;
+
+use crate::ast::{Expression, numeral::Numeral};
+
+use super::{
+    exact_value,
+    polynomial::{deflate, find_rational_root},
+    polynomial_coefficients,
+};
+
+/// Simple fraction of a partial fraction decomposition.
+#[derive(Debug, PartialEq, Clone)]
+pub(crate) enum Fraction {
+    /// `coeff / (variable - root)^power`
+    Linear {
+        coeff: Expression,
+        root: Expression,
+        power: u32,
+    },
+    /// `(a*variable + b) / (variable^2 + p*variable + q)` where the quadratic has no rational root
+    Quadratic {
+        a: Expression,
+        b: Expression,
+        p: Expression,
+        q: Expression,
+    },
+}
+
+/// `num/den` written as `polynomial + fractions`.
+#[derive(Debug, PartialEq, Clone)]
+pub(crate) struct PartialFractions {
+    /// Coefficients `[c0, c1, ..., cn]` of the polynomial part
+    pub(crate) polynomial: Vec<Expression>,
+    pub(crate) fractions: Vec<Fraction>,
+}
+
+/// Decompose `num/den` into partial fractions over the rationals.
+///
+/// Both must be polynomials in `variable` with rational coefficients and the
+/// denominator must split into linear factors and at most one quadratic factor,
+/// otherwise `None` is returned.
+pub(crate) fn partial_fractions(
+    num: &Expression,
+    den: &Expression,
+    variable: &str,
+) -> Option<PartialFractions> {
+    let num = rational_coefficients(num, variable)?;
+    let den = rational_coefficients(den, variable)?;
+    if den.len() < 2 {
+        return None;
+    }
+
+    // Work with a monic denominator
+    let leading = den.last()?.inverse()?;
+    let num = scale(&num, &leading);
+    let den = scale(&den, &leading);
+    let (quotient, remainder) = divide(&num, &den)?;
+
+    // den = (x - r1)^m1 * ... * (x - rk)^mk * rest
+    let mut roots: Vec<(Numeral, u32)> = Vec::new();
+    let mut rest = den.clone();
+    while rest.len() > 1 {
+        let root = if rest[0].is_zero() {
+            Numeral::Integer(BigInt::from(0))
+        } else if let Some(root) = find_rational_root(&rest) {
+            root
+        } else {
+            break;
+        };
+        rest = deflate(&rest, &root);
+        match roots.iter_mut().find(|(r, _)| *r == root) {
+            Some((_, multiplicity)) => *multiplicity += 1,
+            None => roots.push((root, 1)),
+        }
+    }
+    let quadratic = match rest.len() {
+        1 => None,
+        3 => Some(rest),
+        _ => return None,
+    };
+
+    // The numerator is a combination of den/(x - r)^k and den/rest, x*den/rest
+    let mut basis = Vec::new();
+    for (root, multiplicity) in &roots {
+        let mut cofactor = den.clone();
+        for _ in 0..*multiplicity {
+            cofactor = deflate(&cofactor, root);
+            basis.push(cofactor.clone());
+        }
+    }
+    if let Some(quadratic) = &quadratic {
+        let (cofactor, _) = divide(&den, quadratic)?;
+        let mut shifted = vec![Numeral::Integer(BigInt::from(0))];
+        shifted.extend(cofactor.iter().cloned());
+        basis.push(shifted);
+        basis.push(cofactor);
+    }
+    let mut unknowns = solve(&basis, &remainder)?.into_iter();
+
+    let mut fractions = Vec::new();
+    for (root, multiplicity) in &roots {
+        for power in 1..=*multiplicity {
+            let coeff = unknowns.next()?;
+            if !coeff.is_zero() {
+                fractions.push(Fraction::Linear {
+                    coeff: coeff.into_expression(),
+                    root: root.clone().into_expression(),
+                    power,
+                });
+            }
+        }
+    }
+    if let Some(quadratic) = quadratic {
+        let (a, b) = (unknowns.next()?, unknowns.next()?);
+        if !a.is_zero() || !b.is_zero() {
+            fractions.push(Fraction::Quadratic {
+                a: a.into_expression(),
+                b: b.into_expression(),
+                p: quadratic[1].clone().into_expression(),
+                q: quadratic[0].clone().into_expression(),
+            });
+        }
+    }
+
+    Some(PartialFractions {
+        polynomial: quotient.into_iter().map(Numeral::into_expression).collect(),
+        fractions,
+    })
+}
+
+fn rational_coefficients(expr: &Expression, variable: &str) -> Option<Vec<Numeral>> {
+    polynomial_coefficients(expr, variable)
+        .ok()?
+        .iter()
+        .map(exact_value)
+        .collect()
+}
+
+fn scale(coefficients: &[Numeral], factor: &Numeral) -> Vec<Numeral> {
+    coefficients
+        .iter()
+        .map(|coeff| coeff.mul(factor).reduced())
+        .collect()
+}
+
+/// Long division of `num` by the monic polynomial `den`, returns `(quotient, remainder)`.
+fn divide(num: &[Numeral], den: &[Numeral]) -> Option<(Vec<Numeral>, Vec<Numeral>)> {
+    let degree = den.len() - 1;
+    let mut remainder = num.to_vec();
+    if remainder.len() <= degree {
+        return Some((vec![Numeral::Integer(BigInt::from(0))], remainder));
+    }
+
+    let mut quotient = vec![Numeral::Integer(BigInt::from(0)); remainder.len() - degree];
+    for i in (0..quotient.len()).rev() {
+        let coeff = remainder[i + degree].clone();
+        for (j, d) in den.iter().enumerate() {
+            remainder[i + j] = remainder[i + j].sub(&coeff.mul(d)).reduced();
+        }
+        quotient[i] = coeff;
+    }
+    remainder.truncate(degree);
+    Some((quotient, remainder))
+}
+
+/// Gaussian elimination of the square system whose columns are the `basis` polynomials.
+fn solve(basis: &[Vec<Numeral>], rhs: &[Numeral]) -> Option<Vec<Numeral>> {
+    let size = basis.len();
+    let coefficient = |poly: &[Numeral], i: usize| {
+        poly.get(i)
+            .cloned()
+            .unwrap_or(Numeral::Integer(BigInt::from(0)))
+    };
+    let mut rows: Vec<Vec<Numeral>> = (0..size)
+        .map(|i| {
+            basis
+                .iter()
+                .map(|poly| coefficient(poly, i))
+                .chain([coefficient(rhs, i)])
+                .collect()
+        })
+        .collect();
+
+    for col in 0..size {
+        let pivot = (col..size).find(|row| !rows[*row][col].is_zero())?;
+        rows.swap(col, pivot);
+        let inverse = rows[col][col].inverse()?;
+        rows[col] = scale(&rows[col], &inverse);
+        let pivot_row = rows[col].clone();
+        for (row, values) in rows.iter_mut().enumerate() {
+            let factor = values[col].clone();
+            if row != col && !factor.is_zero() {
+                for (value, pivot) in values.iter_mut().zip(&pivot_row).skip(col) {
+                    *value = value.sub(&factor.mul(pivot)).reduced();
+                }
+            }
+        }
+    }
+
+    Some(rows.into_iter().map(|mut row| row.remove(size)).collect())
+}
@@ -0,0 +1,1222 @@
+use std::{cmp::Ordering, collections::HashMap};
+
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::{One, ToPrimitive, Zero};
+
+use crate::{
+    ast::{Expression, SimplifyError, SimplifyOptions, numeral::Numeral},
+    explanation::FormattingObserver,
+    utils,
+};
+
+use super::{
+    Solution, Solver, SolverError, add_polynomials, exact_value, fold, is_one, is_zero,
+    neg_coefficient, polynomial_coefficients, signum,
+};
+
+/// Factors below this bound are taken out of the radicals, a larger factor
+/// is only taken out when what is left of the radicand is a perfect power.
+const ROOT_TRIAL_DIVISION_LIMIT: u32 = 10_000;
+
+/// Values closer to zero than this are considered to be zero when the sign of an
+/// irrational number is decided numerically.
+const SIGN_TOLERANCE: f64 = 1e-10;
+
+// Polynomial equations
+impl Solver {
+    /// Solves the polynomial equation `lhs = rhs` for the specified variable.
+    ///
+    /// Every term is moved to the left side, the rational roots are factored out and
+    /// what remains is solved exactly up to degree 4 with the quadratic formula,
+    /// Cardano's method or Ferrari's method.
+    ///
+    /// Complex roots are returned as `Expression::Complex`.
+    pub fn solve_polynomial(
+        lhs: Expression,
+        rhs: Expression,
+        variable: &str,
+        explanation: &mut Option<Box<FormattingObserver>>,
+    ) -> Result<Solution, SolverError> {
+        Self::solve_polynomial_with(lhs, rhs, variable, &SimplifyOptions::default(), explanation)
+    }
+
+    /// Solves the polynomial equation like `Solver::solve_polynomial`, the sides
+    /// and the roots are simplified with `options`.
+    pub fn solve_polynomial_with(
+        mut lhs: Expression,
+        mut rhs: Expression,
+        variable: &str,
+        options: &SimplifyOptions,
+        explanation: &mut Option<Box<FormattingObserver>>,
+    ) -> Result<Solution, SolverError> {
+        let var = Expression::variable(variable);
+
+        if let Some(explanation) = explanation {
+            explanation.open_explaination(format!(
+                "Solving {} for {}",
+                Expression::equality(lhs.clone(), rhs.clone()),
+                variable
+            ));
+        }
+
+        let lhs = lhs.simplify_with(options, explanation)?;
+        let rhs = rhs.simplify_with(options, explanation)?;
+
+        let coefficients = add_polynomials(
+            polynomial_coefficients(&lhs, variable)?,
+            polynomial_coefficients(&rhs, variable)?
+                .into_iter()
+                .map(neg_coefficient)
+                .collect::<Result<_, _>>()?,
+        )?;
+
+        let before = Expression::equality(lhs, rhs);
+        let after = Expression::equality(
+            polynomial_expression(&coefficients, &var),
+            Expression::integer(0),
+        );
+        if let Some(explanation) = explanation
+            && !before.is_equal(&after)
+        {
+            explanation.rule_applied("Move every term to the left side", &before, &after);
+        }
+
+        // c = 0 has no solution unless c is also zero
+        if let [constant] = coefficients.as_slice() {
+            return Ok(if is_zero(constant) {
+                if let Some(explanation) = explanation {
+                    explanation.rule_applied(
+                        "The equation is always true, every value is a solution",
+                        &after,
+                        &after,
+                    );
+                }
+                Solution::Infinite
+            } else {
+                if let Some(explanation) = explanation {
+                    explanation.rule_applied(
+                        "The equation is never true, there is no solution",
+                        &after,
+                        &after,
+                    );
+                }
+                Solution::NoSolution
+            });
+        }
+
+        let roots = match coefficients
+            .iter()
+            .map(exact_value)
+            .collect::<Option<Vec<_>>>()
+        {
+            Some(coefficients) => rational_polynomial_roots(coefficients, &var, explanation)?,
+            None => symbolic_polynomial_roots(&coefficients, &var, explanation)?,
+        };
+
+        let mut distinct: Vec<Expression> = Vec::new();
+        for mut root in roots {
+            let simplified = root.simplify_with(options, &mut None)?;
+            if let Some(explanation) = explanation
+                && !simplified.is_equal(&root)
+            {
+                explanation.rule_applied(
+                    "Simplify the root",
+                    &Expression::equality(var.clone(), root),
+                    &Expression::equality(var.clone(), simplified.clone()),
+                );
+            }
+            let root = simplified;
+            if !distinct.iter().any(|other| other.is_equal(&root)) {
+                distinct.push(root);
+            }
+        }
+
+        Ok(match distinct.len() {
+            0 => Solution::NoSolution,
+            1 => Solution::Single(distinct.remove(0)),
+            _ => Solution::Multiple(distinct),
+        })
+    }
+}
+
+/// Roots of a polynomial with rational coefficients, the rational roots are
+/// factored out first so the closed forms are only used when needed.
+fn rational_polynomial_roots(
+    mut coefficients: Vec<Numeral>,
+    var: &Expression,
+    explanation: &mut Option<Box<FormattingObserver>>,
+) -> Result<Vec<Expression>, SolverError> {
+    let mut roots = Vec::new();
+
+    // x^k * p(x) = 0 => x = 0
+    let zeros = coefficients
+        .iter()
+        .take_while(|coeff| coeff.is_zero())
+        .count();
+    if zeros > 0 {
+        let before = rational_equation(&coefficients, var);
+        coefficients.drain(..zeros);
+        let after = Expression::equality(
+            Expression::multiplication(vec![
+                power_of(var, zeros),
+                rational_polynomial(&coefficients, var),
+            ]),
+            Expression::integer(0),
+        );
+        if let Some(explanation) = explanation {
+            explanation.rule_applied(
+                &format!(
+                    "Factor out {}, so {} = 0 is a root",
+                    power_of(var, zeros),
+                    var
+                ),
+                &before,
+                &after,
+            );
+        }
+        roots.push(Expression::integer(0));
+    }
+
+    // p(r) = 0 => p(x) = (x - r) * q(x)
+    while coefficients.len() > 3 {
+        let Some(root) = find_rational_root(&coefficients) else {
+            break;
+        };
+        let before = rational_equation(&coefficients, var);
+        coefficients = deflate(&coefficients, &root);
+        let root = number(root);
+        let factor = sum(var.clone(), negate(root.clone())?)?;
+        let after = Expression::equality(
+            Expression::multiplication(vec![
+                factor.clone(),
+                rational_polynomial(&coefficients, var),
+            ]),
+            Expression::integer(0),
+        );
+        if let Some(explanation) = explanation {
+            explanation.rule_applied(
+                &format!(
+                    "{} = {} is a rational root, factor out {}",
+                    var, root, factor
+                ),
+                &before,
+                &after,
+            );
+        }
+        roots.push(root);
+    }
+
+    match coefficients.len() {
+        1 => {}
+        // a x + b = 0 => x = -b/a
+        2 => {
+            let root = number(coefficients[0].neg().div(&coefficients[1]));
+            if let Some(explanation) = explanation {
+                explanation.rule_applied(
+                    "Solve the linear factor",
+                    &rational_equation(&coefficients, var),
+                    &Expression::equality(var.clone(), root.clone()),
+                );
+            }
+            roots.push(root);
+        }
+        3 => {
+            let [c, b, a] = [&coefficients[0], &coefficients[1], &coefficients[2]];
+            let discriminant = b.mul(b).sub(&a.mul(c).mul(&integer(4)));
+            roots.extend(solve_quadratic(
+                a.clone(),
+                number(b.clone()),
+                number(c.clone()),
+                number(discriminant),
+                var,
+                explanation,
+            )?);
+        }
+        4 => roots.extend(solve_cubic(&coefficients, var, explanation)?),
+        5 => roots.extend(solve_quartic(&coefficients, var, explanation)?),
+        _ => return Err(SolverError::UnsupportedEquationType),
+    }
+
+    Ok(roots)
+}
+
+/// Roots of a polynomial with symbolic coefficients, only the linear and
+/// quadratic cases have a general formula that does not depend on signs.
+fn symbolic_polynomial_roots(
+    coefficients: &[Expression],
+    var: &Expression,
+    explanation: &mut Option<Box<FormattingObserver>>,
+) -> Result<Vec<Expression>, SolverError> {
+    let before = Expression::equality(
+        polynomial_expression(coefficients, var),
+        Expression::integer(0),
+    );
+    match coefficients {
+        // a x + b = 0 => x = -b/a
+        [b, a] => {
+            let root = fold(Expression::division(neg_coefficient(b.clone())?, a.clone()))?;
+            if let Some(explanation) = explanation {
+                explanation.rule_applied(
+                    &format!("Divide by {}", a),
+                    &before,
+                    &Expression::equality(var.clone(), root.clone()),
+                );
+            }
+            Ok(vec![root])
+        }
+        // a x^2 + c = 0 => x = ±sqrt(-c/a)
+        [c, b, a] if is_zero(b) => {
+            let square = fold(Expression::division(neg_coefficient(c.clone())?, a.clone()))?;
+            let root = Expression::sqrt(square.clone());
+            if let Some(explanation) = explanation {
+                explanation.rule_applied(
+                    &format!("Divide by {} and take the square roots", a),
+                    &before,
+                    &Expression::equality(var.clone(), root.clone()),
+                );
+            }
+            Ok(vec![root.clone(), Expression::negation(root)])
+        }
+        // a x^2 + b x + c = 0 => x = (-b ± sqrt(b^2 - 4ac))/(2a)
+        [c, b, a] => {
+            let discriminant = fold(discriminant_expression(a, b, c))?;
+            if let Some(explanation) = explanation {
+                explanation.rule_applied(
+                    "Compute the discriminant Δ = b^2 - 4ac",
+                    &discriminant_expression(a, b, c),
+                    &discriminant,
+                );
+            }
+            let den = Expression::multiplication(vec![Expression::integer(2), a.clone()]);
+            let mut roots = Vec::new();
+            for (rule, sqrt) in [
+                (
+                    "Apply the quadratic formula\nx = (-b + sqrt(Δ))/(2a)",
+                    Expression::sqrt(discriminant.clone()),
+                ),
+                (
+                    "x = (-b - sqrt(Δ))/(2a)",
+                    Expression::negation(Expression::sqrt(discriminant.clone())),
+                ),
+            ] {
+                let root = fold(Expression::division(
+                    Expression::addition(vec![neg_coefficient(b.clone())?, sqrt]),
+                    den.clone(),
+                ))?;
+                if let Some(explanation) = explanation {
+                    explanation.rule_applied(
+                        rule,
+                        &before,
+                        &Expression::equality(var.clone(), root.clone()),
+                    );
+                }
+                roots.push(root);
+            }
+            Ok(roots)
+        }
+        _ => Err(SolverError::UnsupportedEquationType),
+    }
+}
+
+/// Solve `a x^2 + b x + c = 0` knowing its discriminant.
+///
+/// `a` is rational while `b`, `c` and the discriminant can contain radicals,
+/// the sign of the discriminant is then decided numerically.
+fn solve_quadratic(
+    a: Numeral,
+    b: Expression,
+    c: Expression,
+    discriminant: Expression,
+    var: &Expression,
+    explanation: &mut Option<Box<FormattingObserver>>,
+) -> Result<Vec<Expression>, SolverError> {
+    let before = Expression::equality(
+        polynomial_expression(&[c.clone(), b.clone(), number(a.clone())], var),
+        Expression::integer(0),
+    );
+    if let Some(explanation) = explanation {
+        explanation.rule_applied(
+            "Compute the discriminant Δ = b^2 - 4ac",
+            &discriminant_expression(&number(a.clone()), &b, &c),
+            &discriminant,
+        );
+    }
+
+    // x = -b/(2a) ± sqrt(|Δ|)/(2|a|)
+    let two_a = a.mul(&integer(2));
+    let center = scale(inverse(&two_a)?.neg(), b)?;
+    let half_width = |radicand: Expression| -> Result<Expression, SolverError> {
+        scale(inverse(&two_a.abs())?, square_root(radicand)?)
+    };
+
+    let (rules, roots) = match sign(&discriminant).ok_or(SolverError::UnsupportedEquationType)? {
+        Ordering::Greater => {
+            let width = half_width(discriminant)?;
+            (
+                [
+                    "The discriminant is positive, there are two real roots\nx = (-b + sqrt(Δ))/(2a)",
+                    "x = (-b - sqrt(Δ))/(2a)",
+                ]
+                .as_slice(),
+                vec![
+                    sum(center.clone(), width.clone())?,
+                    sum(center, negate(width)?)?,
+                ],
+            )
+        }
+        Ordering::Equal => (
+            ["The discriminant is zero, there is one repeated root\nx = -b/(2a)"].as_slice(),
+            vec![center],
+        ),
+        Ordering::Less => {
+            let width = half_width(negate(discriminant)?)?;
+            (
+                [
+                    "The discriminant is negative, there are two complex conjugate roots\nx = (-b + i*sqrt(-Δ))/(2a)",
+                    "x = (-b - i*sqrt(-Δ))/(2a)",
+                ]
+                .as_slice(),
+                vec![
+                    Expression::complex(center.clone(), width.clone()),
+                    Expression::complex(center, negate(width)?),
+                ],
+            )
+        }
+    };
+
+    if let Some(explanation) = explanation {
+        for (rule, root) in rules.iter().zip(&roots) {
+            explanation.rule_applied(
+                rule,
+                &before,
+                &Expression::equality(var.clone(), root.clone()),
+            );
+        }
+    }
+    Ok(roots)
+}
+
+/// Solve `a x^3 + b x^2 + c x + d = 0` with Cardano's method.
+fn solve_cubic(
+    coefficients: &[Numeral],
+    var: &Expression,
+    explanation: &mut Option<Box<FormattingObserver>>,
+) -> Result<Vec<Expression>, SolverError> {
+    let before = rational_equation(coefficients, var);
+    let t = substitution_variable(var, "t");
+
+    // x = t - b/3 removes the squared term: t^3 + p t + q = 0
+    let (shift, p, q) = depressed_cubic(coefficients)?;
+    let depressed = rational_equation(&[q.clone(), p.clone(), integer(0), integer(1)], &t);
+    if let Some(explanation) = explanation {
+        explanation.rule_applied(
+            &format!(
+                "Substitute {} = {} - b/(3a) to remove the squared term",
+                var, t
+            ),
+            &before,
+            &depressed,
+        );
+    }
+
+    // Δ = (q/2)^2 + (p/3)^3
+    let half_q = q.div(&integer(2)).reduced();
+    let third_p = p.div(&integer(3)).reduced();
+    let delta = half_q.pow(2).add(&third_p.pow(3)).reduced();
+    if let Some(explanation) = explanation {
+        explanation.rule_applied(
+            "Compute Δ = (q/2)^2 + (p/3)^3",
+            &Expression::addition(vec![
+                Expression::exponentiation(number(half_q.clone()), Expression::integer(2)),
+                Expression::exponentiation(number(third_p), Expression::integer(3)),
+            ]),
+            &number(delta.clone()),
+        );
+    }
+
+    let (rules, roots): (&[&str], Vec<Expression>) = match signum(&delta) {
+        // One real root and two complex conjugate roots
+        Ordering::Greater => {
+            let sqrt_delta = rational_square_root(delta)?;
+            let minus_half_q = number(half_q.neg());
+            let u = cube_root(sum(minus_half_q.clone(), sqrt_delta.clone())?)?;
+            let v = cube_root(sum(minus_half_q, negate(sqrt_delta)?)?)?;
+            let real = scale(fraction(-1, 2), sum(u.clone(), v.clone())?)?;
+            let imag = Expression::multiplication(vec![
+                scale(
+                    fraction(1, 2),
+                    Expression::sqrt(Expression::integer(3)),
+                )?,
+                sum(u.clone(), negate(v.clone())?)?,
+            ]);
+            (
+                &[
+                    "Δ is positive, Cardano's formula gives one real root\nt = root(3, -q/2 + sqrt(Δ)) + root(3, -q/2 - sqrt(Δ))",
+                    "and two complex conjugate roots\nt = -(u + v)/2 + i*sqrt(3)/2*(u - v)",
+                    "t = -(u + v)/2 - i*sqrt(3)/2*(u - v)",
+                ],
+                vec![
+                    sum(u, v)?,
+                    Expression::complex(real.clone(), imag.clone()),
+                    Expression::complex(real, negate(imag)?),
+                ],
+            )
+        }
+        // Every root is real and two of them are equal
+        Ordering::Equal if p.is_zero() => (
+            &["Δ and p are zero, there is one triple root\nt = 0"],
+            vec![Expression::integer(0)],
+        ),
+        Ordering::Equal => (
+            &[
+                "Δ is zero, there is a simple root\nt = 3q/p",
+                "and a double root\nt = -3q/(2p)",
+            ],
+            vec![
+                number(q.mul(&integer(3)).div(&p)),
+                number(q.mul(&fraction(-3, 2)).div(&p)),
+            ],
+        ),
+        // Three distinct real roots
+        Ordering::Less => (
+            &[
+                "Δ is negative, there are three real roots given by\nt = 2*sqrt(-p/3)*cos(acos(3q/(2p)*sqrt(-3/p))/3 - 2πk/3) with k = 0",
+                "k = 1",
+                "k = 2",
+            ],
+            trigonometric_cubic_roots(&p, &q)?,
+        ),
+    };
+
+    let mut solutions = Vec::new();
+    for (rule, root) in rules.iter().zip(roots) {
+        if let Some(explanation) = explanation {
+            explanation.rule_applied(
+                rule,
+                &depressed,
+                &Expression::equality(t.clone(), root.clone()),
+            );
+        }
+        solutions.push(substitute_back(root, &shift, &t, var, explanation)?);
+    }
+    Ok(solutions)
+}
+
+/// Solve `a x^4 + b x^3 + c x^2 + d x + e = 0` with Ferrari's method.
+fn solve_quartic(
+    coefficients: &[Numeral],
+    var: &Expression,
+    explanation: &mut Option<Box<FormattingObserver>>,
+) -> Result<Vec<Expression>, SolverError> {
+    let before = rational_equation(coefficients, var);
+    let y = substitution_variable(var, "y");
+
+    // x = y - b/4 removes the cubic term: y^4 + p y^2 + q y + r = 0
+    let (shift, p, q, r) = depressed_quartic(coefficients)?;
+    let depressed = rational_equation(
+        &[r.clone(), q.clone(), p.clone(), integer(0), integer(1)],
+        &y,
+    );
+    if let Some(explanation) = explanation {
+        explanation.rule_applied(
+            &format!(
+                "Substitute {} = {} - b/(4a) to remove the cubic term",
+                var, y
+            ),
+            &before,
+            &depressed,
+        );
+    }
+
+    let roots = if q.is_zero() {
+        biquadratic_roots(p, r, &y, &depressed, explanation)?
+    } else {
+        ferrari_roots(p, q, r, &y, &depressed, explanation)?
+    };
+
+    roots
+        .into_iter()
+        .map(|root| substitute_back(root, &shift, &y, var, explanation))
+        .collect()
+}
+
+/// Roots of `y^4 + p y^2 + r = 0` solved as a quadratic in `z = y^2`.
+fn biquadratic_roots(
+    p: Numeral,
+    r: Numeral,
+    y: &Expression,
+    depressed: &Expression,
+    explanation: &mut Option<Box<FormattingObserver>>,
+) -> Result<Vec<Expression>, SolverError> {
+    let z = Expression::variable(&format!("{}^2", y));
+    if let Some(explanation) = explanation {
+        explanation.rule_applied(
+            &format!("The equation is biquadratic, solve it for {}", z),
+            depressed,
+            &rational_equation(&[r.clone(), p.clone(), integer(1)], &z),
+        );
+    }
+
+    let discriminant = p.mul(&p).sub(&r.mul(&integer(4))).reduced();
+    let squares = solve_quadratic(
+        integer(1),
+        number(p.clone()),
+        number(r.clone()),
+        number(discriminant.clone()),
+        &z,
+        explanation,
+    )?;
+
+    let mut roots = Vec::new();
+    if signum(&discriminant) == Ordering::Less {
+        // y^2 = a ± i b with |y^2| = sqrt(r)
+        // y = ±(sqrt((|y^2| + a)/2) ± i*sqrt((|y^2| - a)/2))
+        let modulus = rational_square_root(r)?;
+        let half_p = number(p.div(&integer(2)));
+        let real = square_root(scale(
+            fraction(1, 2),
+            sum(modulus.clone(), negate(half_p.clone())?)?,
+        )?)?;
+        let imag = square_root(scale(fraction(1, 2), sum(modulus, half_p)?)?)?;
+        for square in &squares {
+            let imag = match square {
+                Expression::Complex(complex) if matches!(complex.imag, Expression::Negation(_)) => {
+                    negate(imag.clone())?
+                }
+                _ => imag.clone(),
+            };
+            let root = Expression::complex(real.clone(), imag.clone());
+            let opposite = Expression::complex(negate(real.clone())?, negate(imag)?);
+            if let Some(explanation) = explanation {
+                explanation.rule_applied(
+                    "Take the complex square roots\nsqrt(a + i*b) = ±(sqrt((|a + i*b| + a)/2) + i*sign(b)*sqrt((|a + i*b| - a)/2))",
+                    &Expression::equality(z.clone(), square.clone()),
+                    &Expression::equality(y.clone(), root.clone()),
+                );
+            }
+            roots.push(root);
+            roots.push(opposite);
+        }
+    } else {
+        for square in &squares {
+            let (root, opposite) = match sign(square).ok_or(SolverError::UnsupportedEquationType)? {
+                Ordering::Less => {
+                    let width = square_root(negate(square.clone())?)?;
+                    (
+                        Expression::complex(Expression::integer(0), width.clone()),
+                        Expression::complex(Expression::integer(0), negate(width)?),
+                    )
+                }
+                _ => {
+                    let width = square_root(square.clone())?;
+                    (width.clone(), negate(width)?)
+                }
+            };
+            if let Some(explanation) = explanation {
+                explanation.rule_applied(
+                    "Take the square roots",
+                    &Expression::equality(z.clone(), square.clone()),
+                    &Expression::equality(y.clone(), root.clone()),
+                );
+            }
+            roots.push(root);
+            roots.push(opposite);
+        }
+    }
+    Ok(roots)
+}
+
+/// Roots of `y^4 + p y^2 + q y + r = 0` with `q != 0`.
+///
+/// With `m` a positive root of the resolvent cubic `8m^3 + 8p m^2 + (2p^2 - 8r) m - q^2`,
+/// `y^4 + p y^2 + q y + r = (y^2 + p/2 + m)^2 - 2m(y - q/(4m))^2`
+/// which splits into two quadratics.
+fn ferrari_roots(
+    p: Numeral,
+    q: Numeral,
+    r: Numeral,
+    y: &Expression,
+    depressed: &Expression,
+    explanation: &mut Option<Box<FormattingObserver>>,
+) -> Result<Vec<Expression>, SolverError> {
+    let resolvent = [
+        q.pow(2).neg().reduced(),
+        p.pow(2).mul(&integer(2)).sub(&r.mul(&integer(8))).reduced(),
+        p.mul(&integer(8)).reduced(),
+        integer(8),
+    ];
+    let m_var = Expression::variable("m");
+
+    // The resolvent is negative at 0 and goes to infinity so it has a positive root
+    let m = match find_rational_root(&resolvent).filter(|m| signum(m) == Ordering::Greater) {
+        Some(m) => Ok(m),
+        None => Err(largest_cubic_root(&resolvent)?),
+    };
+    let m_expr = match &m {
+        Ok(m) => number(m.clone()),
+        Err(m) => m.clone(),
+    };
+    if let Some(explanation) = explanation {
+        explanation.rule_applied(
+            "Find a positive root m of the resolvent cubic\n8m^3 + 8p m^2 + (2p^2 - 8r) m - q^2 = 0",
+            &rational_equation(&resolvent, &m_var),
+            &Expression::equality(m_var.clone(), m_expr.clone()),
+        );
+    }
+
+    // s = sqrt(2m), the quadratics are y^2 ± s y + p/2 + m ∓ q/(2s)
+    // and their discriminants -2p - 2m ± 2q/s
+    let half_p = p.div(&integer(2)).reduced();
+    let (s, offset, base_discriminant, slope) = match m {
+        Ok(m) => {
+            let s = rational_square_root(m.mul(&integer(2)).reduced())?;
+            // q/(2s) = q s/(4m) and 2q/s = q s/m
+            (
+                s.clone(),
+                scale(q.div(&m.mul(&integer(4))).reduced(), s.clone())?,
+                number(p.add(&m).mul(&integer(-2))),
+                scale(q.div(&m).reduced(), s)?,
+            )
+        }
+        Err(m) => {
+            let s = Expression::sqrt(scale(integer(2), m.clone())?);
+            (
+                s.clone(),
+                Expression::division(number(q.div(&integer(2))), s.clone()),
+                sum(number(half_p.mul(&integer(-4))), scale(integer(-2), m)?)?,
+                Expression::division(number(q.mul(&integer(2))), s),
+            )
+        }
+    };
+
+    let constant = sum(number(half_p), m_expr)?;
+    let quadratics = [
+        (
+            s.clone(),
+            sum(constant.clone(), negate(offset.clone())?)?,
+            sum(base_discriminant.clone(), slope.clone())?,
+        ),
+        (
+            negate(s)?,
+            sum(constant, offset)?,
+            sum(base_discriminant, negate(slope)?)?,
+        ),
+    ];
+
+    if let Some(explanation) = explanation {
+        let factors = quadratics
+            .iter()
+            .map(|(b, c, _)| {
+                polynomial_expression(&[c.clone(), b.clone(), Expression::integer(1)], y)
+            })
+            .collect();
+        explanation.rule_applied(
+            "Write the equation as a difference of squares\n(y^2 + p/2 + m)^2 - (sqrt(2m)*y - q/(2*sqrt(2m)))^2 = 0\nand split it into two quadratics",
+            depressed,
+            &Expression::equality(Expression::multiplication(factors), Expression::integer(0)),
+        );
+    }
+
+    let mut roots = Vec::new();
+    for (b, c, discriminant) in quadratics {
+        roots.extend(solve_quadratic(
+            integer(1),
+            b,
+            c,
+            discriminant,
+            y,
+            explanation,
+        )?);
+    }
+    Ok(roots)
+}
+
+/// Coefficients `(b/3, p, q)` of the depressed cubic `t^3 + p t + q` obtained with `x = t - b/3`.
+fn depressed_cubic(
+    coefficients: &[Numeral],
+) -> Result<(Numeral, Numeral, Numeral), SolverError> {
+    let lead = &coefficients[3];
+    let [d, c, b] = [
+        coefficients[0].div(lead),
+        coefficients[1].div(lead),
+        coefficients[2].div(lead),
+    ];
+    let shift = b.div(&integer(3)).reduced();
+    // p = c - b^2/3
+    let p = c.sub(&b.mul(&shift)).reduced();
+    // q = 2b^3/27 - bc/3 + d
+    let q = shift
+        .pow(3)
+        .mul(&integer(2))
+        .sub(&shift.mul(&c))
+        .add(&d)
+        .reduced();
+    Ok((shift, p, q))
+}
+
+/// Coefficients `(b/4, p, q, r)` of the depressed quartic `y^4 + p y^2 + q y + r`
+/// obtained with `x = y - b/4`.
+fn depressed_quartic(
+    coefficients: &[Numeral],
+) -> Result<(Numeral, Numeral, Numeral, Numeral), SolverError> {
+    let lead = &coefficients[4];
+    let [e, d, c, b] = [
+        coefficients[0].div(lead),
+        coefficients[1].div(lead),
+        coefficients[2].div(lead),
+        coefficients[3].div(lead),
+    ];
+    let shift = b.div(&integer(4)).reduced();
+    // Expanding (y - s)^4 + b (y - s)^3 + c (y - s)^2 + d (y - s) + e with s = b/4
+    // p = c - 6s^2
+    let p = c.sub(&shift.pow(2).mul(&integer(6))).reduced();
+    // q = d - 2cs + 8s^3
+    let q = shift
+        .pow(3)
+        .mul(&integer(8))
+        .sub(&c.mul(&shift).mul(&integer(2)))
+        .add(&d)
+        .reduced();
+    // r = e - ds + cs^2 - 3s^4
+    let r = shift
+        .pow(4)
+        .mul(&integer(-3))
+        .add(&c.mul(&shift.pow(2)))
+        .sub(&d.mul(&shift))
+        .add(&e)
+        .reduced();
+    Ok((shift, p, q, r))
+}
+
+/// The three real roots of `t^3 + p t + q` when `(q/2)^2 + (p/3)^3 < 0`.
+fn trigonometric_cubic_roots(p: &Numeral, q: &Numeral) -> Result<Vec<Expression>, SolverError> {
+    // 2*sqrt(-p/3)
+    let amplitude = scale(
+        integer(2),
+        rational_square_root(p.div(&integer(-3)).reduced())?,
+    )?;
+    // acos(3q/(2p)*sqrt(-3/p))/3
+    let cosine = scale(
+        q.mul(&integer(3)).div(&p.mul(&integer(2))).reduced(),
+        rational_square_root(integer(-3).div(p).reduced())?,
+    )?;
+    let angle = Expression::division(Expression::acos(cosine), Expression::integer(3));
+
+    (0..3)
+        .map(|k| {
+            let shifted = match k {
+                0 => angle.clone(),
+                k => Expression::subtraction(
+                    angle.clone(),
+                    scale(fraction(2 * k, 3), Expression::pi())?,
+                ),
+            };
+            Ok(Expression::multiplication(vec![
+                amplitude.clone(),
+                Expression::cos(shifted),
+            ]))
+        })
+        .collect()
+}
+
+/// The largest real root of a cubic, used when the resolvent of a quartic has no rational root.
+fn largest_cubic_root(coefficients: &[Numeral]) -> Result<Expression, SolverError> {
+    let (shift, p, q) = depressed_cubic(coefficients)?;
+    let half_q = q.div(&integer(2)).reduced();
+    let delta = half_q
+        .pow(2)
+        .add(&p.div(&integer(3)).pow(3))
+        .reduced();
+
+    let root = match signum(&delta) {
+        Ordering::Less => trigonometric_cubic_roots(&p, &q)?.remove(0),
+        _ => {
+            let sqrt_delta = rational_square_root(delta)?;
+            let minus_half_q = number(half_q.neg());
+            sum(
+                cube_root(sum(minus_half_q.clone(), sqrt_delta.clone())?)?,
+                cube_root(sum(minus_half_q, negate(sqrt_delta)?)?)?,
+            )?
+        }
+    };
+    sum(root, number(shift.neg()))
+}
+
+/// Go back to the original variable with `x = t - shift`.
+fn substitute_back(
+    root: Expression,
+    shift: &Numeral,
+    substitution: &Expression,
+    var: &Expression,
+    explanation: &mut Option<Box<FormattingObserver>>,
+) -> Result<Expression, SolverError> {
+    if shift.is_zero() {
+        return Ok(root);
+    }
+    let shifted = match &root {
+        Expression::Complex(complex) => Expression::complex(
+            sum(complex.real.clone(), number(shift.neg()))?,
+            complex.imag.clone(),
+        ),
+        root => sum(root.clone(), number(shift.neg()))?,
+    };
+    if let Some(explanation) = explanation {
+        explanation.rule_applied(
+            &format!(
+                "Substitute back {} = {}",
+                var,
+                sum(substitution.clone(), number(shift.neg()))?
+            ),
+            &Expression::equality(substitution.clone(), root),
+            &Expression::equality(var.clone(), shifted.clone()),
+        );
+    }
+    Ok(shifted)
+}
+
+/// Search a root `p/q` with `p` dividing the constant coefficient and `q` the leading one.
+///
+/// The divisors come from the prime factors of the coefficients, so nothing is
+/// searched when one of them has a prime factor too large to be found.
+pub(super) fn find_rational_root(coefficients: &[Numeral]) -> Option<Numeral> {
+    // Scale the polynomial to integer coefficients
+    let lcm = coefficients.iter().fold(BigInt::one(), |lcm, coeff| match coeff {
+        Numeral::Rational(_, den) => lcm.lcm(den),
+        _ => lcm,
+    });
+    let lcm = Numeral::Integer(lcm);
+    let (Numeral::Integer(constant), Numeral::Integer(leading)) = (
+        coefficients.first()?.mul(&lcm).reduced(),
+        coefficients.last()?.mul(&lcm).reduced(),
+    ) else {
+        return None;
+    };
+    if constant.is_zero() {
+        return None;
+    }
+
+    let numerators = divisors(&constant)?;
+    let denominators = divisors(&leading)?;
+    for num in &numerators {
+        for den in &denominators {
+            let candidate = Numeral::Rational((*num).into(), (*den).into()).reduced();
+            for candidate in [candidate.clone(), candidate.neg()] {
+                if evaluate(coefficients, &candidate).is_zero() {
+                    return Some(candidate);
+                }
+            }
+        }
+    }
+    None
+}
+
+/// The positive divisors of `n` in increasing order.
+fn divisors(n: &BigInt) -> Option<Vec<u64>> {
+    let n = n.magnitude().to_u64()?;
+    let mut divisors = vec![1];
+    if n > 1 {
+        for (prime, count) in utils::prime_factors(n)? {
+            let mut multiples = Vec::new();
+            for divisor in &divisors {
+                let mut power = 1;
+                for _ in 0..count {
+                    power *= prime;
+                    multiples.push(divisor * power);
+                }
+            }
+            divisors.extend(multiples);
+        }
+    }
+    divisors.sort_unstable();
+    Some(divisors)
+}
+
+/// Horner's evaluation of a polynomial.
+fn evaluate(coefficients: &[Numeral], value: &Numeral) -> Numeral {
+    coefficients
+        .iter()
+        .rev()
+        .fold(integer(0), |acc, coeff| acc.mul(value).add(coeff).reduced())
+}
+
+/// Divide the polynomial by `(x - root)` using synthetic division.
+pub(super) fn deflate(coefficients: &[Numeral], root: &Numeral) -> Vec<Numeral> {
+    let mut quotient = vec![integer(0); coefficients.len() - 1];
+    let mut carry = integer(0);
+    for i in (1..coefficients.len()).rev() {
+        carry = carry.mul(root).add(&coefficients[i]).reduced();
+        quotient[i - 1] = carry.clone();
+    }
+    quotient
+}
+
+/// Build `cn*x^n + ... + c1*x + c0` from the coefficients `[c0, c1, ..., cn]`.
+pub(crate) fn polynomial_expression(coefficients: &[Expression], var: &Expression) -> Expression {
+    let mut terms: Vec<Expression> = coefficients
+        .iter()
+        .enumerate()
+        .rev()
+        .filter(|(_, coeff)| !is_zero(coeff))
+        .map(|(degree, coeff)| {
+            if degree == 0 {
+                return coeff.clone();
+            }
+            let power = power_of(var, degree);
+            match coeff {
+                coeff if is_one(coeff) => power,
+                Expression::Negation(neg) if is_one(&neg.term) => Expression::negation(power),
+                Expression::Negation(neg) => {
+                    Expression::negation(Expression::multiplication(vec![neg.term.clone(), power]))
+                }
+                coeff => Expression::multiplication(vec![coeff.clone(), power]),
+            }
+        })
+        .collect();
+
+    match terms.len() {
+        0 => Expression::integer(0),
+        1 => terms.remove(0),
+        _ => Expression::addition(terms),
+    }
+}
+
+fn rational_polynomial(coefficients: &[Numeral], var: &Expression) -> Expression {
+    let coefficients = coefficients
+        .iter()
+        .map(|coeff| number(coeff.clone()))
+        .collect::<Vec<_>>();
+    polynomial_expression(&coefficients, var)
+}
+
+fn rational_equation(coefficients: &[Numeral], var: &Expression) -> Expression {
+    Expression::equality(rational_polynomial(coefficients, var), Expression::integer(0))
+}
+
+fn discriminant_expression(a: &Expression, b: &Expression, c: &Expression) -> Expression {
+    Expression::subtraction(
+        Expression::exponentiation(b.clone(), Expression::integer(2)),
+        Expression::multiplication(vec![Expression::integer(4), a.clone(), c.clone()]),
+    )
+}
+
+fn power_of(var: &Expression, degree: usize) -> Expression {
+    match degree {
+        1 => var.clone(),
+        degree => Expression::exponentiation(var.clone(), Expression::integer(degree as u64)),
+    }
+}
+
+/// Name of the variable introduced by a substitution, distinct from the solved one.
+fn substitution_variable(var: &Expression, name: &str) -> Expression {
+    if var.contains_var(name) {
+        Expression::variable(&format!("{}'", name))
+    } else {
+        Expression::variable(name)
+    }
+}
+
+fn integer(n: i64) -> Numeral {
+    Numeral::Integer(BigInt::from(n))
+}
+
+fn fraction(num: i64, den: i64) -> Numeral {
+    Numeral::Rational(BigInt::from(num), BigInt::from(den)).reduced()
+}
+
+fn number(value: Numeral) -> Expression {
+    value.reduced().into_expression()
+}
+
+/// `1/value`, dividing by zero is an error.
+fn inverse(value: &Numeral) -> Result<Numeral, SolverError> {
+    value
+        .inverse()
+        .ok_or(SolverError::Simplification(SimplifyError::DivisionByZero))
+}
+
+/// `lhs + rhs` written as a subtraction when `rhs` is negative.
+fn sum(lhs: Expression, rhs: Expression) -> Result<Expression, SolverError> {
+    if let (Some(a), Some(b)) = (exact_value(&lhs), exact_value(&rhs)) {
+        return Ok(number(a.add(&b)));
+    }
+    Ok(if is_zero(&lhs) {
+        rhs
+    } else if is_zero(&rhs) {
+        lhs
+    } else {
+        match rhs {
+            Expression::Negation(neg) => Expression::subtraction(lhs, neg.term),
+            rhs => match lhs {
+                Expression::Addition(mut add) => {
+                    add.terms.push(rhs);
+                    Expression::Addition(add)
+                }
+                lhs => Expression::addition(vec![lhs, rhs]),
+            },
+        }
+    })
+}
+
+fn negate(expr: Expression) -> Result<Expression, SolverError> {
+    if let Some(value) = exact_value(&expr) {
+        return Ok(number(value.neg()));
+    }
+    Ok(match expr {
+        Expression::Negation(neg) => neg.term,
+        Expression::Subtraction(sub) => Expression::subtraction(sub.right, sub.left),
+        Expression::Division(div) => Expression::division(negate(div.num)?, div.den),
+        expr => Expression::negation(expr),
+    })
+}
+
+/// `factor * expr` written as a fraction when `factor` is not an integer.
+fn scale(factor: Numeral, expr: Expression) -> Result<Expression, SolverError> {
+    if let Some(value) = exact_value(&expr) {
+        return Ok(number(factor.mul(&value)));
+    }
+    if factor.is_negative() {
+        return negate(scale(factor.neg(), expr)?);
+    }
+    // Merge with the numerical factor already present
+    let (coeff, expr) = split_coefficient(expr);
+    let factor = factor.mul(&coeff).reduced();
+    if factor.is_negative() {
+        return negate(scale(factor.neg(), expr)?);
+    }
+    let (num, den) = match factor {
+        Numeral::Integer(num) => (num, BigInt::one()),
+        Numeral::Rational(num, den) => (num, den),
+        Numeral::Float(_) => return Err(SolverError::UnsupportedEquationType),
+    };
+    let scaled = if num.is_zero() {
+        return Ok(Expression::integer(0));
+    } else if num.is_one() {
+        expr
+    } else {
+        Expression::multiplication(vec![Expression::integer(num), expr])
+    };
+    Ok(if den.is_one() {
+        scaled
+    } else {
+        Expression::division(scaled, Expression::integer(den))
+    })
+}
+
+/// Split `expr` into a rational factor and the rest of the expression.
+fn split_coefficient(expr: Expression) -> (Numeral, Expression) {
+    match expr {
+        Expression::Negation(neg) => {
+            let (coeff, rest) = split_coefficient(neg.term);
+            (coeff.neg(), rest)
+        }
+        Expression::Multiplication(mul) if mul.terms.len() == 2 => {
+            match exact_value(&mul.terms[0]) {
+                Some(coeff) => (coeff, mul.terms[1].clone()),
+                None => (integer(1), Expression::Multiplication(mul)),
+            }
+        }
+        Expression::Division(div) => {
+            match exact_value(&div.den).and_then(|den| den.inverse()) {
+                Some(inverse) => {
+                    let (coeff, rest) = split_coefficient(div.num);
+                    (coeff.mul(&inverse).reduced(), rest)
+                }
+                None => (integer(1), Expression::Division(div)),
+            }
+        }
+        expr => (integer(1), expr),
+    }
+}
+
+/// `root(order, value)` of a positive rational as `coeff * root(order, radicand)`
+/// with `radicand` an integer, `None` for an inexact value.
+///
+/// `a/b = a*b^(order-1) / b^order` so only the numerator has to be reduced.
+fn extract_root(value: &Numeral, order: u32) -> Option<(Numeral, BigInt)> {
+    let (num, den) = match value {
+        Numeral::Integer(num) => (num.clone(), BigInt::one()),
+        Numeral::Rational(num, den) => (num.clone(), den.clone()),
+        Numeral::Float(_) => return None,
+    };
+    let mut rest = num * den.pow(order - 1);
+    let (mut outside, mut inside) = (BigInt::one(), BigInt::one());
+    let mut factor = BigInt::from(2);
+    while &factor * &factor <= rest && factor < BigInt::from(ROOT_TRIAL_DIVISION_LIMIT) {
+        let mut count = 0;
+        while rest.is_multiple_of(&factor) {
+            rest /= &factor;
+            count += 1;
+        }
+        outside *= factor.pow(count / order);
+        inside *= factor.pow(count % order);
+        factor += 1;
+    }
+    let root = rest.nth_root(order);
+    if root.pow(order) == rest {
+        outside *= root;
+    } else {
+        inside *= rest;
+    }
+    Some((Numeral::Rational(outside, den).reduced(), inside))
+}
+
+/// `sqrt(r)` with the square factors taken out of the radical.
+fn rational_square_root(value: Numeral) -> Result<Expression, SolverError> {
+    if value.is_zero() {
+        return Ok(Expression::integer(0));
+    }
+    if value.is_negative() {
+        return Ok(Expression::sqrt(number(value)));
+    }
+    match extract_root(&value, 2) {
+        Some((coeff, radicand)) if radicand.is_one() => Ok(number(coeff)),
+        Some((coeff, radicand)) => scale(coeff, Expression::sqrt(Expression::integer(radicand))),
+        None => Ok(Expression::sqrt(number(value))),
+    }
+}
+
+/// Square root of a positive expression.
+pub(crate) fn square_root(expr: Expression) -> Result<Expression, SolverError> {
+    match exact_value(&expr) {
+        Some(value) => rational_square_root(value),
+        None => Ok(Expression::sqrt(expr)),
+    }
+}
+
+/// Real cube root, negative values have a negative cube root.
+fn cube_root(expr: Expression) -> Result<Expression, SolverError> {
+    let Some(value) = exact_value(&expr) else {
+        return Ok(Expression::root(Expression::integer(3), expr));
+    };
+    match signum(&value) {
+        Ordering::Less => negate(cube_root(number(value.neg()))?),
+        Ordering::Equal => Ok(Expression::integer(0)),
+        Ordering::Greater => match extract_root(&value, 3) {
+            Some((coeff, radicand)) if radicand.is_one() => Ok(number(coeff)),
+            Some((coeff, radicand)) => scale(
+                coeff,
+                Expression::root(Expression::integer(3), Expression::integer(radicand)),
+            ),
+            None => Ok(Expression::root(Expression::integer(3), expr)),
+        },
+    }
+}
+
+/// Sign of a real constant expression, exact for rationals and numerical otherwise.
+fn sign(expr: &Expression) -> Option<Ordering> {
+    if let Some(value) = exact_value(expr) {
+        return Some(signum(&value));
+    }
+    let value = expr
+        .evaluate(&HashMap::new())
+        .ok()
+        .filter(|value| value.is_finite())?;
+    Some(if value.abs() < SIGN_TOLERANCE {
+        Ordering::Equal
+    } else if value > 0.0 {
+        Ordering::Greater
+    } else {
+        Ordering::Less
+    })
+}
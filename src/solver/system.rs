@@ -0,0 +1,545 @@
+use crate::{
+    ast::{
+        Expression, SimplifyOptions,
+        assumptions::{Assumption, Assumptions},
+        inequality::Relation,
+    },
+    explanation::FormattingObserver,
+};
+
+use super::{
+    Solver, SolverError, SystemSolution, add_coefficients, exact_value, fold, is_one, is_zero,
+    mul_coefficients, neg_coefficient, polynomial_coefficients, signum,
+};
+
+// Systems of linear equations
+impl Solver {
+    /// Solves a system of linear equations for the specified unknowns.
+    ///
+    /// Every equation is written as `a1*x1 + ... + an*xn = b` and the augmented
+    /// matrix is reduced with Gauss-Jordan elimination. Numerical coefficients are
+    /// computed exactly and the equations are only divided by pivots proven non
+    /// zero. When a symbolic pivot can be zero the system is solved in both cases
+    /// and `SystemSolution::Cases` gives the solution under each condition.
+    ///
+    /// The unknowns must be `Expression::Variable` type and the equations
+    /// `Expression::Equality` type.
+    ///
+    /// # Examples
+    /// ```
+    /// use sym_rustic::ast::Expression;
+    /// use sym_rustic::solver::{Solver, SystemSolution};
+    ///
+    /// // x + y = 3, x - y = 1
+    /// let equations = vec![
+    ///     Expression::equality(
+    ///         Expression::addition(vec![Expression::variable("x"), Expression::variable("y")]),
+    ///         Expression::integer(3),
+    ///     ),
+    ///     Expression::equality(
+    ///         Expression::subtraction(Expression::variable("x"), Expression::variable("y")),
+    ///         Expression::integer(1),
+    ///     ),
+    /// ];
+    ///
+    /// let solution = Solver::solve_system(
+    ///     equations,
+    ///     vec![Expression::variable("x"), Expression::variable("y")],
+    ///     &mut None,
+    /// );
+    /// assert_eq!(
+    ///     solution,
+    ///     Ok(SystemSolution::Unique(vec![Expression::integer(2), Expression::integer(1)]))
+    /// );
+    /// ```
+    pub fn solve_system(
+        equations: Vec<Expression>,
+        variables: Vec<Expression>,
+        explanation: &mut Option<Box<FormattingObserver>>,
+    ) -> Result<SystemSolution, SolverError> {
+        let names = variables
+            .iter()
+            .map(|variable| match variable {
+                Expression::Variable(var) => Ok(var.name.clone()),
+                _ => Err(SolverError::InvalidVariable),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if equations
+            .iter()
+            .any(|equation| !matches!(equation, Expression::Equality(_)))
+        {
+            return Err(SolverError::InvalidEquation);
+        }
+        if !equations
+            .iter()
+            .any(|equation| names.iter().any(|name| equation.contains_var(name)))
+        {
+            return Err(SolverError::VariableNotFound);
+        }
+
+        if let Some(explanation) = explanation {
+            explanation.open_explaination(format!(
+                "Solving the system {} for {}",
+                equations
+                    .iter()
+                    .map(|equation| equation.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                names.join(", ")
+            ));
+        }
+
+        // Augmented matrix, the last column holds the constants
+        let mut rows = Vec::new();
+        for (i, equation) in equations.into_iter().enumerate() {
+            let Expression::Equality(mut equality) = equation else {
+                return Err(SolverError::InvalidEquation);
+            };
+            let before = Expression::equality(equality.left.clone(), equality.right.clone());
+            let lhs = equality.left.simplify(explanation)?;
+            let rhs = equality.right.simplify(explanation)?;
+
+            // a x + b = c x + d => (a - c) x = d - b
+            let (lhs_coeffs, lhs_const) = linear_combination(&lhs, &names)?;
+            let (rhs_coeffs, rhs_const) = linear_combination(&rhs, &names)?;
+            let mut row = lhs_coeffs
+                .into_iter()
+                .zip(rhs_coeffs)
+                .map(|(lhs, rhs)| add_coefficients(lhs, neg_coefficient(rhs)?))
+                .collect::<Result<Vec<_>, _>>()?;
+            row.push(add_coefficients(rhs_const, neg_coefficient(lhs_const)?)?);
+
+            let after = row_equation(&row, &variables);
+            if let Some(explanation) = explanation
+                && !before.is_equal(&after)
+            {
+                explanation.rule_applied(
+                    &format!(
+                        "Write equation {} as a linear combination of the unknowns",
+                        i + 1
+                    ),
+                    &before,
+                    &after,
+                );
+            }
+            rows.push(row);
+        }
+
+        solve_rows(rows, &variables, &[], &Assumptions::new(), explanation)
+    }
+}
+
+/// Solve the augmented matrix, `nonzero` holds the symbolic entries already
+/// supposed different from zero and `assumptions` the symbols supposed non zero.
+fn solve_rows(
+    mut rows: Vec<Vec<Expression>>,
+    variables: &[Expression],
+    nonzero: &[Expression],
+    assumptions: &Assumptions,
+    explanation: &mut Option<Box<FormattingObserver>>,
+) -> Result<SystemSolution, SolverError> {
+    let is_nonzero = |entry: &Expression| {
+        entry.is_nonzero(assumptions) || nonzero.iter().any(|other| other.is_equal(entry))
+    };
+    let pivots = match reduce(&mut rows, variables, &is_nonzero, explanation)? {
+        Reduction::Reduced(pivots) => pivots,
+        Reduction::Undecided { row, pivot } => {
+            return split(rows, row, pivot, variables, nonzero, assumptions, explanation);
+        }
+    };
+    let unknowns = variables.len();
+
+    // The rows without pivot are 0 = c
+    for (i, row) in rows.iter().enumerate().skip(pivots.len()) {
+        let constant = &row[unknowns];
+        if is_zero(constant) {
+            continue;
+        }
+        if !is_nonzero(constant) {
+            let constant = constant.clone();
+            return split(rows, i, constant, variables, nonzero, assumptions, explanation);
+        }
+        if let Some(explanation) = explanation {
+            explanation.rule_applied(
+                "The equation is never true, the system has no solution",
+                &row_equation(row, variables),
+                &row_equation(row, variables),
+            );
+        }
+        return Ok(SystemSolution::NoSolution);
+    }
+
+    let parameters: Vec<usize> = (0..unknowns)
+        .filter(|column| !pivots.contains(column))
+        .collect();
+    let mut values = variables.to_vec();
+    for (row, column) in rows.iter().zip(&pivots) {
+        // x + a z = b => x = b - a z
+        let mut value = row[unknowns].clone();
+        for parameter in &parameters {
+            let term = mul_coefficients(row[*parameter].clone(), variables[*parameter].clone())?;
+            value = add_coefficients(value, neg_coefficient(term)?)?;
+        }
+        let options = SimplifyOptions {
+            assumptions: assumptions.clone(),
+            ..Default::default()
+        };
+        let value = value.simplify_with(&options, &mut None)?;
+        if let Some(explanation) = explanation
+            && !parameters.is_empty()
+        {
+            explanation.rule_applied(
+                &format!("Express {} with the free unknowns", variables[*column]),
+                &row_equation(row, variables),
+                &Expression::equality(variables[*column].clone(), value.clone()),
+            );
+        }
+        values[*column] = value;
+    }
+
+    if parameters.is_empty() {
+        Ok(SystemSolution::Unique(values))
+    } else {
+        Ok(SystemSolution::Infinite {
+            parameters: parameters
+                .into_iter()
+                .map(|column| variables[column].clone())
+                .collect(),
+            values,
+        })
+    }
+}
+
+/// Solve the system when the symbolic `entry` of equation `row` is not zero
+/// and when it is zero.
+fn split(
+    rows: Vec<Vec<Expression>>,
+    row: usize,
+    entry: Expression,
+    variables: &[Expression],
+    nonzero: &[Expression],
+    assumptions: &Assumptions,
+    explanation: &mut Option<Box<FormattingObserver>>,
+) -> Result<SystemSolution, SolverError> {
+    // e != 0 or e = 0, written as s != v or s = v when e = 0 gives s = v
+    let (target, value) = zero_of(&entry)?;
+    let when_nonzero = Expression::inequality(target.clone(), Relation::NotEqual, value.clone());
+    let when_zero = Expression::equality(target.clone(), value.clone());
+    if let Some(explanation) = explanation {
+        explanation.rule_applied(
+            &format!(
+                "{} can be zero, solve the system when {} and when {}",
+                entry, when_nonzero, when_zero
+            ),
+            &row_equation(&rows[row], variables),
+            &row_equation(&rows[row], variables),
+        );
+    }
+
+    let mut supposed = nonzero.to_vec();
+    supposed.push(entry);
+    let mut nonzero_assumptions = assumptions.clone();
+    if let Expression::Variable(var) = &target
+        && is_zero(&value)
+    {
+        nonzero_assumptions.assume(&var.name, Assumption::nonzero());
+    }
+    let nonzero_case = solve_rows(
+        rows.clone(),
+        variables,
+        &supposed,
+        &nonzero_assumptions,
+        explanation,
+    )?;
+
+    let substitute = |expr: &Expression| fold(expr.substitute(&target, &value));
+    let rows = rows
+        .iter()
+        .map(|row| row.iter().map(substitute).collect::<Result<Vec<_>, _>>())
+        .collect::<Result<Vec<_>, _>>()?;
+    let supposed = nonzero
+        .iter()
+        .map(substitute)
+        .collect::<Result<Vec<_>, _>>()?;
+    // An entry supposed non zero vanishes, the case is impossible
+    if supposed.iter().any(is_zero) {
+        return Ok(nonzero_case);
+    }
+    let zero_case = solve_rows(rows, variables, &supposed, assumptions, explanation)?;
+
+    Ok(SystemSolution::Cases(vec![
+        (when_nonzero, nonzero_case),
+        (when_zero, zero_case),
+    ]))
+}
+
+/// `target` and `value` such that `expr = 0` is `target = value`.
+///
+/// `expr` is solved for the first symbol it is linear in with a numerical
+/// slope, otherwise the condition is kept as `expr = 0`.
+fn zero_of(expr: &Expression) -> Result<(Expression, Expression), SolverError> {
+    let mut names = Vec::new();
+    symbols(expr, &mut names);
+    for name in names {
+        if let Ok(coefficients) = polynomial_coefficients(expr, &name)
+            && let [constant, slope] = coefficients.as_slice()
+            && exact_value(slope).is_some()
+        {
+            let value = fold(Expression::division(
+                neg_coefficient(constant.clone())?,
+                slope.clone(),
+            ))?;
+            return Ok((Expression::variable(&name), value));
+        }
+    }
+    Ok((expr.clone(), Expression::integer(0)))
+}
+
+/// Names of the variables of `expr` in the order they appear.
+fn symbols(expr: &Expression, names: &mut Vec<String>) {
+    match expr {
+        Expression::Variable(var) => {
+            if !names.contains(&var.name) {
+                names.push(var.name.clone());
+            }
+        }
+        expr => {
+            let _ = expr.map_children(|child| {
+                symbols(child, names);
+                Ok(child.clone())
+            });
+        }
+    }
+}
+
+/// Result of the elimination.
+enum Reduction {
+    /// The column of the pivot of each row, the rows after the last pivot are all zero
+    Reduced(Vec<usize>),
+    /// The symbolic `pivot` of equation `row` is the only candidate of its column
+    /// and it is not known if it is zero
+    Undecided { row: usize, pivot: Expression },
+}
+
+/// Gauss-Jordan elimination of the augmented matrix into its reduced row echelon form.
+///
+/// The pivots are chosen among the entries for which `is_nonzero` holds, the
+/// reduction stops at a column whose only candidates can be zero.
+fn reduce(
+    rows: &mut [Vec<Expression>],
+    variables: &[Expression],
+    is_nonzero: &impl Fn(&Expression) -> bool,
+    explanation: &mut Option<Box<FormattingObserver>>,
+) -> Result<Reduction, SolverError> {
+    let mut pivots = Vec::new();
+
+    for column in 0..variables.len() {
+        let current = pivots.len();
+        let Some(pivot) = (current..rows.len()).find(|i| is_nonzero(&rows[*i][column])) else {
+            match (current..rows.len()).find(|i| !is_zero(&rows[*i][column])) {
+                Some(row) => {
+                    return Ok(Reduction::Undecided {
+                        row,
+                        pivot: rows[row][column].clone(),
+                    });
+                }
+                None => continue,
+            }
+        };
+
+        if pivot != current {
+            rows.swap(pivot, current);
+            if let Some(explanation) = explanation {
+                explanation.rule_applied(
+                    &format!("Swap equations {} and {}", current + 1, pivot + 1),
+                    &row_equation(&rows[pivot], variables),
+                    &row_equation(&rows[current], variables),
+                );
+            }
+        }
+
+        // Make the pivot one
+        let pivot = rows[current][column].clone();
+        if !is_one(&pivot) {
+            let before = row_equation(&rows[current], variables);
+            rows[current] = rows[current]
+                .iter()
+                .map(|entry| divide(entry.clone(), pivot.clone()))
+                .collect::<Result<_, _>>()?;
+            if let Some(explanation) = explanation {
+                explanation.rule_applied(
+                    &format!("Divide equation {} by {}", current + 1, pivot),
+                    &before,
+                    &row_equation(&rows[current], variables),
+                );
+            }
+        }
+
+        // Eliminate the unknown from every other equation
+        for i in 0..rows.len() {
+            let factor = rows[i][column].clone();
+            if i == current || is_zero(&factor) {
+                continue;
+            }
+            let before = row_equation(&rows[i], variables);
+            rows[i] = rows[i]
+                .iter()
+                .zip(&rows[current])
+                .map(|(entry, pivot_entry)| {
+                    let term = mul_coefficients(factor.clone(), pivot_entry.clone())?;
+                    add_coefficients(entry.clone(), neg_coefficient(term)?)
+                })
+                .collect::<Result<_, _>>()?;
+            if let Some(explanation) = explanation {
+                let rule = match exact_value(&factor) {
+                    Some(factor) if factor.is_integer(1) => {
+                        format!("Subtract equation {} from equation {}", current + 1, i + 1)
+                    }
+                    Some(factor) if factor.is_integer(-1) => {
+                        format!("Add equation {} to equation {}", current + 1, i + 1)
+                    }
+                    Some(factor) if signum(&factor).is_lt() => format!(
+                        "Add {} times equation {} to equation {}",
+                        factor.neg().into_expression(),
+                        current + 1,
+                        i + 1
+                    ),
+                    _ => format!(
+                        "Subtract {} times equation {} from equation {}",
+                        factor,
+                        current + 1,
+                        i + 1
+                    ),
+                };
+                explanation.rule_applied(&rule, &before, &row_equation(&rows[i], variables));
+            }
+        }
+
+        pivots.push(column);
+    }
+
+    Ok(Reduction::Reduced(pivots))
+}
+
+/// Returns the coefficients `[a1, ..., an]` and `b` such that `expr = a1*x1 + ... + an*xn + b`.
+fn linear_combination(
+    expr: &Expression,
+    variables: &[String],
+) -> Result<(Vec<Expression>, Expression), SolverError> {
+    let zeros = || vec![Expression::integer(0); variables.len()];
+    if !variables.iter().any(|variable| expr.contains_var(variable)) {
+        return Ok((zeros(), fold(expr.clone())?));
+    }
+
+    match expr {
+        Expression::Variable(var) => {
+            let mut coeffs = zeros();
+            if let Some(i) = variables.iter().position(|name| *name == var.name) {
+                coeffs[i] = Expression::integer(1);
+            }
+            Ok((coeffs, Expression::integer(0)))
+        }
+        Expression::Negation(neg) => {
+            let (coeffs, constant) = linear_combination(&neg.term, variables)?;
+            Ok((
+                coeffs
+                    .into_iter()
+                    .map(neg_coefficient)
+                    .collect::<Result<_, _>>()?,
+                neg_coefficient(constant)?,
+            ))
+        }
+        Expression::Addition(add) => add.terms.iter().try_fold(
+            (zeros(), Expression::integer(0)),
+            |(coeffs, constant), term| {
+                let (term_coeffs, term_constant) = linear_combination(term, variables)?;
+                Ok((
+                    coeffs
+                        .into_iter()
+                        .zip(term_coeffs)
+                        .map(|(a, b)| add_coefficients(a, b))
+                        .collect::<Result<_, _>>()?,
+                    add_coefficients(constant, term_constant)?,
+                ))
+            },
+        ),
+        Expression::Subtraction(sub) => linear_combination(
+            &Expression::addition(vec![
+                sub.left.clone(),
+                Expression::negation(sub.right.clone()),
+            ]),
+            variables,
+        ),
+        // Only one factor can contain the unknowns
+        Expression::Multiplication(mul) => {
+            let (linear, rest): (Vec<&Expression>, Vec<&Expression>) = mul
+                .terms
+                .iter()
+                .partition(|term| variables.iter().any(|variable| term.contains_var(variable)));
+            let [linear] = linear.as_slice() else {
+                return Err(SolverError::UnsupportedEquationType);
+            };
+            let factor = fold(Expression::multiplication(
+                rest.into_iter().cloned().collect(),
+            ))?;
+            let (coeffs, constant) = linear_combination(linear, variables)?;
+            Ok((
+                coeffs
+                    .into_iter()
+                    .map(|coeff| mul_coefficients(factor.clone(), coeff))
+                    .collect::<Result<_, _>>()?,
+                mul_coefficients(factor, constant)?,
+            ))
+        }
+        Expression::Division(div)
+            if !variables
+                .iter()
+                .any(|variable| div.den.contains_var(variable)) =>
+        {
+            let (coeffs, constant) = linear_combination(&div.num, variables)?;
+            Ok((
+                coeffs
+                    .into_iter()
+                    .map(|coeff| divide(coeff, div.den.clone()))
+                    .collect::<Result<_, _>>()?,
+                divide(constant, div.den.clone())?,
+            ))
+        }
+        _ => Err(SolverError::UnsupportedEquationType),
+    }
+}
+
+fn divide(num: Expression, den: Expression) -> Result<Expression, SolverError> {
+    if is_zero(&num) || is_one(&den) {
+        Ok(num)
+    } else {
+        fold(Expression::division(num, den))
+    }
+}
+
+/// Build the equation `a1*x1 + ... + an*xn = b` of a row of the augmented matrix.
+fn row_equation(row: &[Expression], variables: &[Expression]) -> Expression {
+    let mut terms: Vec<Expression> = row
+        .iter()
+        .zip(variables)
+        .filter(|(coeff, _)| !is_zero(coeff))
+        .map(|(coeff, variable)| match coeff {
+            coeff if is_one(coeff) => variable.clone(),
+            Expression::Negation(neg) if is_one(&neg.term) => {
+                Expression::negation(variable.clone())
+            }
+            Expression::Negation(neg) => Expression::negation(Expression::multiplication(vec![
+                neg.term.clone(),
+                variable.clone(),
+            ])),
+            coeff => Expression::multiplication(vec![coeff.clone(), variable.clone()]),
+        })
+        .collect();
+
+    let lhs = match terms.len() {
+        0 => Expression::integer(0),
+        1 => terms.remove(0),
+        _ => Expression::addition(terms),
+    };
+    Expression::equality(lhs, row[variables.len()].clone())
+}
@@ -1,5 +1,8 @@
 use std::collections::HashMap;
 
+use num_bigint::BigInt;
+use num_traits::{One, Zero};
+
 use crate::ast::{numeral, Expression};
 
 pub fn gcd(mut a: u64, mut b: u64) -> u64 {
@@ -21,7 +24,10 @@ pub fn gcd(mut a: u64, mut b: u64) -> u64 {
 }
 
 pub fn lcm(a: u64, b: u64) -> u64 {
-    a * b / gcd(a, b)
+    if a == 0 || b == 0 {
+        return 0;
+    }
+    a / gcd(a, b) * b
 }
 
 pub fn is_prime(n: u64) -> bool {
@@ -29,7 +35,7 @@ pub fn is_prime(n: u64) -> bool {
         return false;
     }
     for i in 2..=n {
-        if n % i == 0 {
+        if n.is_multiple_of(i) {
             return false;
         }
     }
@@ -39,7 +45,7 @@ pub fn is_prime(n: u64) -> bool {
 const PRIMES_25: [u64; 25] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71, 73, 79, 83, 89, 97];
 const PRIMES_FACTORS_UPPER_LIMIT: u64 = 1000000;
 /// Returns an `Option` of a `HashMap` prime factors and their counts.
-/// Up to 1000000
+/// Up to 1000000, `None` if a factor is bigger
 ///
 /// First term is the prime factor and the second is the count.
 /// 
@@ -61,7 +67,7 @@ pub fn prime_factors(mut n: u64) -> Option<HashMap<u64, u64>> {
     let mut i = 0;
 
     while i < PRIMES_25.len() && n > 1 {
-        if n % PRIMES_25[i] == 0 {
+        if n.is_multiple_of(PRIMES_25[i]) {
             factors.insert(PRIMES_25[i], factors.get(&PRIMES_25[i]).unwrap_or(&0) + 1);
             n /= PRIMES_25[i];
         } else {
@@ -72,24 +78,31 @@ pub fn prime_factors(mut n: u64) -> Option<HashMap<u64, u64>> {
     if n > 1 {
         let mut j = 97;
         while j * j <= n && j < PRIMES_FACTORS_UPPER_LIMIT {
-            while n % j == 0 {
+            while n.is_multiple_of(j) {
                 factors.insert(j, factors.get(&j).unwrap_or(&0) + 1);
                 n /= j;
             }
             j += 1;
         }
+        // The rest is a prime unless the search stopped at the limit
+        if n > 1 {
+            if j * j <= n {
+                return None;
+            }
+            factors.insert(n, factors.get(&n).unwrap_or(&0) + 1);
+        }
     } 
 
     Some(factors)
 }
 
 
-pub fn factorial(n: u64) -> u64 {
-    (1..=n).product()
+pub fn factorial(n: u64) -> BigInt {
+    (1..=n).map(BigInt::from).product()
 }
 
-fn multinomial_coefficient(n: u64, k: &[u64]) -> u64 {
-    factorial(n) / k.iter().map(|k| factorial(*k)).product::<u64>()
+fn multinomial_coefficient(n: u64, k: &[u64]) -> BigInt {
+    factorial(n) / k.iter().map(|k| factorial(*k)).product::<BigInt>()
 }
 
 /// Expand a multinomial expression
@@ -97,7 +110,7 @@ pub fn multinomial_expansion(terms: &[Expression], n: u64) -> Expression {
         let mut result: Vec<Expression> = vec![];
         let m = terms.len();
         let exponent_permutations: Vec<Vec<u64>> = find_permutations_with_sum(m, n);
-        let mut already_calc_coef: Vec<(Vec<u64>, u64)> = Vec::new();
+        let mut already_calc_coef: Vec<(Vec<u64>, BigInt)> = Vec::new();
 
         for exponent_permutation in exponent_permutations {
             let mut mult: Vec<Expression> = Vec::new();
@@ -108,19 +121,19 @@ pub fn multinomial_expansion(terms: &[Expression], n: u64) -> Expression {
     
             // Sort the permutation because in permutation [3, 1, 0, 0] != [1, 3, 0, 0] but the associated coefficient is the same
             sorted_exponent_permutations.sort();
-            let mut coeff: u64 = 0;
+            let mut coeff = BigInt::zero();
     
             // Check if we already calculate the coefficient
             for (term_exponents, coefficient) in &already_calc_coef {
                 if *term_exponents == sorted_exponent_permutations {
-                    coeff = *coefficient;
+                    coeff = coefficient.clone();
                     break;
                 }
             }
             // If not we add it to the list
-            if coeff == 0 {
+            if coeff.is_zero() {
                 coeff = multinomial_coefficient(n, &sorted_exponent_permutations);
-                already_calc_coef.push((sorted_exponent_permutations, coeff));
+                already_calc_coef.push((sorted_exponent_permutations, coeff.clone()));
             }
 
     
@@ -181,9 +194,9 @@ fn find_permutations_with_sum(m: usize, n: u64) -> Vec<Vec<u64>> {
 /// a tuple that represent the terms with the sign and the coefficient separate.
 /// 
 /// The form is (negative, coefficient, terms)
-pub fn transform_multiplication(terms: Vec<Expression>) -> (bool, u64, Vec<Expression>) {
+pub fn transform_multiplication(terms: Vec<Expression>) -> (bool, BigInt, Vec<Expression>) {
     let mut negative = false;
-    let mut coeff = 1;
+    let mut coeff = BigInt::one();
     let mut striped_terms = vec![];
 
     terms
@@ -191,7 +204,7 @@ pub fn transform_multiplication(terms: Vec<Expression>) -> (bool, u64, Vec<Expre
         match term {
             Expression::Negation(inner) => {
                 if let Expression::Number(numeral::Numeral::Integer(a)) =
-                    inner.term
+                    &inner.term
                 {
                     coeff *= a;
                     negative = !negative;
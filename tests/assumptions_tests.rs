@@ -0,0 +1,146 @@
+use sym_rustic::{
+    ast::{
+        Expression, SimplifyOptions,
+        assumptions::{Assumption, Assumptions},
+    },
+    lexer::{Lexer, Token},
+    parser::Parser,
+};
+
+fn lex(input: &str) -> Vec<Token> {
+    let mut lexer = Lexer::new(input);
+    let mut tokens: Vec<Token> = Vec::new();
+    while let Some(token) = lexer.next_token() {
+        tokens.push(token);
+    }
+    tokens
+}
+
+fn parse(input: &str) -> Expression {
+    let tokens = lex(input);
+    let mut parser = Parser::new(&tokens);
+    parser.parse_expression().unwrap()
+}
+
+/// Simplify `input` with `assumption` on `x`.
+fn simplify_assuming(input: &str, assumption: Option<Assumption>) -> String {
+    let mut assumptions = Assumptions::new();
+    if let Some(assumption) = assumption {
+        assumptions.assume("x", assumption);
+    }
+    let options = SimplifyOptions {
+        assumptions,
+        ..Default::default()
+    };
+    parse(input)
+        .simplify_with(&options, &mut None)
+        .unwrap()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests_assumptions {
+    use crate::{parse, simplify_assuming};
+    use sym_rustic::{
+        ast::{
+            Expression,
+            assumptions::{Assumption, Assumptions},
+        },
+        solver::{Bound, Interval},
+    };
+
+    fn negative() -> Assumption {
+        Assumption::interval(Interval {
+            lower: Bound::Unbounded,
+            upper: Bound::Open(Expression::integer(0)),
+        })
+    }
+
+    fn nonnegative() -> Assumption {
+        Assumption::interval(Interval {
+            lower: Bound::Closed(Expression::integer(0)),
+            upper: Bound::Unbounded,
+        })
+    }
+
+    #[test]
+    fn test_queries() {
+        let mut assumptions = Assumptions::new();
+        assumptions.assume("x", Assumption::positive());
+        assumptions.assume("n", Assumption::integer());
+        assumptions.assume("y", negative());
+
+        assert!(parse("x").is_positive(&assumptions));
+        assert!(parse("x^2 + 1").is_positive(&assumptions));
+        assert!(parse("x*y").is_nonpositive(&assumptions));
+        assert!(parse("-y").is_positive(&assumptions));
+        assert!(parse("y^2").is_positive(&assumptions));
+        assert!(parse("x/y").is_nonzero(&assumptions));
+        assert!(parse("exp(n)").is_positive(&assumptions));
+        assert!(parse("n^2 + 3*n").is_integer_valued(&assumptions));
+        assert!(parse("n").is_real(&assumptions));
+        assert!(!parse("n").is_nonzero(&assumptions));
+        assert!(!parse("z").is_real(&assumptions));
+        assert!(!parse("x - 1").is_nonnegative(&assumptions));
+    }
+
+    #[test]
+    fn test_square_root_of_square() {
+        assert_eq!(simplify_assuming("sqrt(x^2)", None), "abs(x)");
+        assert_eq!(simplify_assuming("sqrt(x^2)", Some(Assumption::positive())), "x");
+        assert_eq!(simplify_assuming("sqrt(x^2)", Some(nonnegative())), "x");
+        assert_eq!(simplify_assuming("sqrt(x^2)", Some(negative())), "-x");
+        assert_eq!(simplify_assuming("(x^2)^(1/2)", None), "abs(x)");
+        assert_eq!(simplify_assuming("(x^2)^(1/2)", Some(Assumption::positive())), "x");
+        assert_eq!(simplify_assuming("sqrt(x^4)", Some(Assumption::real())), "x^2");
+        assert_eq!(simplify_assuming("root(3, x^3)", None), "x");
+    }
+
+    #[test]
+    fn test_absolute_value() {
+        assert_eq!(simplify_assuming("abs(x)", None), "abs(x)");
+        assert_eq!(simplify_assuming("abs(x)", Some(Assumption::positive())), "x");
+        assert_eq!(simplify_assuming("abs(x)", Some(negative())), "-x");
+        assert_eq!(simplify_assuming("abs(x)^2", Some(Assumption::real())), "x^2");
+    }
+
+    #[test]
+    fn test_integral_assumptions() {
+        assert_eq!(simplify_assuming("int(1/x, x)", None), "ln(abs(x))");
+        assert_eq!(simplify_assuming("int(1/x, x)", Some(Assumption::positive())), "ln(x)");
+    }
+
+    #[test]
+    fn test_division_by_itself() {
+        // x could be zero, like sqrt(x^2) could be negative
+        assert_eq!(simplify_assuming("x/x", None), "x/x");
+        assert_eq!(simplify_assuming("x/x", Some(Assumption::integer())), "x/x");
+        assert_eq!(simplify_assuming("x/x", Some(nonnegative())), "x/x");
+        assert_eq!(simplify_assuming("x/x", Some(Assumption::nonzero())), "1");
+        assert_eq!(simplify_assuming("x/x", Some(Assumption::positive())), "1");
+        assert_eq!(simplify_assuming("x/x", Some(negative())), "1");
+
+        // Only a factor proven nonzero is cancelled, x - 1 is zero at 1
+        assert_eq!(
+            simplify_assuming("(x^2 - 1)/(x - 1)", None),
+            "((x^2) + -1)/(x + -1)"
+        );
+        assert_eq!(
+            simplify_assuming("(x^2 - 1)/(x - 1)", Some(Assumption::positive())),
+            "((x^2) + -1)/(x + -1)"
+        );
+        assert_eq!(simplify_assuming("(x^2 - 1)/(x - 1)", Some(negative())), "x + 1");
+        assert_eq!(simplify_assuming("(x^2 - 1)/(x + 1)", Some(nonnegative())), "x + -1");
+    }
+
+    #[test]
+    fn test_combine_assumptions() {
+        let mut assumptions = Assumptions::new();
+        assert!(assumptions.is_empty());
+        assumptions.assume("x", Assumption::integer());
+        assumptions.assume("x", Assumption::nonzero());
+        let x = assumptions.get("x").unwrap();
+        assert!(x.real && x.integer && x.nonzero && !x.positive);
+        assert!(assumptions.get("y").is_none());
+    }
+}
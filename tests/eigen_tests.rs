@@ -0,0 +1,235 @@
+use std::collections::HashMap;
+
+use sym_rustic::{
+    ast::Expression,
+    lexer::{Lexer, Token},
+    parser::Parser,
+};
+
+fn lex(input: &str) -> Vec<Token> {
+    let mut lexer = Lexer::new(input);
+    let mut tokens: Vec<Token> = Vec::new();
+    while let Some(token) = lexer.next_token() {
+        tokens.push(token);
+    }
+    tokens
+}
+
+fn parse(input: &str) -> Expression {
+    let tokens = lex(input);
+    let mut parser = Parser::new(&tokens);
+    parser.parse_expression().unwrap()
+}
+
+/// Check that `values` are the simplified `expected` in any order.
+fn assert_same_values(values: &[Expression], expected: &[&str]) {
+    assert_eq!(values.len(), expected.len(), "{:?}", values);
+    for expected in expected {
+        let expected = parse(expected).simplify(&mut None).unwrap();
+        assert!(
+            values
+                .iter()
+                .any(|value| value.clone().simplify(&mut None).unwrap().is_equal(&expected)),
+            "{} is missing from {:?}",
+            expected,
+            values.iter().map(|value| value.to_string()).collect::<Vec<_>>()
+        );
+    }
+}
+
+/// Check numerically that `vector` is a non zero solution of `matrix * vector = value * vector`.
+fn assert_eigenvector(matrix: &Expression, value: &Expression, vector: &Expression) {
+    let (Some(matrix), Some(vector)) = (matrix.to_matrix(), vector.to_matrix()) else {
+        panic!("{} is not a vector", vector);
+    };
+    let env = HashMap::new();
+    let entries: Vec<_> = vector
+        .rows
+        .iter()
+        .map(|row| row[0].evaluate_complex(&env).unwrap())
+        .collect();
+    assert!(entries.iter().any(|entry| entry.norm() > 1e-9));
+    let value = value.evaluate_complex(&env).unwrap();
+    for (row, entry) in matrix.rows.iter().zip(&entries) {
+        let product = row
+            .iter()
+            .zip(&entries)
+            .map(|(coeff, x)| coeff.evaluate_complex(&env).unwrap() * x)
+            .sum::<num_complex::Complex64>();
+        assert!((product - value * entry).norm() < 1e-9);
+    }
+}
+
+#[cfg(test)]
+mod tests_eigen {
+    use crate::{assert_eigenvector, assert_same_values, parse};
+    use sym_rustic::{
+        ast::{Expression, SimplifyError},
+        explanation::{FormattingObserver, OutputFormat},
+    };
+
+    #[test]
+    fn test_characteristic_polynomial() {
+        assert_eq!(
+            parse("[[2, 1], [1, 2]]")
+                .characteristic_polynomial("x")
+                .unwrap()
+                .to_string(),
+            "(x^2) + -(4 * x) + 3"
+        );
+        assert_eq!(
+            parse("[[1, 2, 3], [4, 5, 6], [7, 8, 9]]")
+                .characteristic_polynomial("t")
+                .unwrap()
+                .to_string(),
+            "(t^3) + -(15 * (t^2)) + -(18 * t)"
+        );
+        assert_eq!(
+            parse("[[a, b], [c, d]]")
+                .characteristic_polynomial("x")
+                .unwrap()
+                .to_string(),
+            "(x^2) + ((-a + -d) * x) + ((a * d) + -(b * c))"
+        );
+        assert_eq!(
+            parse("[[x, 1], [0, x]]").characteristic_polynomial("x"),
+            Err(SimplifyError::Unsupported)
+        );
+        assert_eq!(
+            parse("[[1, 2, 3]]").characteristic_polynomial("x"),
+            Err(SimplifyError::DimensionMismatch)
+        );
+    }
+
+    #[test]
+    fn test_rational_eigenvalues() {
+        let values = parse("[[2, 1], [1, 2]]").eigenvalues(&mut None).unwrap();
+        assert_same_values(&values, &["1", "3"]);
+
+        let values = parse("[[2, 0, 0], [0, 3, 4], [0, 4, 9]]")
+            .eigenvalues(&mut None)
+            .unwrap();
+        assert_same_values(&values, &["1", "2", "11"]);
+
+        // Repeated eigenvalues are given once
+        let values = parse("[[5, 0], [0, 5]]").eigenvalues(&mut None).unwrap();
+        assert_same_values(&values, &["5"]);
+    }
+
+    #[test]
+    fn test_exact_eigenvalues() {
+        let values = parse("[[1, 2], [3, 4]]").eigenvalues(&mut None).unwrap();
+        assert_same_values(&values, &["5/2 + sqrt(33)/2", "5/2 - sqrt(33)/2"]);
+
+        let values = parse("[[2, 1, 0], [1, 2, 1], [0, 1, 2]]")
+            .eigenvalues(&mut None)
+            .unwrap();
+        assert_same_values(&values, &["2", "2 + sqrt(2)", "2 - sqrt(2)"]);
+
+        let values = parse("[[0, -1], [1, 0]]").eigenvalues(&mut None).unwrap();
+        assert_eq!(values.len(), 2);
+        for value in [
+            Expression::complex(Expression::integer(0), Expression::integer(1)),
+            Expression::complex(
+                Expression::integer(0),
+                Expression::negation(Expression::integer(1)),
+            ),
+        ] {
+            assert!(values.iter().any(|other| other.is_equal(&value)), "{:?}", values);
+        }
+    }
+
+    #[test]
+    fn test_eigenvectors() {
+        for input in [
+            "[[2, 1], [1, 2]]",
+            "[[1, 2], [3, 4]]",
+            "[[0, -1], [1, 0]]",
+            "[[1, 2, 3], [4, 5, 6], [7, 8, 9]]",
+            "[[2, 1, 0], [1, 2, 1], [0, 1, 2]]",
+        ] {
+            let matrix = parse(input);
+            let eigenvectors = matrix.eigenvectors(&mut None).unwrap();
+            assert_eq!(eigenvectors.len(), matrix.eigenvalues(&mut None).unwrap().len());
+            for (value, basis) in eigenvectors {
+                assert_eq!(basis.len(), 1, "{}", input);
+                assert_eigenvector(&matrix, &value, &basis[0]);
+            }
+        }
+
+        let eigenvectors = parse("[[2, 1], [1, 2]]").eigenvectors(&mut None).unwrap();
+        assert!(eigenvectors.contains(&(Expression::integer(3), vec![parse("[1, 1]")
+            .simplify(&mut None)
+            .unwrap()])));
+
+        // An eigenvalue with two independent eigenvectors
+        let eigenvectors = parse("[[5, 0], [0, 5]]").eigenvectors(&mut None).unwrap();
+        assert_eq!(eigenvectors.len(), 1);
+        assert_eq!(eigenvectors[0].1.len(), 2);
+
+        // A defective matrix has a single eigenvector
+        let eigenvectors = parse("[[1, 1], [0, 1]]").eigenvectors(&mut None).unwrap();
+        assert_eq!(
+            eigenvectors,
+            vec![(Expression::integer(1), vec![parse("[1, 0]").simplify(&mut None).unwrap()])]
+        );
+    }
+
+    #[test]
+    fn test_symbolic_eigenvectors() {
+        // The eigenvalues of a triangular matrix are its diagonal entries
+        let eigenvectors = parse("[[a, 0], [0, b]]").eigenvectors(&mut None).unwrap();
+        assert_eq!(eigenvectors.len(), 2);
+        for ((value, basis), (expected_value, expected_vector)) in
+            eigenvectors.iter().zip([("a", "[1, 0]"), ("b", "[0, 1]")])
+        {
+            assert!(value.is_equal(&parse(expected_value)), "{}", value);
+            assert_eq!(basis.len(), 1);
+            assert!(basis[0].is_equal(&parse(expected_vector)), "{}", basis[0]);
+        }
+
+        // The vectors hold on both sides of a = b, not only at the generic values
+        for point in [[("a", 2), ("b", 5)], [("a", 3), ("b", 1)]] {
+            let at_point = |expr: &Expression| {
+                point.iter().fold(expr.clone(), |expr, (name, value)| {
+                    expr.substitute(&Expression::variable(name), &Expression::integer(*value))
+                })
+            };
+            for input in ["[[a, 0], [0, b]]", "[[a, 1], [0, b]]", "[[a, b], [b, a]]"] {
+                let matrix = parse(input);
+                let eigenvectors = matrix.eigenvectors(&mut None).unwrap();
+                assert_eq!(eigenvectors.len(), 2, "{}", input);
+                for (value, basis) in eigenvectors {
+                    assert_eq!(basis.len(), 1, "{}", input);
+                    assert_eigenvector(&at_point(&matrix), &at_point(&value), &at_point(&basis[0]));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_nullspace() {
+        assert_eq!(
+            parse("[[1, 2, 3], [4, 5, 6], [7, 8, 9]]").nullspace().unwrap(),
+            vec![parse("[1, -2, 1]").simplify(&mut None).unwrap()]
+        );
+        assert_eq!(
+            parse("[[1, 2], [3, 4]]").nullspace().unwrap(),
+            Vec::<Expression>::new()
+        );
+        assert_eq!(parse("[[1, 1, 1]]").nullspace().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_eigen_explanation() {
+        let mut explanation = Some(Box::new(FormattingObserver::new(OutputFormat::Text)));
+        parse("[[2, 1], [1, 2]]")
+            .eigenvectors(&mut explanation)
+            .unwrap();
+        let steps = explanation.unwrap().into_explanations().to_vec();
+        assert!(steps
+            .iter()
+            .any(|step| step.contains("roots of the characteristic polynomial")));
+        assert!(steps.iter().any(|step| step.contains("(A - λI)v = 0")));
+    }
+}
@@ -0,0 +1,178 @@
+use std::collections::HashMap;
+
+use sym_rustic::{
+    ast::Expression,
+    lexer::{Lexer, Token},
+    parser::Parser,
+};
+
+fn lex(input: &str) -> Vec<Token> {
+    let mut lexer = Lexer::new(input);
+    let mut tokens: Vec<Token> = Vec::new();
+    while let Some(token) = lexer.next_token() {
+        tokens.push(token);
+    }
+    tokens
+}
+
+fn parse(input: &str) -> Expression {
+    let tokens = lex(input);
+    let mut parser = Parser::new(&tokens);
+    parser.parse_expression().unwrap()
+}
+
+fn env<T: Copy>(values: &[(&str, T)]) -> HashMap<String, T> {
+    values
+        .iter()
+        .map(|(name, value)| (name.to_string(), *value))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests_real {
+    use std::f64::consts::{E, PI};
+
+    use crate::{env, parse};
+    use sym_rustic::ast::{evaluate::EvalError, function::FunctionType};
+
+    fn assert_close(value: f64, expected: f64) {
+        assert!(
+            (value - expected).abs() < 1e-12,
+            "{} is not {}",
+            value,
+            expected
+        );
+    }
+
+    #[test]
+    fn test_evaluate_numbers() {
+        assert_eq!(parse("1/4 + 2").evaluate(&env(&[])), Ok(2.25));
+        assert_eq!(parse("-3 * 2^3").evaluate(&env(&[])), Ok(-24.0));
+        assert_eq!(parse("1.5e-3").evaluate(&env(&[])), Ok(0.0015));
+        assert_close(parse("2*pi - tau + e").evaluate(&env(&[])).unwrap(), E);
+    }
+
+    #[test]
+    fn test_evaluate_variables() {
+        let expr = parse("sin(x)^2 + pi*y");
+        assert_close(
+            expr.evaluate(&env(&[("x", 1.0), ("y", 2.0)])).unwrap(),
+            1f64.sin().powi(2) + 2.0 * PI,
+        );
+        assert_eq!(
+            expr.evaluate(&env(&[("x", 1.0)])),
+            Err(EvalError::UnboundVariable("y".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_evaluate_functions() {
+        let x = env(&[("x", 0.5)]);
+        assert_close(parse("asin(x) + acos(x)").evaluate(&x).unwrap(), PI / 2.0);
+        assert_close(parse("cosh(x)^2 - sinh(x)^2").evaluate(&x).unwrap(), 1.0);
+        assert_close(parse("atanh(tanh(x))").evaluate(&x).unwrap(), 0.5);
+        assert_close(
+            parse("log(2, 8) + log10(100) + log2(4)")
+                .evaluate(&x)
+                .unwrap(),
+            7.0,
+        );
+        assert_close(parse("exp(ln(3)) + abs(-2)").evaluate(&x).unwrap(), 5.0);
+        assert_close(parse("ceil(x) + floor(x)").evaluate(&x).unwrap(), 1.0);
+        // pow(o, x) is x^o
+        assert_close(
+            parse("root(3, -8) + sqrt(16) + pow(2, 3)")
+                .evaluate(&x)
+                .unwrap(),
+            11.0,
+        );
+    }
+
+    #[test]
+    fn test_evaluate_errors() {
+        let x = env(&[("x", -1.0)]);
+        assert_eq!(
+            parse("ln(x)").evaluate(&x),
+            Err(EvalError::Domain(FunctionType::Ln, -1.0))
+        );
+        assert_eq!(
+            parse("sqrt(x)").evaluate(&x),
+            Err(EvalError::Domain(FunctionType::Sqrt, -1.0))
+        );
+        assert_eq!(
+            parse("asin(2)").evaluate(&x),
+            Err(EvalError::Domain(FunctionType::Asin, 2.0))
+        );
+        assert_eq!(
+            parse("x^(1/2)").evaluate(&x),
+            Err(EvalError::Domain(FunctionType::Pow, -1.0))
+        );
+        assert_eq!(
+            parse("1/(x + 1)").evaluate(&x),
+            Err(EvalError::DivisionByZero)
+        );
+        assert_eq!(
+            parse("(x + 1)^0").evaluate(&x),
+            Err(EvalError::ZeroExponentiationZero)
+        );
+        assert_eq!(parse("x = 1").evaluate(&x), Err(EvalError::Unsupported));
+    }
+}
+
+#[cfg(test)]
+mod tests_complex {
+    use num_complex::Complex64;
+
+    use crate::{env, parse};
+    use sym_rustic::ast::{Expression, evaluate::EvalError, function::FunctionType};
+
+    fn assert_close(value: Complex64, expected: Complex64) {
+        assert!(
+            (value - expected).norm() < 1e-12,
+            "{} is not {}",
+            value,
+            expected
+        );
+    }
+
+    #[test]
+    fn test_evaluate_complex() {
+        let expr = Expression::complex(Expression::integer(1), Expression::integer(2));
+        assert_eq!(
+            expr.evaluate_complex(&env(&[])),
+            Ok(Complex64::new(1.0, 2.0))
+        );
+        assert_eq!(expr.evaluate(&env(&[])), Err(EvalError::ComplexValue));
+
+        let z = env(&[("z", Complex64::new(0.0, 1.0))]);
+        assert_close(
+            parse("z^2 + 1").evaluate_complex(&z).unwrap(),
+            Complex64::new(0.0, 0.0),
+        );
+        assert_close(
+            parse("exp(pi*z)").evaluate_complex(&z).unwrap(),
+            Complex64::new(-1.0, 0.0),
+        );
+    }
+
+    #[test]
+    fn test_evaluate_complex_branches() {
+        let z = env(&[("z", Complex64::new(-1.0, 0.0))]);
+        assert_close(
+            parse("sqrt(z)").evaluate_complex(&z).unwrap(),
+            Complex64::new(0.0, 1.0),
+        );
+        assert_close(
+            parse("ln(z)").evaluate_complex(&z).unwrap(),
+            Complex64::new(0.0, std::f64::consts::PI),
+        );
+        assert_eq!(
+            parse("ln(z + 1)").evaluate_complex(&z),
+            Err(EvalError::Domain(FunctionType::Ln, 0.0))
+        );
+        assert_eq!(
+            parse("1/(z + 1)").evaluate_complex(&z),
+            Err(EvalError::DivisionByZero)
+        );
+    }
+}
@@ -0,0 +1,105 @@
+use sym_rustic::{
+    ast::Expression,
+    lexer::{Lexer, Token},
+    parser::Parser,
+};
+
+fn lex(input: &str) -> Vec<Token> {
+    let mut lexer = Lexer::new(input);
+    let mut tokens: Vec<Token> = Vec::new();
+    while let Some(token) = lexer.next_token() {
+        tokens.push(token);
+    }
+    tokens
+}
+
+fn parse(input: &str) -> Expression {
+    let tokens = lex(input);
+    let mut parser = Parser::new(&tokens);
+    parser.parse_expression().unwrap()
+}
+
+#[cfg(test)]
+mod tests_expand {
+    use crate::parse;
+    use sym_rustic::{
+        ast::SimplifyError,
+        explanation::{FormattingObserver, OutputFormat},
+    };
+
+    #[test]
+    fn test_simplify_keeps_powers() {
+        let simplified = parse("(x + 1)^2").simplify(&mut None).unwrap();
+        assert_eq!(simplified.to_string(), "(x + 1)^2");
+        let simplified = parse("(x + 1)^100000").simplify(&mut None).unwrap();
+        assert_eq!(simplified.to_string(), "(x + 1)^100000");
+    }
+
+    #[test]
+    fn test_expand() {
+        assert_eq!(
+            parse("(x + 1)^2").expand(&mut None).unwrap().to_string(),
+            "1 + (2 * x) + (x^2)"
+        );
+        assert_eq!(
+            parse("(x - y)^3").expand(&mut None).unwrap().to_string(),
+            "-(y^3) + (3 * x * (y^2)) + (-3 * (x^2) * y) + (x^3)"
+        );
+        assert_eq!(
+            parse("2*(x + 1)^2").expand(&mut None).unwrap().to_string(),
+            "2 + (4 * x) + (2 * (x^2))"
+        );
+        assert_eq!(
+            parse("sin((x + 1)*(x + 2))").expand(&mut None).unwrap().to_string(),
+            "sin((x^2) + (3 * x) + 2)"
+        );
+        assert_eq!(
+            parse("(x + 1)^100000").expand(&mut None),
+            Err(SimplifyError::Unsupported)
+        );
+    }
+
+    #[test]
+    fn test_expand_power() {
+        assert_eq!(
+            parse("(x + 1)^2*y").expand_power(&mut None).unwrap().to_string(),
+            "(1 + (2 * x) + (x^2)) * y"
+        );
+        assert_eq!(
+            parse("(a + b + c)^2").expand_power(&mut None).unwrap().to_string(),
+            "(c^2) + (2 * c * b) + (b^2) + (2 * a * c) + (2 * a * b) + (a^2)"
+        );
+        assert_eq!(
+            parse("(x + 1)*(x + 2)").expand_power(&mut None).unwrap().to_string(),
+            "(x + 1) * (x + 2)"
+        );
+    }
+
+    #[test]
+    fn test_expand_mul() {
+        assert_eq!(
+            parse("(x + 1)*(x + 2)").expand_mul(&mut None).unwrap().to_string(),
+            "(x^2) + (3 * x) + 2"
+        );
+        assert_eq!(
+            parse("(x + 1)^2*y").expand_mul(&mut None).unwrap().to_string(),
+            "((x + 1)^2) * y"
+        );
+    }
+
+    #[test]
+    fn test_negated_base() {
+        assert_eq!(parse("(-y)^2").simplify(&mut None).unwrap().to_string(), "y^2");
+        assert_eq!(parse("(-y)^3").simplify(&mut None).unwrap().to_string(), "-(y^3)");
+    }
+
+    #[test]
+    fn test_expand_explanation() {
+        let mut explanation = Some(Box::new(FormattingObserver::new(OutputFormat::Text)));
+        parse("(x + 1)^2").expand(&mut explanation).unwrap();
+        parse("(x + 1)*(x + 2)").expand_mul(&mut explanation).unwrap();
+        let steps = explanation.unwrap().into_explanations().to_vec();
+        assert!(steps.iter().any(|step| step.contains("Use the multinomial theorem")));
+        assert!(steps.iter().any(|step| step.contains("Multiply by distributing each term")));
+    }
+}
@@ -0,0 +1,143 @@
+use sym_rustic::{
+    ast::Expression,
+    lexer::{Lexer, Token},
+    parser::Parser,
+    polynomial::Polynomial,
+};
+
+fn lex(input: &str) -> Vec<Token> {
+    let mut lexer = Lexer::new(input);
+    let mut tokens: Vec<Token> = Vec::new();
+    while let Some(token) = lexer.next_token() {
+        tokens.push(token);
+    }
+    tokens
+}
+
+fn parse(input: &str) -> Expression {
+    let tokens = lex(input);
+    let mut parser = Parser::new(&tokens);
+    parser.parse_expression().unwrap()
+}
+
+fn polynomial(input: &str) -> Polynomial {
+    Polynomial::from_expression(&parse(input)).unwrap()
+}
+
+/// Check that `input` factors to `expected` and that the factors expand back to `input`.
+fn assert_factors_to(input: &str, expected: &str) {
+    let factored = parse(input).factor(&mut None).unwrap();
+    assert_eq!(factored.to_string(), expected, "{}", input);
+    assert_eq!(
+        Polynomial::from_expression(&factored).unwrap(),
+        polynomial(input),
+        "{}",
+        input
+    );
+}
+
+#[cfg(test)]
+mod tests_factor {
+    use crate::{assert_factors_to, parse, polynomial};
+    use sym_rustic::{
+        ast::{Expression, SimplifyError, numeral::Numeral},
+        explanation::{FormattingObserver, OutputFormat},
+        polynomial::Polynomial,
+    };
+
+    #[test]
+    fn test_factor_univariate() {
+        assert_factors_to("x^3 - x", "x * (x + -1) * (x + 1)");
+        assert_factors_to("2*x^2 - 8", "2 * (x + -2) * (x + 2)");
+        assert_factors_to("6*x^2 + 5*x + 1", "((2 * x) + 1) * ((3 * x) + 1)");
+        assert_factors_to("-x^2 + 1", "-((x + -1) * (x + 1))");
+        assert_factors_to("x^4 + 4", "((x^2) + (-2 * x) + 2) * ((x^2) + (2 * x) + 2)");
+        assert_factors_to("x^5 + x + 1", "((x^2) + x + 1) * ((x^3) + -(x^2) + 1)");
+        assert_factors_to(
+            "x^6 - 1",
+            "(x + -1) * (x + 1) * ((x^2) + -x + 1) * ((x^2) + x + 1)",
+        );
+        assert_factors_to("x^2 + 1", "(x^2) + 1");
+        assert_factors_to("7", "7");
+        assert_factors_to("0", "0");
+    }
+
+    #[test]
+    fn test_factor_irreducible() {
+        // Factors modulo every prime but is irreducible over the rationals
+        assert_factors_to("x^4 - 10*x^2 + 1", "(x^4) + (-10 * (x^2)) + 1");
+        let (_, factors) = polynomial("x^30 - 1").factor_list().unwrap();
+        assert_eq!(factors.len(), 8);
+        assert!(factors.iter().all(|(_, multiplicity)| *multiplicity == 1));
+    }
+
+    #[test]
+    fn test_factor_repeated() {
+        assert_factors_to("(x + 1)^3*(x - 2)^2", "((x + -2)^2) * ((x + 1)^3)");
+        assert_factors_to("x^4 + 2*x^3 + x^2", "(x^2) * ((x + 1)^2)");
+
+        let x = Expression::variable("x");
+        let parts = polynomial("(x + 1)^3*(x - 2)^2*(x^2 + 1)").square_free(&x);
+        assert_eq!(
+            parts,
+            vec![
+                (polynomial("x^2 + 1"), 1),
+                (polynomial("x - 2"), 2),
+                (polynomial("x + 1"), 3),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_factor_multivariate() {
+        assert_factors_to("x^2 - y^2", "(x + -y) * (x + y)");
+        assert_factors_to("x^3*y - x*y^3", "x * y * (x + -y) * (x + y)");
+        assert_factors_to("x^2 + 2*x*y + y^2", "(x + y)^2");
+        assert_factors_to("(2*y + 1)*(x + 1)*y", "y * (x + 1) * ((2 * y) + 1)");
+        assert_factors_to(
+            "(x^2 + y^2 + 1)*(x - y^3)",
+            "((x^2) + (y^2) + 1) * (x + -(y^3))",
+        );
+        assert_eq!(
+            parse("sin(x)^2 - 1").factor(&mut None).unwrap().to_string(),
+            "(sin(x) + -1) * (sin(x) + 1)"
+        );
+    }
+
+    #[test]
+    fn test_factor_rational() {
+        assert_factors_to("x^2/2 - 1/2", "((x + -1) * (x + 1))/2");
+        assert_eq!(
+            parse("(x^2 - 1)/(x^2 + 2*x + 1)")
+                .factor(&mut None)
+                .unwrap()
+                .to_string(),
+            "((x + -1) * (x + 1))/((x + 1)^2)"
+        );
+        let (constant, factors) = polynomial("-x/2 + 1/2").factor_list().unwrap();
+        assert_eq!(constant, Numeral::Rational((-1).into(), 2.into()));
+        assert_eq!(factors, vec![(polynomial("x - 1"), 1)]);
+        assert_eq!(parse("x = 1").factor(&mut None), Err(SimplifyError::Unsupported));
+    }
+
+    #[test]
+    fn test_factor_explanation() {
+        let mut explanation = Some(Box::new(FormattingObserver::new(OutputFormat::Text)));
+        parse("2*x^4*y + 4*x^3*y + 2*x^2*y - 2*y")
+            .factor(&mut explanation)
+            .unwrap();
+        parse("(x + 1)^2*(x - 1)").factor(&mut explanation).unwrap();
+        let steps = explanation.unwrap().into_explanations().to_vec();
+        assert!(steps.iter().any(|step| step.contains("Take out the common factor 2 * y")));
+        assert!(steps.iter().any(|step| step.contains("with Berlekamp's algorithm")));
+        assert!(steps.iter().any(|step| step.contains("square-free factors")));
+        assert!(steps.iter().any(|step| step.contains("Factor over the rationals")));
+    }
+
+    #[test]
+    fn test_polynomial_derivative() {
+        let x = Expression::variable("x");
+        assert_eq!(polynomial("x^3*y + 2*x + y").derivative(&x), polynomial("3*x^2*y + 2"));
+        assert_eq!(polynomial("y").derivative(&x), Polynomial::zero());
+    }
+}
@@ -0,0 +1,85 @@
+use sym_rustic::{
+    ast::Expression,
+    lexer::{Lexer, Token},
+    parser::Parser,
+};
+
+fn lex(input: &str) -> Vec<Token> {
+    let mut lexer = Lexer::new(input);
+    let mut tokens: Vec<Token> = Vec::new();
+    while let Some(token) = lexer.next_token() {
+        tokens.push(token);
+    }
+    tokens
+}
+
+fn parse(input: &str) -> Expression {
+    let tokens = lex(input);
+    let mut parser = Parser::new(&tokens);
+    parser.parse_expression().unwrap()
+}
+
+fn simplify(input: &str) -> String {
+    parse(input).simplify(&mut None).unwrap().to_string()
+}
+
+#[cfg(test)]
+mod tests_function {
+    use crate::{parse, simplify};
+    use sym_rustic::{
+        ast::SimplifyError,
+        explanation::{FormattingObserver, OutputFormat},
+    };
+
+    #[test]
+    fn test_arguments_are_simplified() {
+        assert_eq!(simplify("sin(x + 0)"), "sin(x)");
+        assert_eq!(simplify("ln(2*3)"), "ln(6)");
+        assert_eq!(simplify("floor(abs(-5/3))"), "1");
+        assert_eq!(simplify("sqrt(3*4)"), "2 * sqrt(3)");
+    }
+
+    #[test]
+    fn test_rounding() {
+        assert_eq!(simplify("abs(-3/2)"), "3/2");
+        assert_eq!(simplify("abs(-4)"), "4");
+        assert_eq!(simplify("abs(-x)"), "abs(x)");
+        assert_eq!(simplify("ceil(7/2)"), "4");
+        assert_eq!(simplify("ceil(-7/2)"), "-3");
+        assert_eq!(simplify("floor(7/2)"), "3");
+        assert_eq!(simplify("floor(-7/2)"), "-4");
+        assert_eq!(simplify("floor(3)"), "3");
+        assert_eq!(simplify("ceil(x)"), "ceil(x)");
+    }
+
+    #[test]
+    fn test_pow() {
+        assert_eq!(simplify("pow(0, x)"), "1");
+        assert_eq!(simplify("pow(x, 1)"), "1");
+        assert_eq!(simplify("pow(1, x)"), "x");
+        assert_eq!(simplify("pow(2, x)"), "pow(2, x)");
+        assert_eq!(
+            parse("pow(0, 0)").simplify(&mut None),
+            Err(SimplifyError::ZeroExponentiationZero)
+        );
+    }
+
+    #[test]
+    fn test_function_explanation() {
+        let mut explanation = Some(Box::new(FormattingObserver::new(OutputFormat::Text)));
+        parse("floor(7/2 + 0) + ceil(1/2) + abs(-2) + pow(1, x) + cos(0)")
+            .simplify(&mut explanation)
+            .unwrap();
+        let steps = explanation.unwrap().into_explanations().to_vec();
+        for rule in [
+            "Adding zero stay the same",
+            "Floor of a number",
+            "Ceiling of a number",
+            "Absolute value of a number",
+            "Anything to the 1st power stay the same",
+            "Value of the function is known",
+        ] {
+            assert!(steps.iter().any(|step| step.contains(rule)), "{rule}");
+        }
+    }
+}
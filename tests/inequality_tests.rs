@@ -0,0 +1,192 @@
+use sym_rustic::{
+    ast::Expression,
+    lexer::{Lexer, Token},
+    parser::Parser,
+    solver::{SolutionSet, Solver},
+};
+
+fn lex(input: &str) -> Vec<Token> {
+    let mut lexer = Lexer::new(input);
+    let mut tokens: Vec<Token> = Vec::new();
+    while let Some(token) = lexer.next_token() {
+        tokens.push(token);
+    }
+    tokens
+}
+
+fn parse(input: &str) -> Expression {
+    let tokens = lex(input);
+    let mut parser = Parser::new(&tokens);
+    parser.parse_expression().unwrap()
+}
+
+fn solve(input: &str) -> SolutionSet {
+    Solver::solve_inequality(parse(input), Expression::variable("x"), &mut None).unwrap()
+}
+
+#[cfg(test)]
+mod tests_inequality {
+    use std::collections::HashMap;
+
+    use crate::{lex, parse, solve};
+    use sym_rustic::{
+        ast::{Expression, inequality::Relation},
+        explanation::{FormattingObserver, OutputFormat},
+        lexer::Token,
+        prints::PrettyPrints,
+        solver::{Bound, Interval, Solver, SolverError},
+    };
+
+    #[test]
+    fn test_lex_relations() {
+        assert_eq!(
+            lex("<<=>>=!=!"),
+            vec![
+                Token::Less,
+                Token::LessEqual,
+                Token::Greater,
+                Token::GreaterEqual,
+                Token::NotEqual,
+                Token::Not,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_inequality() {
+        assert_eq!(
+            parse("2*x + 1 <= 5"),
+            Expression::inequality(
+                Expression::addition(vec![
+                    Expression::multiplication(vec![
+                        Expression::integer(2),
+                        Expression::variable("x")
+                    ]),
+                    Expression::integer(1)
+                ]),
+                Relation::LessEqual,
+                Expression::integer(5)
+            )
+        );
+        assert_eq!(
+            parse("x > 0 && x != 1"),
+            Expression::and(vec![
+                Expression::inequality(
+                    Expression::variable("x"),
+                    Relation::Greater,
+                    Expression::integer(0)
+                ),
+                Expression::inequality(
+                    Expression::variable("x"),
+                    Relation::NotEqual,
+                    Expression::integer(1)
+                ),
+            ])
+        );
+        assert_eq!(parse("x >= -1").to_string(), "x >= -1");
+    }
+
+    #[test]
+    fn test_simplify_inequality() {
+        assert_eq!(parse("1 < 2").simplify(&mut None), Ok(Expression::boolean(true)));
+        assert_eq!(parse("pi >= 22/7").simplify(&mut None), Ok(Expression::boolean(false)));
+        assert_eq!(parse("2/4 != 1/2").simplify(&mut None), Ok(Expression::boolean(false)));
+        assert_eq!(
+            parse("x + x < 3").simplify(&mut None).unwrap().to_string(),
+            "2 * x < 3"
+        );
+        assert_eq!(parse("x < 2 || 3 > 1").simplify(&mut None), Ok(Expression::boolean(true)));
+
+        let env = HashMap::from([("x".to_string(), 1.0)]);
+        assert_eq!(parse("x^2 <= x").evaluate(&env), Ok(1.0));
+        assert_eq!(parse("x > 1").evaluate(&env), Ok(0.0));
+    }
+
+    #[test]
+    fn test_linear_inequality() {
+        assert_eq!(solve("2*x + 3 < 7").to_string(), "(-inf, 2)");
+        // Dividing by a negative number flips the relation
+        assert_eq!(solve("-3*x >= 6").to_string(), "(-inf, -2]");
+        assert_eq!(solve("x != 3").to_string(), "(-inf, 3) ∪ (3, inf)");
+        assert_eq!(solve("x >= x").to_string(), "(-inf, inf)");
+        assert!(solve("x + 1 < x").is_empty());
+    }
+
+    #[test]
+    fn test_polynomial_inequality() {
+        assert_eq!(
+            solve("x^2 < 4").intervals,
+            vec![Interval {
+                lower: Bound::Open(
+                    Expression::negation(Expression::integer(2))
+                        .simplify(&mut None)
+                        .unwrap()
+                ),
+                upper: Bound::Open(Expression::integer(2)),
+            }]
+        );
+        assert_eq!(solve("x^2 - x - 2 >= 0").to_string(), "(-inf, -1] ∪ [2, inf)");
+        assert_eq!(solve("x^3 - x > 0").to_string(), "(-1, 0) ∪ (1, inf)");
+        assert_eq!(solve("x^2 <= 2").to_string(), "[-sqrt(2), sqrt(2)]");
+        assert_eq!(solve("(x - 1)^2 <= 0").to_string(), "{1}");
+        assert_eq!(solve("x^2 + 1 > 0").to_string(), "(-inf, inf)");
+        assert!(solve("x^2 + 1 < 0").is_empty());
+    }
+
+    #[test]
+    fn test_rational_inequality() {
+        assert_eq!(solve("(x - 1)/(x + 2) <= 0").to_string(), "(-2, 1]");
+        assert_eq!(solve("1/x > 1").to_string(), "(0, 1)");
+        assert_eq!(solve("(x^2 - 1)/(x - 1) > 0").to_string(), "(-1, 1) ∪ (1, inf)");
+        // The zero of the denominator stays excluded when the fraction cancels
+        let solution = solve("x/(x + 1) + 1/(x + 1) >= 1");
+        assert_eq!(solution.to_string(), "(-inf, -1) ∪ (-1, inf)");
+        assert!(solution.contains(0.0));
+        assert!(!solution.contains(-1.0));
+    }
+
+    #[test]
+    fn test_inequality_errors() {
+        let x = Expression::variable("x");
+        assert_eq!(
+            Solver::solve_inequality(parse("x = 1"), x.clone(), &mut None),
+            Err(SolverError::InvalidEquation)
+        );
+        assert_eq!(
+            Solver::solve_inequality(parse("y < 1"), x.clone(), &mut None),
+            Err(SolverError::VariableNotFound)
+        );
+        assert_eq!(
+            Solver::solve_inequality(parse("x^2 < a"), x, &mut None),
+            Err(SolverError::UnsupportedEquationType)
+        );
+    }
+
+    #[test]
+    fn test_inequality_explanation() {
+        let mut explanation = Some(Box::new(FormattingObserver::new(OutputFormat::Text)));
+        Solver::solve_inequality(
+            parse("(x - 1)/(x + 2) <= 0"),
+            Expression::variable("x"),
+            &mut explanation,
+        )
+        .unwrap();
+        let steps = explanation.unwrap().into_explanations().to_vec();
+        assert!(steps.contains(
+            &"The critical points are the zeros of the numerator and of the denominator\nx = -2 (excluded), x = 1"
+                .to_string()
+        ));
+        assert!(steps.contains(
+            &"Sign chart\n       x         | (-inf, -2) |    -2     | (-2, 1) | 1 | (1, inf)\n     x + -1      |     -      |     -     |    -    | 0 |    +\n     x + 2       |     -      |     0     |    +    | + |    +\n(x + -1)/(x + 2) |     +      | undefined |    -    | 0 |    +"
+                .to_string()
+        ));
+        assert!(steps.contains(&"Solution: x ∈ (-2, 1]".to_string()));
+    }
+
+    #[test]
+    fn test_print_inequality() {
+        let expr = parse("x <= 1/2");
+        assert_eq!(expr.get_processed(), "     1\nx <= -\n     2");
+        assert_eq!(expr.calculate_tree(0), "Inequality:\n  x\n  <= Division:\n    1\n    / 2");
+    }
+}
@@ -0,0 +1,381 @@
+use std::collections::HashMap;
+
+use sym_rustic::{
+    ast::Expression,
+    lexer::{Lexer, Token},
+    parser::Parser,
+};
+
+fn lex(input: &str) -> Vec<Token> {
+    let mut lexer = Lexer::new(input);
+    let mut tokens: Vec<Token> = Vec::new();
+    while let Some(token) = lexer.next_token() {
+        tokens.push(token);
+    }
+    tokens
+}
+
+fn parse(input: &str) -> Expression {
+    let tokens = lex(input);
+    let mut parser = Parser::new(&tokens);
+    parser.parse_expression().unwrap()
+}
+
+/// Check that the derivative of `∫expr dx` is `expr` at a few points.
+fn assert_antiderivative(input: &str) {
+    let expr = parse(input);
+    let antiderivative = expr.integrate("x", &mut None).unwrap();
+    let at = |f: &Expression, x: f64| {
+        f.evaluate(&HashMap::from([("x".to_string(), x)])).unwrap()
+    };
+
+    for x in [0.3, 0.7, 1.3, 2.1] {
+        let h = 1e-5;
+        let derivative = (at(&antiderivative, x + h) - at(&antiderivative, x - h)) / (2.0 * h);
+        let value = at(&expr, x);
+        assert!(
+            (derivative - value).abs() < 1e-6 * (1.0 + value.abs()),
+            "∫{} dx = {} is wrong at x = {}",
+            input,
+            antiderivative,
+            x
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests_integral {
+    use std::collections::HashMap;
+
+    use crate::{assert_antiderivative, parse};
+    use sym_rustic::{
+        ast::{Expression, SimplifyError, evaluate::EvalError, integral::IntegralValue},
+        explanation::{FormattingObserver, OutputFormat},
+        prints::PrettyPrints,
+    };
+
+    #[test]
+    fn test_parse_integral() {
+        assert_eq!(
+            parse("int(x^2, x)"),
+            Expression::integral(
+                Expression::exponentiation(Expression::variable("x"), Expression::integer(2)),
+                "x"
+            )
+        );
+        assert_eq!(parse("int(x, y)").to_string(), "∫x dy");
+    }
+
+    #[test]
+    fn test_integrate_exact() {
+        let x = Expression::variable("x");
+        assert!(
+            parse("int(3*x^2, x)")
+                .simplify(&mut None)
+                .unwrap()
+                .is_equal(&Expression::exponentiation(x.clone(), Expression::integer(3)))
+        );
+        assert!(
+            parse("int(cos(x), x)")
+                .simplify(&mut None)
+                .unwrap()
+                .is_equal(&Expression::sin(x.clone()))
+        );
+        assert!(
+            parse("int(1/(x^2 + 1), x)")
+                .simplify(&mut None)
+                .unwrap()
+                .is_equal(&Expression::atan(x.clone()))
+        );
+        assert!(
+            parse("int(y, x)")
+                .simplify(&mut None)
+                .unwrap()
+                .is_equal(&Expression::multiplication(vec![Expression::variable("y"), x]))
+        );
+    }
+
+    #[test]
+    fn test_integrate_elementary() {
+        for input in [
+            "x^5 - 3*x + 2",
+            "1/x",
+            "x^(-2)",
+            "sqrt(x)",
+            "sin(x)",
+            "cos(2*x + 1)",
+            "e^x",
+            "2^x",
+            "exp(3*x)",
+            "tan(x)",
+            "ln(x)",
+            "atan(x)",
+            "asin(x/3)",
+            "1/(2*x + 1)",
+            "(2*x + 1)^3",
+        ] {
+            assert_antiderivative(input);
+        }
+    }
+
+    #[test]
+    fn test_integrate_substitution() {
+        for input in [
+            "2*x*cos(x^2)",
+            "x*e^(x^2)",
+            "x/(x^2 + 1)",
+            "sin(x)*cos(x)",
+            "ln(x)/x",
+            "x*(x^2 + 1)^10",
+            "x*sqrt(x^2 + 1)",
+        ] {
+            assert_antiderivative(input);
+        }
+    }
+
+    #[test]
+    fn test_integrate_by_parts() {
+        for input in [
+            "x*e^x",
+            "x^2*e^x",
+            "x*sin(x)",
+            "x*ln(x)",
+            "ln(x)^2",
+            "x*atan(x)",
+        ] {
+            assert_antiderivative(input);
+        }
+    }
+
+    #[test]
+    fn test_integrate_rational() {
+        for input in [
+            "1/(x^2 - 1)",
+            "(x^3 + 1)/(x^2 - 3*x + 2)",
+            "1/(x*(x + 1)^2)",
+            "(2*x + 3)/(x^2 + 2*x + 5)",
+            "1/(x^2 - 2)",
+            "1/(x^3 + 1)",
+            "x^4/(x^2 + 1)",
+        ] {
+            assert_antiderivative(input);
+        }
+    }
+
+    #[test]
+    fn test_integrate_unsupported() {
+        assert_eq!(
+            parse("sin(x^2)").integrate("x", &mut None),
+            Err(SimplifyError::Unsupported)
+        );
+        // The integral is kept as is
+        assert_eq!(
+            parse("int(sin(x^2), x)")
+                .simplify(&mut None)
+                .unwrap()
+                .to_string(),
+            "∫sin(x^2) dx"
+        );
+    }
+
+    #[test]
+    fn test_derivative_of_integral() {
+        assert!(
+            Expression::derivative(parse("int(sin(x^2), x)"), "x", 1)
+                .simplify(&mut None)
+                .unwrap()
+                .is_equal(&parse("sin(x^2)"))
+        );
+    }
+
+    #[test]
+    fn test_integral_explanation() {
+        let mut explanation = Some(Box::new(FormattingObserver::new(OutputFormat::Text)));
+        parse("x*e^x").integrate("x", &mut explanation).unwrap();
+        let steps = explanation.unwrap().into_explanations().to_vec();
+        assert!(
+            steps
+                .iter()
+                .any(|step| step.contains("Integration by parts is given by"))
+        );
+
+        let mut explanation = Some(Box::new(FormattingObserver::new(OutputFormat::Text)));
+        parse("2*x*cos(x^2)")
+            .integrate("x", &mut explanation)
+            .unwrap();
+        let steps = explanation.unwrap().into_explanations().to_vec();
+        assert!(steps.iter().any(|step| step.contains("Substitution of u = ")));
+    }
+
+    #[test]
+    fn test_print_integral() {
+        let expr = Expression::integral(Expression::variable("x"), "x");
+        assert_eq!(expr.get_processed(), "⌠     \n⎮ x dx\n⌡     ");
+        assert_eq!(expr.calculate_tree(0), "Integral:\n  x\n  d x");
+    }
+
+    #[test]
+    fn test_parse_definite_integral() {
+        assert_eq!(
+            parse("int(x^2, x, 0, 1)"),
+            Expression::definite_integral(
+                Expression::exponentiation(Expression::variable("x"), Expression::integer(2)),
+                "x",
+                Expression::integer(0),
+                Expression::integer(1),
+            )
+        );
+        assert_eq!(parse("int(x, x, 0, y)").to_string(), "∫[0, y] x dx");
+    }
+
+    #[test]
+    fn test_definite_integral_exact() {
+        for (input, expected) in [
+            ("int(x, x, 0, 1)", Expression::rational(1, 2)),
+            ("int(x^3 - x, x, -1, 2)", Expression::rational(9, 4)),
+            ("int(sin(x), x, 0, pi)", Expression::integer(2)),
+            ("int(1/x, x, 1, e)", Expression::integer(1)),
+            ("int(x*y, x, 0, 1)", parse("y/2")),
+        ] {
+            let result = parse(input).simplify(&mut None).unwrap();
+            assert!(result.is_equal(&expected), "{} is not {}", result, expected);
+        }
+
+        assert_eq!(
+            Expression::exponentiation(Expression::variable("x"), Expression::integer(2))
+                .integrate_definite("x", &Expression::integer(0), &Expression::integer(3), &mut None),
+            Ok(IntegralValue::Exact(Expression::integer(9)))
+        );
+    }
+
+    #[test]
+    fn test_improper_integral() {
+        // The antiderivative is replaced by its one-sided limits at a singularity
+        for (input, expected) in [
+            ("int(1/sqrt(x), x, 0, 1)", Expression::integer(2)),
+            ("int(ln(x), x, 0, 1)", parse("-1")),
+        ] {
+            let result = parse(input).simplify(&mut None).unwrap();
+            assert!(result.is_equal(&expected), "{} is not {}", result, expected);
+        }
+
+        for input in [
+            "int(1/x, x, -1, 1)",
+            "int(1/x, x, 0, 1)",
+            "int(1/x^2, x, -1, 1)",
+            "int(1/(x^2 - 4), x, 0, 3)",
+        ] {
+            assert_eq!(
+                parse(input).simplify(&mut None),
+                Err(SimplifyError::DivergentIntegral),
+                "{}",
+                input
+            );
+        }
+
+        // The pole of tan is not found exactly, the integral is kept
+        assert_eq!(
+            parse("int(tan(x), x, 0, 2)")
+                .simplify(&mut None)
+                .unwrap()
+                .to_string(),
+            "∫[0, 2] tan(x) dx"
+        );
+    }
+
+    #[test]
+    fn test_definite_integral_approximate() {
+        let result = parse("sin(x^2)").integrate_definite(
+            "x",
+            &Expression::integer(0),
+            &Expression::integer(2),
+            &mut None,
+        );
+        let Ok(IntegralValue::Approximate { value, error }) = result else {
+            panic!("{:?} is not approximate", result);
+        };
+        assert!((value - 0.804_776_489_343_756).abs() < 1e-12);
+        assert!(error < 1e-10);
+
+        let result = parse("int(e^(-x^2), x, 0, 1)").simplify(&mut None).unwrap();
+        let Expression::Number(value) = result else {
+            panic!("{} is not a number", result);
+        };
+        assert!((value.to_f64() - 0.746_824_132_812_427).abs() < 1e-12);
+
+        // A symbolic bound can't be approximated
+        assert_eq!(
+            parse("int(sin(x^2), x, 0, y)")
+                .simplify(&mut None)
+                .unwrap()
+                .to_string(),
+            "∫[0, y] sin(x^2) dx"
+        );
+    }
+
+    #[test]
+    fn test_evaluate_definite_integral() {
+        let env = HashMap::from([("y".to_string(), 2.0)]);
+        let value = parse("int(sin(x^2), x, 0, y)").evaluate(&env).unwrap();
+        assert!((value - 0.804_776_489_343_756).abs() < 1e-12);
+
+        // Integrable singularity at the bound
+        let value = parse("int(1/sqrt(x), x, 0, 1)").evaluate(&env).unwrap();
+        assert!((value - 2.0).abs() < 1e-8);
+
+        assert_eq!(
+            parse("int(sin(x^2), x)").evaluate(&env),
+            Err(EvalError::Unsupported)
+        );
+    }
+
+    #[test]
+    fn test_derivative_of_definite_integral() {
+        // Leibniz integral rule
+        let mut expr = Expression::derivative(parse("int(sin(t^2), t, 0, x^2)"), "x", 1);
+        let env = HashMap::from([("x".to_string(), 1.3)]);
+        let expected = 1.3f64.powi(4).sin() * 2.0 * 1.3;
+        let value = expr.simplify(&mut None).unwrap().evaluate(&env).unwrap();
+        assert!((value - expected).abs() < 1e-12);
+
+        assert!(
+            Expression::derivative(parse("int(sin(x^2), x, 0, 1)"), "x", 1)
+                .simplify(&mut None)
+                .unwrap()
+                .is_equal(&Expression::integer(0))
+        );
+    }
+
+    #[test]
+    fn test_definite_integral_explanation() {
+        let mut explanation = Some(Box::new(FormattingObserver::new(OutputFormat::Text)));
+        parse("int(x, x, 0, 1)").simplify(&mut explanation).unwrap();
+        let steps = explanation.unwrap().into_explanations().to_vec();
+        assert!(
+            steps
+                .iter()
+                .any(|step| step.contains("Fundamental theorem of calculus"))
+        );
+
+        let mut explanation = Some(Box::new(FormattingObserver::new(OutputFormat::Text)));
+        parse("int(sin(x^2), x, 0, 1)")
+            .simplify(&mut explanation)
+            .unwrap();
+        let steps = explanation.unwrap().into_explanations().to_vec();
+        assert!(
+            steps
+                .iter()
+                .any(|step| step.contains("approximated\nby adaptive Gauss–Kronrod quadrature"))
+        );
+    }
+
+    #[test]
+    fn test_print_definite_integral() {
+        let expr = parse("int(x, x, 0, 1)");
+        assert_eq!(
+            expr.get_processed(),
+            " 1    \n⌠     \n⎮ x dx\n⌡     \n0     "
+        );
+        assert_eq!(expr.calculate_tree(0), "Integral:\n  x\n  d x\n  from 0\n  to 1");
+    }
+}
@@ -61,4 +61,30 @@ mod tests_good_token {
             ]
         )
     }
+    #[test]
+    fn test_good_token_scientific() {
+        let tokens: Vec<Token> = lex("1.5e-3");
+
+        assert_eq!(
+            tokens,
+            vec![
+                Token::Number("1".to_string()),
+                Token::Dot,
+                Token::Number("5e-3".to_string())
+            ]
+        )
+    }
+
+    #[test]
+    fn test_good_token_exponent_without_digits() {
+        let tokens: Vec<Token> = lex("2ex");
+
+        assert_eq!(
+            tokens,
+            vec![
+                Token::Number("2".to_string()),
+                Token::Literal("ex".to_string())
+            ]
+        )
+    }
 }
\ No newline at end of file
@@ -0,0 +1,183 @@
+use sym_rustic::{
+    ast::Expression,
+    lexer::{Lexer, Token},
+    parser::Parser,
+};
+
+fn lex(input: &str) -> Vec<Token> {
+    let mut lexer = Lexer::new(input);
+    let mut tokens: Vec<Token> = Vec::new();
+    while let Some(token) = lexer.next_token() {
+        tokens.push(token);
+    }
+    tokens
+}
+
+fn parse(input: &str) -> Expression {
+    let tokens = lex(input);
+    let mut parser = Parser::new(&tokens);
+    parser.parse_expression().unwrap()
+}
+
+/// Check that the limit `input` simplifies to `expected`.
+fn assert_limit(input: &str, expected: &str) {
+    let result = parse(input).simplify(&mut None).unwrap();
+    assert!(
+        result.is_equal(&parse(expected).simplify(&mut None).unwrap()),
+        "{} gave {} instead of {}",
+        input,
+        result,
+        expected
+    );
+}
+
+#[cfg(test)]
+mod tests_limit {
+    use crate::{assert_limit, parse};
+    use sym_rustic::{
+        ast::{Expression, SimplifyError, limit::Direction},
+        explanation::{FormattingObserver, OutputFormat},
+        prints::PrettyPrints,
+    };
+
+    #[test]
+    fn test_parse_limit() {
+        assert_eq!(
+            parse("lim(1/x, x, 0)"),
+            Expression::limit(
+                Expression::division(Expression::integer(1), Expression::variable("x")),
+                "x",
+                Expression::integer(0),
+                Direction::Both
+            )
+        );
+        assert_eq!(
+            parse("lim(1/x, x, 0, right)"),
+            Expression::limit(
+                Expression::division(Expression::integer(1), Expression::variable("x")),
+                "x",
+                Expression::integer(0),
+                Direction::Right
+            )
+        );
+        assert_eq!(parse("lim(x, x, inf)").to_string(), "lim(x->inf) x");
+        assert_eq!(parse("lim(x, x, 1, left)").to_string(), "lim(x->1-) x");
+    }
+
+    #[test]
+    fn test_limit_substitution() {
+        assert_limit("lim(x^2 + 1, x, 2)", "5");
+        assert_limit("lim(cos(x), x, 0)", "1");
+        assert_limit("lim(x*y, x, 3)", "3*y");
+    }
+
+    #[test]
+    fn test_limit_indeterminate() {
+        assert_limit("lim(sin(x)/x, x, 0)", "1");
+        assert_limit("lim((x^2 - 1)/(x - 1), x, 1)", "2");
+        assert_limit("lim((1 - cos(x))/x^2, x, 0)", "1/2");
+        assert_limit("lim((e^x - 1 - x)/x^2, x, 0)", "1/2");
+        assert_limit("lim(tan(x)/x, x, 0)", "1");
+        assert_limit("lim(y*x/x, x, 0)", "y");
+    }
+
+    #[test]
+    fn test_limit_one_sided() {
+        assert_limit("lim(1/x, x, 0, right)", "inf");
+        assert_limit("lim(1/x, x, 0, left)", "-inf");
+        assert_limit("lim(ln(x), x, 0, right)", "-inf");
+        assert_limit("lim(x*ln(x), x, 0, right)", "0");
+        assert_limit("lim(x^x, x, 0, right)", "1");
+        assert_limit("lim(1/x^2, x, 0)", "inf");
+    }
+
+    #[test]
+    fn test_limit_at_infinity() {
+        assert_limit("lim(1/x, x, inf)", "0");
+        assert_limit("lim(x^2 - x, x, inf)", "inf");
+        assert_limit("lim(x^3, x, -inf)", "-inf");
+        assert_limit("lim((2*x^2 + 1)/(x^2 - 3), x, inf)", "2");
+        assert_limit("lim(x*e^(-x), x, inf)", "0");
+        assert_limit("lim(e^x/x^3, x, inf)", "inf");
+        assert_limit("lim(ln(x)/x, x, inf)", "0");
+        assert_limit("lim(atan(x), x, -inf)", "-(pi/2)");
+        assert_limit("lim((1 + 1/x)^x, x, inf)", "e");
+        assert_limit("lim((1 + 2/x)^(3*x), x, inf)", "e^6");
+    }
+
+    #[test]
+    fn test_limit_undefined() {
+        assert_eq!(
+            parse("lim(1/x, x, 0)").simplify(&mut None),
+            Err(SimplifyError::UndefinedLimit)
+        );
+        assert_eq!(
+            parse("lim(sin(x), x, inf)").simplify(&mut None),
+            Err(SimplifyError::UndefinedLimit)
+        );
+        assert_eq!(
+            parse("lim(sin(1/x), x, 0)").simplify(&mut None),
+            Err(SimplifyError::UndefinedLimit)
+        );
+    }
+
+    #[test]
+    fn test_limit_bounded() {
+        assert_limit("lim(sin(x)/x, x, inf)", "0");
+        assert_limit("lim(cos(x)/exp(x), x, inf)", "0");
+        assert_limit("lim(x*sin(1/x), x, 0)", "0");
+        assert_limit("lim(x + sin(x), x, inf)", "inf");
+        assert_eq!(
+            parse("lim(2*sin(x) + 1, x, inf)").simplify(&mut None),
+            Err(SimplifyError::UndefinedLimit)
+        );
+        // tan is not bounded, its limit is left unevaluated
+        assert_eq!(
+            parse("lim(tan(x), x, inf)").simplify(&mut None).unwrap().to_string(),
+            "lim(x->inf) tan(x)"
+        );
+    }
+
+    #[test]
+    fn test_limit_floor_ceil() {
+        assert_eq!(
+            parse("lim(floor(x), x, 1)").simplify(&mut None),
+            Err(SimplifyError::UndefinedLimit)
+        );
+        assert_eq!(
+            parse("lim(ceil(x), x, 2)").simplify(&mut None),
+            Err(SimplifyError::UndefinedLimit)
+        );
+        assert_limit("lim(floor(x), x, 1, left)", "0");
+        assert_limit("lim(floor(x), x, 1, right)", "1");
+        assert_limit("lim(ceil(x), x, 2, left)", "2");
+        assert_limit("lim(ceil(x), x, 2, right)", "3");
+        assert_limit("lim(x - floor(x), x, 2, left)", "1");
+        assert_limit("lim(floor(x^2), x, 0)", "0");
+        assert_limit("lim(floor(x), x, 3/2)", "1");
+    }
+
+    #[test]
+    fn test_limit_explanation() {
+        let mut explanation = Some(Box::new(FormattingObserver::new(OutputFormat::Text)));
+        parse("lim(sin(x)/x, x, 0)")
+            .simplify(&mut explanation)
+            .unwrap();
+        let steps = explanation.unwrap().into_explanations().to_vec();
+        assert!(
+            steps
+                .iter()
+                .any(|step| step.contains("L'Hôpital's rule for an indeterminate form"))
+        );
+    }
+
+    #[test]
+    fn test_print_limit() {
+        let expr = parse("lim(1/x, x, 0, right)");
+        assert_eq!(expr.get_processed(), "      1\n lim  -\nx->0+ x");
+        assert_eq!(
+            expr.calculate_tree(0),
+            "Limit from above:\n  Division:\n    1\n    / x\n  x -> 0"
+        );
+    }
+}
@@ -0,0 +1,128 @@
+use sym_rustic::{
+    ast::Expression,
+    lexer::{Lexer, Token},
+    parser::Parser,
+};
+
+fn lex(input: &str) -> Vec<Token> {
+    let mut lexer = Lexer::new(input);
+    let mut tokens: Vec<Token> = Vec::new();
+    while let Some(token) = lexer.next_token() {
+        tokens.push(token);
+    }
+    tokens
+}
+
+fn parse(input: &str) -> Expression {
+    let tokens = lex(input);
+    let mut parser = Parser::new(&tokens);
+    parser.parse_expression().unwrap()
+}
+
+fn simplify(input: &str) -> String {
+    parse(input).simplify(&mut None).unwrap().to_string()
+}
+
+#[cfg(test)]
+mod tests_logarithm {
+    use crate::{parse, simplify};
+    use sym_rustic::{
+        ast::{
+            SimplifyOptions,
+            assumptions::{Assumption, Assumptions},
+        },
+        explanation::{FormattingObserver, OutputFormat},
+    };
+
+    #[test]
+    fn test_exact_values() {
+        assert_eq!(simplify("ln(e)"), "1");
+        assert_eq!(simplify("log10(1000)"), "3");
+        assert_eq!(simplify("log2(8)"), "3");
+        assert_eq!(simplify("log(4, 8)"), "3/2");
+        assert_eq!(simplify("log(2, 1/8)"), "-3");
+        assert_eq!(simplify("log10(1/100)"), "-2");
+        assert_eq!(simplify("log(3, 3)"), "1");
+        assert_eq!(simplify("log(3, 1)"), "0");
+        assert_eq!(simplify("log10(20)"), "log10(20)");
+        assert_eq!(simplify("ln(8)"), "3 * ln(2)");
+    }
+
+    #[test]
+    fn test_invalid_base() {
+        assert_eq!(simplify("log(0, 0)"), "log(0, 0)");
+        assert_eq!(simplify("log(1, 1)"), "log(1, 1)");
+        assert_eq!(simplify("log(1, 8)"), "log(1, 8)");
+        assert_eq!(simplify("log(-2, 4)"), "log(-2, 4)");
+        assert_eq!(simplify("log(x, x)"), "1");
+        let expanded = parse("log(1, x*y)").expand_log(&mut None).unwrap();
+        assert_eq!(expanded.to_string(), "log(1, x * y)");
+    }
+
+    #[test]
+    fn test_inverse_functions() {
+        assert_eq!(simplify("exp(ln(x))"), "x");
+        assert_eq!(simplify("ln(exp(x))"), "x");
+        assert_eq!(simplify("e^(ln(x))"), "x");
+        assert_eq!(simplify("2^log2(x)"), "x");
+        assert_eq!(simplify("log(2, 2^x)"), "x");
+        assert_eq!(simplify("exp(2*ln(x))"), "x^2");
+    }
+
+    #[test]
+    fn test_power_law() {
+        assert_eq!(simplify("ln(x^3)"), "3 * ln(x)");
+        assert_eq!(simplify("log10(x^y)"), "y * log10(x)");
+        assert_eq!(simplify("ln(x^2)"), "2 * ln(abs(x))");
+        let mut assumptions = Assumptions::new();
+        assumptions.assume("x", Assumption::positive());
+        let options = SimplifyOptions {
+            assumptions,
+            ..Default::default()
+        };
+        let simplified = parse("ln(x^2)").simplify_with(&options, &mut None).unwrap();
+        assert_eq!(simplified.to_string(), "2 * ln(x)");
+    }
+
+    #[test]
+    fn test_expand_log() {
+        let expand = |input: &str| parse(input).expand_log(&mut None).unwrap().to_string();
+        assert_eq!(expand("ln(x*y)"), "ln(x) + ln(y)");
+        assert_eq!(expand("ln(x/y)"), "ln(x) + -ln(y)");
+        assert_eq!(expand("log(2, x*y*z)"), "log(2, y) + log(2, z) + log(2, x)");
+        assert_eq!(expand("ln(x^3*y/z)"), "ln(y) + -ln(z) + (3 * ln(x))");
+    }
+
+    #[test]
+    fn test_combine_log() {
+        let combine = |input: &str| parse(input).combine_log(&mut None).unwrap().to_string();
+        assert_eq!(combine("ln(x) + ln(y)"), "ln(x * y)");
+        assert_eq!(combine("ln(x) - ln(y)"), "ln(x/y)");
+        assert_eq!(combine("2*ln(x)"), "ln(x^2)");
+        assert_eq!(combine("ln(2) + ln(3)"), "ln(6)");
+        assert_eq!(combine("log(2, x) + log(2, y)"), "log(2, x * y)");
+        assert_eq!(combine("ln(x) + log10(y)"), "ln(x) + log10(y)");
+        assert_eq!(
+            combine("ln(x) - 2*ln(y) + log10(a) + log10(b) + 3"),
+            "3 + log10(a * b) + ln(x/(y^2))"
+        );
+    }
+
+    #[test]
+    fn test_logarithm_explanation() {
+        let mut explanation = Some(Box::new(FormattingObserver::new(OutputFormat::Text)));
+        parse("log10(1000) + exp(ln(x)) + ln(x^3)").simplify(&mut explanation).unwrap();
+        parse("ln(x*y)").expand_log(&mut explanation).unwrap();
+        parse("ln(x) + ln(y)").combine_log(&mut explanation).unwrap();
+        let steps = explanation.unwrap().into_explanations().to_vec();
+        for rule in [
+            "Exact value of the logarithm",
+            "Exponential and logarithm are inverse",
+            "Logarithm of a power",
+            "Logarithm of a product",
+            "Sum of logarithms",
+        ] {
+            assert!(steps.iter().any(|step| step.contains(rule)), "{rule}");
+        }
+    }
+}
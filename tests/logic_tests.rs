@@ -0,0 +1,250 @@
+use sym_rustic::{
+    ast::Expression,
+    lexer::{Lexer, Token},
+    parser::Parser,
+};
+
+fn lex(input: &str) -> Vec<Token> {
+    let mut lexer = Lexer::new(input);
+    let mut tokens: Vec<Token> = Vec::new();
+    while let Some(token) = lexer.next_token() {
+        tokens.push(token);
+    }
+    tokens
+}
+
+fn parse(input: &str) -> Expression {
+    let tokens = lex(input);
+    let mut parser = Parser::new(&tokens);
+    parser.parse_expression().unwrap()
+}
+
+/// Check that `input` simplifies to `expected`.
+fn assert_simplifies_to(input: &str, expected: &str) {
+    let result = parse(input).simplify(&mut None).unwrap();
+    assert!(
+        result.is_equal(&parse(expected).simplify(&mut None).unwrap()),
+        "{} gave {} instead of {}",
+        input,
+        result,
+        expected
+    );
+}
+
+/// Check that `rhs` has the value of `lhs` for every row of the truth table of `lhs`.
+fn assert_equivalent(lhs: &Expression, rhs: &Expression) {
+    let table = lhs.truth_table().unwrap();
+    for (values, value) in table.rows {
+        let mut substituted = table
+            .atoms
+            .iter()
+            .zip(values)
+            .fold(rhs.clone(), |expr, (atom, value)| {
+                expr.substitute(atom, &Expression::boolean(value))
+            });
+        assert_eq!(
+            substituted.simplify(&mut None).unwrap(),
+            Expression::boolean(value),
+            "{} and {} differ",
+            lhs,
+            rhs
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests_logic {
+    use std::collections::HashMap;
+
+    use crate::{assert_equivalent, assert_simplifies_to, lex, parse};
+    use sym_rustic::{
+        ast::{Expression, SimplifyError},
+        explanation::{FormattingObserver, OutputFormat},
+        lexer::Token,
+        prints::PrettyPrints,
+    };
+
+    #[test]
+    fn test_lex_logic() {
+        assert_eq!(
+            lex("!a&&b||c xor d"),
+            vec![
+                Token::Not,
+                Token::Literal("a".to_string()),
+                Token::And,
+                Token::Literal("b".to_string()),
+                Token::Or,
+                Token::Literal("c".to_string()),
+                Token::WhiteSpace,
+                Token::Xor,
+                Token::WhiteSpace,
+                Token::Literal("d".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_logic() {
+        let a = || Expression::variable("a");
+        let b = || Expression::variable("b");
+        let c = || Expression::variable("c");
+        // && binds tighter than xor which binds tighter than ||
+        assert_eq!(
+            parse("a || b && c"),
+            Expression::or(vec![a(), Expression::and(vec![b(), c()])])
+        );
+        assert_eq!(
+            parse("a xor b || c"),
+            Expression::or(vec![Expression::xor(a(), b()), c()])
+        );
+        assert_eq!(
+            parse("!a && b"),
+            Expression::and(vec![Expression::logical_not(a()), b()])
+        );
+        assert_eq!(
+            parse("x = 1 && true"),
+            Expression::and(vec![
+                Expression::equality(Expression::variable("x"), Expression::integer(1)),
+                Expression::boolean(true)
+            ])
+        );
+        assert_eq!(parse("!(a || b)").to_string(), "!(a || b)");
+        assert_eq!(parse("(a && b) || !c").to_string(), "(a && b) || !c");
+    }
+
+    #[test]
+    fn test_simplify_logic() {
+        assert_simplifies_to("a && true", "a");
+        assert_simplifies_to("a || true", "true");
+        assert_simplifies_to("a && false && b", "false");
+        assert_simplifies_to("a || b || a", "a || b");
+        assert_simplifies_to("a && !a", "false");
+        assert_simplifies_to("b || !b || c", "true");
+        assert_simplifies_to("a && (a || b)", "a");
+        assert_simplifies_to("a || (a && b)", "a");
+        assert_simplifies_to("!!a", "a");
+        assert_simplifies_to("!false", "true");
+        assert_simplifies_to("a xor a", "false");
+        assert_simplifies_to("a xor !a", "true");
+        assert_simplifies_to("a xor true", "!a");
+        assert_simplifies_to("(a && b) && (c && a)", "a && b && c");
+    }
+
+    #[test]
+    fn test_normal_forms() {
+        assert_eq!(
+            parse("(a && b) || !c").to_cnf(&mut None).unwrap().to_string(),
+            "(a || !c) && (b || !c)"
+        );
+        assert_eq!(
+            parse("!(a || b) && (c xor true)")
+                .to_dnf(&mut None)
+                .unwrap()
+                .to_string(),
+            "!a && !b && !c"
+        );
+        assert_eq!(
+            parse("a xor b").to_dnf(&mut None).unwrap().to_string(),
+            "(a && !b) || (!a && b)"
+        );
+        assert_eq!(parse("a || !a").to_cnf(&mut None), Ok(Expression::boolean(true)));
+
+        for input in [
+            "a xor b",
+            "(a || b) && !(a && c)",
+            "!(a xor b) || (c && !d)",
+            "(a && b) || (c && d) || (a && !d)",
+        ] {
+            let expr = parse(input);
+            assert_equivalent(&expr, &expr.to_cnf(&mut None).unwrap());
+            assert_equivalent(&expr, &expr.to_dnf(&mut None).unwrap());
+        }
+
+        // The CNF of this DNF has 2^10 clauses
+        let large = (0..10)
+            .map(|i| format!("(a_{} && b_{})", i, i))
+            .collect::<Vec<_>>()
+            .join(" || ");
+        assert_eq!(parse(&large).to_cnf(&mut None), Err(SimplifyError::Unsupported));
+    }
+
+    #[test]
+    fn test_truth_table() {
+        let table = parse("a && !b").truth_table().unwrap();
+        assert_eq!(
+            table.atoms,
+            vec![Expression::variable("a"), Expression::variable("b")]
+        );
+        assert_eq!(
+            table.rows,
+            vec![
+                (vec![false, false], false),
+                (vec![false, true], false),
+                (vec![true, false], true),
+                (vec![true, true], false),
+            ]
+        );
+        assert_eq!(
+            table.to_string(),
+            "a | b | a && !b\nF | F | F\nF | T | F\nT | F | T\nT | T | F"
+        );
+    }
+
+    #[test]
+    fn test_satisfiable() {
+        for input in [
+            "(a || b) && !(a && b)",
+            "(a || b) && (!a || c) && (!b || !c) && !c",
+            "!(a || b) && (c xor true)",
+        ] {
+            let expr = parse(input);
+            let model = expr.satisfiable(&mut None).unwrap().unwrap();
+            let mut substituted = model.iter().fold(expr.clone(), |expr, (atom, value)| {
+                expr.substitute(atom, &Expression::boolean(*value))
+            });
+            assert_eq!(
+                substituted.simplify(&mut None).unwrap(),
+                Expression::boolean(true),
+                "{}",
+                input
+            );
+        }
+
+        assert_eq!(parse("a && !a").satisfiable(&mut None), Ok(None));
+        assert_eq!(
+            parse("(a || b) && (!a || b) && (a || !b) && (!a || !b)").satisfiable(&mut None),
+            Ok(None)
+        );
+    }
+
+    #[test]
+    fn test_logic_explanation() {
+        let mut explanation = Some(Box::new(FormattingObserver::new(OutputFormat::Text)));
+        parse("!(a && b) || a").simplify(&mut explanation).unwrap();
+        parse("!(a && b) || c").to_cnf(&mut explanation).unwrap();
+        parse("(a || b) && !a && !b").satisfiable(&mut explanation).unwrap();
+        let steps = explanation.unwrap().into_explanations().to_vec();
+        assert!(steps.iter().any(|step| step.contains("De Morgan's law")));
+        assert!(steps.iter().any(|step| step.contains("Unit propagation")));
+        assert!(steps.iter().any(|step| step.contains("Conflict")));
+    }
+
+    #[test]
+    fn test_evaluate_logic() {
+        let env = HashMap::from([("x".to_string(), 2.0), ("y".to_string(), 0.0)]);
+        assert_eq!(parse("x && !y").evaluate(&env), Ok(1.0));
+        assert_eq!(parse("x xor 1").evaluate(&env), Ok(0.0));
+        assert_eq!(parse("y || false").evaluate(&env), Ok(0.0));
+    }
+
+    #[test]
+    fn test_print_logic() {
+        let expr = parse("(a || b) && !c");
+        assert_eq!(expr.get_processed(), "(a || b) && !c");
+        assert_eq!(
+            expr.calculate_tree(0),
+            "And:\n  && Or:\n    || a\n    || b\n  && Not:\n    ! c"
+        );
+        assert_eq!(parse("a xor 1/2").get_processed(), "      /1\\\na xor |-|\n      \\2/");
+    }
+}
@@ -0,0 +1,195 @@
+use sym_rustic::{
+    ast::Expression,
+    lexer::{Lexer, Token},
+    parser::Parser,
+};
+
+fn lex(input: &str) -> Vec<Token> {
+    let mut lexer = Lexer::new(input);
+    let mut tokens: Vec<Token> = Vec::new();
+    while let Some(token) = lexer.next_token() {
+        tokens.push(token);
+    }
+    tokens
+}
+
+fn parse(input: &str) -> Expression {
+    let tokens = lex(input);
+    let mut parser = Parser::new(&tokens);
+    parser.parse_expression().unwrap()
+}
+
+/// Check that `input` simplifies to `expected`.
+fn assert_simplifies_to(input: &str, expected: &str) {
+    let result = parse(input).simplify(&mut None).unwrap();
+    assert!(
+        result.is_equal(&parse(expected).simplify(&mut None).unwrap()),
+        "{} gave {} instead of {}",
+        input,
+        result,
+        expected
+    );
+}
+
+#[cfg(test)]
+mod tests_matrix {
+    use crate::{assert_simplifies_to, lex, parse};
+    use sym_rustic::{
+        ast::{Expression, SimplifyError},
+        explanation::{FormattingObserver, OutputFormat},
+        parser::{ParseError, Parser},
+        prints::PrettyPrints,
+    };
+
+    #[test]
+    fn test_parse_matrix() {
+        assert_eq!(
+            parse("[[a, b], [c, d]]"),
+            Expression::matrix(vec![
+                vec![Expression::variable("a"), Expression::variable("b")],
+                vec![Expression::variable("c"), Expression::variable("d")],
+            ])
+        );
+        assert_eq!(
+            parse("[1, x]"),
+            Expression::vector(vec![Expression::integer(1), Expression::variable("x")])
+        );
+        assert_eq!(parse("[[1, 2]]").to_string(), "[[1, 2]]");
+        assert_eq!(parse("[[x + 1], [2]]").to_string(), "[[x + 1], [2]]");
+
+        let tokens = lex("[[1, 2], [3]]");
+        assert_eq!(
+            Parser::new(&tokens).parse_expression(),
+            Err(ParseError::InvalidMatrixFormat(0))
+        );
+    }
+
+    #[test]
+    fn test_matrix_arithmetic() {
+        assert_simplifies_to("[[1, 2], [3, 4]] + [[a, b], [c, d]]", "[[1 + a, 2 + b], [3 + c, 4 + d]]");
+        assert_simplifies_to("[[1, 2], [3, 4]] - [[1, 2], [3, 4]]", "[[0, 0], [0, 0]]");
+        assert_simplifies_to("2*[[1, x], [0, 3]]", "[[2, 2*x], [0, 6]]");
+        assert_simplifies_to("-[1, x]", "[-1, -x]");
+        assert_simplifies_to("[[2, 4], [6, 8]]/2", "[[1, 2], [3, 4]]");
+    }
+
+    #[test]
+    fn test_matrix_product() {
+        assert_simplifies_to("[[1, 2], [3, 4]] * [[0, 1], [1, 0]]", "[[2, 1], [4, 3]]");
+        // The product doesn't commute
+        assert_simplifies_to("[[0, 1], [1, 0]] * [[1, 2], [3, 4]]", "[[3, 4], [1, 2]]");
+        assert_simplifies_to("[[1, 2], [3, 4]] * [5, 6]", "[17, 39]");
+        assert_simplifies_to("[[1, 2, 3]] * [x, y, z]", "[x + 2*y + 3*z]");
+        assert_simplifies_to("[[1, 2], [3, 4]]^2", "[[7, 10], [15, 22]]");
+        assert_simplifies_to("[[1, 2], [3, 4]]^0", "[[1, 0], [0, 1]]");
+        assert_eq!(
+            parse("[[1, 2], [3, 4]] * [1, 2, 3]").simplify(&mut None),
+            Err(SimplifyError::DimensionMismatch)
+        );
+        assert_eq!(
+            parse("[1, 2] + [1, 2, 3]").simplify(&mut None),
+            Err(SimplifyError::DimensionMismatch)
+        );
+    }
+
+    #[test]
+    fn test_determinant() {
+        assert_eq!(
+            parse("[[a, b], [c, d]]")
+                .determinant(&mut None)
+                .unwrap()
+                .to_string(),
+            "(a * d) + -(b * c)"
+        );
+        assert_eq!(
+            parse("[[2, 0, 1], [1, 3, 2], [1, 1, 2]]").determinant(&mut None),
+            Ok(Expression::integer(6))
+        );
+        assert_eq!(
+            parse("[[1, 2, 3], [4, 5, 6], [7, 8, 9]]").determinant(&mut None),
+            Ok(Expression::integer(0))
+        );
+        assert_eq!(
+            parse("[[1, 2, 3]]").determinant(&mut None),
+            Err(SimplifyError::DimensionMismatch)
+        );
+        assert_eq!(parse("x").determinant(&mut None), Err(SimplifyError::DimensionMismatch));
+    }
+
+    #[test]
+    fn test_inverse() {
+        assert_eq!(
+            parse("[[1, 2], [3, 4]]").inverse(&mut None).unwrap(),
+            parse("[[-2, 1], [3/2, -1/2]]").simplify(&mut None).unwrap()
+        );
+        assert_simplifies_to("[[2, 0], [0, 4]]^-1", "[[1/2, 0], [0, 1/4]]");
+        assert_simplifies_to("[[1, 2], [3, 4]] * [[1, 2], [3, 4]]^-1", "[[1, 0], [0, 1]]");
+        assert_simplifies_to("[[1, 1], [0, 1]] / [[1, 1], [0, 1]]", "[[1, 0], [0, 1]]");
+        // v/A is v*A^-1
+        assert_eq!(
+            parse("[1, 1] / [[1, 1], [0, 1]]").simplify(&mut None),
+            Err(SimplifyError::DimensionMismatch)
+        );
+        assert_eq!(
+            parse("[[1, 2], [2, 4]]").inverse(&mut None),
+            Err(SimplifyError::SingularMatrix)
+        );
+        assert_eq!(
+            parse("[[x, y], [2*x, 2*y]]^-1").simplify(&mut None),
+            Err(SimplifyError::SingularMatrix)
+        );
+    }
+
+    #[test]
+    fn test_trace_transpose_rank() {
+        assert_eq!(
+            parse("[[a, b], [c, d]]").trace(&mut None).unwrap().to_string(),
+            "a + d"
+        );
+        assert_eq!(
+            parse("[[1, 2, 3], [4, 5, 6]]").transpose().unwrap(),
+            parse("[[1, 4], [2, 5], [3, 6]]").simplify(&mut None).unwrap()
+        );
+        assert_eq!(parse("[1, x]").transpose().unwrap().to_string(), "[[1, x]]");
+
+        assert_eq!(parse("[[1, 2, 3], [4, 5, 6], [7, 8, 9]]").rank(), Ok(2));
+        assert_eq!(parse("[[0, 0], [0, 0]]").rank(), Ok(0));
+        assert_eq!(parse("[[x, y], [2*x, 2*y]]").rank(), Ok(1));
+        assert_eq!(parse("[[a, b], [c, d]]").rank(), Ok(2));
+        assert_eq!(parse("[[1, 2, 3]]").trace(&mut None), Err(SimplifyError::DimensionMismatch));
+    }
+
+    #[test]
+    fn test_matrix_derivative_and_substitution() {
+        assert_simplifies_to("d/dx [[x^2, 1], [sin(x), x]]", "[[2*x, 0], [cos(x), 1]]");
+        assert_eq!(
+            parse("[x, 2*x]").substitute(&Expression::variable("x"), &Expression::integer(3)),
+            parse("[3, 2*3]")
+        );
+        assert!(parse("[[1, y]]").contains_var("y"));
+    }
+
+    #[test]
+    fn test_matrix_explanation() {
+        let mut explanation = Some(Box::new(FormattingObserver::new(OutputFormat::Text)));
+        parse("[[1, 2], [3, 4]] * [5, 6]")
+            .simplify(&mut explanation)
+            .unwrap();
+        let steps = explanation.unwrap().into_explanations().to_vec();
+        assert!(steps.iter().any(|step| step.contains("Matrix product")));
+    }
+
+    #[test]
+    fn test_print_matrix() {
+        let expr = parse("[[a, b], [c, 10]]");
+        assert_eq!(expr.get_processed(), "┌a  b ┐\n└c  10┘");
+        assert_eq!(
+            expr.calculate_tree(0),
+            "Matrix:\n  Row:\n    a\n    b\n  Row:\n    c\n    10"
+        );
+
+        assert_eq!(parse("[[1, 2]]").get_processed(), "[1  2]");
+        assert_eq!(parse("[x, 1/2, y]").get_processed(), "┌x┐\n│1│\n│-│\n│2│\n└y┘");
+        assert_eq!(parse("[x, y]").calculate_tree(0), "Vector:\n  x\n  y");
+    }
+}
@@ -0,0 +1,202 @@
+use sym_rustic::{
+    ast::Expression,
+    lexer::{Lexer, Token},
+    parser::Parser,
+};
+
+fn lex(input: &str) -> Vec<Token> {
+    let mut lexer = Lexer::new(input);
+    let mut tokens: Vec<Token> = Vec::new();
+    while let Some(token) = lexer.next_token() {
+        tokens.push(token);
+    }
+    tokens
+}
+
+fn parse(input: &str) -> Expression {
+    let tokens = lex(input);
+    let mut parser = Parser::new(&tokens);
+    parser.parse_expression().unwrap()
+}
+
+#[cfg(test)]
+mod tests_options {
+    use crate::parse;
+    use sym_rustic::ast::{Expression, SimplifyError, SimplifyOptions};
+
+    fn simplify_with(input: &str, options: &SimplifyOptions) -> Result<String, SimplifyError> {
+        parse(input)
+            .simplify_with(options, &mut None)
+            .map(|expr| expr.to_string())
+    }
+
+    #[test]
+    fn test_default_options() {
+        let options = SimplifyOptions::default();
+        for input in ["(x + 1)^2*y", "1/3 + 1/6", "2 + 3*4*(x - x)"] {
+            assert_eq!(
+                parse(input).simplify_with(&options, &mut None),
+                parse(input).simplify(&mut None)
+            );
+        }
+    }
+
+    #[test]
+    fn test_expand_option() {
+        let options = SimplifyOptions {
+            expand: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            simplify_with("(x + 1)^2*y", &options),
+            Ok("y + (x * y * 2) + ((x^2) * y)".to_string())
+        );
+        assert_eq!(
+            simplify_with("(x - y)^2", &options),
+            Ok("(y^2) + (-2 * x * y) + (x^2)".to_string())
+        );
+        // Too many terms, the power is kept
+        assert_eq!(
+            simplify_with("(x + 1)^100000", &options),
+            Ok("(x + 1)^100000".to_string())
+        );
+    }
+
+    #[test]
+    fn test_decimals_option() {
+        let options = SimplifyOptions {
+            decimals: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            parse("1/3 + 1/6").simplify_with(&options, &mut None),
+            Ok(Expression::float(0.5))
+        );
+        assert_eq!(simplify_with("2 + 3*4", &options), Ok("14".to_string()));
+        assert_eq!(simplify_with("1/3 + 1/6", &SimplifyOptions::default()), Ok("1/2".to_string()));
+    }
+
+    #[test]
+    fn test_precision_option() {
+        let options = SimplifyOptions {
+            decimals: true,
+            precision: Some(4),
+            ..Default::default()
+        };
+        assert_eq!(simplify_with("2/3", &options), Ok("0.6667".to_string()));
+        assert_eq!(simplify_with("x + 1/3", &options), Ok("x + 0.3333".to_string()));
+        assert_eq!(simplify_with("1.5e-12", &options), Ok("1.5e-12".to_string()));
+        assert_eq!(simplify_with("1/3", &options), Ok("0.3333".to_string()));
+        // Only the result is rounded, not each step of the product 1 * 0.5 * 0.3333
+        assert_eq!(simplify_with("prod(1/k, k, 1, 3)", &options), Ok("0.1667".to_string()));
+        // The exact numbers are kept
+        assert_eq!(
+            simplify_with("2/3", &SimplifyOptions { precision: Some(4), ..Default::default() }),
+            Ok("2/3".to_string())
+        );
+        assert_eq!(
+            simplify_with("2/3", &SimplifyOptions { decimals: true, ..Default::default() }),
+            Ok("0.6666666666666666".to_string())
+        );
+    }
+
+    #[test]
+    fn test_integral_options() {
+        let options = SimplifyOptions {
+            decimals: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            simplify_with("int(x/4, x)", &options),
+            Ok("0.125 * (x^2)".to_string())
+        );
+        assert_eq!(simplify_with("int(x, x, 0, 1)", &options), Ok("0.5".to_string()));
+        assert_eq!(
+            parse("x/4")
+                .integrate_with("x", &options, &mut None)
+                .map(|expr| expr.to_string()),
+            Ok("0.125 * (x^2)".to_string())
+        );
+    }
+
+    #[test]
+    fn test_nested_decimals_option() {
+        // The options reach the simplifications made inside each kind of node
+        let options = SimplifyOptions {
+            decimals: true,
+            ..Default::default()
+        };
+        for (input, expected) in [
+            ("sum(1/k, k, 1, 3)", "1.8333333333333333"),
+            ("prod(1/k, k, 1, 3)", "0.16666666666666666"),
+            ("lim(x/3, x, 1)", "0.3333333333333333"),
+            ("d/dx(x/3)", "0.3333333333333333"),
+            ("[[1, 2], [3, 4]]^(-1)", "[[-2, 1], [1.5, -0.5]]"),
+            ("[[1, 2], [3, 4]] * [[1/2, 0], [0, 1]]", "[[0.5, 2], [1.5, 4]]"),
+            ("[1/2, 1/4] + [1/4, 0]", "[0.75, 0.25]"),
+        ] {
+            assert_eq!(simplify_with(input, &options), Ok(expected.to_string()), "{}", input);
+        }
+
+        // The floats are rounded inside the nodes that are kept
+        let options = SimplifyOptions {
+            decimals: true,
+            precision: Some(4),
+            ..Default::default()
+        };
+        for (input, expected) in [
+            ("[[1/3, x], [0, 1]]", "[[0.3333, x], [0, 1]]"),
+            ("int(1/3 * e^(x^2), x)", "∫(0.3333 * (e^(x^2))) dx"),
+            ("int(1/3 * e^(x^2), x, 0, y)", "∫[0, y] (0.3333 * (e^(x^2))) dx"),
+        ] {
+            assert_eq!(simplify_with(input, &options), Ok(expected.to_string()), "{}", input);
+        }
+    }
+
+    #[test]
+    fn test_nested_max_depth_option() {
+        let depth = |max_depth| SimplifyOptions {
+            max_depth: Some(max_depth),
+            ..Default::default()
+        };
+        for input in [
+            "sum(1/k, k, 1, 3)",
+            "prod(1/k, k, 1, 3)",
+            "lim(x/3, x, 1)",
+            "d/dx(x/3)",
+            "int(x/3, x)",
+            "[[1, 2], [3, 4]]^(-1)",
+            "[[1, 2], [3, 4]] * [[1/2, 0], [0, 1]]",
+            "[1/2, 1/4] + [1/4, 0]",
+        ] {
+            assert_eq!(
+                simplify_with(input, &depth(2)),
+                Err(SimplifyError::DepthExceeded),
+                "{}",
+                input
+            );
+            assert_eq!(
+                simplify_with(input, &depth(10)),
+                simplify_with(input, &SimplifyOptions::default()),
+                "{}",
+                input
+            );
+        }
+        // The entries of the inverse are simplified one level below the power
+        assert_eq!(
+            simplify_with("[[1, 2], [3, 4]]^(-1)", &depth(3)),
+            Err(SimplifyError::DepthExceeded)
+        );
+    }
+
+    #[test]
+    fn test_max_depth_option() {
+        let depth = |max_depth| SimplifyOptions {
+            max_depth: Some(max_depth),
+            ..Default::default()
+        };
+        assert_eq!(simplify_with("x", &depth(1)), Ok("x".to_string()));
+        assert_eq!(simplify_with("2 + 3*4", &depth(1)), Err(SimplifyError::DepthExceeded));
+        assert_eq!(simplify_with("2 + 3*4", &depth(10)), Ok("14".to_string()));
+    }
+}
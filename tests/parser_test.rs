@@ -103,6 +103,38 @@ mod tests_number {
         let expr = parser.parse_expression().unwrap();
         assert_eq!(expr, Expression::rational(55, 100));
     }
+
+    #[test]
+    fn test_number_decimal_leading_zeros() {
+        let tokens: Vec<Token> = lex("1.05");
+        let mut parser = Parser::new(&tokens);
+        let expr = parser.parse_expression().unwrap();
+        assert_eq!(expr, Expression::rational(105, 100));
+    }
+
+    #[test]
+    fn test_number_scientific() {
+        let tokens: Vec<Token> = lex("1e-12");
+        let mut parser = Parser::new(&tokens);
+        let expr = parser.parse_expression().unwrap();
+        assert_eq!(expr, Expression::float(1e-12));
+    }
+
+    #[test]
+    fn test_number_scientific_decimal() {
+        let tokens: Vec<Token> = lex("2.5E3");
+        let mut parser = Parser::new(&tokens);
+        let expr = parser.parse_expression().unwrap();
+        assert_eq!(expr, Expression::float(2500.0));
+    }
+
+    #[test]
+    fn test_number_scientific_invalid() {
+        let tokens: Vec<Token> = lex("1e5.3");
+        let mut parser = Parser::new(&tokens);
+        let expr = parser.parse_expression().unwrap_err();
+        assert_eq!(expr, ParseError::InvalidNumberFormat(2));
+    }
 }
 
 #[cfg(test)]
@@ -1134,8 +1166,6 @@ mod tests_negation {
 #[cfg(test)]
 mod tests_derivative {
 
-    use std::u32;
-
     use sym_rustic::{
         ast::Expression,
         parser::{ParseError, Parser},
@@ -0,0 +1,269 @@
+use sym_rustic::{
+    ast::Expression,
+    lexer::{Lexer, Token},
+    parser::Parser,
+    polynomial::Polynomial,
+};
+
+fn lex(input: &str) -> Vec<Token> {
+    let mut lexer = Lexer::new(input);
+    let mut tokens: Vec<Token> = Vec::new();
+    while let Some(token) = lexer.next_token() {
+        tokens.push(token);
+    }
+    tokens
+}
+
+fn parse(input: &str) -> Expression {
+    let tokens = lex(input);
+    let mut parser = Parser::new(&tokens);
+    parser.parse_expression().unwrap()
+}
+
+fn polynomial(input: &str) -> Polynomial {
+    Polynomial::from_expression(&parse(input)).unwrap()
+}
+
+#[cfg(test)]
+mod tests_polynomial {
+    use crate::{parse, polynomial};
+    use sym_rustic::{
+        ast::{
+            Expression, SimplifyError, SimplifyOptions,
+            assumptions::{Assumption, Assumptions},
+            numeral::Numeral,
+        },
+        explanation::{FormattingObserver, OutputFormat},
+        polynomial::{Polynomial, PolynomialError},
+    };
+
+    #[test]
+    fn test_from_expression() {
+        assert_eq!(polynomial("(x + 1)*(x - 1)").to_string(), "(x^2) + -1");
+        assert_eq!(
+            polynomial("(x + y)^2 - 2*x*y").to_string(),
+            "(x^2) + (y^2)"
+        );
+        assert_eq!(polynomial("x/2 + x/3").to_string(), "(5/6) * x");
+        assert_eq!(polynomial("2^(-2)*x").to_string(), "(1/4) * x");
+        assert_eq!(polynomial("x - x").to_string(), "0");
+        assert!(polynomial("x - x").is_zero());
+        // Like terms are collected whatever their order
+        assert_eq!(polynomial("y*x + 2 + x*y - 2"), polynomial("2*x*y"));
+    }
+
+    #[test]
+    fn test_generators() {
+        let p = polynomial("sin(x)^2 + 2*pi*sin(x) + x^(1/2)");
+        assert_eq!(
+            p.generators(),
+            &[
+                Expression::pi(),
+                parse("x^(1/2)"),
+                Expression::sin(Expression::variable("x")),
+            ]
+        );
+        assert_eq!(p.degree(&Expression::sin(Expression::variable("x"))), Some(2));
+        assert_eq!(p.total_degree(), Some(2));
+        assert_eq!(
+            p.terms().next_back(),
+            Some((&[1, 0, 1][..], &Numeral::Integer(2.into())))
+        );
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let p = polynomial("x + y");
+        let q = polynomial("x - y");
+        assert_eq!(p.add(&q), polynomial("2*x"));
+        assert_eq!(p.sub(&q), polynomial("2*y"));
+        assert_eq!(p.mul(&q), polynomial("x^2 - y^2"));
+        assert_eq!(p.neg().to_string(), "-x + -y");
+        assert_eq!(p.scale(&Numeral::Rational(1.into(), 2.into())).to_string(), "((1/2) * x) + ((1/2) * y)");
+        assert_eq!(p.pow(3), polynomial("x^3 + 3*x^2*y + 3*x*y^2 + y^3"));
+        assert_eq!(p.pow(0), Polynomial::integer(1));
+        assert!(p.pow(0).is_one());
+        assert_eq!(Polynomial::zero().as_constant(), Some(Numeral::Integer(0.into())));
+    }
+
+    #[test]
+    fn test_division() {
+        let (quotient, remainder) = polynomial("x^3 - 2*x^2 + 4").div_rem(&polynomial("x - 3")).unwrap();
+        assert_eq!(quotient, polynomial("x^2 + x + 3"));
+        assert_eq!(remainder, polynomial("13"));
+
+        let (quotient, remainder) = polynomial("x^2 - 1").div_rem(&polynomial("2*x + 2")).unwrap();
+        assert_eq!(quotient, polynomial("x/2 - 1/2"));
+        assert!(remainder.is_zero());
+
+        let (quotient, remainder) = polynomial("x^2*y + x*y^2 + y^2").div_rem(&polynomial("x*y - 1")).unwrap();
+        assert_eq!(quotient, polynomial("x + y"));
+        assert_eq!(remainder, polynomial("x + y^2 + y"));
+
+        // dividend = quotient * divisor + remainder
+        let dividend = polynomial("x^4*y + 3*x*y^3 - y + 7");
+        let divisor = polynomial("x^2 + y");
+        let (quotient, remainder) = dividend.div_rem(&divisor).unwrap();
+        assert_eq!(quotient.mul(&divisor).add(&remainder), dividend);
+        assert!(remainder.degree(&Expression::variable("x")).unwrap() < 2);
+
+        assert_eq!(
+            polynomial("x").div_rem(&Polynomial::zero()),
+            Err(PolynomialError::DivisionByZero)
+        );
+    }
+
+    #[test]
+    fn test_coefficients() {
+        let p = polynomial("3*x^2*y + x^2 - 2*x + y^3");
+        let x = Expression::variable("x");
+        let y = Expression::variable("y");
+        assert_eq!(p.degree(&x), Some(2));
+        assert_eq!(p.degree(&y), Some(3));
+        assert_eq!(p.degree(&Expression::variable("z")), Some(0));
+        assert_eq!(Polynomial::zero().degree(&x), None);
+        assert_eq!(p.leading_coefficient(&x), polynomial("3*y + 1"));
+        assert_eq!(p.leading_coefficient(&y), Polynomial::integer(1));
+        assert_eq!(p.coefficient(&x, 1), Polynomial::integer(-2));
+        assert_eq!(p.coefficient(&x, 5), Polynomial::zero());
+        assert_eq!(
+            p.coefficients(&x),
+            vec![polynomial("y^3"), Polynomial::integer(-2), polynomial("3*y + 1")]
+        );
+        assert_eq!(p.leading_numeral(), Numeral::Integer(3.into()));
+    }
+
+    #[test]
+    fn test_to_expression() {
+        let p = polynomial("(x - 2*y)^2");
+        assert_eq!(p.to_string(), "(x^2) + -(4 * x * y) + (4 * (y^2))");
+        let mut expr = p.to_expression();
+        assert_eq!(
+            Polynomial::from_expression(&expr.simplify(&mut None).unwrap()),
+            Ok(p)
+        );
+    }
+
+    #[test]
+    fn test_polynomial_errors() {
+        for (input, error) in [
+            ("1/x", PolynomialError::NotPolynomial),
+            ("x^(-2)", PolynomialError::NotPolynomial),
+            ("x = 1", PolynomialError::NotPolynomial),
+            ("x/(2 - 2)", PolynomialError::DivisionByZero),
+            ("(x + 1)^5000", PolynomialError::DegreeTooLarge),
+        ] {
+            assert_eq!(Polynomial::from_expression(&parse(input)), Err(error), "{}", input);
+        }
+        assert_eq!(
+            Polynomial::from_expression(&Expression::addition(vec![
+                Expression::variable("x"),
+                Expression::float(0.5)
+            ])),
+            Err(PolynomialError::InexactCoefficient)
+        );
+    }
+
+    #[test]
+    fn test_gcd() {
+        assert_eq!(polynomial("x^2 - 1").gcd(&polynomial("x^2 - 2*x + 1")), polynomial("x - 1"));
+        assert_eq!(polynomial("x^2 + 1").gcd(&polynomial("x - 1")), Polynomial::integer(1));
+        assert_eq!(polynomial("6*x + 6").gcd(&polynomial("4*x^2 - 4")), polynomial("x + 1"));
+        assert_eq!(polynomial("x/2 - 1/2").gcd(&Polynomial::zero()), polynomial("x - 1"));
+        assert_eq!(
+            polynomial("x^2*y - y^3").gcd(&polynomial("x*y^2 + y^3")),
+            polynomial("x*y + y^2")
+        );
+        assert_eq!(
+            polynomial("(x + y + 1)*(x - y)^2*(z + 2)").gcd(&polynomial("(x - y)*(x + 1)*(z + 2)^2")),
+            polynomial("(x - y)*(z + 2)")
+        );
+        assert_eq!(polynomial("x - 1").lcm(&polynomial("x^2 - 1")), polynomial("x^2 - 1"));
+
+        let (content, primitive) = polynomial("-x/2 - 1/3").primitive();
+        assert_eq!(content, Numeral::Rational((-1).into(), 6.into()));
+        assert_eq!(primitive, polynomial("3*x + 2"));
+
+        let (gcd, lhs, rhs) = polynomial("x^3 - x").cofactors(&polynomial("x^2 + x"));
+        assert_eq!(gcd, polynomial("x^2 + x"));
+        assert_eq!(lhs, polynomial("x - 1"));
+        assert_eq!(rhs, Polynomial::integer(1));
+    }
+
+    #[test]
+    fn test_cancel() {
+        for (input, expected) in [
+            ("(x^2 - 1)/(x - 1)", "x + 1"),
+            ("(x^2 - 1)/(2*x - 2)", "(x + 1)/2"),
+            ("(x - 1)/(1 - x)", "-1"),
+            ("(x^2 - y^2)/(x^2 + 2*x*y + y^2)", "(x - y)/(x + y)"),
+            ("(x^2 + 1)/(x + 1)", "(x^2 + 1)/(x + 1)"),
+            ("x/(x^2 + x) + 1/(x + 1)", "2/(x + 1)"),
+        ] {
+            let result = parse(input).cancel(&mut None).unwrap();
+            assert!(
+                result.is_equal(&parse(expected)) || {
+                    let mut lhs = result.clone();
+                    let mut rhs = parse(expected);
+                    lhs.simplify(&mut None) == rhs.simplify(&mut None)
+                },
+                "{} gave {} instead of {}",
+                input,
+                result,
+                expected
+            );
+        }
+        assert_eq!(parse("x/(x - x)").cancel(&mut None), Err(SimplifyError::DivisionByZero));
+        assert_eq!(parse("x = 1").cancel(&mut None), Err(SimplifyError::Unsupported));
+    }
+
+    #[test]
+    fn test_together() {
+        assert_eq!(
+            parse("1/x + 1/2").together(&mut None).unwrap().to_string(),
+            "(x + 2)/(2 * x)"
+        );
+        assert_eq!(
+            parse("1/(x - 1) - 1/(x + 1)").together(&mut None).unwrap().to_string(),
+            "2/((x^2) + -1)"
+        );
+        // The common factors stay
+        assert_eq!(
+            parse("x/(x^2 + x) + 0").together(&mut None).unwrap().to_string(),
+            "x/((x^2) + x)"
+        );
+        assert_eq!(
+            parse("(1 + 1/x)/(1 - 1/x^2)").cancel(&mut None).unwrap().to_string(),
+            "x/(x + -1)"
+        );
+    }
+
+    #[test]
+    fn test_simplify_cancels() {
+        // x - 1 could be zero
+        assert_eq!(
+            parse("(x^2 - 1)/(x^2 - 2*x + 1)").simplify(&mut None).unwrap().to_string(),
+            "((x^2) + -1)/((-2 * x) + 1 + (x^2))"
+        );
+
+        let mut assumptions = Assumptions::new();
+        assumptions.assume("x", Assumption::positive());
+        assumptions.assume("y", Assumption::positive());
+        let options = SimplifyOptions {
+            assumptions,
+            ..Default::default()
+        };
+        let mut explanation = Some(Box::new(FormattingObserver::new(OutputFormat::Text)));
+        let result = parse("(x^2 + 2*x + 1)/(x^2 - 1)")
+            .simplify_with(&options, &mut explanation)
+            .unwrap();
+        assert_eq!(result.to_string(), "(x + 1)/(x + -1)");
+        let steps = explanation.unwrap().into_explanations().to_vec();
+        assert!(steps.iter().any(|step| step.contains("Simplify by the common factor x + 1")));
+
+        assert_eq!(
+            parse("(a*x + a*y)/(x + y)").simplify_with(&options, &mut None),
+            Ok(Expression::variable("a"))
+        );
+    }
+}
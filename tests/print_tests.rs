@@ -19,6 +19,16 @@ mod print_tests {
         assert_eq!(neg.calculate_tree(0), "Negation:\n  - 5");
     }
 
+    #[test]
+    fn test_float_precision() {
+        // Every digit needed to read back the same value
+        let num = Expression::float(2.0 / 3.0);
+        assert_eq!(num.to_string(), "0.6666666666666666");
+        assert_eq!(num.calculate_tree(0), "0.6666666666666666");
+        assert_eq!(Expression::float(1.5e-12).to_string(), "1.5e-12");
+        assert_eq!(Expression::float(2.0).to_string(), "2.0");
+    }
+
     #[test]
     fn test_calculate_tree_addition() {
         // Test addition with multiple terms
@@ -163,6 +173,25 @@ mod print_tests {
         assert_eq!(expr.calculate_tree(2), expected);
     }
 
+    #[test]
+    fn test_get_processed_single_rows() {
+        // A variable takes one row, an empty layout can't be printed
+        assert_eq!(Expression::variable("x").get_processed(), "x");
+        assert_eq!(
+            Expression::division(Expression::variable("x"), Expression::variable("y"))
+                .get_processed(),
+            "x\n-\ny"
+        );
+
+        // A rational is laid out as a division of its numerator by its denominator
+        assert_eq!(Expression::rational(1, 2).get_processed(), "1\n-\n2");
+        assert_eq!(
+            Expression::addition(vec![Expression::variable("x"), Expression::rational(1, 2)])
+                .get_processed(),
+            "    1\nx + -\n    2"
+        );
+    }
+
     #[test]
     fn test_get_processed() {
         let expr = Expression::exponentiation(
@@ -0,0 +1,94 @@
+use sym_rustic::{
+    ast::Expression,
+    lexer::{Lexer, Token},
+    parser::Parser,
+};
+
+fn lex(input: &str) -> Vec<Token> {
+    let mut lexer = Lexer::new(input);
+    let mut tokens: Vec<Token> = Vec::new();
+    while let Some(token) = lexer.next_token() {
+        tokens.push(token);
+    }
+    tokens
+}
+
+fn parse(input: &str) -> Expression {
+    let tokens = lex(input);
+    let mut parser = Parser::new(&tokens);
+    parser.parse_expression().unwrap()
+}
+
+fn simplify(input: &str) -> String {
+    parse(input).simplify(&mut None).unwrap().to_string()
+}
+
+#[cfg(test)]
+mod tests_radical {
+    use crate::{parse, simplify};
+    use sym_rustic::{
+        ast::Expression,
+        explanation::{FormattingObserver, OutputFormat},
+    };
+
+    #[test]
+    fn test_perfect_powers() {
+        let mut sqrt = Expression::sqrt(Expression::integer(12));
+        assert_eq!(sqrt.simplify(&mut None).unwrap().to_string(), "2 * sqrt(3)");
+        assert_eq!(simplify("root(3, 54)"), "3 * root(3, 2)");
+        assert_eq!(simplify("sqrt(4)"), "2");
+        assert_eq!(simplify("root(3, 8)"), "2");
+        assert_eq!(simplify("sqrt(72)"), "6 * sqrt(2)");
+        assert_eq!(simplify("root(3, -16)"), "-2 * root(3, 2)");
+        assert_eq!(simplify("sqrt(15)"), "sqrt(15)");
+        assert_eq!(simplify("sqrt(-4)"), "sqrt(-4)");
+    }
+
+    #[test]
+    fn test_rational_radicand() {
+        assert_eq!(simplify("sqrt(1/4)"), "1/2");
+        assert_eq!(simplify("sqrt(8/9)"), "(2 * sqrt(2))/3");
+        assert_eq!(simplify("sqrt(1/2)"), "sqrt(2)/2");
+        assert_eq!(simplify("root(3, 1/4)"), "root(3, 2)/2");
+    }
+
+    #[test]
+    fn test_rationalize_denominator() {
+        assert_eq!(simplify("1/sqrt(2)"), "sqrt(2)/2");
+        assert_eq!(simplify("3/sqrt(6)"), "sqrt(6)/2");
+        assert_eq!(simplify("6/sqrt(3)"), "2 * sqrt(3)");
+        assert_eq!(simplify("1/(2*sqrt(3))"), "sqrt(3)/6");
+        assert_eq!(simplify("1/root(3, 2)"), "root(3, 4)/2");
+        assert_eq!(simplify("x/sqrt(2)"), "(x * sqrt(2))/2");
+        assert_eq!(simplify("1/(3 - sqrt(5))"), "(3 + sqrt(5))/4");
+        assert_eq!(simplify("1/(1 + sqrt(2))"), "-1 + sqrt(2)");
+        assert_eq!(simplify("1/sqrt(x)"), "1/sqrt(x)");
+    }
+
+    #[test]
+    fn test_denesting() {
+        assert_eq!(simplify("sqrt(3 + 2*sqrt(2))"), "sqrt(2) + 1");
+        assert_eq!(simplify("sqrt(5 - 2*sqrt(6))"), "sqrt(3) + -sqrt(2)");
+        assert_eq!(simplify("sqrt(7 + 4*sqrt(3))"), "2 + sqrt(3)");
+        assert_eq!(simplify("sqrt(2 + sqrt(3))"), "(sqrt(6) + sqrt(2))/2");
+        assert_eq!(simplify("sqrt(1 + sqrt(2))"), "sqrt(1 + sqrt(2))");
+    }
+
+    #[test]
+    fn test_radical_explanation() {
+        let mut explanation = Some(Box::new(FormattingObserver::new(OutputFormat::Text)));
+        parse("sqrt(12) + sqrt(1/2)").simplify(&mut explanation).unwrap();
+        parse("1/sqrt(2) + 1/(1 + sqrt(2))").simplify(&mut explanation).unwrap();
+        parse("sqrt(3 + 2*sqrt(2))").simplify(&mut explanation).unwrap();
+        let steps = explanation.unwrap().into_explanations().to_vec();
+        for rule in [
+            "Take the perfect powers out of the root",
+            "Take the denominator out of the root",
+            "Rationalize the denominator",
+            "Multiply by the conjugate of the denominator",
+            "Denest the square root",
+        ] {
+            assert!(steps.iter().any(|step| step.contains(rule)), "{rule}");
+        }
+    }
+}
@@ -0,0 +1,132 @@
+use sym_rustic::{
+    ast::Expression,
+    lexer::{Lexer, Token},
+    parser::Parser,
+};
+
+fn lex(input: &str) -> Vec<Token> {
+    let mut lexer = Lexer::new(input);
+    let mut tokens: Vec<Token> = Vec::new();
+    while let Some(token) = lexer.next_token() {
+        tokens.push(token);
+    }
+    tokens
+}
+
+fn parse(input: &str) -> Expression {
+    let tokens = lex(input);
+    let mut parser = Parser::new(&tokens);
+    parser.parse_expression().unwrap()
+}
+
+#[cfg(test)]
+mod tests_series {
+    use std::collections::HashMap;
+
+    use crate::parse;
+    use sym_rustic::{
+        ast::{Expression, SimplifyError},
+        prints::PrettyPrints,
+    };
+
+    fn series(input: &str, point: Expression, order: u32) -> Expression {
+        parse(input).series("x", &point, order).unwrap()
+    }
+
+    #[test]
+    fn test_maclaurin_series() {
+        assert_eq!(
+            series("e^x", Expression::integer(0), 3).to_string(),
+            "1 + x + ((x^2)/2) + O(x^3)"
+        );
+        assert_eq!(
+            series("sin(x)", Expression::integer(0), 6).to_string(),
+            "(x - ((x^3)/6)) + ((x^5)/120) + O(x^6)"
+        );
+        assert_eq!(
+            series("1/(1 - x)", Expression::integer(0), 4).to_string(),
+            "1 + x + (x^2) + (x^3) + O(x^4)"
+        );
+        assert_eq!(
+            series("atan(x)", Expression::integer(0), 4).to_string(),
+            "x - ((x^3)/3) + O(x^4)"
+        );
+    }
+
+    #[test]
+    fn test_taylor_series() {
+        assert_eq!(
+            series("ln(x)", Expression::integer(1), 3).to_string(),
+            "x - 1 - (((x - 1)^2)/2) + O((x - 1)^3)"
+        );
+        // A polynomial is its own series
+        assert_eq!(
+            series("x^2 + 3*x", Expression::integer(2), 4).to_string(),
+            "10 + (7 * (x - 2)) + ((x - 2)^2) + O((x - 2)^4)"
+        );
+    }
+
+    #[test]
+    fn test_series_other_variable() {
+        assert_eq!(
+            series("e^(a*x)", Expression::integer(0), 2).to_string(),
+            "1 + (a * x) + O(x^2)"
+        );
+    }
+
+    #[test]
+    fn test_series_remainder_only() {
+        assert_eq!(series("sin(x)", Expression::integer(0), 1).to_string(), "O(x)");
+        assert_eq!(series("x^3", Expression::integer(0), 2).to_string(), "O(x^2)");
+    }
+
+    #[test]
+    fn test_series_unsupported() {
+        assert_eq!(
+            parse("ln(x)").series("x", &Expression::integer(0), 3),
+            Err(SimplifyError::Unsupported)
+        );
+        assert_eq!(
+            parse("sqrt(x)").series("x", &Expression::integer(0), 3),
+            Err(SimplifyError::Unsupported)
+        );
+        assert_eq!(
+            parse("e^x").series("x", &Expression::infinity(), 3),
+            Err(SimplifyError::Unsupported)
+        );
+    }
+
+    #[test]
+    fn test_evaluate_series() {
+        // The remainder is dropped
+        let env = HashMap::from([("x".to_string(), 0.1)]);
+        let value = series("cos(x)", Expression::integer(0), 8)
+            .evaluate(&env)
+            .unwrap();
+        assert!((value - 0.1f64.cos()).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_derivative_of_series() {
+        let derivative = Expression::derivative(series("sin(x)", Expression::integer(0), 4), "x", 1)
+            .simplify(&mut None)
+            .unwrap();
+        let Expression::Series(derivative) = derivative else {
+            panic!("{} is not a series", derivative);
+        };
+        assert_eq!(derivative.order, 3);
+        let env = HashMap::from([("x".to_string(), 0.3)]);
+        let value = derivative.terms.evaluate(&env).unwrap();
+        assert!((value - (1.0 - 0.3f64.powi(2) / 2.0)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_print_series() {
+        let expr = series("e^x", Expression::integer(0), 2);
+        assert_eq!(expr.get_processed(), "         / 2\\\n1 + x + O\\x /");
+        assert_eq!(
+            expr.calculate_tree(0),
+            "Series:\n  Addition:\n    + 1\n    + x\n  O Exponentiation:\n    x\n    ^ 2"
+        );
+    }
+}
@@ -1,5 +1,8 @@
 use sym_rustic::{
-    ast::{Expression, SimplifyError},
+    ast::{
+        Expression, SimplifyError, SimplifyOptions,
+        assumptions::{Assumption, Assumptions},
+    },
     explanation::FormattingObserver,
     lexer::{Lexer, Token},
     parser::Parser,
@@ -26,9 +29,32 @@ fn simplify(
     expr.simplify(explanation)
 }
 
+/// Simplify with an assumption on each of the variables, the common factors
+/// are only cancelled when they are proven nonzero.
+fn simplify_assuming(
+    mut expr: Expression,
+    variables: &[(&str, Assumption)],
+) -> Result<Expression, SimplifyError> {
+    let mut assumptions = Assumptions::new();
+    for (variable, assumption) in variables {
+        assumptions.assume(variable, assumption.clone());
+    }
+    let options = SimplifyOptions {
+        assumptions,
+        ..Default::default()
+    };
+    expr.simplify_with(&options, &mut None)
+}
+
+/// A real variable different from zero
+fn nonzero_real() -> Assumption {
+    Assumption::real().and(Assumption::nonzero())
+}
+
 #[cfg(test)]
 mod tests_additions {
     use crate::{lex, parse, simplify};
+    use num_bigint::BigInt;
     use sym_rustic::ast::Expression;
 
     #[test]
@@ -198,10 +224,65 @@ mod tests_additions {
             Expression::negation(Expression::variable("d")),
         ])));
     }
+
+    #[test]
+    fn test_addition_23() {
+        let expr = simplify(parse(lex("a + b/2")), &mut None).unwrap();
+        assert!(expr.is_equal(&Expression::division(
+            Expression::addition(vec![
+                Expression::multiplication(vec![
+                    Expression::variable("a"),
+                    Expression::integer(2)
+                ]),
+                Expression::variable("b"),
+            ]),
+            Expression::integer(2)
+        )));
+    }
+
+    #[test]
+    fn test_addition_24() {
+        let expr = simplify(parse(lex("18446744073709551615 + 1")), &mut None).unwrap();
+        assert_eq!(
+            expr,
+            Expression::integer("18446744073709551616".parse::<BigInt>().unwrap())
+        );
+    }
+
+    #[test]
+    fn test_addition_25() {
+        let expr = simplify(parse(lex("1/2 + 1/4")), &mut None).unwrap();
+        assert_eq!(expr, Expression::rational(3, 4));
+    }
+
+    #[test]
+    fn test_addition_26() {
+        // A negative result is the negation of a positive numeral
+        for (input, expected) in [
+            ("1 - 4", Expression::integer(3)),
+            ("1/4 - 1/2", Expression::rational(1, 4)),
+            ("-18446744073709551615 - 1", Expression::integer("18446744073709551616".parse::<BigInt>().unwrap())),
+        ] {
+            let expr = simplify(parse(lex(input)), &mut None).unwrap();
+            assert!(expr.is_equal(&Expression::negation(expected)), "{} gave {:?}", input, expr);
+        }
+        let expr = simplify(Expression::integer(-3), &mut None).unwrap();
+        assert!(expr.is_equal(&Expression::negation(Expression::integer(3))));
+        let expr = Expression::addition(vec![Expression::float(1.5), Expression::integer(-3)]);
+        let expr = simplify(expr, &mut None).unwrap();
+        assert!(expr.is_equal(&Expression::negation(Expression::float(1.5))));
+    }
+
+    #[test]
+    fn test_addition_float() {
+        let expr = simplify(parse(lex("1/4 + 5e-1")), &mut None).unwrap();
+        assert_eq!(expr, Expression::float(0.75));
+    }
 }
 
 mod tests_multiplication {
-    use crate::{lex, parse, simplify};
+    use crate::{lex, nonzero_real, parse, simplify, simplify_assuming};
+    use num_bigint::BigInt;
     use sym_rustic::ast::Expression;
 
     #[test]
@@ -239,13 +320,13 @@ mod tests_multiplication {
 
     #[test]
     fn test_multiplication_5() {
-        let expr = simplify(parse(lex("a * a / a")), &mut None).unwrap();
+        let expr = simplify_assuming(parse(lex("a * a / a")), &[("a", nonzero_real())]).unwrap();
         assert!(expr.is_equal(&Expression::variable("a")));
     }
 
     #[test]
     fn test_multiplication_6() {
-        let expr = simplify(parse(lex("a * a / a * a")), &mut None).unwrap();
+        let expr = simplify_assuming(parse(lex("a * a / a * a")), &[("a", nonzero_real())]).unwrap();
         assert!(expr.is_equal(&Expression::exponentiation(
             Expression::variable("a"),
             Expression::integer(2)
@@ -254,13 +335,13 @@ mod tests_multiplication {
 
     #[test]
     fn test_multiplication_7() {
-        let expr = simplify(parse(lex("a * a / a / a")), &mut None).unwrap();
+        let expr = simplify_assuming(parse(lex("a * a / a / a")), &[("a", nonzero_real())]).unwrap();
         assert!(expr.is_equal(&Expression::integer(1)));
     }
 
     #[test]
     fn test_multiplication_8() {
-        let expr = simplify(parse(lex("(a * a) / (a * a)")), &mut None).unwrap();
+        let expr = simplify_assuming(parse(lex("(a * a) / (a * a)")), &[("a", nonzero_real())]).unwrap();
         assert!(expr.is_equal(&Expression::integer(1)));
     }
 
@@ -413,14 +494,14 @@ mod tests_multiplication {
 
     #[test]
     fn test_multiplication_20() {
-        let expr = simplify(parse(lex("(a * b) / a")), &mut None).unwrap();
+        let expr = simplify_assuming(parse(lex("(a * b) / a")), &[("a", nonzero_real())]).unwrap();
 
         assert!(expr.is_equal(&Expression::variable("b")));
     }
 
     #[test]
     fn test_multiplication_21() {
-        let expr = simplify(parse(lex("a * b / a")), &mut None).unwrap();
+        let expr = simplify_assuming(parse(lex("a * b / a")), &[("a", nonzero_real())]).unwrap();
 
         assert!(expr.is_equal(&Expression::variable("b")));
     }
@@ -495,11 +576,29 @@ mod tests_multiplication {
 
         assert!(expr.is_equal(&Expression::rational(2, 3)));
     }
+
+    #[test]
+    fn test_multiplication_28() {
+        let expr = simplify(parse(lex("4294967296 * 4294967296")), &mut None).unwrap();
+        assert_eq!(
+            expr,
+            Expression::integer("18446744073709551616".parse::<BigInt>().unwrap())
+        );
+    }
+
+    #[test]
+    fn test_multiplication_float() {
+        let expr = simplify(parse(lex("2/3 * 1.5e0")), &mut None).unwrap();
+        assert_eq!(expr, Expression::float(1.0));
+    }
 }
 
 mod tests_division {
-    use crate::{lex, parse, simplify};
-    use sym_rustic::ast::{Expression, SimplifyError};
+    use crate::{lex, nonzero_real, parse, simplify, simplify_assuming};
+    use sym_rustic::{
+        ast::{Expression, SimplifyError, assumptions::Assumption},
+        solver::{Bound, Interval},
+    };
 
     #[test]
     fn test_division_1() {
@@ -539,7 +638,14 @@ mod tests_division {
 
     #[test]
     fn test_division_7() {
+        // a could be zero
         let expr = simplify(parse(lex("a / a")), &mut None).unwrap();
+        assert_eq!(
+            expr,
+            Expression::division(Expression::variable("a"), Expression::variable("a"))
+        );
+
+        let expr = simplify_assuming(parse(lex("a / a")), &[("a", Assumption::nonzero())]).unwrap();
         assert_eq!(expr, Expression::integer(1));
     }
 
@@ -551,7 +657,7 @@ mod tests_division {
 
     #[test]
     fn test_division_9() {
-        let expr = simplify(parse(lex("(a + b i) / (a + b i)")), &mut None).unwrap();
+        let expr = simplify_assuming(parse(lex("(a + b i) / (a + b i)")), &[("a", nonzero_real()), ("b", nonzero_real())]).unwrap();
         assert_eq!(expr, Expression::integer(1));
     }
 
@@ -672,6 +778,29 @@ mod tests_division {
             ]))
         );
     }
+
+    #[test]
+    fn test_division_14() {
+        // x - 1 is negative so it is not zero
+        let negative = Assumption::interval(Interval {
+            lower: Bound::Unbounded,
+            upper: Bound::Open(Expression::integer(0)),
+        });
+        let expr = simplify_assuming(parse(lex("(x^2 - 1) / (x - 1)")), &[("x", negative)]).unwrap();
+        assert_eq!(
+            expr,
+            Expression::addition(vec![Expression::variable("x"), Expression::integer(1)])
+        );
+    }
+
+    #[test]
+    fn test_division_float() {
+        let expr = simplify(parse(lex("1/4e0")), &mut None).unwrap();
+        assert_eq!(expr, Expression::float(0.25));
+
+        let expr = simplify(parse(lex("1/0e0")), &mut None);
+        assert_eq!(expr, Err(SimplifyError::DivisionByZero));
+    }
 }
 
 #[cfg(test)]
@@ -780,6 +909,22 @@ mod tests_subtraction {
         let expr = simplify(parse(lex("3 - 4")), &mut None).unwrap();
         assert!(expr.is_equal(&Expression::negation(Expression::integer(1))));
     }
+
+    #[test]
+    fn test_subtraction_13() {
+        // 0 - 4*(-a) => 4*a, the signs of the factor and of the product cancel
+        let expr = simplify(parse(lex("0 - 4*(-a)")), &mut None).unwrap();
+        assert_eq!(expr.to_string(), "4 * a");
+        let expr = simplify(parse(lex("-(-(4*a))")), &mut None).unwrap();
+        assert_eq!(expr.to_string(), "4 * a");
+    }
+
+    #[test]
+    fn test_subtraction_14() {
+        // -((a + b)/c) => (-a - b)/c
+        let expr = simplify(parse(lex("0 - (-5 + sqrt(5))/2")), &mut None).unwrap();
+        assert_eq!(expr.to_string(), "(5 + -sqrt(5))/2");
+    }
 }
 
 #[cfg(test)]
@@ -1,8 +1,632 @@
+use sym_rustic::{
+    ast::Expression,
+    lexer::{Lexer, Token},
+    parser::Parser,
+};
+
+fn lex(input: &str) -> Vec<Token> {
+    let mut lexer = Lexer::new(input);
+    let mut tokens: Vec<Token> = Vec::new();
+    while let Some(token) = lexer.next_token() {
+        tokens.push(token);
+    }
+    tokens
+}
+
+fn parse(input: &str) -> Expression {
+    let tokens = lex(input);
+    let mut parser = Parser::new(&tokens);
+    parser.parse_expression().unwrap()
+}
+
 #[cfg(test)]
-mod tests {
+mod tests_linear {
+    use crate::parse;
+    use sym_rustic::{
+        ast::Expression,
+        explanation::{FormattingObserver, OutputFormat},
+        solver::{Solution, Solver, SolverError},
+    };
 
     #[test]
     fn test_linear() {
+        let solution = Solver::solve_for(
+            parse("3x + 2 = 5x - 7"),
+            Expression::variable("x"),
+            &mut None,
+        );
+        assert_eq!(solution, Ok(Solution::Single(Expression::rational(9, 2))));
+
+        let solution = Solver::solve_for(parse("2x = 8"), Expression::variable("x"), &mut None);
+        assert_eq!(solution, Ok(Solution::Single(Expression::integer(4))));
+
+        let solution = Solver::solve_for(parse("x + 3 = 1"), Expression::variable("x"), &mut None);
+        assert_eq!(
+            solution,
+            Ok(Solution::Single(Expression::negation(Expression::integer(
+                2
+            ))))
+        );
+
+        let solution =
+            Solver::solve_for(parse("x/2 + x/3 = 5"), Expression::variable("x"), &mut None);
+        assert_eq!(solution, Ok(Solution::Single(Expression::integer(6))));
+
+        let solution =
+            Solver::solve_for(parse("4 = 2(x - 1)"), Expression::variable("x"), &mut None);
+        assert_eq!(solution, Ok(Solution::Single(Expression::integer(3))));
+    }
+
+    #[test]
+    fn test_linear_symbolic() {
+        let solution =
+            Solver::solve_for(parse("a*x + b = c"), Expression::variable("x"), &mut None).unwrap();
+        let Solution::Single(x) = solution else {
+            panic!("Expected a single solution");
+        };
+        assert!(x.is_equal(&Expression::division(
+            Expression::addition(vec![
+                Expression::variable("c"),
+                Expression::negation(Expression::variable("b")),
+            ]),
+            Expression::variable("a"),
+        )));
+    }
+
+    #[test]
+    fn test_linear_degenerate() {
+        let solution =
+            Solver::solve_for(parse("x + 1 = x + 1"), Expression::variable("x"), &mut None);
+        assert_eq!(solution, Ok(Solution::Infinite));
+
+        let solution =
+            Solver::solve_for(parse("x + 1 = x + 2"), Expression::variable("x"), &mut None);
+        assert_eq!(solution, Ok(Solution::NoSolution));
+    }
+
+    #[test]
+    fn test_linear_errors() {
+        assert_eq!(
+            Solver::solve_for(parse("x + 1"), Expression::variable("x"), &mut None),
+            Err(SolverError::InvalidEquation)
+        );
+        assert_eq!(
+            Solver::solve_for(parse("x + 1 = 2"), Expression::integer(1), &mut None),
+            Err(SolverError::InvalidVariable)
+        );
+        assert_eq!(
+            Solver::solve_for(parse("y + 1 = 2"), Expression::variable("x"), &mut None),
+            Err(SolverError::VariableNotFound)
+        );
+        assert_eq!(
+            Solver::solve_for(parse("sin(x) = 2"), Expression::variable("x"), &mut None),
+            Err(SolverError::UnsupportedEquationType)
+        );
+    }
+
+    #[test]
+    fn test_linear_explanation() {
+        let mut explanation = Some(Box::new(FormattingObserver::new(OutputFormat::Text)));
+        let solution = Solver::solve_for(
+            parse("3x + 2 = 5x - 7"),
+            Expression::variable("x"),
+            &mut explanation,
+        );
+        assert_eq!(solution, Ok(Solution::Single(Expression::rational(9, 2))));
+
+        let steps = explanation.unwrap().into_explanations().to_vec();
+        assert_eq!(steps[0], "Solving (3 * x) + 2 = 5 * x - 7 for x");
+        assert!(
+            steps
+                .iter()
+                .any(|step| step.starts_with("- Subtract 5 * x from both sides"))
+        );
+        assert!(
+            steps
+                .iter()
+                .any(|step| step.starts_with("- Subtract 2 from both sides"))
+        );
+        assert!(
+            steps
+                .iter()
+                .any(|step| step.starts_with("- Divide both sides by -2"))
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests_polynomial {
+    use crate::parse;
+    use num_bigint::BigInt;
+    use sym_rustic::{
+        ast::{Expression, SimplifyOptions},
+        explanation::{FormattingObserver, OutputFormat},
+        solver::{Solution, Solver, SolverError},
+    };
+
+    fn roots(equation: &str) -> Vec<Expression> {
+        match Solver::solve_for(parse(equation), Expression::variable("x"), &mut None) {
+            Ok(Solution::Single(root)) => vec![root],
+            Ok(Solution::Multiple(roots)) => roots,
+            solution => panic!("Expected roots, got {:?}", solution),
+        }
+    }
+
+    fn assert_roots(equation: &str, expected: &[Expression]) {
+        let roots = roots(equation);
+        assert_eq!(roots.len(), expected.len(), "{:?}", roots);
+        for root in expected {
+            assert!(
+                roots.iter().any(|other| other.is_equal(root)),
+                "{} is not a root of {}, got {:?}",
+                root,
+                equation,
+                roots
+            );
+        }
+    }
+
+    /// Compare the roots with the printed simplified forms.
+    fn assert_simplified_roots(equation: &str, expected: &[&str]) {
+        let roots = roots(equation);
+        let mut printed: Vec<String> = roots.iter().map(|root| root.to_string()).collect();
+        printed.sort();
+        let mut expected: Vec<String> = expected.iter().map(|root| root.to_string()).collect();
+        expected.sort();
+        assert_eq!(printed, expected, "roots of {}", equation);
+    }
+
+    #[test]
+    fn test_quadratic() {
+        assert_roots(
+            "x^2 = 4",
+            &[
+                Expression::integer(2),
+                Expression::negation(Expression::integer(2)),
+            ],
+        );
+        assert_roots(
+            "x^2 - 2 = 0",
+            &[
+                Expression::sqrt(Expression::integer(2)),
+                Expression::negation(Expression::sqrt(Expression::integer(2))),
+            ],
+        );
+        assert_roots(
+            "2x^2 = 3x",
+            &[Expression::integer(0), Expression::rational(3, 2)],
+        );
+        assert_simplified_roots(
+            "x^2 + x - 1 = 0",
+            &["(-1 + sqrt(5))/2", "-(1/2) + -(sqrt(5)/2)"],
+        );
+    }
+
+    #[test]
+    fn test_quadratic_repeated_root() {
+        assert_eq!(
+            Solver::solve_for(
+                parse("x^2 - 6x + 9 = 0"),
+                Expression::variable("x"),
+                &mut None
+            ),
+            Ok(Solution::Single(Expression::integer(3)))
+        );
+    }
+
+    #[test]
+    fn test_quadratic_complex() {
+        assert_roots(
+            "x^2 + 1 = 0",
+            &[
+                Expression::complex(Expression::integer(0), Expression::integer(1)),
+                Expression::complex(
+                    Expression::integer(0),
+                    Expression::negation(Expression::integer(1)),
+                ),
+            ],
+        );
+        assert_roots(
+            "x^2 + 2x + 5 = 0",
+            &[
+                Expression::complex(
+                    Expression::negation(Expression::integer(1)),
+                    Expression::integer(2),
+                ),
+                Expression::complex(
+                    Expression::negation(Expression::integer(1)),
+                    Expression::negation(Expression::integer(2)),
+                ),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_cubic() {
+        assert_roots(
+            "x^3 - 6x^2 + 11x - 6 = 0",
+            &[
+                Expression::integer(1),
+                Expression::integer(2),
+                Expression::integer(3),
+            ],
+        );
+        assert_roots(
+            "(x - 1)^2 * (x + 2) = 0",
+            &[
+                Expression::integer(1),
+                Expression::negation(Expression::integer(2)),
+            ],
+        );
+        assert_simplified_roots(
+            "x^3 = 2",
+            &[
+                "root(3, 2)",
+                "-(root(3, 2)/2) + i*((root(3, 2) * sqrt(3))/2)",
+                "-(root(3, 2)/2) + i*-((root(3, 2) * sqrt(3))/2)",
+            ],
+        );
+    }
+
+    #[test]
+    fn test_cubic_casus_irreducibilis() {
+        // Three real irrational roots need the trigonometric form
+        let roots = roots("x^3 - 3x + 1 = 0");
+        assert_eq!(roots.len(), 3);
+        assert!(roots.iter().all(|root| matches!(
+            root,
+            Expression::Multiplication(mul) if matches!(mul.terms[1], Expression::Function(_))
+        )));
+    }
+
+    #[test]
+    fn test_quartic() {
+        assert_roots(
+            "x^4 - 5x^2 + 4 = 0",
+            &[
+                Expression::integer(1),
+                Expression::negation(Expression::integer(1)),
+                Expression::integer(2),
+                Expression::negation(Expression::integer(2)),
+            ],
+        );
+        assert_roots(
+            "x^4 - 2 x^3 - x^2 + 2x = 0",
+            &[
+                Expression::integer(0),
+                Expression::integer(1),
+                Expression::negation(Expression::integer(1)),
+                Expression::integer(2),
+            ],
+        );
+        assert_eq!(roots("x^4 + 1 = 0").len(), 4);
+        assert_eq!(roots("x^4 + x + 1 = 0").len(), 4);
+    }
+
+    #[test]
+    fn test_large_coefficients() {
+        // The coefficients do not fit in 128 bits
+        let power = |n: u32| Expression::integer(BigInt::from(10).pow(n));
+        assert_roots(
+            "x^2 = 10^40",
+            &[power(20), Expression::negation(power(20))],
+        );
+        assert_simplified_roots(
+            "x^2 = 2 * 10^40",
+            &[
+                "100000000000000000000 * sqrt(2)",
+                "-100000000000000000000 * sqrt(2)",
+            ],
+        );
+        assert_roots(
+            "(x - 10^18) * (x^2 - 1) = 0",
+            &[
+                power(18),
+                Expression::integer(1),
+                Expression::negation(Expression::integer(1)),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_polynomial_symbolic() {
+        assert_simplified_roots("x^2 = a", &["sqrt(a)", "-sqrt(a)"]);
+        assert_simplified_roots("2x^2 = a", &["sqrt(a/2)", "-sqrt(a/2)"]);
+        assert_simplified_roots(
+            "a*x^2 + b*x + c = 0",
+            &[
+                "(-b + sqrt((b^2) + (-4 * a * c)))/(2 * a)",
+                "(-b + -sqrt((b^2) + (-4 * a * c)))/(2 * a)",
+            ],
+        );
+    }
+
+    #[test]
+    fn test_simplified_roots() {
+        // The complex fifth roots of unity
+        assert_simplified_roots(
+            "x^5 - 1 = 0",
+            &[
+                "1",
+                "-(sqrt(5)/4) + -(1/4) + i*(sqrt((5 + -sqrt(5))/2)/2)",
+                "-(sqrt(5)/4) + -(1/4) + i*-(sqrt((5 + -sqrt(5))/2)/2)",
+                "(-1 + sqrt(5))/4 + i*(sqrt((5 + sqrt(5))/2)/2)",
+                "(-1 + sqrt(5))/4 + i*-(sqrt((5 + sqrt(5))/2)/2)",
+            ],
+        );
+
+        // The roots are simplified with the options
+        let options = SimplifyOptions {
+            decimals: true,
+            precision: Some(4),
+            ..Default::default()
+        };
+        let solution = Solver::solve_polynomial_with(
+            parse("x^2 - x/3"),
+            Expression::integer(0),
+            "x",
+            &options,
+            &mut None,
+        );
+        let Ok(Solution::Multiple(roots)) = solution else {
+            panic!("Expected roots, got {:?}", solution);
+        };
+        let roots: Vec<String> = roots.iter().map(|root| root.to_string()).collect();
+        assert_eq!(roots, ["0", "0.3333"]);
+    }
+
+    #[test]
+    fn test_polynomial_errors() {
+        assert_eq!(
+            Solver::solve_for(
+                parse("x^5 + x + 1 = 0"),
+                Expression::variable("x"),
+                &mut None
+            ),
+            Err(SolverError::UnsupportedEquationType)
+        );
+        assert_eq!(
+            Solver::solve_for(parse("x^2 = x^2 + 1"), Expression::variable("x"), &mut None),
+            Ok(Solution::NoSolution)
+        );
+    }
+
+    #[test]
+    fn test_polynomial_explanation() {
+        let mut explanation = Some(Box::new(FormattingObserver::new(OutputFormat::Text)));
+        let solution = Solver::solve_for(
+            parse("x^2 + 4 = 0"),
+            Expression::variable("x"),
+            &mut explanation,
+        );
+        assert!(matches!(solution, Ok(Solution::Multiple(_))));
+
+        let steps = explanation.unwrap().into_explanations().to_vec();
+        assert!(
+            steps
+                .iter()
+                .any(|step| step.starts_with("- Compute the discriminant"))
+        );
+        assert!(
+            steps
+                .iter()
+                .any(|step| step.starts_with("- The discriminant is negative"))
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests_system {
+    use crate::parse;
+    use sym_rustic::{
+        ast::Expression,
+        explanation::{FormattingObserver, OutputFormat},
+        solver::{Solver, SolverError, SystemSolution},
+    };
+
+    fn solve(equations: &[&str], variables: &[&str]) -> Result<SystemSolution, SolverError> {
+        Solver::solve_system(
+            equations.iter().map(|equation| parse(equation)).collect(),
+            variables
+                .iter()
+                .map(|variable| Expression::variable(variable))
+                .collect(),
+            &mut None,
+        )
+    }
+
+    #[test]
+    fn test_system_unique() {
+        assert_eq!(
+            solve(&["x + y = 3", "x - y = 1"], &["x", "y"]),
+            Ok(SystemSolution::Unique(vec![
+                Expression::integer(2),
+                Expression::integer(1)
+            ]))
+        );
+        assert_eq!(
+            solve(
+                &["2x + y - z = 8", "-3x - y + 2z = -11", "-2x + y + 2z = -3"],
+                &["x", "y", "z"]
+            ),
+            Ok(SystemSolution::Unique(vec![
+                Expression::integer(2),
+                Expression::integer(3),
+                Expression::negation(Expression::integer(1))
+                    .simplify(&mut None)
+                    .unwrap(),
+            ]))
+        );
+        assert_eq!(
+            solve(&["y + x/2 = 3", "2x = y + 1"], &["x", "y"]),
+            Ok(SystemSolution::Unique(vec![
+                Expression::rational(8, 5),
+                Expression::rational(11, 5)
+            ]))
+        );
+        // The first equation does not contain x so the rows are swapped
+        assert_eq!(
+            solve(&["y = 2", "x + y = 5"], &["x", "y"]),
+            Ok(SystemSolution::Unique(vec![
+                Expression::integer(3),
+                Expression::integer(2)
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_system_infinite() {
+        let Ok(SystemSolution::Infinite { parameters, values }) =
+            solve(&["x + y = 2", "2x + 2y = 4"], &["x", "y"])
+        else {
+            panic!("Expected infinitely many solutions");
+        };
+        assert_eq!(parameters, vec![Expression::variable("y")]);
+        assert!(values[0].is_equal(&Expression::addition(vec![
+            Expression::integer(2),
+            Expression::negation(Expression::variable("y")),
+        ])));
+        assert_eq!(values[1], Expression::variable("y"));
+
+        let Ok(SystemSolution::Infinite { parameters, values }) = solve(
+            &["x + y + z + w = 10", "x - y = 1", "z - w = 2"],
+            &["x", "y", "z", "w"],
+        ) else {
+            panic!("Expected infinitely many solutions");
+        };
+        assert_eq!(parameters, vec![Expression::variable("w")]);
+        assert_eq!(values[3], Expression::variable("w"));
+    }
+
+    #[test]
+    fn test_system_no_solution() {
+        assert_eq!(
+            solve(&["x + y = 1", "x + y = 2"], &["x", "y"]),
+            Ok(SystemSolution::NoSolution)
+        );
+        assert_eq!(
+            solve(&["x + y = 1", "x - y = 1", "x = 3"], &["x", "y"]),
+            Ok(SystemSolution::NoSolution)
+        );
+    }
+
+    #[test]
+    fn test_system_symbolic() {
+        let Ok(SystemSolution::Unique(values)) = solve(&["x + y = a", "x - y = b"], &["x", "y"])
+        else {
+            panic!("Expected a unique solution");
+        };
+        assert!(values[0].is_equal(&Expression::division(
+            Expression::addition(vec![Expression::variable("a"), Expression::variable("b")]),
+            Expression::integer(2),
+        )));
+        assert!(values[1].contains_var("a") && values[1].contains_var("b"));
+    }
+
+    #[test]
+    fn test_system_cases() {
+        let condition = |input: &str| parse(input).simplify(&mut None).unwrap();
+        let values = |values: &[&str]| {
+            values
+                .iter()
+                .map(|value| value.to_string())
+                .collect::<Vec<_>>()
+        };
+
+        // The pivot a + 1 vanishes for a = -1
+        let Ok(SystemSolution::Cases(cases)) = solve(&["a*x + y = 1", "x - y = 0"], &["x", "y"])
+        else {
+            panic!("Expected a solution for each case");
+        };
+        assert_eq!(cases.len(), 2);
+        assert!(cases[0].0.is_equal(&condition("a != -1")));
+        let SystemSolution::Unique(unique) = &cases[0].1 else {
+            panic!("Expected a unique solution when a != -1");
+        };
+        assert_eq!(
+            unique.iter().map(|value| value.to_string()).collect::<Vec<_>>(),
+            values(&["1/(1 + a)", "1/(1 + a)"])
+        );
+        assert!(cases[1].0.is_equal(&condition("a = -1")));
+        assert_eq!(cases[1].1, SystemSolution::NoSolution);
+
+        // The values are simplified knowing a != 0
+        let Ok(SystemSolution::Cases(cases)) = solve(&["a*x + a*y = a", "x - y = 0"], &["x", "y"])
+        else {
+            panic!("Expected a solution for each case");
+        };
+        assert_eq!(
+            cases[0].1,
+            SystemSolution::Unique(vec![Expression::rational(1, 2), Expression::rational(1, 2)])
+        );
+        assert!(matches!(cases[1].1, SystemSolution::Infinite { .. }));
+
+        let Ok(SystemSolution::Cases(cases)) = solve(&["a*x = 1", "y = 2"], &["x", "y"]) else {
+            panic!("Expected a solution for each case");
+        };
+        assert!(cases[0].0.is_equal(&condition("a != 0")));
+        assert!(matches!(cases[0].1, SystemSolution::Unique(_)));
+        assert_eq!(cases[1].1, SystemSolution::NoSolution);
+
+        // When a = b the system is only consistent for a = 1/2
+        let Ok(SystemSolution::Cases(cases)) = solve(&["a*x + b*y = 1", "x + y = 2"], &["x", "y"])
+        else {
+            panic!("Expected a solution for each case");
+        };
+        assert!(matches!(cases[0].1, SystemSolution::Unique(_)));
+        let SystemSolution::Cases(cases) = &cases[1].1 else {
+            panic!("Expected a solution for each case when a = b");
+        };
+        assert_eq!(cases[0].1, SystemSolution::NoSolution);
+        assert!(cases[1].0.is_equal(&condition("a = 1/2")));
+        assert!(matches!(cases[1].1, SystemSolution::Infinite { .. }));
+    }
+
+    #[test]
+    fn test_system_errors() {
+        assert_eq!(
+            solve(&["x * y = 1", "x = 2"], &["x", "y"]),
+            Err(SolverError::UnsupportedEquationType)
+        );
+        assert_eq!(
+            solve(&["x + y"], &["x", "y"]),
+            Err(SolverError::InvalidEquation)
+        );
+        assert_eq!(
+            solve(&["a = 1"], &["x", "y"]),
+            Err(SolverError::VariableNotFound)
+        );
+        assert_eq!(
+            Solver::solve_system(
+                vec![parse("x = 1")],
+                vec![Expression::integer(1)],
+                &mut None
+            ),
+            Err(SolverError::InvalidVariable)
+        );
+    }
+
+    #[test]
+    fn test_system_explanation() {
+        let mut explanation = Some(Box::new(FormattingObserver::new(OutputFormat::Text)));
+        let solution = Solver::solve_system(
+            vec![parse("x + y = 3"), parse("x - y = 1")],
+            vec![Expression::variable("x"), Expression::variable("y")],
+            &mut explanation,
+        );
+        assert!(matches!(solution, Ok(SystemSolution::Unique(_))));
 
+        let steps = explanation.unwrap().into_explanations().to_vec();
+        assert_eq!(steps[0], "Solving the system x + y = 3, x - y = 1 for x, y");
+        assert!(
+            steps
+                .iter()
+                .any(|step| step.starts_with("- Subtract equation 1 from equation 2"))
+        );
+        assert!(
+            steps
+                .iter()
+                .any(|step| step.starts_with("- Divide equation 2 by -2"))
+        );
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,212 @@
+use sym_rustic::{
+    ast::Expression,
+    lexer::{Lexer, Token},
+    parser::Parser,
+};
+
+fn lex(input: &str) -> Vec<Token> {
+    let mut lexer = Lexer::new(input);
+    let mut tokens: Vec<Token> = Vec::new();
+    while let Some(token) = lexer.next_token() {
+        tokens.push(token);
+    }
+    tokens
+}
+
+fn parse(input: &str) -> Expression {
+    let tokens = lex(input);
+    let mut parser = Parser::new(&tokens);
+    parser.parse_expression().unwrap()
+}
+
+/// Check that `input` simplifies to `expected`.
+fn assert_simplifies_to(input: &str, expected: &str) {
+    let result = parse(input).simplify(&mut None).unwrap();
+    assert!(
+        result.is_equal(&parse(expected).simplify(&mut None).unwrap()),
+        "{} gave {} instead of {}",
+        input,
+        result,
+        expected
+    );
+}
+
+#[cfg(test)]
+mod tests_summation {
+    use std::collections::HashMap;
+
+    use crate::{assert_simplifies_to, parse};
+    use sym_rustic::{
+        ast::{Expression, evaluate::EvalError},
+        explanation::{FormattingObserver, OutputFormat},
+        prints::PrettyPrints,
+    };
+
+    #[test]
+    fn test_parse_summation() {
+        assert_eq!(
+            parse("sum(k^2, k, 1, n)"),
+            Expression::summation(
+                Expression::exponentiation(Expression::variable("k"), Expression::integer(2)),
+                "k",
+                Expression::integer(1),
+                Expression::variable("n"),
+            )
+        );
+        assert_eq!(parse("sum(k, k, 1, n)").to_string(), "Σ(k=1..n) k");
+        assert_eq!(parse("prod(k + 1, k, 0, n)").to_string(), "Π(k=0..n) (k + 1)");
+    }
+
+    #[test]
+    fn test_expand_small_bounds() {
+        assert_simplifies_to("sum(k^2, k, 1, 10)", "385");
+        assert_simplifies_to("sum(x^k, k, 0, 2)", "1 + x + x^2");
+        assert_simplifies_to("prod(k, k, 1, 5)", "120");
+        // Empty sum and product
+        assert_simplifies_to("sum(k, k, 5, 2)", "0");
+        assert_simplifies_to("prod(k, k, 5, 2)", "1");
+    }
+
+    #[test]
+    fn test_non_integer_bounds() {
+        // The index only takes integer values, the closed forms don't apply
+        for input in [
+            "sum(k, k, 1, 2.5)",
+            "sum(k^2, k, 1, 5/2)",
+            "sum(1, k, 1, pi)",
+            "sum(2^k, k, 1/2, n)",
+            "sum(k, k, 1, n/2)",
+            "prod(k, k, 1, 5/2)",
+        ] {
+            let result = parse(input).simplify(&mut None).unwrap();
+            assert!(
+                matches!(result, Expression::Summation(_) | Expression::Product(_)),
+                "{} gave {}",
+                input,
+                result
+            );
+        }
+        let result = parse("sum(k, k, 1, 2*n - 1)").simplify(&mut None).unwrap();
+        assert!(!matches!(result, Expression::Summation(_)), "{}", result);
+    }
+
+    #[test]
+    fn test_arithmetic_and_power_sums() {
+        assert_simplifies_to("sum(c, k, 1, n)", "n*c");
+        assert_simplifies_to("sum(k, k, 1, n)", "(n + n^2)/2");
+        assert_simplifies_to("sum(k^2, k, 1, n)", "(1/6)*n + (1/2)*n^2 + (1/3)*n^3");
+        assert_simplifies_to("sum(k^3, k, 0, n)", "(1/4)*n^2 + (1/2)*n^3 + (1/4)*n^4");
+
+        // Faulhaber's formula is checked against the sum written out
+        let env = HashMap::from([("n".to_string(), 20.0)]);
+        let closed = parse("sum(k^5 - 3*k, k, 3, n)")
+            .simplify(&mut None)
+            .unwrap()
+            .evaluate(&env)
+            .unwrap();
+        let expected: f64 = (3..=20).map(|k| (k as f64).powi(5) - 3.0 * k as f64).sum();
+        assert!((closed - expected).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_geometric_series() {
+        assert_simplifies_to("sum(x^k, k, 0, n)", "(x^(n + 1) - 1)/(x - 1)");
+        assert_simplifies_to("sum(1/2^k, k, 0, inf)", "2");
+        assert_simplifies_to("sum((1/3)^k, k, 1, inf)", "1/2");
+        assert_simplifies_to("sum(-(1/2)^k, k, 0, inf)", "-2");
+    }
+
+    #[test]
+    fn test_telescoping_sums() {
+        assert_simplifies_to("sum(1/(k*(k + 1)), k, 1, n)", "n/(n + 1)");
+        assert_simplifies_to("sum(1/(k*(k + 1)), k, 1, inf)", "1");
+        assert_simplifies_to("sum(1/(k*(k + 2)), k, 1, inf)", "3/4");
+        assert_simplifies_to("sum(sqrt(k + 1) - sqrt(k), k, 0, n)", "sqrt(n + 1)");
+    }
+
+    #[test]
+    fn test_products() {
+        assert_simplifies_to("prod(2, k, 1, n)", "2^n");
+        assert_simplifies_to("prod((k + 1)/k, k, 1, n)", "n + 1");
+        assert_simplifies_to("prod(k/(k + 1), k, 1, inf)", "0");
+        assert_simplifies_to("prod(2^k, k, 1, n)", "2^((n + n^2)/2)");
+    }
+
+    #[test]
+    fn test_no_closed_form() {
+        assert_eq!(
+            parse("sum(1/k, k, 1, n)")
+                .simplify(&mut None)
+                .unwrap()
+                .to_string(),
+            "Σ(k=1..n) (1/k)"
+        );
+        assert_eq!(
+            parse("prod(k, k, 1, n)")
+                .simplify(&mut None)
+                .unwrap()
+                .to_string(),
+            "Π(k=1..n) k"
+        );
+    }
+
+    #[test]
+    fn test_evaluate_summation() {
+        let env = HashMap::from([("n".to_string(), 100.0)]);
+        let value = parse("sum(1/k, k, 1, n)").evaluate(&env).unwrap();
+        let expected: f64 = (1..=100).map(|k| 1.0 / k as f64).sum();
+        assert!((value - expected).abs() < 1e-12);
+
+        let value = parse("prod(1 + 1/k^2, k, 1, 3)").evaluate(&env).unwrap();
+        assert!((value - 2.0 * 1.25 * (10.0 / 9.0)).abs() < 1e-12);
+
+        assert_eq!(
+            parse("sum(1/k^2, k, 1, inf)").evaluate(&env),
+            Err(EvalError::Unsupported)
+        );
+    }
+
+    #[test]
+    fn test_summation_bound_variable() {
+        let sum = parse("sum(k*x, k, 1, n)");
+        assert!(!sum.contains_var("k"));
+        assert!(sum.contains_var("x"));
+        assert_eq!(
+            sum.substitute(&Expression::variable("k"), &Expression::integer(2)),
+            sum
+        );
+    }
+
+    #[test]
+    fn test_summation_explanation() {
+        let mut explanation = Some(Box::new(FormattingObserver::new(OutputFormat::Text)));
+        parse("sum(1/(k*(k + 1)), k, 1, n)")
+            .simplify(&mut explanation)
+            .unwrap();
+        let steps = explanation.unwrap().into_explanations().to_vec();
+        assert!(steps.iter().any(|step| step.contains("Telescoping sum")));
+
+        let mut explanation = Some(Box::new(FormattingObserver::new(OutputFormat::Text)));
+        parse("sum(2^k, k, 0, n)")
+            .simplify(&mut explanation)
+            .unwrap();
+        let steps = explanation.unwrap().into_explanations().to_vec();
+        assert!(steps.iter().any(|step| step.contains("Sum of a geometric series")));
+    }
+
+    #[test]
+    fn test_print_summation() {
+        let expr = parse("sum(k^2, k, 1, n)");
+        assert_eq!(
+            expr.get_processed(),
+            " n    \n___   \n╲    2\n╱   k \n‾‾‾   \nk=1   "
+        );
+        assert_eq!(
+            expr.calculate_tree(0),
+            "Summation:\n  Exponentiation:\n    k\n    ^ 2\n  k from 1\n  to n"
+        );
+
+        let expr = parse("prod(k, k, 1, n)");
+        assert_eq!(expr.get_processed(), " n   \n┬─┬  \n│ │  \n│ │ k\nk=1  ");
+    }
+}
@@ -0,0 +1,110 @@
+use sym_rustic::{
+    ast::Expression,
+    lexer::{Lexer, Token},
+    parser::Parser,
+};
+
+fn lex(input: &str) -> Vec<Token> {
+    let mut lexer = Lexer::new(input);
+    let mut tokens: Vec<Token> = Vec::new();
+    while let Some(token) = lexer.next_token() {
+        tokens.push(token);
+    }
+    tokens
+}
+
+fn parse(input: &str) -> Expression {
+    let tokens = lex(input);
+    let mut parser = Parser::new(&tokens);
+    parser.parse_expression().unwrap()
+}
+
+fn simplify(input: &str) -> String {
+    parse(input).simplify(&mut None).unwrap().to_string()
+}
+
+#[cfg(test)]
+mod tests_trigonometry {
+    use crate::{parse, simplify};
+    use sym_rustic::explanation::{FormattingObserver, OutputFormat};
+
+    #[test]
+    fn test_exact_values() {
+        assert_eq!(simplify("sin(pi/6)"), "1/2");
+        assert_eq!(simplify("cos(pi/3)"), "1/2");
+        assert_eq!(simplify("sin(3*pi/4)"), "sqrt(2)/2");
+        assert_eq!(simplify("cos(5*pi/6)"), "-(sqrt(3)/2)");
+        assert_eq!(simplify("sin(-pi/6)"), "-(1/2)");
+        assert_eq!(simplify("tan(pi/3)"), "sqrt(3)");
+        assert_eq!(simplify("tan(3*pi/4)"), "-1");
+        assert_eq!(simplify("tan(pi/2)"), "tan(pi/2)");
+        assert_eq!(simplify("sin(pi/5)"), "sin(pi/5)");
+    }
+
+    #[test]
+    fn test_parity() {
+        assert_eq!(simplify("sin(-x)"), "-sin(x)");
+        assert_eq!(simplify("cos(-x)"), "cos(x)");
+        assert_eq!(simplify("tan(-x)"), "-tan(x)");
+        assert_eq!(simplify("cosh(-x)"), "cosh(x)");
+    }
+
+    #[test]
+    fn test_pythagorean_identity() {
+        assert_eq!(simplify("sin(x)^2 + cos(x)^2"), "1");
+        assert_eq!(simplify("cos(2*x)^2 + sin(2*x)^2"), "1");
+        assert_eq!(simplify("3*sin(x)^2 + 3*cos(x)^2"), "3");
+        assert_eq!(simplify("-sin(x)^2 - cos(x)^2"), "-1");
+        assert_eq!(simplify("sin(x)^2 + cos(y)^2"), "(sin(x)^2) + (cos(y)^2)");
+    }
+
+    #[test]
+    fn test_expand_trig() {
+        let expand = |input: &str| parse(input).expand_trig(&mut None).unwrap().to_string();
+        assert_eq!(expand("sin(x + y)"), "(sin(x) * cos(y)) + (cos(x) * sin(y))");
+        assert_eq!(expand("cos(x - y)"), "(cos(x) * cos(y)) + (sin(x) * sin(y))");
+        assert_eq!(expand("sin(2*x)"), "2 * sin(x) * cos(x)");
+        assert_eq!(expand("cos(2*x)"), "(cos(x)^2) + -(sin(x)^2)");
+        assert_eq!(expand("tan(2*x)"), "(2 * tan(x))/(1 + -(tan(x)^2))");
+        assert_eq!(expand("sin(3*x)"), "((cos(x)^2) * sin(x) * 3) + -(sin(x)^3)");
+    }
+
+    #[test]
+    fn test_trigsimp() {
+        let trigsimp = |input: &str| parse(input).trigsimp(&mut None).unwrap().to_string();
+        assert_eq!(trigsimp("2*sin(x)*cos(x)"), "sin(2 * x)");
+        assert_eq!(trigsimp("cos(x)^2 - sin(x)^2"), "cos(2 * x)");
+        assert_eq!(trigsimp("sin(x)*cos(y) + cos(x)*sin(y)"), "sin(x + y)");
+        assert_eq!(trigsimp("cos(x)*cos(y) - sin(x)*sin(y)"), "cos(x + y)");
+        assert_eq!(trigsimp("sin(x)/cos(x)"), "tan(x)");
+        assert_eq!(trigsimp("1 - sin(x)^2"), "cos(x)^2");
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for input in ["sin(x + y)", "cos(x - y)", "cos(2*x)", "sin(2*x)"] {
+            let expanded = parse(input).expand_trig(&mut None).unwrap();
+            let combined = expanded.trigsimp(&mut None).unwrap();
+            assert!(combined.is_equal(&parse(input).simplify(&mut None).unwrap()));
+        }
+    }
+
+    #[test]
+    fn test_trigonometry_explanation() {
+        let mut explanation = Some(Box::new(FormattingObserver::new(OutputFormat::Text)));
+        parse("sin(pi/6) + sin(-x)").simplify(&mut explanation).unwrap();
+        parse("sin(x)^2 + cos(x)^2").simplify(&mut explanation).unwrap();
+        parse("sin(2*x)").expand_trig(&mut explanation).unwrap();
+        parse("sin(x)*cos(y) + cos(x)*sin(y)").trigsimp(&mut explanation).unwrap();
+        let steps = explanation.unwrap().into_explanations().to_vec();
+        for rule in [
+            "Exact value of the trigonometric function at a known angle",
+            "Odd function",
+            "Pythagorean identity",
+            "Double angle",
+            "Angle addition",
+        ] {
+            assert!(steps.iter().any(|step| step.contains(rule)), "{rule}");
+        }
+    }
+}
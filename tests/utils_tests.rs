@@ -2,6 +2,7 @@
 mod tests {
     use std::collections::HashMap;
 
+    use num_bigint::BigInt;
     use sym_rustic::{
         ast::Expression, prints::PrettyPrints, utils::{factorial, gcd, lcm, multinomial_expansion, prime_factors}
     };
@@ -71,32 +72,41 @@ mod tests {
             prime_factors(10000000),
             Some(HashMap::from([(2, 7), (5, 7)]))
         );
+
+        assert_eq!(prime_factors(202), Some(HashMap::from([(2, 1), (101, 1)])));
+
+        assert_eq!(prime_factors(1018081), Some(HashMap::from([(1009, 2)])));
     }
 
     #[test]
     fn test_factorial() {
-        assert_eq!(factorial(0), 1);
+        assert_eq!(factorial(0), BigInt::from(1));
 
-        assert_eq!(factorial(1), 1);
+        assert_eq!(factorial(1), BigInt::from(1));
 
-        assert_eq!(factorial(2), 2);
+        assert_eq!(factorial(2), BigInt::from(2));
 
-        assert_eq!(factorial(3), 6);
+        assert_eq!(factorial(3), BigInt::from(6));
 
-        assert_eq!(factorial(4), 24);
+        assert_eq!(factorial(4), BigInt::from(24));
 
-        assert_eq!(factorial(5), 120);
+        assert_eq!(factorial(5), BigInt::from(120));
 
-        assert_eq!(factorial(6), 720);
+        assert_eq!(factorial(6), BigInt::from(720));
 
-        assert_eq!(factorial(7), 5040);
+        assert_eq!(factorial(7), BigInt::from(5040));
+
+        assert_eq!(
+            factorial(30),
+            "265252859812191058636308480000000".parse::<BigInt>().unwrap()
+        );
     }
 
     #[test]
     fn test_multinomial_expansion() {
         assert!(
             multinomial_expansion(
-                &vec![
+                &[
                     Expression::variable("a"),
                     Expression::variable("b"),
                 ],
@@ -120,7 +130,7 @@ mod tests {
 
         assert!(
             multinomial_expansion(
-                &(vec![
+                &([
                     Expression::variable("a"),
                     Expression::variable("b"),
                 ]),
@@ -154,7 +164,7 @@ mod tests {
         );
 
         println!("{}", multinomial_expansion(
-            &vec![
+            &[
                 Expression::variable("a"),
                 Expression::variable("b"),
             ],
@@ -201,7 +211,7 @@ mod tests {
         ]).calculate_tree(0));
 
         // multinomial_expansion(
-        //     &vec![
+        //     &[
         //         Expression::variable("a"),
         //         Expression::variable("b"),
         //     ],
@@ -210,7 +220,7 @@ mod tests {
 
         assert!(
             multinomial_expansion(
-                &vec![
+                &[
                     Expression::variable("a"),
                     Expression::variable("b"),
                 ],
@@ -253,5 +263,29 @@ mod tests {
                 ]),
             ]))
         );
+
+        // 30! does not fit in a u64
+        let expansion = multinomial_expansion(
+            &[
+                Expression::variable("a"),
+                Expression::variable("b"),
+            ],
+            30
+        ).simplify(&mut None).unwrap();
+        let Expression::Addition(add) = expansion else {
+            panic!("Expected an addition");
+        };
+        assert_eq!(add.terms.len(), 31);
+        assert!(add.terms.iter().any(|term| term.is_equal(&Expression::multiplication(vec![
+            Expression::integer(155117520),
+            Expression::exponentiation(
+                Expression::variable("a"),
+                Expression::integer(15)
+            ),
+            Expression::exponentiation(
+                Expression::variable("b"),
+                Expression::integer(15)
+            ),
+        ]))));
     }
 }